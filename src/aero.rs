@@ -0,0 +1,390 @@
+use super::boat::Boat;
+use super::si::{Area, Force, Length};
+use super::spline::{Interpolation, Spline};
+use super::stability::AIR_DENSITY;
+use super::wind::Wind;
+use std::fmt;
+
+/// Rig height above the DWL over the square root of the sail area, when the rig is not placed.
+pub const RIG_HEIGHT_FACTOR: f64 = 2.0;
+
+/// Mainsail fraction of the sail area, when the rig is not informed.
+pub const MAINSAIL_FRACTION: f64 = 0.55;
+
+/// Spinnaker area over the foretriangle area.
+pub const SPINNAKER_FACTOR: f64 = 1.8;
+
+/// Fraction of the full lift of the flat trim.
+pub const FLAT_TRIM: f64 = 0.7;
+
+/// Flattest trim, the fraction of the full lift the sails keep.
+pub const MIN_FLAT: f64 = 0.5;
+
+/// Mainsail lift coefficient by the apparent wind angle (degree).
+/// After Hazen, G. S. (1980) A model of sail aerodynamics for diverse rig types. SNAME.
+const MAINSAIL_LIFT: [(f64, f64); 10] = [
+    (0.0, 0.0),
+    (7.0, 0.86),
+    (9.0, 1.05),
+    (12.0, 1.2),
+    (28.0, 1.375),
+    (60.0, 1.45),
+    (90.0, 1.45),
+    (120.0, 1.3),
+    (150.0, 1.0),
+    (180.0, 0.8),
+];
+
+/// Mainsail parasitic drag coefficient by the apparent wind angle (degree).
+const MAINSAIL_DRAG: [(f64, f64); 10] = [
+    (0.0, 0.043),
+    (7.0, 0.034),
+    (9.0, 0.042),
+    (12.0, 0.056),
+    (28.0, 0.106),
+    (60.0, 0.198),
+    (90.0, 0.6),
+    (120.0, 0.8),
+    (150.0, 0.95),
+    (180.0, 1.0),
+];
+
+/// Jib lift coefficient by the apparent wind angle (degree).
+const JIB_LIFT: [(f64, f64); 9] = [
+    (0.0, 0.0),
+    (7.0, 0.0),
+    (15.0, 1.0),
+    (20.0, 1.375),
+    (27.0, 1.45),
+    (50.0, 1.45),
+    (60.0, 1.25),
+    (100.0, 0.4),
+    (180.0, 0.0),
+];
+
+/// Jib parasitic drag coefficient by the apparent wind angle (degree).
+const JIB_DRAG: [(f64, f64); 9] = [
+    (0.0, 0.05),
+    (7.0, 0.05),
+    (15.0, 0.032),
+    (20.0, 0.031),
+    (27.0, 0.037),
+    (50.0, 0.25),
+    (60.0, 0.35),
+    (100.0, 0.73),
+    (180.0, 0.9),
+];
+
+/// Spinnaker lift coefficient by the apparent wind angle (degree).
+const SPINNAKER_LIFT: [(f64, f64); 7] = [
+    (0.0, 0.0),
+    (28.0, 0.0),
+    (41.0, 1.0),
+    (50.0, 1.3),
+    (80.0, 1.4),
+    (120.0, 1.0),
+    (180.0, 0.4),
+];
+
+/// Spinnaker parasitic drag coefficient by the apparent wind angle (degree).
+const SPINNAKER_DRAG: [(f64, f64); 7] = [
+    (0.0, 0.2),
+    (28.0, 0.2),
+    (41.0, 0.25),
+    (50.0, 0.3),
+    (80.0, 0.5),
+    (120.0, 0.8),
+    (180.0, 1.1),
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SAIL AERODYNAMICS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Sails set.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SailSet {
+    /// Mainsail and jib.
+    Upwind,
+    /// Mainsail and spinnaker.
+    Downwind,
+}
+
+impl SailSet {
+    pub const ALL: [SailSet; 2] = [SailSet::Upwind, SailSet::Downwind];
+}
+
+impl fmt::Display for SailSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SailSet::Upwind => write!(f, "Main and jib"),
+            SailSet::Downwind => write!(f, "Main and spinnaker"),
+        }
+    }
+}
+
+/// Sail trim, flattened sails keep a fraction of the full lift.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SailTrim {
+    Full,
+    Flat,
+    /// Fraction of the full lift, down to MIN_FLAT.
+    Flattened(f64),
+}
+
+impl SailTrim {
+    /// Fraction of the full lift.
+    pub fn flat(self) -> f64 {
+        match self {
+            SailTrim::Full => 1.0,
+            SailTrim::Flat => FLAT_TRIM,
+            SailTrim::Flattened(flat) => flat.clamp(MIN_FLAT, 1.0),
+        }
+    }
+}
+
+impl fmt::Display for SailTrim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SailTrim::Full => write!(f, "Full"),
+            SailTrim::Flat => write!(f, "Flat"),
+            SailTrim::Flattened(_) => write!(f, "Flattened {:.0}%", self.flat() * 100.0),
+        }
+    }
+}
+
+/// Sail forces in the apparent wind, lift square to the wind and drag along it, and in the
+/// boat, driving along the heading and heeling across.
+#[derive(Debug, Copy, Clone)]
+pub struct SailForces {
+    pub lift_coefficient: f64,
+    pub drag_coefficient: f64,
+    pub lift: Force,
+    pub drag: Force,
+    pub drive: Force,
+    pub heel: Force,
+}
+
+/// Sail aerodynamics
+///
+/// Lift and drag coefficients of each sail by the apparent wind angle, combined by the sail
+/// areas, CL = sum(CLi Ai) / A and CD = sum(CDi Ai) / A + CL^2 / (pi AR), with the induced
+/// drag of the effective rig aspect ratio AR = (1.1 h)^2 / A, h the rig height above the DWL.
+/// Flattening the sails scales the lift down. The forces, q = rho AWS^2 / 2:
+/// L = q A CL and D = q A CD
+/// Driving force L sin(AWA) - D cos(AWA)
+/// Heeling force L cos(AWA) + D sin(AWA)
+#[derive(Debug, Copy, Clone)]
+pub struct SailAerodynamics {
+    mainsail: Area,
+    jib: Area,
+    spinnaker: Option<Area>,
+    /// Rig height above the DWL.
+    rig_height: Length,
+}
+
+#[allow(dead_code)]
+impl SailAerodynamics {
+    pub fn new(
+        mainsail: Area,
+        jib: Area,
+        spinnaker: Option<Area>,
+        rig_height: Length,
+    ) -> SailAerodynamics {
+        SailAerodynamics {
+            mainsail,
+            jib,
+            spinnaker,
+            rig_height,
+        }
+    }
+
+    /// Sails of the rig, the rest of its sail area past the mainsail as the jib and the
+    /// spinnaker of SPINNAKER_FACTOR times the foretriangle. Without the rig the sail area split
+    /// by MAINSAIL_FRACTION, the jib taken as the foretriangle.
+    pub fn from_boat(boat: &Boat) -> SailAerodynamics {
+        let root = boat.sail_area().to_meter2().sqrt();
+        let height = |r: &super::rig::Rig| {
+            r.placement()
+                .map(|p| p.sheer + r.i())
+                .unwrap_or_else(|| Length::from_meter(RIG_HEIGHT_FACTOR * root))
+        };
+        match boat.rig() {
+            Some(rig) => {
+                let foretriangle = rig.foretriangle_area().to_meter2();
+                let jib = (rig.sail_area().to_meter2() - rig.mainsail_area().to_meter2()).max(0.0);
+                SailAerodynamics::new(
+                    rig.mainsail_area(),
+                    Area::from_meter2(jib),
+                    Some(Area::from_meter2(SPINNAKER_FACTOR * foretriangle))
+                        .filter(|a| a.to_meter2() > 0.0),
+                    height(rig),
+                )
+            }
+            None => {
+                let area = boat.sail_area().to_meter2();
+                let jib = (1.0 - MAINSAIL_FRACTION) * area;
+                SailAerodynamics::new(
+                    Area::from_meter2(MAINSAIL_FRACTION * area),
+                    Area::from_meter2(jib),
+                    Some(Area::from_meter2(SPINNAKER_FACTOR * jib)).filter(|a| a.to_meter2() > 0.0),
+                    Length::from_meter(RIG_HEIGHT_FACTOR * root),
+                )
+            }
+        }
+    }
+
+    /// Sets the boat carries.
+    pub fn sets(&self) -> Vec<SailSet> {
+        SailSet::ALL
+            .iter()
+            .copied()
+            .filter(|&s| s == SailSet::Upwind || self.spinnaker.is_some())
+            .collect()
+    }
+
+    /// Area of a set.
+    pub fn area(&self, set: SailSet) -> Area {
+        let fore = match set {
+            SailSet::Upwind => self.jib.to_meter2(),
+            SailSet::Downwind => self.spinnaker.map_or(0.0, |a| a.to_meter2()),
+        };
+        Area::from_meter2(self.mainsail.to_meter2() + fore)
+    }
+
+    /// Effective rig aspect ratio of a set.
+    pub fn aspect_ratio(&self, set: SailSet) -> f64 {
+        (1.1 * self.rig_height.to_meter()).powi(2) / self.area(set).to_meter2()
+    }
+
+    /// Lift and drag coefficients of a set at an apparent wind angle (degree) and trim.
+    pub fn coefficients(
+        &self,
+        set: SailSet,
+        apparent_wind_angle: f64,
+        trim: SailTrim,
+    ) -> (f64, f64) {
+        let angle = apparent_wind_angle.abs().min(180.0);
+        let value = |table: &[(f64, f64)]| {
+            Spline::new(table, Interpolation::Linear)
+                .and_then(|s| s.eval(angle))
+                .unwrap_or(0.0)
+        };
+        let main = self.mainsail.to_meter2();
+        let (fore, lift, drag) = match set {
+            SailSet::Upwind => (self.jib.to_meter2(), &JIB_LIFT[..], &JIB_DRAG[..]),
+            SailSet::Downwind => (
+                self.spinnaker.map_or(0.0, |a| a.to_meter2()),
+                &SPINNAKER_LIFT[..],
+                &SPINNAKER_DRAG[..],
+            ),
+        };
+        let area = main + fore;
+        if area <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let cl = (value(&MAINSAIL_LIFT) * main + value(lift) * fore) / area * trim.flat();
+        let cd = (value(&MAINSAIL_DRAG) * main + value(drag) * fore) / area
+            + cl * cl / (std::f64::consts::PI * self.aspect_ratio(set));
+        (cl, cd)
+    }
+
+    /// Forces of a set in an apparent wind.
+    pub fn forces(&self, set: SailSet, apparent_wind: Wind, trim: SailTrim) -> SailForces {
+        let (cl, cd) = self.coefficients(set, apparent_wind.angle, trim);
+        let q = 0.5
+            * AIR_DENSITY
+            * apparent_wind.speed.to_meter_second().powi(2)
+            * self.area(set).to_meter2();
+        let awa = apparent_wind.off_the_bow().to_radians();
+        let (lift, drag) = (q * cl, q * cd);
+        SailForces {
+            lift_coefficient: cl,
+            drag_coefficient: cd,
+            lift: Force::from_newton(lift),
+            drag: Force::from_newton(drag),
+            drive: Force::from_newton(lift * awa.sin() - drag * awa.cos()),
+            heel: Force::from_newton(lift * awa.cos() + drag * awa.sin()),
+        }
+    }
+}
+
+impl fmt::Display for SailAerodynamics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Sail aerodynamics]\n\
+            \tMainsail:   {:>9.2}m2\n\
+            \tJib:        {:>9.2}m2\n\
+            \tSpinnaker:  {:>9}\n\
+            \tRig height: {:>9.2}m",
+            self.mainsail.to_meter2(),
+            self.jib.to_meter2(),
+            self.spinnaker
+                .map_or("-".to_string(), |a| format!("{:.2}m2", a.to_meter2())),
+            self.rig_height.to_meter()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn sail_aerodynamics() {
+        use crate::si::Speed;
+
+        let m = Length::from_meter;
+        let sails = SailAerodynamics::new(
+            Area::from_meter2(30.0),
+            Area::from_meter2(30.0),
+            Some(Area::from_meter2(90.0)),
+            m(14.0),
+        );
+        assert_eq!(sails.sets().len(), 2);
+        assert_eq!(
+            format!("{:.2}", sails.aspect_ratio(SailSet::Upwind)),
+            "3.95"
+        );
+        // Main and jib at 30 degree, both near the full lift.
+        let (cl, cd) = sails.coefficients(SailSet::Upwind, 30.0, SailTrim::Full);
+        assert_eq!(format!("{:.3}", cl), "1.415");
+        assert_eq!(format!("{:.3}", cd), "0.249");
+        let (flat, _) = sails.coefficients(SailSet::Upwind, 30.0, SailTrim::Flat);
+        assert_eq!(format!("{:.3}", flat), format!("{:.3}", cl * FLAT_TRIM));
+        assert_eq!(SailTrim::Flattened(0.2).flat(), MIN_FLAT);
+
+        let wind = Wind::new(Speed::from_meter_second(8.0), 30.0);
+        let forces = sails.forces(SailSet::Upwind, wind, SailTrim::Full);
+        let q = 0.5 * AIR_DENSITY * 64.0 * 60.0;
+        assert_eq!(
+            format!("{:.1}", forces.lift.to_newton()),
+            format!("{:.1}", q * cl)
+        );
+        assert!(forces.heel.to_newton() > forces.drive.to_newton());
+        assert!(forces.drive.to_newton() > 0.0);
+
+        // Downwind the spinnaker drives harder than the jib, upwind it cannot.
+        let run = Wind::new(Speed::from_meter_second(5.0), -150.0);
+        let jib = sails.forces(SailSet::Upwind, run, SailTrim::Full);
+        let spinnaker = sails.forces(SailSet::Downwind, run, SailTrim::Full);
+        assert!(spinnaker.drive.to_newton() > 2.0 * jib.drive.to_newton());
+        let beat = sails.forces(SailSet::Downwind, wind, SailTrim::Full);
+        assert!(beat.drive.to_newton() < forces.drive.to_newton());
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_sail_area(Area::from_meter2(20.0));
+        let plain = SailAerodynamics::from_boat(&boat);
+        assert_eq!(
+            format!("{:.1}", plain.area(SailSet::Upwind).to_meter2()),
+            "20.0"
+        );
+        assert_eq!(
+            format!("{:.1}", plain.area(SailSet::Downwind).to_meter2()),
+            "27.2"
+        );
+    }
+}
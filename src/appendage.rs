@@ -0,0 +1,759 @@
+use super::balance::LateralPlane;
+use super::diagnostics::Diagnostics;
+use super::foil::Foil;
+use super::hull::HullPoint;
+use super::hydrostatics::simpson;
+use super::si::{Area, Length, Volume, Weight};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Typical rudder area as a fraction of the lateral plane (canoe body plus keel).
+pub const RUDDER_AREA_RANGE: (f64, f64) = (0.08, 0.15);
+
+/// Typical rudder geometric aspect ratio.
+pub const RUDDER_ASPECT_RATIO_RANGE: (f64, f64) = (1.0, 4.0);
+
+/// Above this fraction of the area forward of the stock the rudder is overbalanced.
+pub const RUDDER_MAX_BALANCE: f64 = 0.22;
+
+/// Thickness of the keel and rudder foil sections as a fraction of the chord.
+pub const APPENDAGE_THICKNESS: f64 = 0.12;
+
+/// Foil sections of an appendage down the span, even for Simpson's rule.
+pub const APPENDAGE_STATIONS: usize = 10;
+
+/// Points of a foil section on each side, from the leading to the trailing edge.
+pub const FOIL_POINTS: usize = 24;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// KEEL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Keel type.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum KeelType {
+    Fin,
+    Full,
+    Bulb,
+    Centerboard,
+    /// Twin keels, dimensions are of each keel.
+    Bilge,
+}
+
+impl fmt::Display for KeelType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KeelType::Fin => write!(f, "Fin"),
+            KeelType::Full => write!(f, "Full"),
+            KeelType::Bulb => write!(f, "Bulb"),
+            KeelType::Centerboard => write!(f, "Centerboard"),
+            KeelType::Bilge => write!(f, "Bilge"),
+        }
+    }
+}
+
+/// Keel
+///
+/// Trapezoidal planform below the canoe body, the span is measured down from the canoe body
+/// (the centerboard fully down). The leading edge is taken as vertical.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Keel {
+    keel_type: KeelType,
+    root_chord: Length,
+    tip_chord: Length,
+    span: Length,
+    /// Ballast weight.
+    ballast: Weight,
+    /// Ballast VCG (height above the bottom of the canoe body, negative below it).
+    vcg: Option<Length>,
+    /// Root leading edge aft of the forward perpendicular.
+    leading_edge: Option<Length>,
+}
+
+#[allow(dead_code)]
+impl Keel {
+    pub fn new(
+        keel_type: KeelType,
+        root_chord: Length,
+        tip_chord: Length,
+        span: Length,
+        ballast: Weight,
+    ) -> Keel {
+        Keel {
+            keel_type,
+            root_chord,
+            tip_chord,
+            span,
+            ballast,
+            vcg: None,
+            leading_edge: None,
+        }
+    }
+
+    /// Keel type.
+    pub fn keel_type(&self) -> KeelType {
+        self.keel_type
+    }
+
+    /// Root chord.
+    pub fn root_chord(&self) -> Length {
+        self.root_chord
+    }
+
+    /// Tip chord.
+    pub fn tip_chord(&self) -> Length {
+        self.tip_chord
+    }
+
+    /// Span.
+    pub fn span(&self) -> Length {
+        self.span
+    }
+
+    /// Ballast weight.
+    pub fn ballast(&self) -> Weight {
+        self.ballast
+    }
+
+    /// Ballast VCG (height above the bottom of the canoe body).
+    pub fn vcg(&self) -> Option<Length> {
+        self.vcg
+    }
+    #[allow(dead_code)]
+    pub fn set_vcg(&mut self, val: Length) {
+        self.vcg = Some(val);
+    }
+
+    /// Root leading edge aft of the forward perpendicular.
+    pub fn leading_edge(&self) -> Option<Length> {
+        self.leading_edge
+    }
+    #[allow(dead_code)]
+    pub fn set_leading_edge(&mut self, val: Length) {
+        self.leading_edge = Some(val);
+    }
+
+    /// Number of keels.
+    pub fn count(&self) -> usize {
+        match self.keel_type {
+            KeelType::Bilge => 2,
+            _ => 1,
+        }
+    }
+
+    /// Lateral (profile) area of one keel.
+    pub fn lateral_area(&self) -> Area {
+        Area::from_meter2(
+            (self.root_chord.to_meter() + self.tip_chord.to_meter()) / 2.0 * self.span.to_meter(),
+        )
+    }
+
+    /// Wetted surface, both sides of all keels.
+    pub fn wetted_surface(&self) -> Area {
+        Area::from_meter2(2.0 * self.count() as f64 * self.lateral_area().to_meter2())
+    }
+
+    /// Profile centroid aft of the forward perpendicular, when the keel is positioned.
+    pub fn centroid(&self) -> Option<Length> {
+        let (a, b) = (self.root_chord.to_meter(), self.tip_chord.to_meter());
+        Some(Length::from_meter(
+            self.leading_edge?.to_meter() + (a * a + a * b + b * b) / (3.0 * (a + b)),
+        ))
+    }
+
+    /// Geometry of one keel below the bottom of the canoe body, when the keel is positioned.
+    pub fn geometry(&self) -> Option<AppendageGeometry> {
+        Some(AppendageGeometry::new(
+            self.root_chord,
+            self.tip_chord,
+            self.span,
+            self.leading_edge?,
+            Length::from_meter(0.0),
+            0.0,
+            Foil::symmetrical(APPENDAGE_THICKNESS),
+        ))
+    }
+}
+
+impl fmt::Display for Keel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Keel - {}]\n\
+            \tRoot chord:  {:>9.3}m\n\
+            \tTip chord:   {:>9.3}m\n\
+            \tSpan:        {:>9.3}m\n\
+            \tBallast:     {:>9.0}kg\n\
+            \tWetted surf: {:>9.2}m2",
+            self.keel_type,
+            self.root_chord.to_meter(),
+            self.tip_chord.to_meter(),
+            self.span.to_meter(),
+            self.ballast.to_kilogram(),
+            self.wetted_surface().to_meter2()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// RUDDER
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Rudder
+///
+/// Trapezoidal blade, the balance area is the part forward of the stock.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Rudder {
+    span: Length,
+    root_chord: Length,
+    tip_chord: Length,
+    balance_area: Area,
+    /// Stock aft of the forward perpendicular.
+    stock: Option<Length>,
+}
+
+#[allow(dead_code)]
+impl Rudder {
+    pub fn new(span: Length, root_chord: Length, tip_chord: Length, balance_area: Area) -> Rudder {
+        Rudder {
+            span,
+            root_chord,
+            tip_chord,
+            balance_area,
+            stock: None,
+        }
+    }
+
+    /// Span.
+    pub fn span(&self) -> Length {
+        self.span
+    }
+
+    /// Root chord.
+    pub fn root_chord(&self) -> Length {
+        self.root_chord
+    }
+
+    /// Tip chord.
+    pub fn tip_chord(&self) -> Length {
+        self.tip_chord
+    }
+
+    /// Area forward of the stock.
+    pub fn balance_area(&self) -> Area {
+        self.balance_area
+    }
+
+    /// Stock aft of the forward perpendicular.
+    pub fn stock(&self) -> Option<Length> {
+        self.stock
+    }
+    #[allow(dead_code)]
+    pub fn set_stock(&mut self, val: Length) {
+        self.stock = Some(val);
+    }
+
+    /// Blade area.
+    pub fn area(&self) -> Area {
+        Area::from_meter2(
+            (self.root_chord.to_meter() + self.tip_chord.to_meter()) / 2.0 * self.span.to_meter(),
+        )
+    }
+
+    /// Geometric aspect ratio, span^2 / area.
+    pub fn aspect_ratio(&self) -> f64 {
+        self.span.to_meter().powi(2) / self.area().to_meter2()
+    }
+
+    /// Balance, fraction of the area forward of the stock.
+    pub fn balance(&self) -> f64 {
+        self.balance_area.to_meter2() / self.area().to_meter2()
+    }
+
+    /// Geometry of the blade, when the stock is positioned. The root is taken at the bottom of
+    /// the canoe body and the leading edge forward of the stock by the balance of the root chord.
+    pub fn geometry(&self) -> Option<AppendageGeometry> {
+        let leading_edge = self.stock?.to_meter() - self.balance() * self.root_chord.to_meter();
+        Some(AppendageGeometry::new(
+            self.root_chord,
+            self.tip_chord,
+            self.span,
+            Length::from_meter(leading_edge),
+            Length::from_meter(0.0),
+            0.0,
+            Foil::symmetrical(APPENDAGE_THICKNESS),
+        ))
+    }
+
+    /// Area as a fraction of the lateral plane.
+    pub fn area_ratio(&self, plane: &LateralPlane) -> f64 {
+        self.area().to_meter2() / plane.area().to_meter2()
+    }
+
+    /// Warnings when the rudder is outside typical ranges.
+    pub fn check(&self, plane: &LateralPlane) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        let area_ratio = self.area_ratio(plane);
+        if area_ratio < RUDDER_AREA_RANGE.0 {
+            diagnostics.warning(format!(
+                "Rudder area of {:.1}% of the lateral plane is small, control may be poor",
+                area_ratio * 100.0
+            ));
+        } else if area_ratio > RUDDER_AREA_RANGE.1 {
+            diagnostics.warning(format!(
+                "Rudder area of {:.1}% of the lateral plane is large, drag will be high",
+                area_ratio * 100.0
+            ));
+        }
+        let aspect_ratio = self.aspect_ratio();
+        if !(RUDDER_ASPECT_RATIO_RANGE.0..=RUDDER_ASPECT_RATIO_RANGE.1).contains(&aspect_ratio) {
+            diagnostics.warning(format!(
+                "Rudder aspect ratio of {:.2} is outside the typical range",
+                aspect_ratio
+            ));
+        }
+        let balance = self.balance();
+        if balance > RUDDER_MAX_BALANCE {
+            diagnostics.warning(format!(
+                "Rudder balance of {:.0}% is high, the helm may snatch",
+                balance * 100.0
+            ));
+        }
+        diagnostics
+    }
+}
+
+impl fmt::Display for Rudder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Rudder]\n\
+            \tSpan:         {:>9.3}m\n\
+            \tArea:         {:>9.3}m2\n\
+            \tAspect ratio: {:>9.2}\n\
+            \tBalance:      {:>9.1}%",
+            self.span.to_meter(),
+            self.area().to_meter2(),
+            self.aspect_ratio(),
+            self.balance() * 100.0
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// APPENDAGE GEOMETRY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Appendage geometry
+///
+/// Keel or rudder blade as a trapezoidal planform with a symmetrical foil section, the
+/// thickness of a NACA section, down from the root. The leading edge sweeps aft with the depth by
+/// the sweep angle. Sections, areas and volume are integrated down the span, the wetted
+/// surface from the girth of the foil.
+#[derive(Debug, Copy, Clone)]
+pub struct AppendageGeometry {
+    root_chord: Length,
+    tip_chord: Length,
+    span: Length,
+    /// Root leading edge aft of the forward perpendicular.
+    leading_edge: Length,
+    /// Root height above the baseline.
+    root: Length,
+    /// Leading edge sweep aft (degree).
+    sweep: f64,
+    foil: Foil,
+}
+
+#[allow(dead_code)]
+impl AppendageGeometry {
+    pub fn new(
+        root_chord: Length,
+        tip_chord: Length,
+        span: Length,
+        leading_edge: Length,
+        root: Length,
+        sweep: f64,
+        foil: Foil,
+    ) -> AppendageGeometry {
+        AppendageGeometry {
+            root_chord,
+            tip_chord,
+            span,
+            leading_edge,
+            root,
+            sweep,
+            foil,
+        }
+    }
+
+    pub fn span(&self) -> Length {
+        self.span
+    }
+
+    /// Root height above the baseline.
+    pub fn root(&self) -> Length {
+        self.root
+    }
+    pub fn set_root(&mut self, val: Length) {
+        self.root = val;
+    }
+
+    pub fn sweep(&self) -> f64 {
+        self.sweep
+    }
+
+    /// Section of the blade, its thickness as the camber is ignored.
+    pub fn foil_section(&self) -> Foil {
+        self.foil
+    }
+
+    /// Thickness as a fraction of the chord.
+    pub fn thickness(&self) -> f64 {
+        self.foil.thickness()
+    }
+
+    /// Chord at a depth below the root.
+    pub fn chord(&self, depth: Length) -> Length {
+        let t = (depth.to_meter() / self.span.to_meter()).clamp(0.0, 1.0);
+        Length::from_meter(
+            self.root_chord.to_meter()
+                + (self.tip_chord.to_meter() - self.root_chord.to_meter()) * t,
+        )
+    }
+
+    /// Leading edge aft of the forward perpendicular at a depth below the root.
+    pub fn leading_edge(&self, depth: Length) -> Length {
+        Length::from_meter(
+            self.leading_edge.to_meter() + depth.to_meter() * self.sweep.to_radians().tan(),
+        )
+    }
+
+    /// Foil section at a depth below the root, (position, half-thickness) points from the
+    /// leading to the trailing edge, closer at the leading edge.
+    pub fn foil(&self, depth: Length) -> Vec<(Length, Length)> {
+        let chord = self.chord(depth).to_meter();
+        let start = self.leading_edge(depth).to_meter();
+        (0..=FOIL_POINTS)
+            .map(|i| {
+                let x = (1.0 - (std::f64::consts::PI * i as f64 / FOIL_POINTS as f64).cos()) / 2.0;
+                (
+                    Length::from_meter(start + x * chord),
+                    Length::from_meter(self.foil.half_thickness(x) * chord),
+                )
+            })
+            .collect()
+    }
+
+    /// Closed outline of the foil section at a depth, starboard from the leading to the
+    /// trailing edge and port back.
+    pub fn section(&self, depth: Length) -> Vec<HullPoint> {
+        let z = Length::from_meter(self.root.to_meter() - depth.to_meter());
+        let foil = self.foil(depth);
+        let mut points: Vec<HullPoint> =
+            foil.iter().map(|&(x, y)| HullPoint::new(x, y, z)).collect();
+        points.extend(
+            foil.iter()
+                .rev()
+                .skip(1)
+                .map(|&(x, y)| HullPoint::new(x, Length::from_meter(-y.to_meter()), z)),
+        );
+        points
+    }
+
+    /// Profile outline, (position, height) from the root leading edge down to the tip and up
+    /// the trailing edge to the root.
+    pub fn profile(&self) -> Vec<(Length, Length)> {
+        let corner = |depth: f64, aft: f64| {
+            let d = Length::from_meter(depth);
+            (
+                Length::from_meter(
+                    self.leading_edge(d).to_meter() + aft * self.chord(d).to_meter(),
+                ),
+                Length::from_meter(self.root.to_meter() - depth),
+            )
+        };
+        let span = self.span.to_meter();
+        vec![
+            corner(0.0, 0.0),
+            corner(span, 0.0),
+            corner(span, 1.0),
+            corner(0.0, 1.0),
+        ]
+    }
+
+    fn depths(&self) -> Vec<f64> {
+        (0..=APPENDAGE_STATIONS)
+            .map(|i| self.span.to_meter() * i as f64 / APPENDAGE_STATIONS as f64)
+            .collect()
+    }
+
+    /// Sections down the span and the leading and trailing edges, for the lines drawing.
+    pub fn wireframe(&self) -> Vec<Vec<HullPoint>> {
+        let depths = self.depths();
+        let mut lines: Vec<Vec<HullPoint>> = depths
+            .iter()
+            .map(|&d| self.section(Length::from_meter(d)))
+            .collect();
+        let edge = |aft: f64| {
+            depths
+                .iter()
+                .map(|&d| {
+                    let depth = Length::from_meter(d);
+                    HullPoint::new(
+                        Length::from_meter(
+                            self.leading_edge(depth).to_meter()
+                                + aft * self.chord(depth).to_meter(),
+                        ),
+                        Length::from_meter(0.0),
+                        Length::from_meter(self.root.to_meter() - d),
+                    )
+                })
+                .collect()
+        };
+        lines.push(edge(0.0));
+        lines.push(edge(1.0));
+        lines
+    }
+
+    /// Integral down the span of a value of the depth.
+    fn integral<F>(&self, value: F) -> f64
+    where
+        F: Fn(Length) -> f64,
+    {
+        let depths = self.depths();
+        let values: Vec<f64> = depths
+            .iter()
+            .map(|&d| value(Length::from_meter(d)))
+            .collect();
+        simpson(&depths, &values)
+    }
+
+    /// Lateral (profile) area.
+    pub fn lateral_area(&self) -> Area {
+        Area::from_meter2(self.integral(|d| self.chord(d).to_meter()))
+    }
+
+    /// Profile centroid aft of the forward perpendicular.
+    pub fn centroid(&self) -> Length {
+        let moment = self.integral(|d| {
+            let chord = self.chord(d).to_meter();
+            chord * (self.leading_edge(d).to_meter() + chord / 2.0)
+        });
+        Length::from_meter(moment / self.lateral_area().to_meter2())
+    }
+
+    /// Profile centroid below the root.
+    pub fn centroid_depth(&self) -> Length {
+        let moment = self.integral(|d| self.chord(d).to_meter() * d.to_meter());
+        Length::from_meter(moment / self.lateral_area().to_meter2())
+    }
+
+    /// Wetted surface of both sides, from the girth of the sections.
+    pub fn wetted_surface(&self) -> Area {
+        Area::from_meter2(self.integral(|d| {
+            let foil = self.foil(d);
+            2.0 * foil
+                .windows(2)
+                .map(|w| {
+                    (w[1].0.to_meter() - w[0].0.to_meter())
+                        .hypot(w[1].1.to_meter() - w[0].1.to_meter())
+                })
+                .sum::<f64>()
+        }))
+    }
+
+    /// Volume, from the area of the sections.
+    pub fn volume(&self) -> Volume {
+        Volume::from_meter3(self.integral(|d| {
+            let foil = self.foil(d);
+            2.0 * foil
+                .windows(2)
+                .map(|w| {
+                    (w[1].0.to_meter() - w[0].0.to_meter())
+                        * (w[0].1.to_meter() + w[1].1.to_meter())
+                        / 2.0
+                })
+                .sum::<f64>()
+        }))
+    }
+}
+
+impl fmt::Display for AppendageGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Appendage geometry]\n\
+            \tLateral area: {:>9.3}m2\n\
+            \tCentroid:     {:>9.3}m\n\
+            \tWetted surf:  {:>9.3}m2\n\
+            \tVolume:       {:>9.4}m3\n\
+            \tSection:      {:>9}",
+            self.lateral_area().to_meter2(),
+            self.centroid().to_meter(),
+            self.wetted_surface().to_meter2(),
+            self.volume().to_meter3(),
+            self.foil.to_string()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::boat::Boat;
+
+    #[test]
+    fn keel() {
+        let mut keel = Keel::new(
+            KeelType::Fin,
+            Length::from_meter(1.2),
+            Length::from_meter(0.6),
+            Length::from_meter(1.5),
+            Weight::from_kilogram(1500.0),
+        );
+        assert_eq!(format!("{:.2}", keel.lateral_area().to_meter2()), "1.35");
+        assert_eq!(format!("{:.2}", keel.wetted_surface().to_meter2()), "2.70");
+        assert!(keel.centroid().is_none());
+        keel.set_leading_edge(Length::from_meter(3.0));
+        // (1.44 + 0.72 + 0.36) / 5.4
+        assert_eq!(
+            format!("{:.3}", keel.centroid().unwrap().to_meter()),
+            "3.467"
+        );
+
+        let bilge = Keel::new(
+            KeelType::Bilge,
+            Length::from_meter(1.2),
+            Length::from_meter(0.6),
+            Length::from_meter(1.5),
+            Weight::from_kilogram(1500.0),
+        );
+        assert_eq!(format!("{:.2}", bilge.wetted_surface().to_meter2()), "5.40");
+    }
+
+    #[test]
+    fn rudder() {
+        let rudder = Rudder::new(
+            Length::from_meter(1.2),
+            Length::from_meter(0.5),
+            Length::from_meter(0.3),
+            Area::from_meter2(0.08),
+        );
+        assert_eq!(format!("{:.2}", rudder.area().to_meter2()), "0.48");
+        assert_eq!(format!("{:.2}", rudder.aspect_ratio()), "3.00");
+        assert_eq!(format!("{:.3}", rudder.balance()), "0.167");
+
+        let mut plane = LateralPlane::new();
+        plane.add(Area::from_meter2(4.8), Length::from_meter(4.0));
+        assert_eq!(format!("{:.2}", rudder.area_ratio(&plane)), "0.10");
+        assert!(rudder.check(&plane).is_empty());
+
+        let mut plane = LateralPlane::new();
+        plane.add(Area::from_meter2(12.0), Length::from_meter(4.0));
+        let diagnostics = rudder.check(&plane);
+        assert_eq!(diagnostics.warnings().count(), 1);
+
+        // Checked with the boat.
+        let mut boat = Boat::new("".to_string());
+        boat.set_rudder(rudder);
+        assert!(boat
+            .validate()
+            .warnings()
+            .any(|w| w.message().starts_with("Rudder area")));
+    }
+
+    #[test]
+    fn appendage_geometry() {
+        let mut keel = Keel::new(
+            KeelType::Fin,
+            Length::from_meter(1.2),
+            Length::from_meter(0.6),
+            Length::from_meter(1.5),
+            Weight::from_kilogram(1500.0),
+        );
+        assert!(keel.geometry().is_none());
+        keel.set_leading_edge(Length::from_meter(3.0));
+        let geometry = keel.geometry().unwrap();
+        // The planform of the keel.
+        assert_eq!(
+            format!("{:.3}", geometry.lateral_area().to_meter2()),
+            "1.350"
+        );
+        assert_eq!(format!("{:.3}", geometry.centroid().to_meter()), "3.467");
+        // (1.2 * 0.5 + 0.6) * 1.5^2 / 6 / 1.35
+        assert_eq!(
+            format!("{:.3}", geometry.centroid_depth().to_meter()),
+            "0.667"
+        );
+        // A little more than both sides of the profile.
+        assert_eq!(
+            format!("{:.3}", geometry.wetted_surface().to_meter2()),
+            "2.752"
+        );
+        // About 0.685 t c^2 down the span, 0.1036.
+        assert_eq!(format!("{:.4}", geometry.volume().to_meter3()), "0.1027");
+
+        let section = geometry.section(Length::from_meter(1.5));
+        assert_eq!(section.len(), 2 * FOIL_POINTS + 1);
+        assert_eq!(section[0].z.to_meter(), -1.5);
+        // 12% thick at 30% of the chord.
+        let foil = geometry.foil(Length::from_meter(0.0));
+        let thickest = foil
+            .iter()
+            .fold(0.0, |max, p| f64::max(max, p.1.to_meter()));
+        assert_eq!(format!("{:.3}", 2.0 * thickest), "0.144");
+        assert_eq!(geometry.wireframe().len(), APPENDAGE_STATIONS + 3);
+
+        // Swept aft, the centroid moves aft by the sweep of the centroid depth.
+        let swept = AppendageGeometry::new(
+            Length::from_meter(1.2),
+            Length::from_meter(0.6),
+            Length::from_meter(1.5),
+            Length::from_meter(3.0),
+            Length::from_meter(0.0),
+            45.0,
+            Foil::symmetrical(APPENDAGE_THICKNESS),
+        );
+        assert_eq!(format!("{:.3}", swept.centroid().to_meter()), "4.133");
+
+        let mut rudder = Rudder::new(
+            Length::from_meter(1.2),
+            Length::from_meter(0.5),
+            Length::from_meter(0.3),
+            Area::from_meter2(0.08),
+        );
+        rudder.set_stock(Length::from_meter(7.0));
+        let blade = rudder.geometry().unwrap();
+        assert_eq!(
+            format!(
+                "{:.3}",
+                blade.leading_edge(Length::from_meter(0.0)).to_meter()
+            ),
+            "6.917"
+        );
+        let mut plane = LateralPlane::new();
+        plane.add_appendage(&geometry);
+        plane.add_appendage(&blade);
+        assert_eq!(format!("{:.2}", plane.area().to_meter2()), "1.83");
+    }
+
+    #[test]
+    fn appendage_profile() {
+        let mut keel = Keel::new(
+            KeelType::Fin,
+            Length::from_meter(1.0),
+            Length::from_meter(0.6),
+            Length::from_meter(1.0),
+            Weight::from_kilogram(500.0),
+        );
+        keel.set_leading_edge(Length::from_meter(1.5));
+        let profile = keel.geometry().unwrap().profile();
+        // Root leading edge, tip leading and trailing edges, root trailing edge.
+        assert_eq!(profile.len(), 4);
+        assert_eq!(profile[0].0.to_meter(), 1.5);
+        assert_eq!(profile[2].0.to_meter(), 2.1);
+        assert_eq!(profile[2].1.to_meter(), -1.0);
+        assert_eq!(profile[3].0.to_meter(), 2.5);
+    }
+}
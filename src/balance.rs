@@ -0,0 +1,271 @@
+use super::appendage::AppendageGeometry;
+use super::boat::Boat;
+use super::rig::SailPoint;
+use super::si::{Area, Length};
+use std::fmt;
+
+/// Fraction of the DWL by draft rectangle taken by the canoe body profile.
+pub const CANOE_BODY_PROFILE_COEFFICIENT: f64 = 0.65;
+
+/// Recommended lead as a fraction of the DWL.
+/// Larsson, L. and Eliasson, R. E. (2000) Principles of yacht design. International Marine.
+pub const LEAD_RANGE: (f64, f64) = (0.05, 0.15);
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LATERAL PLANE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Lateral plane
+///
+/// Underwater profile as areas with the position of their centroids aft of the forward
+/// perpendicular. The CLR is taken as the geometric center of the profile.
+#[derive(Debug, Clone, Default)]
+pub struct LateralPlane {
+    parts: Vec<(Area, Length)>,
+}
+
+#[allow(dead_code)]
+impl LateralPlane {
+    pub fn new() -> LateralPlane {
+        LateralPlane::default()
+    }
+
+    /// Canoe body, approximated by a fraction of the DWL by draft rectangle centered on the
+    /// LCB (or the middle of the DWL when the LCB is not informed), plus the keels and the
+    /// rudder from their geometry when positioned.
+    pub fn from_boat(boat: &Boat) -> LateralPlane {
+        let dwl = boat.dwl().to_meter();
+        let area = CANOE_BODY_PROFILE_COEFFICIENT * dwl * boat.draft().to_meter();
+        let x = boat.lcb().unwrap_or(50.0) / 100.0 * dwl;
+        let mut plane = LateralPlane::new();
+        plane.add(Area::from_meter2(area), Length::from_meter(x));
+        if let Some(keel) = boat.keel() {
+            if let Some(geometry) = keel.geometry() {
+                for _ in 0..keel.count() {
+                    plane.add_appendage(&geometry);
+                }
+            }
+        }
+        if let Some(geometry) = boat.rudder().and_then(|r| r.geometry()) {
+            plane.add_appendage(&geometry);
+        }
+        plane
+    }
+
+    /// Add a part (keel, rudder, skeg) of the profile.
+    pub fn add(&mut self, area: Area, centroid: Length) {
+        self.parts.push((area, centroid));
+    }
+
+    /// Add an appendage from its geometry.
+    pub fn add_appendage(&mut self, appendage: &AppendageGeometry) {
+        self.add(appendage.lateral_area(), appendage.centroid());
+    }
+
+    pub fn parts(&self) -> &[(Area, Length)] {
+        &self.parts
+    }
+
+    /// Lateral area.
+    pub fn area(&self) -> Area {
+        Area::from_meter2(self.parts.iter().map(|(a, _)| a.to_meter2()).sum())
+    }
+
+    /// CLR (center of lateral resistance) aft of the forward perpendicular.
+    pub fn center_of_lateral_resistance(&self) -> Option<Length> {
+        let area = self.area().to_meter2();
+        if area <= 0.0 {
+            return None;
+        }
+        let moment: f64 = self
+            .parts
+            .iter()
+            .map(|(a, x)| a.to_meter2() * x.to_meter())
+            .sum();
+        Some(Length::from_meter(moment / area))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// BALANCE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Helm character.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum HelmCharacter {
+    /// Too little lead, the boat tends to round up.
+    WeatherHelm,
+    Balanced,
+    /// Too much lead, the boat tends to bear away.
+    LeeHelm,
+}
+
+impl fmt::Display for HelmCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HelmCharacter::WeatherHelm => write!(f, "Likely weather helm"),
+            HelmCharacter::Balanced => write!(f, "Balanced"),
+            HelmCharacter::LeeHelm => write!(f, "Likely lee helm"),
+        }
+    }
+}
+
+/// Balance
+///
+/// Lead = (CLR - CE) / DWL, positive with the CE forward of the CLR.
+/// The geometric centers move when sailing, the lead only makes up for it.
+pub struct Balance {
+    center_of_effort: SailPoint,
+    center_of_lateral_resistance: Length,
+    lead: f64,
+    helm_character: HelmCharacter,
+}
+
+#[allow(dead_code)]
+impl Balance {
+    pub fn new(boat: &Boat, center_of_effort: SailPoint, plane: &LateralPlane) -> Option<Balance> {
+        Balance::with_range(boat, center_of_effort, plane, LEAD_RANGE)
+    }
+
+    /// Balance with a recommended lead range.
+    pub fn with_range(
+        boat: &Boat,
+        center_of_effort: SailPoint,
+        plane: &LateralPlane,
+        range: (f64, f64),
+    ) -> Option<Balance> {
+        let center_of_lateral_resistance = plane.center_of_lateral_resistance()?;
+        let lead = (center_of_lateral_resistance.to_meter() - center_of_effort.x.to_meter())
+            / boat.dwl().to_meter();
+        Some(Balance {
+            center_of_effort,
+            center_of_lateral_resistance,
+            lead,
+            helm_character: if lead < range.0 {
+                HelmCharacter::WeatherHelm
+            } else if lead <= range.1 {
+                HelmCharacter::Balanced
+            } else {
+                HelmCharacter::LeeHelm
+            },
+        })
+    }
+
+    /// Needs the rig, with its placement.
+    pub fn from_boat(boat: &Boat, plane: &LateralPlane) -> Option<Balance> {
+        Balance::new(boat, boat.rig()?.center_of_effort()?, plane)
+    }
+
+    /// CE (center of effort).
+    pub fn center_of_effort(&self) -> SailPoint {
+        self.center_of_effort
+    }
+
+    /// CLR (center of lateral resistance) aft of the forward perpendicular.
+    pub fn center_of_lateral_resistance(&self) -> Length {
+        self.center_of_lateral_resistance
+    }
+
+    /// Lead as a fraction of the DWL.
+    pub fn lead(&self) -> f64 {
+        self.lead
+    }
+
+    pub fn character(&self) -> HelmCharacter {
+        self.helm_character
+    }
+}
+
+impl fmt::Display for Balance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Balance]\n\
+            \tCE:   {}\n\
+            \tCLR:  x: {:.3}m\n\
+            \tLead: {:.1}% [{}]",
+            self.center_of_effort,
+            self.center_of_lateral_resistance.to_meter(),
+            self.lead * 100.0,
+            self.helm_character
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn balance() {
+        let mut boat = Boat::new("Sloop".to_string());
+        boat.set_dwl(Length::from_meter(8.0));
+        boat.set_draft(Length::from_meter(0.5));
+        let mut plane = LateralPlane::from_boat(&boat);
+        assert_eq!(format!("{:.2}", plane.area().to_meter2()), "2.60");
+        plane.add(Area::from_meter2(2.6), Length::from_meter(4.4));
+        assert_eq!(
+            format!(
+                "{:.2}",
+                plane.center_of_lateral_resistance().unwrap().to_meter()
+            ),
+            "4.20"
+        );
+
+        let ce = |x| SailPoint::new(Length::from_meter(x), Length::from_meter(4.0));
+        let balance = Balance::new(&boat, ce(3.4), &plane).unwrap();
+        assert_eq!(format!("{:.3}", balance.lead()), "0.100");
+        assert_eq!(balance.character(), HelmCharacter::Balanced);
+        assert_eq!(
+            Balance::new(&boat, ce(4.0), &plane).unwrap().character(),
+            HelmCharacter::WeatherHelm
+        );
+        assert_eq!(
+            Balance::new(&boat, ce(2.6), &plane).unwrap().character(),
+            HelmCharacter::LeeHelm
+        );
+        assert!(Balance::from_boat(&boat, &plane).is_none());
+    }
+
+    #[test]
+    fn lateral_plane_appendages() {
+        use crate::appendage::{Keel, KeelType, Rudder};
+        use crate::si::Weight;
+
+        let m = Length::from_meter;
+        let mut boat = Boat::new("Sloop".to_string());
+        boat.set_dwl(m(8.0));
+        boat.set_draft(m(0.5));
+        // Canoe body 2.6m2 at 4m.
+        let mut keel = Keel::new(
+            KeelType::Fin,
+            m(1.2),
+            m(0.8),
+            m(1.5),
+            Weight::from_kilogram(1000.0),
+        );
+        keel.set_leading_edge(m(3.0));
+        boat.set_keel(keel);
+        // Keel 1.5m2, centroid 3.0 + (1.44 + 0.96 + 0.64) / 6.0 = 3.507m.
+        let plane = LateralPlane::from_boat(&boat);
+        assert_eq!(plane.parts().len(), 2);
+        assert_eq!(format!("{:.2}", plane.area().to_meter2()), "4.10");
+        assert_eq!(format!("{:.3}", plane.parts()[1].1.to_meter()), "3.507");
+
+        // Rudder 0.45m2, the leading edge at 7.6 - 0.2 * 0.5 = 7.5m, centroid 7.726m.
+        let mut rudder = Rudder::new(m(1.0), m(0.5), m(0.4), Area::from_meter2(0.09));
+        rudder.set_stock(m(7.6));
+        boat.set_rudder(rudder);
+        let plane = LateralPlane::from_boat(&boat);
+        assert_eq!(format!("{:.2}", plane.area().to_meter2()), "4.55");
+        // (2.6 * 4.0 + 1.5 * 3.507 + 0.45 * 7.726) / 4.55
+        assert_eq!(
+            format!(
+                "{:.3}",
+                plane.center_of_lateral_resistance().unwrap().to_meter()
+            ),
+            "4.206"
+        );
+    }
+}
@@ -1,7 +1,28 @@
-use super::si::{Area, Length, Weight};
+use super::appendage::{Keel, Rudder};
+use super::balance::LateralPlane;
+use super::diagnostics::Diagnostics;
+use super::hydrostatics::{Hydrostatics, Immersion, Water};
+use super::loading::{LoadCondition, LoadConditionKind};
+use super::propulsion::Engine;
+use super::resistance::{HullType, ResistanceCurve};
+use super::rig::Rig;
+use super::si::{Area, Length, Speed, Volume, Weight, GRAVITY};
+use super::stability::{
+    AvsEstimate, DesignAssessment, InitialStability, InitialStabilityCharacter, StixInputs,
+};
+use super::trailer::{RoadLimits, Trailerability};
+use super::weights::WeightSchedule;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Sea water density (kg/m3).
+pub const SEA_WATER_DENSITY: f64 = 1025.0;
 
 /// BOAT
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Boat {
     /// Boat name.
     name: String,
@@ -24,11 +45,106 @@ pub struct Boat {
     /// Maximum beam.
     b_max: Length,
 
+    /// BWL
+    ///
+    /// Beam at the design water line.
+    bwl: Length,
+
+    /// Draft
+    ///
+    /// Hull (canoe body) draft, does not include keel or rudder.
+    draft: Length,
+
     /// Displacement
     displacement: Weight,
 
     /// Sail area.
+    ///
+    /// Used when the rig is not informed.
     sail_area: Area,
+
+    /// Rig
+    ///
+    /// When informed the sail area is derived from the I, J, P and E measurements.
+    #[serde(default)]
+    rig: Option<Rig>,
+
+    /// Keel
+    #[serde(default)]
+    keel: Option<Keel>,
+
+    /// Rudder
+    #[serde(default)]
+    rudder: Option<Rudder>,
+
+    /// Auxiliary engine
+    #[serde(default)]
+    engine: Option<Engine>,
+
+    /// Weight schedule
+    ///
+    /// Weights and centers estimate, checked against the displacement and LCB.
+    #[serde(default)]
+    weight_schedule: WeightSchedule,
+
+    /// Wetted surface
+    ///
+    /// Wetted surface area of the hull, keel and rudder.
+    /// When not informed it is estimated from the principal dimensions, plus the keel.
+    wetted_surface: Option<Area>,
+
+    /// Cp (Prismatic coefficient)
+    ///
+    /// Ratio of the displaced volume to the volume of a prism with the length of the DWL and
+    /// the cross section of the maximum immersed section.
+    cp: Option<f64>,
+
+    /// Cb (Block coefficient)
+    ///
+    /// Ratio of the displaced volume to the volume of a block with the DWL, BWL and hull draft.
+    cb: Option<f64>,
+
+    /// Cm (Midship section coefficient)
+    ///
+    /// Ratio of the maximum immersed section area to the rectangle with the BWL and hull draft.
+    cm: Option<f64>,
+
+    /// Cw (Waterplane coefficient)
+    ///
+    /// Ratio of the waterplane area to the rectangle with the DWL and BWL.
+    cw: Option<f64>,
+
+    /// LCB (Longitudinal center of buoyancy)
+    ///
+    /// Position as a percentage of the DWL aft of the forward perpendicular.
+    lcb: Option<f64>,
+
+    /// LCF (Longitudinal center of flotation)
+    ///
+    /// Position as a percentage of the DWL aft of the forward perpendicular.
+    lcf: Option<f64>,
+
+    /// VCG (Vertical center of gravity)
+    ///
+    /// Estimated height of the center of gravity above the bottom of the canoe body (KG).
+    vcg: Option<Length>,
+
+    /// Target SLR (speed-length ratio)
+    ///
+    /// Speed, in knots divided by the square root of DWL in feet, the hull is optimized for.
+    target_slr: f64,
+
+    /// Load conditions
+    ///
+    /// Displacement and CG of the boat empty, as designed and fully loaded.
+    #[serde(default)]
+    load_conditions: Vec<LoadCondition>,
+
+    /// Hull type
+    ///
+    /// Sailing yacht or displacement motor hull, chooses the resistance method.
+    #[serde(default)]
+    hull_type: HullType,
 }
 
 #[allow(dead_code)]
@@ -36,15 +152,42 @@ impl Boat {
     /// Create default boat.
     pub fn new(name: String) -> Boat {
         Boat {
-            name: name,
+            name,
             loa: Length::from_meter(4.0),
             dwl: Length::from_meter(3.8),
             b_max: Length::from_meter(1.2),
+            bwl: Length::from_meter(1.0),
+            draft: Length::from_meter(0.15),
             displacement: Weight::from_kilogram(80.0),
             sail_area: Area::from_meter2(6.0),
+            rig: None,
+            keel: None,
+            rudder: None,
+            engine: None,
+            weight_schedule: WeightSchedule::new(),
+            wetted_surface: None,
+            cp: None,
+            cb: None,
+            cm: None,
+            cw: None,
+            lcb: None,
+            lcf: None,
+            vcg: None,
+            target_slr: 1.34,
+            load_conditions: Vec::new(),
+            hull_type: HullType::SailingYacht,
         }
     }
 
+    /// Boat name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    #[allow(dead_code)]
+    pub fn set_name(&mut self, val: String) {
+        self.name = val;
+    }
+
     /// LOA (lenght overall).
     pub fn loa(&self) -> Length {
         self.loa
@@ -72,6 +215,24 @@ impl Boat {
         self.b_max = val;
     }
 
+    /// BWL (beam at the design water line).
+    pub fn bwl(&self) -> Length {
+        self.bwl
+    }
+    #[allow(dead_code)]
+    pub fn set_bwl(&mut self, val: Length) {
+        self.bwl = val;
+    }
+
+    /// Draft (hull draft).
+    pub fn draft(&self) -> Length {
+        self.draft
+    }
+    #[allow(dead_code)]
+    pub fn set_draft(&mut self, val: Length) {
+        self.draft = val;
+    }
+
     /// Displacement.
     pub fn displacement(&self) -> Weight {
         self.displacement
@@ -81,514 +242,2864 @@ impl Boat {
         self.displacement = val;
     }
 
-    /// Sail area.
+    /// Sail area, derived from the rig when informed.
     pub fn sail_area(&self) -> Area {
-        self.sail_area
+        match &self.rig {
+            Some(rig) => rig.sail_area(),
+            None => self.sail_area,
+        }
     }
     #[allow(dead_code)]
     pub fn set_sail_area(&mut self, val: Area) {
         self.sail_area = val;
     }
-}
 
-impl fmt::Display for Boat {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "[{}]\n\
-            \tLOA:         {:>9.3}m\n\
-            \tDWL:         {:>9.3}m\n\
-            \tBeam:        {:>9.3}m\n\
-            \tDisplacment: {:>9.0}kg\n\
-            \tSail area:   {:>9.1}m2",
-            self.name,
-            self.loa.to_meter(),
-            self.dwl.to_meter(),
-            self.b_max.to_meter(),
-            self.displacement.to_kilogram(),
-            self.sail_area.to_meter2()
-        )
+    /// Keel.
+    pub fn keel(&self) -> Option<Keel> {
+        self.keel
     }
-}
-
-///////////////////////////////////////////////////////////////////////////////////////////////////
-// RATIOS
-///////////////////////////////////////////////////////////////////////////////////////////////////
-/// Ratios
-///
-/// The ratios are nondimensional, which simply means that they have no units.
-/// The validity of the comparasions may not hold when one of the boats being compared is shorter than 25ft or longer than 75ft,
-/// but within that range, valid comparaisons can be made.
-/// Perry, R. H. (2008) Yatch design according to perry. International Marine. (pp.10)
-pub struct Ratios {
-    length_beam_ratio: LengthBeamRatio,
-    displacement_lenght_ratio: DisplacementLengthRatio,
-    sail_area_displacement: SailAreaDisplacementRatio,
-}
-
-impl Ratios {
-    pub fn new(boat: &Boat) -> Ratios {
-        Ratios {
-            length_beam_ratio: LengthBeamRatio::from_boat(boat),
-            displacement_lenght_ratio: DisplacementLengthRatio::from_boat(boat),
-            sail_area_displacement: SailAreaDisplacementRatio::from_boat(boat),
-        }
+    #[allow(dead_code)]
+    pub fn set_keel(&mut self, val: Keel) {
+        self.keel = Some(val);
     }
-}
 
-impl fmt::Display for Ratios {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "[Ratio]\n\
-            \tL/B:   {:>5}\n\
-            \tD/L:   {:>5}\n\
-            \tSA/D:  {:>5}\n
-            ",
-            self.length_beam_ratio, self.displacement_lenght_ratio, self.sail_area_displacement
-        )
+    /// Rudder.
+    pub fn rudder(&self) -> Option<Rudder> {
+        self.rudder
+    }
+    #[allow(dead_code)]
+    pub fn set_rudder(&mut self, val: Rudder) {
+        self.rudder = Some(val);
     }
-}
 
-///////////////////////////////////////////////////////////////////////////////////////////////////
-// LBR - LENGHT TO BEAM RATIO
-///////////////////////////////////////////////////////////////////////////////////////////////////
-/// Beam character.
-#[derive(PartialEq, Debug)]
-pub enum BeamCharacter {
-    Narrow,
-    ModerateNarrow,
-    Moderate,
-    ModerateBeamy,
-    Beamy,
-}
+    /// Auxiliary engine.
+    pub fn engine(&self) -> Option<Engine> {
+        self.engine
+    }
+    #[allow(dead_code)]
+    pub fn set_engine(&mut self, val: Engine) {
+        self.engine = Some(val);
+    }
 
-impl fmt::Display for BeamCharacter {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            BeamCharacter::Narrow => write!(f, "Narrow"),
-            BeamCharacter::ModerateNarrow => write!(f, "Moderate narrow"),
-            BeamCharacter::Moderate => write!(f, "Moderate"),
-            BeamCharacter::ModerateBeamy => write!(f, "Moderate beamy"),
-            BeamCharacter::Beamy => write!(f, "Beamy"),
-        }
+    /// Weight schedule.
+    pub fn weight_schedule(&self) -> &WeightSchedule {
+        &self.weight_schedule
+    }
+    #[allow(dead_code)]
+    pub fn set_weight_schedule(&mut self, val: WeightSchedule) {
+        self.weight_schedule = val;
     }
-}
 
-/// LBR - L/B (lenght-to-Beam Ratio)
-/// This ratio is useful for determining whether a boat is beamy or narrow.
-pub struct LengthBeamRatio {
-    value: f64,
-    beam_character: BeamCharacter,
-}
+    /// Ballast ratio, ballast over displacement.
+    pub fn ballast_ratio(&self) -> Option<f64> {
+        Some(self.keel?.ballast().to_kilogram() / self.displacement.to_kilogram())
+    }
 
-impl LengthBeamRatio {
-    pub fn from_boat(boat: &Boat) -> LengthBeamRatio {
-        let value = boat.loa.to_meter() / boat.b_max.to_meter();
-        LengthBeamRatio {
-            value: value,
-            beam_character: if value >= 4.00 {
-                BeamCharacter::Narrow
-            } else if value >= 3.65 {
-                BeamCharacter::ModerateNarrow
-            } else if value >= 3.30 {
-                BeamCharacter::Moderate
-            } else if value > 3.00 {
-                BeamCharacter::ModerateBeamy
-            } else {
-                BeamCharacter::Beamy
-            },
+    /// Draft of the canoe body plus the keel (centerboard down).
+    pub fn total_draft(&self) -> Length {
+        match self.keel {
+            Some(keel) => self.draft + keel.span(),
+            None => self.draft,
         }
     }
-}
 
-impl fmt::Display for LengthBeamRatio {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:.2} [{}]", self.value, self.beam_character)
+    /// Rig.
+    pub fn rig(&self) -> Option<&Rig> {
+        self.rig.as_ref()
+    }
+    #[allow(dead_code)]
+    pub fn set_rig(&mut self, val: Rig) {
+        self.rig = Some(val);
     }
-}
 
-///////////////////////////////////////////////////////////////////////////////////////////////////
-// DLR - DISPLACEMENTE TO LENGTH RATIO
-///////////////////////////////////////////////////////////////////////////////////////////////////
-/// Displacement character.
-#[derive(PartialEq, Debug)]
-pub enum DisplacementCharacter {
-    Ultralight,
-    Light,
-    Moderate,
-    Heavy,
-    Ultraheavy,
-}
+    /// Wetted surface, informed or estimated (canoe body plus keel).
+    pub fn wetted_surface(&self) -> Area {
+        self.wetted_surface.unwrap_or_else(|| {
+            let keel = self.keel.map_or(0.0, |k| k.wetted_surface().to_meter2());
+            Area::from_meter2(self.estimated_wetted_surface().to_meter2() + keel)
+        })
+    }
+    #[allow(dead_code)]
+    pub fn set_wetted_surface(&mut self, val: Area) {
+        self.wetted_surface = Some(val);
+    }
 
-impl fmt::Display for DisplacementCharacter {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            DisplacementCharacter::Ultralight => write!(f, "Ultralight"),
-            DisplacementCharacter::Light => write!(f, "Light"),
-            DisplacementCharacter::Moderate => write!(f, "Moderate"),
-            DisplacementCharacter::Heavy => write!(f, "Heavy"),
-            DisplacementCharacter::Ultraheavy => write!(f, "Ultraheavy"),
+    /// Cp (prismatic coefficient), entered or derived from Cb / Cm.
+    pub fn cp(&self) -> Option<f64> {
+        match (self.cp, self.cb, self.cm) {
+            (Some(cp), _, _) => Some(cp),
+            (None, Some(cb), Some(cm)) => Some(cb / cm),
+            _ => None,
         }
     }
-}
+    #[allow(dead_code)]
+    pub fn set_cp(&mut self, val: f64) {
+        self.cp = Some(val);
+    }
 
-/// DLR - D/L (displacment-to-lenght Ratio)
-/// A DLR less than 200 is indicative of a racing boat, while a DLR greater than 300 or so is indicative of a heavy cruising boat.
-/// This ratio is useful for determining whether a boat is heavy or light.
-pub struct DisplacementLengthRatio {
-    value: f64,
-    displacement_character: DisplacementCharacter,
-}
+    /// Cb (block coefficient), entered or derived from Cp * Cm.
+    pub fn cb(&self) -> Option<f64> {
+        match (self.cp, self.cb, self.cm) {
+            (_, Some(cb), _) => Some(cb),
+            (Some(cp), None, Some(cm)) => Some(cp * cm),
+            _ => None,
+        }
+    }
+    #[allow(dead_code)]
+    pub fn set_cb(&mut self, val: f64) {
+        self.cb = Some(val);
+    }
 
-impl DisplacementLengthRatio {
-    pub fn from_boat(boat: &Boat) -> DisplacementLengthRatio {
-        // Long ton = diplacement(lb) / 2240
-        // DLR = (diplacement(lb) / 2240) / ((0.01 * LWL(ft)) exp 3)
-        // D/L of 260 is considered the "middle" of the overall displacemente range by Perry.
-        // Most full kell boats, by virtue of the volume in their keels, have D/L over 325.
-        let value = boat.displacement.to_long_ton() / (boat.dwl.to_foot() * 0.01).powf(3.0);
-        DisplacementLengthRatio {
-            value: value,
-            displacement_character: if value < 90.0 {
-                DisplacementCharacter::Ultralight
-            } else if value < 180.0 {
-                DisplacementCharacter::Light
-            } else if value < 270.0 {
-                DisplacementCharacter::Moderate
-            } else if value <= 360.0 {
-                DisplacementCharacter::Heavy
-            } else {
-                DisplacementCharacter::Ultraheavy
-            },
+    /// Cm (midship section coefficient), entered or derived from Cb / Cp.
+    pub fn cm(&self) -> Option<f64> {
+        match (self.cp, self.cb, self.cm) {
+            (_, _, Some(cm)) => Some(cm),
+            (Some(cp), Some(cb), None) => Some(cb / cp),
+            _ => None,
         }
     }
-}
+    #[allow(dead_code)]
+    pub fn set_cm(&mut self, val: f64) {
+        self.cm = Some(val);
+    }
 
-impl fmt::Display for DisplacementLengthRatio {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:.0} [{}]", self.value, self.displacement_character)
+    /// Cw (waterplane coefficient).
+    pub fn cw(&self) -> Option<f64> {
+        self.cw
+    }
+    #[allow(dead_code)]
+    pub fn set_cw(&mut self, val: f64) {
+        self.cw = Some(val);
     }
-}
 
-///////////////////////////////////////////////////////////////////////////////////////////////////
-// SADR - SA/D - SAIL AREA DISPLACEMENTE RATIO
-///////////////////////////////////////////////////////////////////////////////////////////////////
-/// Sail area character.
-/// Motorsailers	13 - 14
-/// Slow auxiliary sailboats	14 - 15
-/// Average offshore cruisers	15 - 16
-/// Coastal cruisers	16 - 17
-/// Racing yachts	17 - 19
-/// Ultra light racers, class racers, daysailers	20+
-///
-/// A typical cruising boat today will hava a SA/D of 17.5 to 18.5.
-/// This is enough power to drive the boat reasonably well in light air while not overpowering it too quickly when the breeze picks up.
-#[derive(PartialEq, Debug)]
-pub enum SailAreaCharacter {
-    Low,
-    Moderate,
-    High,
-}
+    /// Waterplane area, from Cw.
+    pub fn waterplane_area(&self) -> Option<Area> {
+        let cw = self.cw?;
+        Some(Area::from_meter2(
+            cw * self.dwl.to_meter() * self.bwl.to_meter(),
+        ))
+    }
 
-impl fmt::Display for SailAreaCharacter {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            SailAreaCharacter::Low => write!(f, "Low"),
-            SailAreaCharacter::Moderate => write!(f, "Moderate"),
-            SailAreaCharacter::High => write!(f, "High"),
-        }
+    /// PPI (pounds per inch immersion)
+    ///
+    /// Weight needed to sink the boat one inch in sea water.
+    pub fn ppi(&self) -> Option<Weight> {
+        Some(self.immersion(Water::Salt)?.weight_per_inch())
     }
-}
 
-/// SADR - SA/D (sail area to displacemet ratio)
-pub struct SailAreaDisplacementRatio {
-    value: f64,
-    sail_area_character: SailAreaCharacter,
-}
+    /// Immersion, from the waterplane area.
+    pub fn immersion(&self, water: Water) -> Option<Immersion> {
+        Immersion::from_boat(self, water)
+    }
 
-impl SailAreaDisplacementRatio {
-    pub fn from_boat(boat: &Boat) -> SailAreaDisplacementRatio {
-        let value = boat.sail_area.to_meter2() / boat.displacement.to_long_ton().powf(2.0 / 3.0);
-        SailAreaDisplacementRatio {
-            value: value,
-            sail_area_character: if value < 15.0 {
-                SailAreaCharacter::Low
-            } else if value <= 20.0 {
-                SailAreaCharacter::Moderate
-            } else {
-                SailAreaCharacter::High
-            },
-        }
+    /// BM (transverse metacentric radius), approximated from Cw.
+    ///
+    /// BM = It / V, with the waterplane transverse moment of inertia approximated by
+    /// It = (0.096 + 0.89 * Cw^2) / 12 * DWL * BWL^3.
+    pub fn bm(&self) -> Option<Length> {
+        let cw = self.cw?;
+        let inertia =
+            (0.096 + 0.89 * cw.powi(2)) / 12.0 * self.dwl.to_meter() * self.bwl.to_meter().powi(3);
+        Some(Length::from_meter(
+            inertia / self.displaced_volume().to_meter3(),
+        ))
     }
-}
 
-impl fmt::Display for SailAreaDisplacementRatio {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:.1} [{}]", self.value, self.sail_area_character)
+    /// LCB (% of DWL aft of forward perpendicular).
+    pub fn lcb(&self) -> Option<f64> {
+        self.lcb
+    }
+    #[allow(dead_code)]
+    pub fn set_lcb(&mut self, val: f64) {
+        self.lcb = Some(val);
+    }
+
+    /// LCF (% of DWL aft of forward perpendicular).
+    pub fn lcf(&self) -> Option<f64> {
+        self.lcf
+    }
+    #[allow(dead_code)]
+    pub fn set_lcf(&mut self, val: f64) {
+        self.lcf = Some(val);
+    }
+
+    /// VCG (height above the bottom of the canoe body).
+    pub fn vcg(&self) -> Option<Length> {
+        self.vcg
+    }
+    #[allow(dead_code)]
+    pub fn set_vcg(&mut self, val: Length) {
+        self.vcg = Some(val);
+    }
+
+    /// Load conditions.
+    pub fn load_conditions(&self) -> &[LoadCondition] {
+        &self.load_conditions
+    }
+
+    /// Load condition of a kind, when informed.
+    pub fn load_condition(&self, kind: LoadConditionKind) -> Option<&LoadCondition> {
+        self.load_conditions.iter().find(|c| c.kind() == kind)
+    }
+
+    /// Add a load condition, replacing the one of the same kind.
+    #[allow(dead_code)]
+    pub fn set_load_condition(&mut self, val: LoadCondition) {
+        match self
+            .load_conditions
+            .iter_mut()
+            .find(|c| c.kind() == val.kind())
+        {
+            Some(condition) => *condition = val,
+            None => self.load_conditions.push(val),
+        }
+    }
+
+    /// Boat floating in a load condition, so ratios and hydrostatics can be computed for it.
+    pub fn in_condition(&self, kind: LoadConditionKind) -> Option<Boat> {
+        Some(self.load_condition(kind)?.apply(self))
+    }
+
+    /// ISO design category assessment, the inputs come from the GZ curve and the sail plan.
+    pub fn design_category(&self, inputs: &StixInputs) -> DesignAssessment {
+        DesignAssessment::new(self, inputs)
+    }
+
+    /// Beam, towed weight and draft against the road limits.
+    pub fn trailerability(&self, limits: &RoadLimits) -> Trailerability {
+        Trailerability::new(self, limits)
+    }
+
+    /// KB (height of the center of buoyancy), approximated from Cb and Cw (Morrish).
+    ///
+    /// KB = T * (5/6 - Cb / (3 * Cw))
+    /// Uses the Cb implied by the displacement when Cb is not informed.
+    pub fn kb(&self) -> Option<Length> {
+        let cw = self.cw?;
+        let cb = self.cb().unwrap_or_else(|| self.implied_cb());
+        Some(Length::from_meter(
+            self.draft.to_meter() * (5.0 / 6.0 - cb / (3.0 * cw)),
+        ))
+    }
+
+    /// Check the form coefficients are in range and, when the three are entered,
+    /// that Cb = Cp * Cm.
+    pub fn check_coefficients(&self) -> Result<(), CoefficientError> {
+        for (name, val) in &[
+            ("Cp", self.cp),
+            ("Cb", self.cb),
+            ("Cm", self.cm),
+            ("Cw", self.cw),
+        ] {
+            if let Some(val) = *val {
+                if val <= 0.0 || val > 1.0 {
+                    return Err(CoefficientError::OutOfRange { name, value: val });
+                }
+            }
+        }
+        if let (Some(cp), Some(cb), Some(cm)) = (self.cp, self.cb, self.cm) {
+            if (cp * cm - cb).abs() > COEFFICIENT_TOLERANCE {
+                return Err(CoefficientError::Inconsistent { cp, cb, cm });
+            }
+        }
+        Ok(())
+    }
+
+    /// Save the boat to a JSON project file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ProjectError> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a boat from a JSON project file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Boat, ProjectError> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Validate the boat, reporting impossible (errors) and implausible (warnings) values.
+    pub fn validate(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        let values = [
+            ("LOA", self.loa.to_meter()),
+            ("DWL", self.dwl.to_meter()),
+            ("Beam", self.b_max.to_meter()),
+            ("BWL", self.bwl.to_meter()),
+            ("Draft", self.draft.to_meter()),
+            ("Displacement", self.displacement.to_kilogram()),
+            ("Sail area", self.sail_area().to_meter2()),
+            ("Wetted surface", self.wetted_surface().to_meter2()),
+        ];
+        let mut positive = true;
+        for (name, val) in &values {
+            if *val <= 0.0 {
+                diagnostics.error(format!("{} must be greater than zero", name));
+                positive = false;
+            }
+        }
+        if self.dwl.to_meter() > self.loa.to_meter() {
+            diagnostics.error("DWL exceeds LOA".to_string());
+        }
+        if self.bwl.to_meter() > self.b_max.to_meter() {
+            diagnostics.error("BWL exceeds maximum beam".to_string());
+        }
+        if let Err(err) = self.check_coefficients() {
+            diagnostics.error(err.to_string());
+        }
+        for (name, val) in &[("LCB", self.lcb), ("LCF", self.lcf)] {
+            if let Some(val) = *val {
+                if !(0.0..=100.0).contains(&val) {
+                    diagnostics.error(format!("{} must be between 0% and 100% of DWL", name));
+                }
+            }
+        }
+        if let Some(ballast_ratio) = self.ballast_ratio() {
+            if ballast_ratio >= 1.0 {
+                diagnostics.error("Ballast exceeds displacement".to_string());
+            }
+        }
+        // Ratios are meaningless without positive dimensions.
+        if !positive {
+            return diagnostics;
+        }
+
+        let ratios = Ratios::new(self);
+        let lb = ratios.length_beam_ratio.value;
+        if !(2.0..=6.0).contains(&lb) {
+            diagnostics.warning(format!("L/B of {:.2} is implausible for a monohull", lb));
+        }
+        let dl = ratios.displacement_lenght_ratio.value;
+        if !(40.0..=500.0).contains(&dl) {
+            diagnostics.warning(format!("D/L of {:.0} is implausible", dl));
+        }
+        let sad = ratios.sail_area_displacement.value;
+        if sad > 30.0 {
+            diagnostics.warning(format!(
+                "SA/D of {:.1} is implausibly high for the displacement",
+                sad
+            ));
+        } else if sad < 8.0 {
+            diagnostics.warning(format!(
+                "SA/D of {:.1} is implausibly low for the displacement",
+                sad
+            ));
+        }
+        let cb = self.implied_cb();
+        if !(0.2..=0.7).contains(&cb) {
+            diagnostics.warning(format!(
+                "Displacement implies a Cb of {:.2}, check DWL, BWL, draft and displacement",
+                cb
+            ));
+        }
+        if let Some(discrepancy) = self.displacement_discrepancy() {
+            if discrepancy.abs() > 0.1 {
+                diagnostics.warning(format!(
+                    "Displacement from Cb differs {:.0}% from the entered displacement",
+                    discrepancy * 100.0
+                ));
+            }
+        }
+        if let Some(rudder) = self.rudder {
+            diagnostics.append(rudder.check(&LateralPlane::from_boat(self)));
+        }
+        diagnostics.append(self.weight_schedule.check(self));
+        diagnostics
+    }
+
+    /// Cb implied by the displacement and the DWL, BWL and hull draft.
+    pub fn implied_cb(&self) -> f64 {
+        self.displaced_volume().to_meter3() / self.block_volume().to_meter3()
+    }
+
+    /// Displacement computed from Cb, in sea water.
+    pub fn displacement_from_cb(&self) -> Option<Weight> {
+        let cb = self.cb()?;
+        Some(Weight::from_kilogram(
+            cb * self.block_volume().to_meter3() * SEA_WATER_DENSITY,
+        ))
+    }
+
+    /// Relative difference between the displacement computed from Cb and the entered one.
+    pub fn displacement_discrepancy(&self) -> Option<f64> {
+        let computed = self.displacement_from_cb()?.to_kilogram();
+        let entered = self.displacement.to_kilogram();
+        Some((computed - entered) / entered)
+    }
+
+    /// Volume of the block with DWL, BWL and hull draft.
+    fn block_volume(&self) -> Volume {
+        Volume::from_meter3(self.dwl.to_meter() * self.bwl.to_meter() * self.draft.to_meter())
+    }
+
+    /// Target SLR (speed-length ratio).
+    pub fn target_slr(&self) -> f64 {
+        self.target_slr
+    }
+    #[allow(dead_code)]
+    pub fn set_target_slr(&mut self, val: f64) {
+        self.target_slr = val;
+    }
+
+    /// SLR (speed-length ratio)
+    ///
+    /// Speed in knots divided by the square root of the DWL in feet.
+    pub fn speed_length_ratio(&self, speed: Speed) -> f64 {
+        speed.to_knot() / self.dwl.to_foot().sqrt()
+    }
+
+    /// Fn (Froude number) based on the DWL.
+    ///
+    /// Fn = V / sqrt(g * DWL), nondimensional, equivalent to SLR / 3.36.
+    pub fn froude_number(&self, speed: Speed) -> f64 {
+        speed.to_meter_second() / (GRAVITY * self.dwl.to_meter()).sqrt()
+    }
+
+    /// Speed for a SLR (speed-length ratio).
+    pub fn speed_for_slr(&self, slr: f64) -> Speed {
+        Speed::from_knot(slr * self.dwl.to_foot().sqrt())
+    }
+
+    /// Speed for a Fn (Froude number).
+    pub fn speed_for_froude_number(&self, froude_number: f64) -> Speed {
+        Speed::from_meter_second(froude_number * (GRAVITY * self.dwl.to_meter()).sqrt())
+    }
+
+    /// Hull speed, the speed at SLR of 1.34.
+    pub fn hull_speed(&self) -> Speed {
+        self.speed_for_slr(1.34)
+    }
+
+    /// Target speed, the speed at the target SLR.
+    pub fn target_speed(&self) -> Speed {
+        self.speed_for_slr(self.target_slr)
+    }
+
+    /// Hull type.
+    pub fn hull_type(&self) -> HullType {
+        self.hull_type
+    }
+    #[allow(dead_code)]
+    pub fn set_hull_type(&mut self, val: HullType) {
+        self.hull_type = val;
+    }
+
+    /// Upright resistance curve by the method of the hull type, from the entered dimensions
+    /// and coefficients.
+    pub fn resistance(&self) -> Option<ResistanceCurve> {
+        self.hull_type.resistance_method().from_boat(self)
+    }
+
+    /// Displaced volume in sea water.
+    pub fn displaced_volume(&self) -> Volume {
+        Volume::from_meter3(self.displacement.to_kilogram() / SEA_WATER_DENSITY)
+    }
+
+    /// Wetted surface estimated from principal dimensions (Denny-Mumford).
+    ///
+    /// S = 1.7 * LWL * T + V / T
+    /// Used when there is no hull geometry, it gives the canoe body wetted surface only.
+    pub fn estimated_wetted_surface(&self) -> Area {
+        let lwl = self.dwl.to_meter();
+        let t = self.draft.to_meter();
+        let v = self.displaced_volume().to_meter3();
+        Area::from_meter2(1.7 * lwl * t + v / t)
+    }
+}
+
+/// Project file error.
+#[derive(Debug)]
+pub enum ProjectError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProjectError::Io(err) => write!(f, "Project file error: {}", err),
+            ProjectError::Json(err) => write!(f, "Invalid project file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+impl From<io::Error> for ProjectError {
+    fn from(err: io::Error) -> ProjectError {
+        ProjectError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ProjectError {
+    fn from(err: serde_json::Error) -> ProjectError {
+        ProjectError::Json(err)
+    }
+}
+
+/// Tolerance accepted between Cb and Cp * Cm.
+const COEFFICIENT_TOLERANCE: f64 = 0.01;
+
+/// Form coefficient error.
+#[derive(PartialEq, Debug)]
+pub enum CoefficientError {
+    /// Coefficient must be greater than zero and not greater than one.
+    OutOfRange { name: &'static str, value: f64 },
+    /// Cb must be equal to Cp * Cm.
+    Inconsistent { cp: f64, cb: f64, cm: f64 },
+}
+
+impl fmt::Display for CoefficientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CoefficientError::OutOfRange { name, value } => {
+                write!(f, "{} of {:.3} is out of range (0, 1]", name, value)
+            }
+            CoefficientError::Inconsistent { cp, cb, cm } => write!(
+                f,
+                "Cb of {:.3} is inconsistent with Cp * Cm ({:.3} * {:.3} = {:.3})",
+                cb,
+                cp,
+                cm,
+                cp * cm
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CoefficientError {}
+
+impl fmt::Display for Boat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}]\n\
+            \tLOA:         {:>9.3}m\n\
+            \tDWL:         {:>9.3}m\n\
+            \tBeam:        {:>9.3}m\n\
+            \tBWL:         {:>9.3}m\n\
+            \tDraft:       {:>9.3}m\n\
+            \tDisplacment: {:>9.0}kg\n\
+            \tSail area:   {:>9.1}m2\n\
+            \tWetted surf: {:>9.1}m2",
+            self.name,
+            self.loa.to_meter(),
+            self.dwl.to_meter(),
+            self.b_max.to_meter(),
+            self.bwl.to_meter(),
+            self.draft.to_meter(),
+            self.displacement.to_kilogram(),
+            self.sail_area().to_meter2(),
+            self.wetted_surface().to_meter2()
+        )
     }
 }
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////
-// TEST
+// BUILDER
 ///////////////////////////////////////////////////////////////////////////////////////////////////
-mod test {
+/// Boat validation error.
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+    /// Required dimension not informed.
+    Missing(&'static str),
+    /// Dimension must be greater than zero.
+    NotPositive(&'static str),
+    /// DWL longer than LOA.
+    DwlExceedsLoa,
+    /// BWL wider than maximum beam.
+    BwlExceedsBeam,
+    /// Position (% of DWL) outside the boat.
+    OutOfBoat(&'static str),
+    /// Invalid form coefficient.
+    Coefficient(CoefficientError),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::Missing(name) => write!(f, "{} not informed", name),
+            ValidationError::NotPositive(name) => write!(f, "{} must be greater than zero", name),
+            ValidationError::DwlExceedsLoa => write!(f, "DWL exceeds LOA"),
+            ValidationError::BwlExceedsBeam => write!(f, "BWL exceeds maximum beam"),
+            ValidationError::OutOfBoat(name) => {
+                write!(f, "{} must be between 0% and 100% of DWL", name)
+            }
+            ValidationError::Coefficient(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<CoefficientError> for ValidationError {
+    fn from(err: CoefficientError) -> ValidationError {
+        ValidationError::Coefficient(err)
+    }
+}
+
+/// Boat builder
+///
+/// LOA, DWL, maximum beam, draft, displacement and sail area (or rig) are required,
+/// BWL defaults to the maximum beam.
+pub struct BoatBuilder {
+    name: String,
+    loa: Option<Length>,
+    dwl: Option<Length>,
+    b_max: Option<Length>,
+    bwl: Option<Length>,
+    draft: Option<Length>,
+    displacement: Option<Weight>,
+    sail_area: Option<Area>,
+    rig: Option<Rig>,
+    wetted_surface: Option<Area>,
+    cp: Option<f64>,
+    cb: Option<f64>,
+    cm: Option<f64>,
+    cw: Option<f64>,
+    lcb: Option<f64>,
+    lcf: Option<f64>,
+    vcg: Option<Length>,
+    target_slr: Option<f64>,
+}
+
+#[allow(dead_code)]
+impl BoatBuilder {
+    pub fn new(name: String) -> BoatBuilder {
+        BoatBuilder {
+            name,
+            loa: None,
+            dwl: None,
+            b_max: None,
+            bwl: None,
+            draft: None,
+            displacement: None,
+            sail_area: None,
+            rig: None,
+            wetted_surface: None,
+            cp: None,
+            cb: None,
+            cm: None,
+            cw: None,
+            lcb: None,
+            lcf: None,
+            vcg: None,
+            target_slr: None,
+        }
+    }
+
+    pub fn loa(mut self, val: Length) -> BoatBuilder {
+        self.loa = Some(val);
+        self
+    }
+
+    pub fn dwl(mut self, val: Length) -> BoatBuilder {
+        self.dwl = Some(val);
+        self
+    }
+
+    pub fn b_max(mut self, val: Length) -> BoatBuilder {
+        self.b_max = Some(val);
+        self
+    }
+
+    pub fn bwl(mut self, val: Length) -> BoatBuilder {
+        self.bwl = Some(val);
+        self
+    }
+
+    pub fn draft(mut self, val: Length) -> BoatBuilder {
+        self.draft = Some(val);
+        self
+    }
+
+    pub fn displacement(mut self, val: Weight) -> BoatBuilder {
+        self.displacement = Some(val);
+        self
+    }
+
+    pub fn sail_area(mut self, val: Area) -> BoatBuilder {
+        self.sail_area = Some(val);
+        self
+    }
+
+    pub fn rig(mut self, val: Rig) -> BoatBuilder {
+        self.rig = Some(val);
+        self
+    }
+
+    pub fn wetted_surface(mut self, val: Area) -> BoatBuilder {
+        self.wetted_surface = Some(val);
+        self
+    }
+
+    pub fn cp(mut self, val: f64) -> BoatBuilder {
+        self.cp = Some(val);
+        self
+    }
+
+    pub fn cb(mut self, val: f64) -> BoatBuilder {
+        self.cb = Some(val);
+        self
+    }
+
+    pub fn cm(mut self, val: f64) -> BoatBuilder {
+        self.cm = Some(val);
+        self
+    }
+
+    pub fn cw(mut self, val: f64) -> BoatBuilder {
+        self.cw = Some(val);
+        self
+    }
+
+    pub fn lcb(mut self, val: f64) -> BoatBuilder {
+        self.lcb = Some(val);
+        self
+    }
+
+    pub fn lcf(mut self, val: f64) -> BoatBuilder {
+        self.lcf = Some(val);
+        self
+    }
+
+    pub fn vcg(mut self, val: Length) -> BoatBuilder {
+        self.vcg = Some(val);
+        self
+    }
+
+    pub fn target_slr(mut self, val: f64) -> BoatBuilder {
+        self.target_slr = Some(val);
+        self
+    }
+
+    /// Build the boat, rejecting impossible combinations.
+    pub fn build(self) -> Result<Boat, ValidationError> {
+        let loa = required("LOA", self.loa.map(|v| v.to_meter()))?;
+        let dwl = required("DWL", self.dwl.map(|v| v.to_meter()))?;
+        let b_max = required("Beam", self.b_max.map(|v| v.to_meter()))?;
+        let draft = required("Draft", self.draft.map(|v| v.to_meter()))?;
+        let displacement = required("Displacement", self.displacement.map(|v| v.to_kilogram()))?;
+        let sail_area = required(
+            "Sail area",
+            self.sail_area
+                .or_else(|| self.rig.as_ref().map(|r| r.sail_area()))
+                .map(|v| v.to_meter2()),
+        )?;
+        let bwl = required("BWL", Some(self.bwl.map_or(b_max, |v| v.to_meter())))?;
+        if let Some(wetted_surface) = self.wetted_surface {
+            required("Wetted surface", Some(wetted_surface.to_meter2()))?;
+        }
+        if let Some(vcg) = self.vcg {
+            required("VCG", Some(vcg.to_meter()))?;
+        }
+        if let Some(target_slr) = self.target_slr {
+            required("Target SLR", Some(target_slr))?;
+        }
+        if dwl > loa {
+            return Err(ValidationError::DwlExceedsLoa);
+        }
+        if bwl > b_max {
+            return Err(ValidationError::BwlExceedsBeam);
+        }
+        for (name, val) in &[("LCB", self.lcb), ("LCF", self.lcf)] {
+            if let Some(val) = *val {
+                if !(0.0..=100.0).contains(&val) {
+                    return Err(ValidationError::OutOfBoat(name));
+                }
+            }
+        }
+
+        let mut boat = Boat::new(self.name);
+        boat.loa = Length::from_meter(loa);
+        boat.dwl = Length::from_meter(dwl);
+        boat.b_max = Length::from_meter(b_max);
+        boat.bwl = Length::from_meter(bwl);
+        boat.draft = Length::from_meter(draft);
+        boat.displacement = Weight::from_kilogram(displacement);
+        boat.sail_area = Area::from_meter2(sail_area);
+        boat.rig = self.rig;
+        boat.wetted_surface = self.wetted_surface;
+        boat.cp = self.cp;
+        boat.cb = self.cb;
+        boat.cm = self.cm;
+        boat.cw = self.cw;
+        boat.lcb = self.lcb;
+        boat.lcf = self.lcf;
+        boat.vcg = self.vcg;
+        if let Some(target_slr) = self.target_slr {
+            boat.target_slr = target_slr;
+        }
+        boat.check_coefficients()?;
+        Ok(boat)
+    }
+}
+
+/// Value informed and greater than zero.
+fn required(name: &'static str, val: Option<f64>) -> Result<f64, ValidationError> {
+    match val {
+        None => Err(ValidationError::Missing(name)),
+        Some(val) if val > 0.0 => Ok(val),
+        Some(_) => Err(ValidationError::NotPositive(name)),
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// RATIOS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Ratios
+///
+/// The ratios are nondimensional, which simply means that they have no units.
+/// The validity of the comparasions may not hold when one of the boats being compared is shorter than 25ft or longer than 75ft,
+/// but within that range, valid comparaisons can be made.
+/// Perry, R. H. (2008) Yatch design according to perry. International Marine. (pp.10)
+pub struct Ratios {
+    length_beam_ratio: LengthBeamRatio,
+    displacement_lenght_ratio: DisplacementLengthRatio,
+    sail_area_displacement: SailAreaDisplacementRatio,
+    sail_area_wetted_surface: SailAreaWettedSurfaceRatio,
+    s_number: SNumber,
+    bruce_number: BruceNumber,
+    screening_stability: Option<ScreeningStabilityValue>,
+    prismatic_coefficient: Option<PrismaticCoefficient>,
+    lcb: Option<LongitudinalCenter>,
+    lcf: Option<LongitudinalCenter>,
+    initial_stability: Option<InitialStability>,
+}
+
+#[allow(dead_code)]
+impl Ratios {
+    /// L/B (length-to-beam ratio).
+    pub fn length_beam(&self) -> &LengthBeamRatio {
+        &self.length_beam_ratio
+    }
+
+    /// D/L (displacement-to-length ratio).
+    pub fn displacement_length(&self) -> &DisplacementLengthRatio {
+        &self.displacement_lenght_ratio
+    }
+
+    /// SA/D (sail area to displacement ratio).
+    pub fn sail_area_displacement(&self) -> &SailAreaDisplacementRatio {
+        &self.sail_area_displacement
+    }
+
+    /// SA/WS (sail area to wetted surface ratio).
+    pub fn sail_area_wetted_surface(&self) -> &SailAreaWettedSurfaceRatio {
+        &self.sail_area_wetted_surface
+    }
+
+    /// S# (S number).
+    pub fn s_number(&self) -> &SNumber {
+        &self.s_number
+    }
+
+    /// BN (Bruce number).
+    pub fn bruce_number(&self) -> &BruceNumber {
+        &self.bruce_number
+    }
+
+    /// SSV (screening stability value), when the ballast is known.
+    pub fn screening_stability(&self) -> Option<&ScreeningStabilityValue> {
+        self.screening_stability.as_ref()
+    }
+
+    /// Cp (prismatic coefficient), when known.
+    pub fn prismatic_coefficient(&self) -> Option<&PrismaticCoefficient> {
+        self.prismatic_coefficient.as_ref()
+    }
+
+    /// LCB (longitudinal center of buoyancy), when known.
+    pub fn lcb(&self) -> Option<&LongitudinalCenter> {
+        self.lcb.as_ref()
+    }
+
+    /// LCF (longitudinal center of flotation), when known.
+    pub fn lcf(&self) -> Option<&LongitudinalCenter> {
+        self.lcf.as_ref()
+    }
+
+    /// Initial stability (GM), when the VCG is known.
+    pub fn initial_stability(&self) -> Option<&InitialStability> {
+        self.initial_stability.as_ref()
+    }
+
+    /// Ratios with the initial stability from the hull geometry instead of the estimate.
+    pub fn with_hydrostatics(mut self, boat: &Boat, hydrostatics: &Hydrostatics) -> Ratios {
+        if let Some(stability) = InitialStability::from_geometry(boat, hydrostatics) {
+            self.initial_stability = Some(stability);
+        }
+        self
+    }
+
+    /// Ratios classified with the Perry thresholds.
+    pub fn new(boat: &Boat) -> Ratios {
+        Ratios::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    /// Ratios classified with a thresholds profile.
+    pub fn with_thresholds(boat: &Boat, thresholds: &RatioThresholds) -> Ratios {
+        let displacement_lenght_ratio = DisplacementLengthRatio::with_thresholds(boat, thresholds);
+        let sail_area_displacement = SailAreaDisplacementRatio::with_thresholds(boat, thresholds);
+        Ratios {
+            length_beam_ratio: LengthBeamRatio::with_thresholds(boat, thresholds),
+            s_number: SNumber::with_thresholds(
+                &displacement_lenght_ratio,
+                &sail_area_displacement,
+                thresholds,
+            ),
+            displacement_lenght_ratio,
+            sail_area_displacement,
+            sail_area_wetted_surface: SailAreaWettedSurfaceRatio::with_thresholds(boat, thresholds),
+            bruce_number: BruceNumber::with_thresholds(boat, thresholds),
+            screening_stability: ScreeningStabilityValue::from_boat(boat),
+            prismatic_coefficient: PrismaticCoefficient::with_thresholds(boat, thresholds),
+            lcb: boat
+                .lcb()
+                .map(|val| LongitudinalCenter::new(val, thresholds.lcb)),
+            lcf: boat
+                .lcf()
+                .map(|val| LongitudinalCenter::new(val, thresholds.lcf)),
+            initial_stability: InitialStability::from_boat(boat),
+        }
+    }
+
+    /// Plain-language interpretation of the ratios.
+    pub fn commentary(&self) -> String {
+        let displacement = match self.displacement_lenght_ratio.character() {
+            DisplacementCharacter::Ultralight => "Ultralight",
+            DisplacementCharacter::Light => "Light",
+            DisplacementCharacter::Moderate => "Moderate displacement",
+            DisplacementCharacter::Heavy => "Heavy",
+            DisplacementCharacter::Ultraheavy => "Very heavy",
+        };
+        let beam = match self.length_beam_ratio.character() {
+            BeamCharacter::Narrow => "narrow",
+            BeamCharacter::ModerateNarrow => "moderately narrow",
+            BeamCharacter::Moderate => "moderate beam",
+            BeamCharacter::ModerateBeamy => "moderately beamy",
+            BeamCharacter::Beamy => "beamy",
+        };
+        let kind = match self.s_number.character() {
+            PerformanceCharacter::HeavyCruiser => "heavy cruiser",
+            PerformanceCharacter::Cruiser => "cruiser",
+            PerformanceCharacter::CruiserRacer => "cruiser racer",
+            PerformanceCharacter::Racer => "racer",
+            PerformanceCharacter::HighPerformance => "high performance boat",
+        };
+        let sail_plan = match self.sail_area_displacement.character() {
+            SailAreaCharacter::Low => "a conservative sail plan",
+            SailAreaCharacter::Moderate => "a moderate sail plan",
+            SailAreaCharacter::High => "a powerful sail plan",
+        };
+        let mut text = format!("{}, {} {} with {}; ", displacement, beam, kind, sail_plan);
+
+        text.push_str(match self.displacement_lenght_ratio.character() {
+            DisplacementCharacter::Ultralight | DisplacementCharacter::Light => {
+                "expect quick acceleration and a lively motion, sensitive to the load carried."
+            }
+            DisplacementCharacter::Moderate => {
+                "expect a reasonable compromise between speed, load carrying and motion comfort."
+            }
+            DisplacementCharacter::Heavy | DisplacementCharacter::Ultraheavy => {
+                "expect an easy motion and good load carrying, at the cost of speed."
+            }
+        });
+        text.push_str(match self.length_beam_ratio.character() {
+            BeamCharacter::Narrow | BeamCharacter::ModerateNarrow => {
+                " The narrow hull should be easily driven but relies on ballast for stiffness."
+            }
+            BeamCharacter::Moderate => "",
+            BeamCharacter::ModerateBeamy | BeamCharacter::Beamy => {
+                " The beam gives form stability and room below, but may pound upwind."
+            }
+        });
+        text.push_str(match self.sail_area_wetted_surface.character() {
+            LightAirCharacter::Poor => " Light air performance will be poor.",
+            LightAirCharacter::Moderate => "",
+            LightAirCharacter::Good => " Light air performance should be good.",
+        });
+        if let Some(cp) = &self.prismatic_coefficient {
+            text.push_str(match cp.character() {
+                PrismaticCharacter::TooLow => {
+                    " The prismatic coefficient is low for the target speed, ends are too fine."
+                }
+                PrismaticCharacter::Ideal => " The prismatic coefficient suits the target speed.",
+                PrismaticCharacter::TooHigh => {
+                    " The prismatic coefficient is high for the target speed, ends are too full."
+                }
+            });
+        }
+        if let Some(lcb) = &self.lcb {
+            text.push_str(match lcb.character() {
+                LongitudinalCenterCharacter::Forward => {
+                    " The LCB is forward, the boat may tend to trim by the bow."
+                }
+                LongitudinalCenterCharacter::Typical => "",
+                LongitudinalCenterCharacter::Aft => {
+                    " The LCB is aft, the boat may tend to squat by the stern."
+                }
+            });
+        }
+        if let Some(stability) = &self.initial_stability {
+            text.push_str(match stability.character() {
+                InitialStabilityCharacter::Unstable => " The GM is negative, the boat is unstable!",
+                InitialStabilityCharacter::Tender => " The GM is small, the boat will be tender.",
+                InitialStabilityCharacter::Moderate => "",
+                InitialStabilityCharacter::Stiff => {
+                    " The GM is large, the boat will be stiff with a quick roll."
+                }
+            });
+        }
+        text
+    }
+}
+
+impl fmt::Display for Ratios {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Ratio]\n\
+            \tL/B:   {:>5}\n\
+            \tD/L:   {:>5}\n\
+            \tSA/D:  {:>5}\n\
+            \tSA/WS: {:>5}\n\
+            \tS#:    {:>5}\n\
+            \tBN:    {:>5}\n\
+            \tSSV:   {:>5}\n\
+            \tCp:    {:>5}\n\
+            \tLCB:   {:>5}\n\
+            \tLCF:   {:>5}\n\
+            \tGM:    {:>5}\n
+            ",
+            self.length_beam_ratio,
+            self.displacement_lenght_ratio,
+            self.sail_area_displacement,
+            self.sail_area_wetted_surface,
+            self.s_number,
+            self.bruce_number,
+            optional(&self.screening_stability),
+            optional(&self.prismatic_coefficient),
+            optional(&self.lcb),
+            optional(&self.lcf),
+            optional(&self.initial_stability.as_ref().map(|s| format!(
+                "{:.3}m [{}]",
+                s.gm().to_meter(),
+                s.character()
+            )))
+        )
+    }
+}
+
+/// Display a value that may not be available.
+fn optional<T: fmt::Display>(val: &Option<T>) -> String {
+    match val {
+        Some(val) => val.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// THRESHOLDS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Ratio thresholds
+///
+/// Limits between the character bands of each ratio. The Perry limits are valid for
+/// monohulls from 25ft to 75ft, the other profiles are starting points for boats outside
+/// that range and may be tuned field by field.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct RatioThresholds {
+    /// L/B lower limits of narrow, moderate narrow, moderate and moderate beamy.
+    pub length_beam: [f64; 4],
+    /// D/L upper limits of ultralight, light, moderate and heavy.
+    pub displacement_length: [f64; 4],
+    /// SA/D upper limits of low and moderate.
+    pub sail_area_displacement: [f64; 2],
+    /// SA/WS upper limits of poor and moderate.
+    pub sail_area_wetted_surface: [f64; 2],
+    /// S# upper limits of heavy cruiser, cruiser, cruiser racer and racer.
+    pub s_number: [f64; 4],
+    /// BN upper limits of underpowered, moderate and powerful.
+    pub bruce_number: [f64; 3],
+    /// Tolerance around the ideal Cp.
+    pub prismatic_tolerance: f64,
+    /// Typical LCB range (% of DWL).
+    pub lcb: (f64, f64),
+    /// Typical LCF range (% of DWL).
+    pub lcf: (f64, f64),
+}
+
+#[allow(dead_code)]
+impl RatioThresholds {
+    /// Monohulls from 25ft to 75ft.
+    /// Perry, R. H. (2008) Yatch design according to perry. International Marine.
+    pub fn perry() -> RatioThresholds {
+        RatioThresholds {
+            length_beam: [4.00, 3.65, 3.30, 3.00],
+            displacement_length: [90.0, 180.0, 270.0, 360.0],
+            sail_area_displacement: [15.0, 20.0],
+            sail_area_wetted_surface: [2.0, 2.5],
+            s_number: [2.0, 3.0, 5.0, 7.0],
+            bruce_number: [1.0, 1.3, 1.6],
+            prismatic_tolerance: PRISMATIC_TOLERANCE,
+            lcb: LCB_RANGE,
+            lcf: LCF_RANGE,
+        }
+    }
+
+    /// Dinghies and small daysailers, shorter than 25ft.
+    /// Small boats are beamier, lighter for the length and carry more sail.
+    pub fn dinghy() -> RatioThresholds {
+        RatioThresholds {
+            length_beam: [3.60, 3.20, 2.80, 2.50],
+            displacement_length: [40.0, 80.0, 130.0, 180.0],
+            sail_area_displacement: [20.0, 30.0],
+            sail_area_wetted_surface: [2.5, 3.5],
+            ..RatioThresholds::perry()
+        }
+    }
+
+    /// Multihulls, L/B of each hull.
+    pub fn multihull() -> RatioThresholds {
+        RatioThresholds {
+            length_beam: [12.0, 10.0, 8.0, 6.0],
+            displacement_length: [40.0, 70.0, 100.0, 140.0],
+            sail_area_displacement: [18.0, 25.0],
+            sail_area_wetted_surface: [2.2, 3.0],
+            ..RatioThresholds::perry()
+        }
+    }
+
+    /// Large yachts, longer than 75ft.
+    /// D/L drops with the length, a moderate big boat has a D/L that would be light at 35ft.
+    pub fn large_yacht() -> RatioThresholds {
+        RatioThresholds {
+            length_beam: [4.50, 4.10, 3.70, 3.30],
+            displacement_length: [60.0, 120.0, 180.0, 240.0],
+            sail_area_displacement: [14.0, 19.0],
+            ..RatioThresholds::perry()
+        }
+    }
+}
+
+impl Default for RatioThresholds {
+    fn default() -> RatioThresholds {
+        RatioThresholds::perry()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// METRIC
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Metric
+///
+/// Dimension or ratio of a boat, used to compare, sort and filter boats.
+/// Dimensions are in SI units (m, m2, kg).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Metric {
+    Loa,
+    Dwl,
+    Beam,
+    Draft,
+    Displacement,
+    SailArea,
+    WettedSurface,
+    LengthBeam,
+    DisplacementLength,
+    SailAreaDisplacement,
+    SailAreaWettedSurface,
+    SNumber,
+    BruceNumber,
+}
+
+#[allow(dead_code)]
+impl Metric {
+    /// All metrics, dimensions first.
+    pub const ALL: [Metric; 13] = [
+        Metric::Loa,
+        Metric::Dwl,
+        Metric::Beam,
+        Metric::Draft,
+        Metric::Displacement,
+        Metric::SailArea,
+        Metric::WettedSurface,
+        Metric::LengthBeam,
+        Metric::DisplacementLength,
+        Metric::SailAreaDisplacement,
+        Metric::SailAreaWettedSurface,
+        Metric::SNumber,
+        Metric::BruceNumber,
+    ];
+
+    /// Metric value for a boat.
+    pub fn value(self, boat: &Boat) -> f64 {
+        match self {
+            Metric::Loa => boat.loa.to_meter(),
+            Metric::Dwl => boat.dwl.to_meter(),
+            Metric::Beam => boat.b_max.to_meter(),
+            Metric::Draft => boat.draft.to_meter(),
+            Metric::Displacement => boat.displacement.to_kilogram(),
+            Metric::SailArea => boat.sail_area().to_meter2(),
+            Metric::WettedSurface => boat.wetted_surface().to_meter2(),
+            Metric::LengthBeam => LengthBeamRatio::from_boat(boat).value,
+            Metric::DisplacementLength => DisplacementLengthRatio::from_boat(boat).value,
+            Metric::SailAreaDisplacement => SailAreaDisplacementRatio::from_boat(boat).value,
+            Metric::SailAreaWettedSurface => SailAreaWettedSurfaceRatio::from_boat(boat).value,
+            Metric::SNumber => Ratios::new(boat).s_number.value,
+            Metric::BruceNumber => BruceNumber::from_boat(boat).value,
+        }
+    }
+
+    /// Unit of the value, empty for ratios.
+    pub fn unit(self) -> &'static str {
+        match self {
+            Metric::Loa | Metric::Dwl | Metric::Beam | Metric::Draft => "m",
+            Metric::Displacement => "kg",
+            Metric::SailArea | Metric::WettedSurface => "m2",
+            _ => "",
+        }
+    }
+
+    /// Ratios are nondimensional.
+    pub fn is_ratio(self) -> bool {
+        self.unit().is_empty()
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Metric::Loa => write!(f, "LOA"),
+            Metric::Dwl => write!(f, "DWL"),
+            Metric::Beam => write!(f, "Beam"),
+            Metric::Draft => write!(f, "Draft"),
+            Metric::Displacement => write!(f, "Displacement"),
+            Metric::SailArea => write!(f, "Sail area"),
+            Metric::WettedSurface => write!(f, "Wetted surface"),
+            Metric::LengthBeam => write!(f, "L/B"),
+            Metric::DisplacementLength => write!(f, "D/L"),
+            Metric::SailAreaDisplacement => write!(f, "SA/D"),
+            Metric::SailAreaWettedSurface => write!(f, "SA/WS"),
+            Metric::SNumber => write!(f, "S#"),
+            Metric::BruceNumber => write!(f, "BN"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LBR - LENGHT TO BEAM RATIO
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Beam character.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum BeamCharacter {
+    Narrow,
+    ModerateNarrow,
+    Moderate,
+    ModerateBeamy,
+    Beamy,
+}
+
+impl fmt::Display for BeamCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BeamCharacter::Narrow => write!(f, "Narrow"),
+            BeamCharacter::ModerateNarrow => write!(f, "Moderate narrow"),
+            BeamCharacter::Moderate => write!(f, "Moderate"),
+            BeamCharacter::ModerateBeamy => write!(f, "Moderate beamy"),
+            BeamCharacter::Beamy => write!(f, "Beamy"),
+        }
+    }
+}
+
+/// LBR - L/B (lenght-to-Beam Ratio)
+/// This ratio is useful for determining whether a boat is beamy or narrow.
+pub struct LengthBeamRatio {
+    value: f64,
+    beam_character: BeamCharacter,
+}
+
+impl LengthBeamRatio {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> BeamCharacter {
+        self.beam_character
+    }
+
+    pub fn from_boat(boat: &Boat) -> LengthBeamRatio {
+        LengthBeamRatio::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    pub fn with_thresholds(boat: &Boat, thresholds: &RatioThresholds) -> LengthBeamRatio {
+        let limits = thresholds.length_beam;
+        let value = boat.loa.to_meter() / boat.b_max.to_meter();
+        LengthBeamRatio {
+            value,
+            beam_character: if value >= limits[0] {
+                BeamCharacter::Narrow
+            } else if value >= limits[1] {
+                BeamCharacter::ModerateNarrow
+            } else if value >= limits[2] {
+                BeamCharacter::Moderate
+            } else if value > limits[3] {
+                BeamCharacter::ModerateBeamy
+            } else {
+                BeamCharacter::Beamy
+            },
+        }
+    }
+}
+
+impl fmt::Display for LengthBeamRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} [{}]", self.value, self.beam_character)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DLR - DISPLACEMENTE TO LENGTH RATIO
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Displacement character.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum DisplacementCharacter {
+    Ultralight,
+    Light,
+    Moderate,
+    Heavy,
+    Ultraheavy,
+}
+
+impl fmt::Display for DisplacementCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DisplacementCharacter::Ultralight => write!(f, "Ultralight"),
+            DisplacementCharacter::Light => write!(f, "Light"),
+            DisplacementCharacter::Moderate => write!(f, "Moderate"),
+            DisplacementCharacter::Heavy => write!(f, "Heavy"),
+            DisplacementCharacter::Ultraheavy => write!(f, "Ultraheavy"),
+        }
+    }
+}
+
+/// DLR - D/L (displacment-to-lenght Ratio)
+/// A DLR less than 200 is indicative of a racing boat, while a DLR greater than 300 or so is indicative of a heavy cruising boat.
+/// This ratio is useful for determining whether a boat is heavy or light.
+pub struct DisplacementLengthRatio {
+    value: f64,
+    displacement_character: DisplacementCharacter,
+}
+
+impl DisplacementLengthRatio {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> DisplacementCharacter {
+        self.displacement_character
+    }
+
+    pub fn from_boat(boat: &Boat) -> DisplacementLengthRatio {
+        DisplacementLengthRatio::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    pub fn with_thresholds(boat: &Boat, thresholds: &RatioThresholds) -> DisplacementLengthRatio {
+        let limits = thresholds.displacement_length;
+        // Long ton = diplacement(lb) / 2240
+        // DLR = (diplacement(lb) / 2240) / ((0.01 * LWL(ft)) exp 3)
+        // D/L of 260 is considered the "middle" of the overall displacemente range by Perry.
+        // Most full kell boats, by virtue of the volume in their keels, have D/L over 325.
+        let value = boat.displacement.to_long_ton() / (boat.dwl.to_foot() * 0.01).powf(3.0);
+        DisplacementLengthRatio {
+            value,
+            displacement_character: if value < limits[0] {
+                DisplacementCharacter::Ultralight
+            } else if value < limits[1] {
+                DisplacementCharacter::Light
+            } else if value < limits[2] {
+                DisplacementCharacter::Moderate
+            } else if value <= limits[3] {
+                DisplacementCharacter::Heavy
+            } else {
+                DisplacementCharacter::Ultraheavy
+            },
+        }
+    }
+}
+
+impl fmt::Display for DisplacementLengthRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.0} [{}]", self.value, self.displacement_character)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SADR - SA/D - SAIL AREA DISPLACEMENTE RATIO
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Sail area character.
+/// Motorsailers    13 - 14
+/// Slow auxiliary sailboats    14 - 15
+/// Average offshore cruisers    15 - 16
+/// Coastal cruisers    16 - 17
+/// Racing yachts    17 - 19
+/// Ultra light racers, class racers, daysailers    20+
+///
+/// A typical cruising boat today will hava a SA/D of 17.5 to 18.5.
+/// This is enough power to drive the boat reasonably well in light air while not overpowering it too quickly when the breeze picks up.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SailAreaCharacter {
+    Low,
+    Moderate,
+    High,
+}
+
+impl fmt::Display for SailAreaCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SailAreaCharacter::Low => write!(f, "Low"),
+            SailAreaCharacter::Moderate => write!(f, "Moderate"),
+            SailAreaCharacter::High => write!(f, "High"),
+        }
+    }
+}
+
+/// SADR - SA/D (sail area to displacemet ratio)
+pub struct SailAreaDisplacementRatio {
+    value: f64,
+    sail_area_character: SailAreaCharacter,
+}
+
+impl SailAreaDisplacementRatio {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> SailAreaCharacter {
+        self.sail_area_character
+    }
+
+    pub fn from_boat(boat: &Boat) -> SailAreaDisplacementRatio {
+        SailAreaDisplacementRatio::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    pub fn with_thresholds(boat: &Boat, thresholds: &RatioThresholds) -> SailAreaDisplacementRatio {
+        SailAreaDisplacementRatio::from_sail_area(boat, boat.sail_area(), thresholds)
+    }
+
+    /// SA/D for a sail combination, working sails or the full downwind inventory.
+    pub fn from_sail_area(
+        boat: &Boat,
+        sail_area: Area,
+        thresholds: &RatioThresholds,
+    ) -> SailAreaDisplacementRatio {
+        let limits = thresholds.sail_area_displacement;
+        let value = sail_area.to_meter2() / boat.displacement.to_long_ton().powf(2.0 / 3.0);
+        SailAreaDisplacementRatio {
+            value,
+            sail_area_character: if value < limits[0] {
+                SailAreaCharacter::Low
+            } else if value <= limits[1] {
+                SailAreaCharacter::Moderate
+            } else {
+                SailAreaCharacter::High
+            },
+        }
+    }
+}
+
+impl fmt::Display for SailAreaDisplacementRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1} [{}]", self.value, self.sail_area_character)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SA/WS - SAIL AREA TO WETTED SURFACE RATIO
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Light air character.
+/// Below 2.0 the boat will be sluggish in light air, cruisers usually stay around 2.0 to 2.5,
+/// while racing boats go above 2.5.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum LightAirCharacter {
+    Poor,
+    Moderate,
+    Good,
+}
+
+impl fmt::Display for LightAirCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LightAirCharacter::Poor => write!(f, "Poor"),
+            LightAirCharacter::Moderate => write!(f, "Moderate"),
+            LightAirCharacter::Good => write!(f, "Good"),
+        }
+    }
+}
+
+/// SA/WS (sail area to wetted surface ratio)
+/// In light air the frictional resistance of the wetted surface is the main drag component,
+/// so this ratio is a good indicator of light air performance.
+pub struct SailAreaWettedSurfaceRatio {
+    value: f64,
+    light_air_character: LightAirCharacter,
+}
+
+impl SailAreaWettedSurfaceRatio {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> LightAirCharacter {
+        self.light_air_character
+    }
+
+    pub fn from_boat(boat: &Boat) -> SailAreaWettedSurfaceRatio {
+        SailAreaWettedSurfaceRatio::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    pub fn with_thresholds(
+        boat: &Boat,
+        thresholds: &RatioThresholds,
+    ) -> SailAreaWettedSurfaceRatio {
+        let limits = thresholds.sail_area_wetted_surface;
+        let value = boat.sail_area().to_meter2() / boat.wetted_surface().to_meter2();
+        SailAreaWettedSurfaceRatio {
+            value,
+            light_air_character: if value < limits[0] {
+                LightAirCharacter::Poor
+            } else if value <= limits[1] {
+                LightAirCharacter::Moderate
+            } else {
+                LightAirCharacter::Good
+            },
+        }
+    }
+}
+
+impl fmt::Display for SailAreaWettedSurfaceRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} [{}]", self.value, self.light_air_character)
+    }
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// S# - S NUMBER
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Performance character.
+/// Less than 2          Heavy cruisers
+/// 2 - 3                Cruisers
+/// 3 - 5                Cruiser racers
+/// 5 - 7                Racers
+/// 7+                   High performance racers
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PerformanceCharacter {
+    HeavyCruiser,
+    Cruiser,
+    CruiserRacer,
+    Racer,
+    HighPerformance,
+}
+
+impl fmt::Display for PerformanceCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PerformanceCharacter::HeavyCruiser => write!(f, "Heavy cruiser"),
+            PerformanceCharacter::Cruiser => write!(f, "Cruiser"),
+            PerformanceCharacter::CruiserRacer => write!(f, "Cruiser racer"),
+            PerformanceCharacter::Racer => write!(f, "Racer"),
+            PerformanceCharacter::HighPerformance => write!(f, "High performance"),
+        }
+    }
+}
+
+/// S# (S number)
+/// Combines SA/D and D/L in a single performance score, usually between 1 and 10.
+/// S# = 3.972 * 10 exp (-D/L / 526 + 0.691 * (log10(SA/D) - 1) exp 0.8)
+pub struct SNumber {
+    value: f64,
+    performance_character: PerformanceCharacter,
+}
+
+impl SNumber {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> PerformanceCharacter {
+        self.performance_character
+    }
+
+    pub fn from_ratios(
+        displacement_length: &DisplacementLengthRatio,
+        sail_area_displacement: &SailAreaDisplacementRatio,
+    ) -> SNumber {
+        SNumber::with_thresholds(
+            displacement_length,
+            sail_area_displacement,
+            &RatioThresholds::default(),
+        )
+    }
+
+    pub fn with_thresholds(
+        displacement_length: &DisplacementLengthRatio,
+        sail_area_displacement: &SailAreaDisplacementRatio,
+        thresholds: &RatioThresholds,
+    ) -> SNumber {
+        let limits = thresholds.s_number;
+        // A SA/D below 10 would make the power term undefined, it gives no sail area bonus.
+        let sail_term = (sail_area_displacement.value.log10() - 1.0)
+            .max(0.0)
+            .powf(0.8);
+        let value = 3.972 * 10_f64.powf(-displacement_length.value / 526.0 + 0.691 * sail_term);
+        SNumber {
+            value,
+            performance_character: if value < limits[0] {
+                PerformanceCharacter::HeavyCruiser
+            } else if value < limits[1] {
+                PerformanceCharacter::Cruiser
+            } else if value < limits[2] {
+                PerformanceCharacter::CruiserRacer
+            } else if value < limits[3] {
+                PerformanceCharacter::Racer
+            } else {
+                PerformanceCharacter::HighPerformance
+            },
+        }
+    }
+}
+
+impl fmt::Display for SNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} [{}]", self.value, self.performance_character)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// BN - BRUCE NUMBER
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Power character.
+/// Less than 1.0        Underpowered, heavy cruisers and motorsailers
+/// 1.0 - 1.3            Moderate, most cruisers
+/// 1.3 - 1.6            Powerful, performance boats
+/// 1.6+                 High performance dinghies and multihulls
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PowerCharacter {
+    Underpowered,
+    Moderate,
+    Powerful,
+    HighPerformance,
+}
+
+impl fmt::Display for PowerCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PowerCharacter::Underpowered => write!(f, "Underpowered"),
+            PowerCharacter::Moderate => write!(f, "Moderate"),
+            PowerCharacter::Powerful => write!(f, "Powerful"),
+            PowerCharacter::HighPerformance => write!(f, "High performance"),
+        }
+    }
+}
+
+/// BN (Bruce number)
+/// Power to weight ratio, the same bands hold for dinghies, keelboats and multihulls.
+/// BN = SA(ft2) exp 0.5 / Disp(lb) exp (1/3)
+pub struct BruceNumber {
+    value: f64,
+    power_character: PowerCharacter,
+}
+
+impl BruceNumber {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> PowerCharacter {
+        self.power_character
+    }
+
+    pub fn from_boat(boat: &Boat) -> BruceNumber {
+        BruceNumber::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    pub fn with_thresholds(boat: &Boat, thresholds: &RatioThresholds) -> BruceNumber {
+        let limits = thresholds.bruce_number;
+        let value =
+            boat.sail_area().to_foot2().sqrt() / boat.displacement.to_pound().powf(1.0 / 3.0);
+        BruceNumber {
+            value,
+            power_character: if value < limits[0] {
+                PowerCharacter::Underpowered
+            } else if value < limits[1] {
+                PowerCharacter::Moderate
+            } else if value < limits[2] {
+                PowerCharacter::Powerful
+            } else {
+                PowerCharacter::HighPerformance
+            },
+        }
+    }
+}
+
+impl fmt::Display for BruceNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} [{}]", self.value, self.power_character)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SSV - SCREENING STABILITY VALUE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// SSV (screening stability value)
+/// Screening of the ultimate stability before entering offshore races, a wide beam raises the
+/// inverted stability while ballast, displacement and length work against capsize.
+/// SSV = B(ft) exp 2 / (BR * DCB(ft) * Vol(ft3) exp (1/3))
+/// DCB is the canoe body draft. Lower is better, read through the angle of vanishing stability
+/// it screens for and the offshore character of that angle, by the US Sailing formula of
+/// AvsEstimate. A screening only, it does not replace a GZ curve.
+pub struct ScreeningStabilityValue {
+    value: f64,
+}
+
+impl ScreeningStabilityValue {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Needs the ballast ratio.
+    pub fn from_boat(boat: &Boat) -> Option<ScreeningStabilityValue> {
+        let ballast_ratio = boat.ballast_ratio()?;
+        let value = boat.b_max.to_foot().powi(2)
+            / (ballast_ratio
+                * boat.draft().to_foot()
+                * boat.displaced_volume().to_foot3().powf(1.0 / 3.0));
+        Some(ScreeningStabilityValue { value })
+    }
+
+    /// AVS and offshore character the value screens for.
+    pub fn avs_estimate(&self) -> AvsEstimate {
+        AvsEstimate::from_screening_value(self.value)
+    }
+}
+
+impl fmt::Display for ScreeningStabilityValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let avs = self.avs_estimate();
+        write!(
+            f,
+            "{:.2} [AVS {:.0}°, {}]",
+            self.value,
+            avs.avs(),
+            avs.character()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// CP - PRISMATIC COEFFICIENT
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Prismatic character.
+/// A Cp lower than the ideal gives less resistance at low speed but the boat will struggle to
+/// reach the target speed, a higher one penalizes the low speed performance.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PrismaticCharacter {
+    TooLow,
+    Ideal,
+    TooHigh,
+}
+
+impl fmt::Display for PrismaticCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PrismaticCharacter::TooLow => write!(f, "Too low"),
+            PrismaticCharacter::Ideal => write!(f, "Ideal"),
+            PrismaticCharacter::TooHigh => write!(f, "Too high"),
+        }
+    }
+}
+
+/// Ideal Cp by SLR (speed-length ratio), the ideal Cp rises with the speed.
+/// Gerr, D. (1992) The nature of boats. International Marine.
+const IDEAL_PRISMATIC: [(f64, f64); 9] = [
+    (0.8, 0.50),
+    (1.0, 0.52),
+    (1.1, 0.54),
+    (1.2, 0.56),
+    (1.3, 0.59),
+    (1.4, 0.62),
+    (1.5, 0.64),
+    (1.6, 0.66),
+    (2.0, 0.69),
+];
+
+/// Tolerance around the ideal Cp.
+pub const PRISMATIC_TOLERANCE: f64 = 0.02;
+
+/// Ideal Cp for a SLR (speed-length ratio).
+pub fn ideal_prismatic(slr: f64) -> f64 {
+    let first = IDEAL_PRISMATIC[0];
+    let last = IDEAL_PRISMATIC[IDEAL_PRISMATIC.len() - 1];
+    if slr <= first.0 {
+        return first.1;
+    }
+    if slr >= last.0 {
+        return last.1;
+    }
+    let i = IDEAL_PRISMATIC
+        .iter()
+        .position(|&(s, _)| s > slr)
+        .unwrap_or(IDEAL_PRISMATIC.len() - 1);
+    let (s0, cp0) = IDEAL_PRISMATIC[i - 1];
+    let (s1, cp1) = IDEAL_PRISMATIC[i];
+    cp0 + (cp1 - cp0) * (slr - s0) / (s1 - s0)
+}
+
+/// Cp (prismatic coefficient)
+/// The Cp is a measure of how the displacement is distributed along the length,
+/// a low Cp means fine ends and a high Cp means full ends.
+pub struct PrismaticCoefficient {
+    value: f64,
+    ideal: f64,
+    target_slr: f64,
+    prismatic_character: PrismaticCharacter,
+}
+
+impl PrismaticCoefficient {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> PrismaticCharacter {
+        self.prismatic_character
+    }
+
+    /// Ideal Cp for the target SLR.
+    pub fn ideal(&self) -> f64 {
+        self.ideal
+    }
+
+    /// Target SLR the ideal Cp is for.
+    pub fn target_slr(&self) -> f64 {
+        self.target_slr
+    }
+
+    pub fn from_boat(boat: &Boat) -> Option<PrismaticCoefficient> {
+        PrismaticCoefficient::with_thresholds(boat, &RatioThresholds::default())
+    }
+
+    pub fn with_thresholds(
+        boat: &Boat,
+        thresholds: &RatioThresholds,
+    ) -> Option<PrismaticCoefficient> {
+        let tolerance = thresholds.prismatic_tolerance;
+        let value = boat.cp()?;
+        let ideal = ideal_prismatic(boat.target_slr);
+        Some(PrismaticCoefficient {
+            value,
+            ideal,
+            target_slr: boat.target_slr,
+            prismatic_character: if value < ideal - tolerance {
+                PrismaticCharacter::TooLow
+            } else if value <= ideal + tolerance {
+                PrismaticCharacter::Ideal
+            } else {
+                PrismaticCharacter::TooHigh
+            },
+        })
+    }
+}
+
+impl fmt::Display for PrismaticCoefficient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.3} [{}] (ideal {:.3} for SLR {:.2})",
+            self.value, self.prismatic_character, self.ideal, self.target_slr
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LCB / LCF - LONGITUDINAL CENTERS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Longitudinal center character.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum LongitudinalCenterCharacter {
+    Forward,
+    Typical,
+    Aft,
+}
+
+impl fmt::Display for LongitudinalCenterCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LongitudinalCenterCharacter::Forward => write!(f, "Forward"),
+            LongitudinalCenterCharacter::Typical => write!(f, "Typical"),
+            LongitudinalCenterCharacter::Aft => write!(f, "Aft"),
+        }
+    }
+}
+
+/// Typical LCB range for sailing yachts (% of DWL aft of forward perpendicular).
+pub const LCB_RANGE: (f64, f64) = (52.0, 55.0);
+
+/// Typical LCF range for sailing yachts (% of DWL aft of forward perpendicular).
+/// The LCF usually lies 1% to 3% of DWL aft of the LCB.
+pub const LCF_RANGE: (f64, f64) = (53.0, 58.0);
+
+/// Longitudinal center (LCB or LCF)
+/// The position of the centers affects the trim and the balance of the boat,
+/// a center too far forward tends to make the boat bury the bow when heeled.
+pub struct LongitudinalCenter {
+    value: f64,
+    longitudinal_character: LongitudinalCenterCharacter,
+}
+
+impl LongitudinalCenter {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn character(&self) -> LongitudinalCenterCharacter {
+        self.longitudinal_character
+    }
+
+    pub fn new(value: f64, range: (f64, f64)) -> LongitudinalCenter {
+        LongitudinalCenter {
+            value,
+            longitudinal_character: if value < range.0 {
+                LongitudinalCenterCharacter::Forward
+            } else if value <= range.1 {
+                LongitudinalCenterCharacter::Typical
+            } else {
+                LongitudinalCenterCharacter::Aft
+            },
+        }
+    }
+}
+
+impl fmt::Display for LongitudinalCenter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1}% [{}]", self.value, self.longitudinal_character)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // LENGHT BEAM RATIO
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn beam_character() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_b_max(Length::from_foot(1.00));
+
+        // Beamy.
+        boat.set_loa(Length::from_foot(2.00));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::Beamy
+        );
+        boat.set_loa(Length::from_foot(3.00));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::Beamy
+        );
+
+        // Moderate beamy.
+        boat.set_loa(Length::from_foot(3.01));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::ModerateBeamy
+        );
+        boat.set_loa(Length::from_foot(3.29));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::ModerateBeamy
+        );
+
+        // Moderate.
+        boat.set_loa(Length::from_foot(3.30));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::Moderate
+        );
+        boat.set_loa(Length::from_foot(3.65));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::Moderate
+        );
+
+        // Moderate narrow.
+        boat.set_loa(Length::from_foot(3.66));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::ModerateNarrow
+        );
+        boat.set_loa(Length::from_foot(3.99));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::ModerateNarrow
+        );
+
+        // Narrow.
+        boat.set_loa(Length::from_foot(4.00));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::Narrow
+        );
+        boat.set_loa(Length::from_foot(5.00));
+        assert_eq!(
+            Ratios::new(&boat).length_beam_ratio.beam_character,
+            BeamCharacter::Narrow
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // DISPLACEMENT LENGHT RATIO
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn displacement_character() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_foot(32.0));
+
+        // Ultralight.
+        boat.set_displacement(Weight::from_long_ton(2.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "Ultralight\n\
+            DWL: {} ft\n\
+            Displacement: {:.1} ton\n\
+            D/L: {:.0} [{}]\n\n",
+            boat.dwl.to_foot(),
+            boat.displacement.to_long_ton(),
+            ratios.displacement_lenght_ratio.value,
+            ratios.displacement_lenght_ratio.displacement_character
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .displacement_lenght_ratio
+                .displacement_character,
+            DisplacementCharacter::Ultralight
+        );
+
+        // Light.
+        boat.set_displacement(Weight::from_long_ton(4.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "Light\n\
+            DWL: {} ft\n\
+            Displacement: {:.1} ton\n\
+            D/L: {:.0} [{}]\n\n",
+            boat.dwl.to_foot(),
+            boat.displacement.to_long_ton(),
+            ratios.displacement_lenght_ratio.value,
+            ratios.displacement_lenght_ratio.displacement_character
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .displacement_lenght_ratio
+                .displacement_character,
+            DisplacementCharacter::Light
+        );
+
+        // Moderate.
+        boat.set_displacement(Weight::from_long_ton(7.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "Moderate\n\
+            DWL: {} ft\n\
+            Displacement: {:.1} ton\n\
+            D/L: {:.0} [{}]\n\n",
+            boat.dwl.to_foot(),
+            boat.displacement.to_long_ton(),
+            ratios.displacement_lenght_ratio.value,
+            ratios.displacement_lenght_ratio.displacement_character
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .displacement_lenght_ratio
+                .displacement_character,
+            DisplacementCharacter::Moderate
+        );
+
+        // Heavy.
+        boat.set_displacement(Weight::from_long_ton(9.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "Heavy\n\
+            DWL: {} ft\n\
+            Displacement: {:.1} ton\n\
+            D/L: {:.0} [{}]\n\n",
+            boat.dwl.to_foot(),
+            boat.displacement.to_long_ton(),
+            ratios.displacement_lenght_ratio.value,
+            ratios.displacement_lenght_ratio.displacement_character
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .displacement_lenght_ratio
+                .displacement_character,
+            DisplacementCharacter::Heavy
+        );
+
+        // Ultraheavy.
+        boat.set_displacement(Weight::from_long_ton(12.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "Ultraheavy\n\
+            DWL: {} ft\n\
+            Displacement: {:.1} ton\n\
+            D/L: {:.0} [{}]\n\n",
+            boat.dwl.to_foot(),
+            boat.displacement.to_long_ton(),
+            ratios.displacement_lenght_ratio.value,
+            ratios.displacement_lenght_ratio.displacement_character
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .displacement_lenght_ratio
+                .displacement_character,
+            DisplacementCharacter::Ultraheavy
+        );
+    }
+
+    #[test]
+    fn displacement_length_ratio() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(Length::from_foot(34.0));
+        boat.set_dwl(Length::from_foot(32.0));
+        boat.set_b_max(Length::from_foot(1.0));
+        boat.set_displacement(Weight::from_pound(15680.0));
+        assert_eq!(
+            Ratios::new(&boat).displacement_lenght_ratio.value.round(),
+            214.0
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // SAIL AREA DISPLACEMENT RATIO
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn sail_area_character() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_displacement(Weight::from_pound(15680.0));
+
+        // Low.
+        boat.set_sail_area(Area::from_foot2(550.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "SailAreaDisplacementRatio: {}",
+            ratios.sail_area_displacement,
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .sail_area_displacement
+                .sail_area_character,
+            SailAreaCharacter::Low
+        );
+
+        // Moderate.
+        boat.set_sail_area(Area::from_foot2(704.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "SailAreaDisplacementRatio: {}",
+            ratios.sail_area_displacement,
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .sail_area_displacement
+                .sail_area_character,
+            SailAreaCharacter::Moderate
+        );
+
+        // High.
+        boat.set_sail_area(Area::from_foot2(800.0));
+        let ratios = Ratios::new(&boat);
+        print!(
+            "SailAreaDisplacementRatio: {}",
+            ratios.sail_area_displacement,
+        );
+        assert_eq!(
+            Ratios::new(&boat)
+                .sail_area_displacement
+                .sail_area_character,
+            SailAreaCharacter::High
+        );
+    }
+
+    #[test]
+    fn sail_area_displacement() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_displacement(Weight::from_pound(15680.0));
+        boat.set_sail_area(Area::from_foot2(704.0));
+        assert_eq!(
+            Ratios::new(&boat).sail_area_displacement.value.round(),
+            18.0
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // SAIL AREA WETTED SURFACE RATIO
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn light_air_character() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_wetted_surface(Area::from_foot2(300.0));
+
+        // Poor.
+        boat.set_sail_area(Area::from_foot2(540.0));
+        assert_eq!(
+            Ratios::new(&boat)
+                .sail_area_wetted_surface
+                .light_air_character,
+            LightAirCharacter::Poor
+        );
+
+        // Moderate.
+        boat.set_sail_area(Area::from_foot2(660.0));
+        assert_eq!(
+            Ratios::new(&boat)
+                .sail_area_wetted_surface
+                .light_air_character,
+            LightAirCharacter::Moderate
+        );
+
+        // Good.
+        boat.set_sail_area(Area::from_foot2(800.0));
+        let ratios = Ratios::new(&boat);
+        assert_eq!(
+            format!("{:.2}", ratios.sail_area_wetted_surface.value),
+            "2.67"
+        );
+        assert_eq!(
+            ratios.sail_area_wetted_surface.light_air_character,
+            LightAirCharacter::Good
+        );
+    }
+
+    #[test]
+    fn estimated_wetted_surface() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(9.0));
+        boat.set_draft(Length::from_meter(0.5));
+        boat.set_displacement(Weight::from_kilogram(5125.0));
+        // 1.7 * 9.0 * 0.5 + 5.0 / 0.5
+        assert_eq!(format!("{:.2}", boat.wetted_surface().to_meter2()), "17.65");
+
+        // Informed value has precedence over the estimate.
+        boat.set_wetted_surface(Area::from_meter2(20.0));
+        assert_eq!(boat.wetted_surface().to_meter2(), 20.0);
+        assert_eq!(
+            format!("{:.2}", boat.estimated_wetted_surface().to_meter2()),
+            "17.65"
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // PRISMATIC COEFFICIENT
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn prismatic_character() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        assert!(Ratios::new(&boat).prismatic_coefficient.is_none());
+
+        boat.set_target_slr(1.2);
+        assert_eq!(ideal_prismatic(1.2), 0.56);
+        assert_eq!(format!("{:.3}", ideal_prismatic(1.25)), "0.575");
+
+        // Too low.
+        boat.set_cp(0.53);
+        assert_eq!(
+            Ratios::new(&boat)
+                .prismatic_coefficient
+                .unwrap()
+                .prismatic_character,
+            PrismaticCharacter::TooLow
+        );
+
+        // Ideal.
+        boat.set_cp(0.57);
+        assert_eq!(
+            Ratios::new(&boat)
+                .prismatic_coefficient
+                .unwrap()
+                .prismatic_character,
+            PrismaticCharacter::Ideal
+        );
+
+        // Too high.
+        boat.set_cp(0.60);
+        assert_eq!(
+            Ratios::new(&boat)
+                .prismatic_coefficient
+                .unwrap()
+                .prismatic_character,
+            PrismaticCharacter::TooHigh
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // BLOCK COEFFICIENT
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn block_coefficient() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(8.0));
+        boat.set_bwl(Length::from_meter(2.5));
+        boat.set_draft(Length::from_meter(0.5));
+        boat.set_displacement(Weight::from_kilogram(4100.0));
+        assert_eq!(format!("{:.2}", boat.implied_cb()), "0.40");
+        assert!(boat.displacement_from_cb().is_none());
+
+        boat.set_cb(0.42);
+        assert_eq!(
+            format!("{:.0}", boat.displacement_from_cb().unwrap().to_kilogram()),
+            "4305"
+        );
+        assert_eq!(
+            format!("{:.3}", boat.displacement_discrepancy().unwrap()),
+            "0.050"
+        );
+    }
+
+    #[test]
+    fn derived_coefficients() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        assert_eq!(boat.cp(), None);
+
+        boat.set_cb(0.40);
+        boat.set_cm(0.72);
+        assert_eq!(format!("{:.3}", boat.cp().unwrap()), "0.556");
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_cp(0.55);
+        boat.set_cm(0.80);
+        assert_eq!(format!("{:.2}", boat.cb().unwrap()), "0.44");
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_cp(0.50);
+        boat.set_cb(0.40);
+        assert_eq!(format!("{:.2}", boat.cm().unwrap()), "0.80");
+        assert_eq!(boat.check_coefficients(), Ok(()));
+
+        // Consistent.
+        boat.set_cm(0.805);
+        assert_eq!(boat.check_coefficients(), Ok(()));
+
+        // Inconsistent.
+        boat.set_cm(0.70);
+        assert_eq!(
+            boat.check_coefficients(),
+            Err(CoefficientError::Inconsistent {
+                cp: 0.50,
+                cb: 0.40,
+                cm: 0.70
+            })
+        );
+
+        // Out of range.
+        boat.set_cm(1.2);
+        assert_eq!(
+            boat.check_coefficients(),
+            Err(CoefficientError::OutOfRange {
+                name: "Cm",
+                value: 1.2
+            })
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // WATERPLANE
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn waterplane() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_foot(32.0));
+        boat.set_bwl(Length::from_foot(10.0));
+        boat.set_displacement(Weight::from_pound(15680.0));
+        assert!(boat.waterplane_area().is_none());
+        assert!(boat.ppi().is_none());
+        assert!(boat.bm().is_none());
+
+        boat.set_cw(0.70);
+        assert_eq!(
+            format!("{:.1}", boat.waterplane_area().unwrap().to_foot2()),
+            "224.0"
+        );
+        // Waterplane area (ft2) * 64 lb/ft3 / 12.
+        assert_eq!(format!("{:.0}", boat.ppi().unwrap().to_pound()), "1194");
+        assert_eq!(format!("{:.2}", boat.bm().unwrap().to_foot()), "5.79");
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // LONGITUDINAL CENTERS
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn longitudinal_center_character() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        assert!(Ratios::new(&boat).lcb.is_none());
+
+        boat.set_lcb(50.0);
+        boat.set_lcf(56.0);
+        let ratios = Ratios::new(&boat);
+        assert_eq!(
+            ratios.lcb.unwrap().longitudinal_character,
+            LongitudinalCenterCharacter::Forward
+        );
+        assert_eq!(
+            ratios.lcf.unwrap().longitudinal_character,
+            LongitudinalCenterCharacter::Typical
+        );
+
+        boat.set_lcb(53.5);
+        assert_eq!(
+            Ratios::new(&boat).lcb.unwrap().longitudinal_character,
+            LongitudinalCenterCharacter::Typical
+        );
+
+        boat.set_lcb(56.0);
+        assert_eq!(
+            Ratios::new(&boat).lcb.unwrap().longitudinal_character,
+            LongitudinalCenterCharacter::Aft
+        );
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // S NUMBER
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn s_number() {
+        use super::*;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_foot(32.0));
+        boat.set_displacement(Weight::from_pound(15680.0));
+        boat.set_sail_area(Area::from_foot2(704.0));
+        let ratios = Ratios::new(&boat);
+        assert_eq!(format!("{:.2}", ratios.s_number.value), "2.65");
+        assert_eq!(
+            ratios.s_number.performance_character,
+            PerformanceCharacter::Cruiser
+        );
+
+        // Light boat with a big rig.
+        boat.set_displacement(Weight::from_pound(8000.0));
+        boat.set_sail_area(Area::from_foot2(600.0));
+        assert_eq!(
+            Ratios::new(&boat).s_number.performance_character,
+            PerformanceCharacter::Racer
+        );
+
+        // Heavy boat with a small rig.
+        boat.set_displacement(Weight::from_pound(30000.0));
+        boat.set_sail_area(Area::from_foot2(500.0));
+        assert_eq!(
+            Ratios::new(&boat).s_number.performance_character,
+            PerformanceCharacter::HeavyCruiser
+        );
+    }
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
-    // LENGHT BEAM RATIO
+    // SPEED
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     #[test]
-    fn beam_character() {
+    fn speed_length_ratio() {
         use super::*;
         let mut boat = Boat::new("".to_string());
-        boat.set_b_max(Length::from_foot(1.00));
-
-        // Beamy.
-        boat.set_loa(Length::from_foot(2.00));
+        boat.set_dwl(Length::from_foot(25.0));
+        assert_eq!(boat.speed_length_ratio(Speed::from_knot(6.0)), 1.2);
+        assert_eq!(format!("{:.2}", boat.hull_speed().to_knot()), "6.70");
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::Beamy
+            format!("{:.3}", boat.froude_number(Speed::from_knot(6.7))),
+            "0.399"
         );
-        boat.set_loa(Length::from_foot(3.00));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::Beamy
+            format!("{:.2}", boat.speed_for_froude_number(0.4).to_knot()),
+            "6.72"
         );
 
-        // Moderate beamy.
-        boat.set_loa(Length::from_foot(3.01));
-        assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::ModerateBeamy
-        );
-        boat.set_loa(Length::from_foot(3.29));
+        boat.set_target_slr(1.1);
+        assert_eq!(format!("{:.1}", boat.target_speed().to_knot()), "5.5");
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // BUILDER
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn builder() {
+        use super::*;
+        let builder = || {
+            BoatBuilder::new("Cruiser".to_string())
+                .loa(Length::from_foot(34.0))
+                .dwl(Length::from_foot(32.0))
+                .b_max(Length::from_foot(11.0))
+                .draft(Length::from_foot(2.0))
+                .displacement(Weight::from_pound(15680.0))
+                .sail_area(Area::from_foot2(704.0))
+        };
+
+        let boat = builder().cp(0.55).build().unwrap();
+        assert_eq!(boat.name, "Cruiser");
+        assert_eq!(boat.bwl().to_foot(), boat.b_max().to_foot());
+        assert_eq!(boat.cp(), Some(0.55));
+
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::ModerateBeamy
+            builder().dwl(Length::from_foot(35.0)).build().err(),
+            Some(ValidationError::DwlExceedsLoa)
         );
-
-        // Moderate.
-        boat.set_loa(Length::from_foot(3.30));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::Moderate
+            builder().b_max(Length::from_foot(0.0)).build().err(),
+            Some(ValidationError::NotPositive("Beam"))
         );
-        boat.set_loa(Length::from_foot(3.65));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::Moderate
+            builder()
+                .displacement(Weight::from_kilogram(-1.0))
+                .build()
+                .err(),
+            Some(ValidationError::NotPositive("Displacement"))
         );
-
-        // Moderate narrow.
-        boat.set_loa(Length::from_foot(3.66));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::ModerateNarrow
+            builder().bwl(Length::from_foot(12.0)).build().err(),
+            Some(ValidationError::BwlExceedsBeam)
         );
-        boat.set_loa(Length::from_foot(3.99));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::ModerateNarrow
+            builder().lcb(120.0).build().err(),
+            Some(ValidationError::OutOfBoat("LCB"))
         );
-
-        // Narrow.
-        boat.set_loa(Length::from_foot(4.00));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::Narrow
+            builder().cp(1.5).build().err(),
+            Some(ValidationError::Coefficient(CoefficientError::OutOfRange {
+                name: "Cp",
+                value: 1.5
+            }))
         );
-        boat.set_loa(Length::from_foot(5.00));
         assert_eq!(
-            Ratios::new(&boat).length_beam_ratio.beam_character,
-            BeamCharacter::Narrow
+            BoatBuilder::new("".to_string()).build().err(),
+            Some(ValidationError::Missing("LOA"))
         );
     }
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
-    // DISPLACEMENT LENGHT RATIO
+    // VALIDATE
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     #[test]
-    fn displacement_character() {
+    fn validate() {
         use super::*;
         let mut boat = Boat::new("".to_string());
+        boat.set_loa(Length::from_foot(34.0));
         boat.set_dwl(Length::from_foot(32.0));
+        boat.set_b_max(Length::from_foot(11.0));
+        boat.set_bwl(Length::from_foot(10.0));
+        boat.set_draft(Length::from_foot(2.0));
+        boat.set_displacement(Weight::from_pound(15680.0));
+        boat.set_sail_area(Area::from_foot2(704.0));
+        let diagnostics = boat.validate();
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
 
-        // Ultralight.
-        boat.set_displacement(Weight::from_long_ton(2.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "Ultralight\n\
-            DWL: {} ft\n\
-            Displacement: {:.1} ton\n\
-            D/L: {:.0} [{}]\n\n",
-            boat.dwl.to_foot(),
-            boat.displacement.to_long_ton(),
-            ratios.displacement_lenght_ratio.value,
-            ratios.displacement_lenght_ratio.displacement_character
-        );
+        boat.set_sail_area(Area::from_foot2(2000.0));
+        let diagnostics = boat.validate();
+        assert!(!diagnostics.has_errors());
         assert_eq!(
-            Ratios::new(&boat)
-                .displacement_lenght_ratio
-                .displacement_character,
-            DisplacementCharacter::Ultralight
+            diagnostics.warnings().next().unwrap().message(),
+            "SA/D of 50.8 is implausibly high for the displacement"
         );
 
-        // Light.
-        boat.set_displacement(Weight::from_long_ton(4.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "Light\n\
-            DWL: {} ft\n\
-            Displacement: {:.1} ton\n\
-            D/L: {:.0} [{}]\n\n",
-            boat.dwl.to_foot(),
-            boat.displacement.to_long_ton(),
-            ratios.displacement_lenght_ratio.value,
-            ratios.displacement_lenght_ratio.displacement_character
-        );
+        boat.set_dwl(Length::from_foot(36.0));
+        boat.set_cp(0.0);
+        let diagnostics = boat.validate();
+        let errors: Vec<&str> = diagnostics.errors().map(|d| d.message()).collect();
         assert_eq!(
-            Ratios::new(&boat)
-                .displacement_lenght_ratio
-                .displacement_character,
-            DisplacementCharacter::Light
+            errors,
+            vec!["DWL exceeds LOA", "Cp of 0.000 is out of range (0, 1]"]
         );
+    }
 
-        // Moderate.
-        boat.set_displacement(Weight::from_long_ton(7.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "Moderate\n\
-            DWL: {} ft\n\
-            Displacement: {:.1} ton\n\
-            D/L: {:.0} [{}]\n\n",
-            boat.dwl.to_foot(),
-            boat.displacement.to_long_ton(),
-            ratios.displacement_lenght_ratio.value,
-            ratios.displacement_lenght_ratio.displacement_character
-        );
-        assert_eq!(
-            Ratios::new(&boat)
-                .displacement_lenght_ratio
-                .displacement_character,
-            DisplacementCharacter::Moderate
-        );
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // PROJECT FILE
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn save_load() {
+        use super::*;
+        let mut boat = Boat::new("Sail cruiser".to_string());
+        boat.set_loa(Length::from_foot(13.0));
+        boat.set_cp(0.55);
+        let path =
+            std::env::temp_dir().join(format!("boat_plan_save_load_{}.json", std::process::id()));
+        boat.save(&path).unwrap();
 
-        // Heavy.
-        boat.set_displacement(Weight::from_long_ton(9.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "Heavy\n\
-            DWL: {} ft\n\
-            Displacement: {:.1} ton\n\
-            D/L: {:.0} [{}]\n\n",
-            boat.dwl.to_foot(),
-            boat.displacement.to_long_ton(),
-            ratios.displacement_lenght_ratio.value,
-            ratios.displacement_lenght_ratio.displacement_character
-        );
-        assert_eq!(
-            Ratios::new(&boat)
-                .displacement_lenght_ratio
-                .displacement_character,
-            DisplacementCharacter::Heavy
-        );
+        let loaded = Boat::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.to_string(), boat.to_string());
+        assert_eq!(loaded.cp(), Some(0.55));
+        assert!(loaded.vcg().is_none());
 
-        // Ultraheavy.
-        boat.set_displacement(Weight::from_long_ton(12.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "Ultraheavy\n\
-            DWL: {} ft\n\
-            Displacement: {:.1} ton\n\
-            D/L: {:.0} [{}]\n\n",
-            boat.dwl.to_foot(),
-            boat.displacement.to_long_ton(),
-            ratios.displacement_lenght_ratio.value,
-            ratios.displacement_lenght_ratio.displacement_character
-        );
-        assert_eq!(
-            Ratios::new(&boat)
-                .displacement_lenght_ratio
-                .displacement_character,
-            DisplacementCharacter::Ultraheavy
-        );
+        assert!(matches!(Boat::load(&path).err(), Some(ProjectError::Io(_))));
     }
 
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // ACCESSORS
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
     #[test]
-    fn displacement_length_ratio() {
+    fn ratio_accessors() {
         use super::*;
         let mut boat = Boat::new("".to_string());
         boat.set_loa(Length::from_foot(34.0));
         boat.set_dwl(Length::from_foot(32.0));
-        boat.set_b_max(Length::from_foot(1.0));
+        boat.set_b_max(Length::from_foot(10.0));
         boat.set_displacement(Weight::from_pound(15680.0));
+        boat.set_sail_area(Area::from_foot2(704.0));
+        boat.set_cp(0.56);
+        boat.set_lcb(53.0);
+        let ratios = Ratios::new(&boat);
+
+        assert_eq!(format!("{:.2}", ratios.length_beam().value()), "3.40");
+        assert_eq!(ratios.length_beam().character(), BeamCharacter::Moderate);
+        assert_eq!(ratios.displacement_length().value().round(), 214.0);
         assert_eq!(
-            Ratios::new(&boat).displacement_lenght_ratio.value.round(),
-            214.0
+            ratios.displacement_length().character(),
+            DisplacementCharacter::Moderate
+        );
+        assert_eq!(ratios.sail_area_displacement().value().round(), 18.0);
+        assert_eq!(
+            ratios.sail_area_displacement().character(),
+            SailAreaCharacter::Moderate
+        );
+        assert!(ratios.sail_area_wetted_surface().value() > 0.0);
+        assert_eq!(ratios.s_number().character(), PerformanceCharacter::Cruiser);
+        let cp = ratios.prismatic_coefficient().unwrap();
+        assert_eq!(cp.value(), 0.56);
+        assert_eq!(cp.target_slr(), 1.34);
+        assert_eq!(format!("{:.3}", cp.ideal()), "0.602");
+        assert_eq!(
+            ratios.lcb().unwrap().character(),
+            LongitudinalCenterCharacter::Typical
         );
+        assert!(ratios.lcf().is_none());
     }
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
-    // SAIL AREA DISPLACEMENT RATIO
+    // THRESHOLDS
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     #[test]
-    fn sail_area_character() {
+    fn ratio_thresholds() {
         use super::*;
-        let mut boat = Boat::new("".to_string());
-        boat.set_displacement(Weight::from_pound(15680.0));
+        let mut boat = Boat::new("Dinghy".to_string());
+        boat.set_loa(Length::from_foot(14.0));
+        boat.set_dwl(Length::from_foot(13.0));
+        boat.set_b_max(Length::from_foot(4.5));
+        boat.set_displacement(Weight::from_pound(300.0));
+        boat.set_sail_area(Area::from_foot2(80.0));
 
-        // Low.
-        boat.set_sail_area(Area::from_foot2(550.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "SailAreaDisplacementRatio: {}",
-            ratios.sail_area_displacement,
+        let perry = Ratios::new(&boat);
+        assert_eq!(
+            perry.length_beam().character(),
+            BeamCharacter::ModerateBeamy
         );
         assert_eq!(
-            Ratios::new(&boat)
-                .sail_area_displacement
-                .sail_area_character,
-            SailAreaCharacter::Low
+            perry.displacement_length().character(),
+            DisplacementCharacter::Ultralight
+        );
+        assert_eq!(
+            perry.sail_area_displacement().character(),
+            SailAreaCharacter::High
         );
 
-        // Moderate.
-        boat.set_sail_area(Area::from_foot2(704.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "SailAreaDisplacementRatio: {}",
-            ratios.sail_area_displacement,
+        let dinghy = Ratios::with_thresholds(&boat, &RatioThresholds::dinghy());
+        assert_eq!(dinghy.length_beam().character(), BeamCharacter::Moderate);
+        assert_eq!(
+            dinghy.displacement_length().character(),
+            DisplacementCharacter::Light
         );
         assert_eq!(
-            Ratios::new(&boat)
-                .sail_area_displacement
-                .sail_area_character,
+            dinghy.sail_area_displacement().character(),
             SailAreaCharacter::Moderate
         );
 
-        // High.
-        boat.set_sail_area(Area::from_foot2(800.0));
-        let ratios = Ratios::new(&boat);
-        print!(
-            "SailAreaDisplacementRatio: {}",
-            ratios.sail_area_displacement,
-        );
+        // Tuned profile.
+        let thresholds = RatioThresholds {
+            sail_area_displacement: [30.0, 40.0],
+            ..RatioThresholds::dinghy()
+        };
         assert_eq!(
-            Ratios::new(&boat)
-                .sail_area_displacement
-                .sail_area_character,
-            SailAreaCharacter::High
+            Ratios::with_thresholds(&boat, &thresholds)
+                .sail_area_displacement()
+                .character(),
+            SailAreaCharacter::Low
         );
     }
 
     #[test]
-    fn sail_area_displacement() {
+    fn commentary() {
+        use super::*;
+        let mut boat = Boat::new("Cruiser".to_string());
+        boat.set_loa(Length::from_foot(35.0));
+        boat.set_dwl(Length::from_foot(28.0));
+        boat.set_b_max(Length::from_foot(11.0));
+        boat.set_displacement(Weight::from_pound(17000.0));
+        boat.set_sail_area(Area::from_foot2(550.0));
+
+        let commentary = Ratios::new(&boat).commentary();
+        assert!(commentary.starts_with("Heavy, moderately beamy heavy cruiser with a conservative"));
+        assert!(commentary.contains("at the cost of speed"));
+        assert!(!commentary.contains("prismatic"));
+    }
+
+    #[test]
+    fn rig_sail_area() {
+        use super::*;
+        let rig = Rig::new(
+            Length::from_foot(40.0),
+            Length::from_foot(13.0),
+            Length::from_foot(35.0),
+            Length::from_foot(12.0),
+        );
+        let mut boat = Boat::new("Sloop".to_string());
+        boat.set_sail_area(Area::from_foot2(500.0));
+        boat.set_rig(rig.clone());
+        assert_eq!(format!("{:.0}", boat.sail_area().to_foot2()), "470");
+
+        let boat = BoatBuilder::new("Sloop".to_string())
+            .loa(Length::from_foot(35.0))
+            .dwl(Length::from_foot(29.0))
+            .b_max(Length::from_foot(11.0))
+            .draft(Length::from_foot(1.5))
+            .displacement(Weight::from_pound(12000.0))
+            .rig(rig)
+            .build()
+            .unwrap();
+        assert_eq!(format!("{:.0}", boat.sail_area().to_foot2()), "470");
+    }
+
+    #[test]
+    fn sail_combination() {
+        use super::*;
+        use crate::rig::{Sail, SailKind};
+        let mut rig = Rig::new(
+            Length::from_foot(40.0),
+            Length::from_foot(13.0),
+            Length::from_foot(35.0),
+            Length::from_foot(12.0),
+        );
+        rig.add_sail(Sail::from_area(
+            "Main".to_string(),
+            SailKind::Main,
+            Area::from_foot2(230.0),
+        ));
+        rig.add_sail(Sail::from_area(
+            "Jib".to_string(),
+            SailKind::Jib,
+            Area::from_foot2(250.0),
+        ));
+        rig.add_sail(Sail::from_area(
+            "Spinnaker".to_string(),
+            SailKind::Spinnaker,
+            Area::from_foot2(800.0),
+        ));
+        let mut boat = Boat::new("Sloop".to_string());
+        boat.set_displacement(Weight::from_pound(12000.0));
+        boat.set_rig(rig);
+
+        let rig = boat.rig().unwrap();
+        let thresholds = RatioThresholds::default();
+        let working = rig.combination_area(&["Main", "Jib"]).unwrap();
+        let downwind = rig.combination_area(&["Main", "Spinnaker"]).unwrap();
+        assert!(rig.combination_area(&["Genoa"]).is_none());
+        let working = SailAreaDisplacementRatio::from_sail_area(&boat, working, &thresholds);
+        let downwind = SailAreaDisplacementRatio::from_sail_area(&boat, downwind, &thresholds);
+        assert!(downwind.value() > 2.0 * working.value());
+    }
+
+    #[test]
+    fn keel() {
+        use super::*;
+        use crate::appendage::KeelType;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(9.0));
+        boat.set_draft(Length::from_meter(0.5));
+        boat.set_displacement(Weight::from_kilogram(5125.0));
+        boat.set_keel(Keel::new(
+            KeelType::Fin,
+            Length::from_meter(1.2),
+            Length::from_meter(0.6),
+            Length::from_meter(1.5),
+            Weight::from_kilogram(2050.0),
+        ));
+        // 17.65 of canoe body plus 2.70 of keel.
+        assert_eq!(format!("{:.2}", boat.wetted_surface().to_meter2()), "20.35");
+        assert_eq!(format!("{:.2}", boat.ballast_ratio().unwrap()), "0.40");
+        assert_eq!(format!("{:.2}", boat.total_draft().to_meter()), "2.00");
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // BRUCE NUMBER
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn bruce_number() {
         use super::*;
         let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_foot(32.0));
         boat.set_displacement(Weight::from_pound(15680.0));
         boat.set_sail_area(Area::from_foot2(704.0));
-        assert_eq!(
-            Ratios::new(&boat).sail_area_displacement.value.round(),
-            18.0
-        );
+        let bruce_number = Ratios::new(&boat).bruce_number;
+        assert_eq!(format!("{:.2}", bruce_number.value()), "1.06");
+        assert_eq!(bruce_number.character(), PowerCharacter::Moderate);
+
+        // Dinghy, 10m2 and 130kg with crew.
+        boat.set_displacement(Weight::from_kilogram(130.0));
+        boat.set_sail_area(Area::from_meter2(10.0));
+        let bruce_number = BruceNumber::from_boat(&boat);
+        assert_eq!(format!("{:.2}", bruce_number.value()), "1.57");
+        assert_eq!(bruce_number.character(), PowerCharacter::Powerful);
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // SCREENING STABILITY VALUE
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn screening_stability() {
+        use super::*;
+        use crate::appendage::KeelType;
+        let mut boat = Boat::new("".to_string());
+        boat.set_b_max(Length::from_foot(10.5));
+        boat.set_draft(Length::from_foot(1.5));
+        boat.set_displacement(Weight::from_pound(15680.0));
+        assert!(Ratios::new(&boat).screening_stability().is_none());
+
+        let keel = |ballast| {
+            Keel::new(
+                KeelType::Fin,
+                Length::from_meter(1.2),
+                Length::from_meter(0.6),
+                Length::from_meter(1.5),
+                Weight::from_pound(ballast),
+            )
+        };
+        boat.set_keel(keel(6272.0));
+        // 10.5 exp 2 / (0.4 * 1.5 * 245.0 exp (1/3)), 15680lb of sea water is 245.0ft3.
+        let ssv = ScreeningStabilityValue::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.2}", ssv.value()), "29.36");
+        // 110 + 400 / (29.36 - 10)
+        assert_eq!(ssv.to_string(), "29.36 [AVS 131°, Ocean]");
+
+        // More ballast, lower value.
+        boat.set_keel(keel(7056.0));
+        let ssv = ScreeningStabilityValue::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.2}", ssv.value()), "26.10");
+        assert_eq!(format!("{:.0}", ssv.avs_estimate().avs()), "135");
     }
 }
 
@@ -0,0 +1,192 @@
+use super::boat::{Boat, Metric};
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// COMPARISON
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Which value wins a metric, from the performance point of view.
+/// Dimensions have no winner, they are only compared.
+fn higher_wins(metric: Metric) -> Option<bool> {
+    match metric {
+        Metric::LengthBeam => Some(true),
+        Metric::DisplacementLength => Some(false),
+        Metric::SailAreaDisplacement => Some(true),
+        Metric::SailAreaWettedSurface => Some(true),
+        Metric::SNumber => Some(true),
+        Metric::BruceNumber => Some(true),
+        _ => None,
+    }
+}
+
+/// Comparison row, one metric for all the boats.
+pub struct ComparisonRow {
+    metric: Metric,
+    values: Vec<f64>,
+    winner: Option<usize>,
+}
+
+#[allow(dead_code)]
+impl ComparisonRow {
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    /// Values in the same order as the boats.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Difference of each boat to the first one (the baseline).
+    pub fn deltas(&self) -> Vec<f64> {
+        self.values.iter().map(|v| v - self.values[0]).collect()
+    }
+
+    /// Index of the boat with the best value, if the metric has one.
+    pub fn winner(&self) -> Option<usize> {
+        self.winner
+    }
+}
+
+/// Comparison
+///
+/// Aligned dimensions and ratios of two or more boats, the first boat is the baseline
+/// for the deltas.
+pub struct Comparison {
+    names: Vec<String>,
+    rows: Vec<ComparisonRow>,
+}
+
+#[allow(dead_code)]
+impl Comparison {
+    /// Needs at least two boats.
+    pub fn new(boats: &[&Boat]) -> Option<Comparison> {
+        if boats.len() < 2 {
+            return None;
+        }
+        let rows = Metric::ALL
+            .iter()
+            .map(|&metric| {
+                let values: Vec<f64> = boats.iter().map(|b| metric.value(b)).collect();
+                let winner = higher_wins(metric).map(|higher| {
+                    let mut best = 0;
+                    for (i, v) in values.iter().enumerate() {
+                        if (higher && *v > values[best]) || (!higher && *v < values[best]) {
+                            best = i;
+                        }
+                    }
+                    best
+                });
+                ComparisonRow {
+                    metric,
+                    values,
+                    winner,
+                }
+            })
+            .collect();
+        Some(Comparison {
+            names: boats.iter().map(|b| b.name().to_string()).collect(),
+            rows,
+        })
+    }
+
+    /// Boat names, in the same order as the values.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn rows(&self) -> &[ComparisonRow] {
+        &self.rows
+    }
+
+    /// Row of a metric.
+    pub fn row(&self, metric: Metric) -> &ComparisonRow {
+        self.rows
+            .iter()
+            .find(|r| r.metric == metric)
+            .expect("comparison has all metrics")
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Comparison]\n{:<20}", "")?;
+        for name in &self.names {
+            write!(f, "{:>20}", name)?;
+        }
+        for row in &self.rows {
+            write!(
+                f,
+                "\n{:<20}",
+                format!("{} {}", row.metric, row.metric.unit()).trim_end()
+            )?;
+            let deltas = row.deltas();
+            for (i, value) in row.values.iter().enumerate() {
+                let mark = if row.winner == Some(i) { "*" } else { " " };
+                let cell = if i == 0 {
+                    format!("{:.2}{}", value, mark)
+                } else {
+                    format!("{:.2} ({:+.2}){}", value, deltas[i], mark)
+                };
+                write!(f, "{:>20}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::si::{Area, Length, Weight};
+
+    #[test]
+    fn comparison() {
+        let mut cruiser = Boat::new("Cruiser".to_string());
+        cruiser.set_loa(Length::from_meter(10.0));
+        cruiser.set_dwl(Length::from_meter(9.0));
+        cruiser.set_b_max(Length::from_meter(3.4));
+        cruiser.set_displacement(Weight::from_kilogram(7000.0));
+        cruiser.set_sail_area(Area::from_meter2(55.0));
+
+        let mut racer = Boat::new("Racer".to_string());
+        racer.set_loa(Length::from_meter(10.0));
+        racer.set_dwl(Length::from_meter(9.5));
+        racer.set_b_max(Length::from_meter(3.0));
+        racer.set_displacement(Weight::from_kilogram(3500.0));
+        racer.set_sail_area(Area::from_meter2(60.0));
+
+        assert!(Comparison::new(&[&cruiser]).is_none());
+        let comparison = Comparison::new(&[&cruiser, &racer]).unwrap();
+        assert_eq!(comparison.names(), ["Cruiser", "Racer"]);
+
+        let loa = comparison.row(Metric::Loa);
+        assert_eq!(loa.deltas(), vec![0.0, 0.0]);
+        assert_eq!(loa.winner(), None);
+
+        let beam = comparison.row(Metric::Beam);
+        assert_eq!(format!("{:.1}", beam.deltas()[1]), "-0.4");
+
+        assert_eq!(comparison.row(Metric::DisplacementLength).winner(), Some(1));
+        assert_eq!(
+            comparison.row(Metric::SailAreaDisplacement).winner(),
+            Some(1)
+        );
+        assert_eq!(comparison.row(Metric::LengthBeam).winner(), Some(1));
+
+        let text = comparison.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "[Comparison]");
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            ["Cruiser", "Racer"]
+        );
+        assert!(lines[4].starts_with("Beam m"));
+        assert!(lines[4].ends_with("3.40        3.00 (-0.40) "));
+        assert!(lines[9].starts_with("L/B"));
+        assert!(lines[9].ends_with("2.94        3.33 (+0.39)*"));
+    }
+}
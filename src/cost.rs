@@ -0,0 +1,192 @@
+use super::boat::Boat;
+use super::si::Area;
+use std::fmt;
+
+/// Hull skin area over the wetted surface, the topsides above the waterline.
+pub const TOPSIDES_FACTOR: f64 = 1.5;
+
+/// Deck area as a fraction of LOA * Beam.
+pub const DECK_AREA_FACTOR: f64 = 0.75;
+
+/// Budget range around the estimate, amateur projects tend to run over.
+pub const COST_RANGE: (f64, f64) = (0.8, 1.4);
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// UNIT COSTS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Unit costs
+///
+/// Currency neutral, set them to the local prices.
+#[derive(Debug, Copy, Clone)]
+pub struct UnitCosts {
+    /// Hull and deck materials per m2 of hull area (panels, resin, glass, fastenings).
+    pub material_rate: f64,
+    /// Rig (spars, standing and running rigging, sails) per kg of displacement.
+    pub rig_rate: f64,
+    /// Deck hardware, fittings and systems per kg of displacement.
+    pub hardware_rate: f64,
+    /// Building hours per lb of displacement.
+    pub labor_hours_per_pound: f64,
+    /// Labor rate per hour, zero when the builder does not pay for the hours.
+    pub labor_rate: f64,
+}
+
+#[allow(dead_code)]
+impl UnitCosts {
+    /// Amateur build in plywood and epoxy, US dollars, own labor.
+    pub fn amateur() -> UnitCosts {
+        UnitCosts {
+            material_rate: 120.0,
+            rig_rate: 8.0,
+            hardware_rate: 6.0,
+            labor_hours_per_pound: 0.5,
+            labor_rate: 0.0,
+        }
+    }
+
+    /// Professional yard, US dollars, paid labor at a yard productivity.
+    pub fn professional() -> UnitCosts {
+        UnitCosts {
+            labor_hours_per_pound: 0.3,
+            labor_rate: 60.0,
+            ..UnitCosts::amateur()
+        }
+    }
+}
+
+impl Default for UnitCosts {
+    fn default() -> UnitCosts {
+        UnitCosts::amateur()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// COST ESTIMATE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Cost estimate
+///
+/// Parametric build cost, to scope a project, not a quote:
+/// Hull area = Wetted surface * TOPSIDES_FACTOR + LOA * Beam * DECK_AREA_FACTOR
+/// Materials = Hull area * material rate
+/// Rig and hardware = Displacement * rates
+/// Labor = Displacement(lb) * hours per lb * labor rate
+pub struct CostEstimate {
+    hull_area: Area,
+    materials: f64,
+    rig: f64,
+    hardware: f64,
+    labor_hours: f64,
+    labor: f64,
+}
+
+#[allow(dead_code)]
+impl CostEstimate {
+    pub fn new(boat: &Boat, costs: &UnitCosts) -> CostEstimate {
+        let hull_area = Area::from_meter2(
+            boat.wetted_surface().to_meter2() * TOPSIDES_FACTOR
+                + boat.loa().to_meter() * boat.b_max().to_meter() * DECK_AREA_FACTOR,
+        );
+        let displacement = boat.displacement();
+        let labor_hours = displacement.to_pound() * costs.labor_hours_per_pound;
+        CostEstimate {
+            hull_area,
+            materials: hull_area.to_meter2() * costs.material_rate,
+            rig: displacement.to_kilogram() * costs.rig_rate,
+            hardware: displacement.to_kilogram() * costs.hardware_rate,
+            labor_hours,
+            labor: labor_hours * costs.labor_rate,
+        }
+    }
+
+    /// Hull and deck area.
+    pub fn hull_area(&self) -> Area {
+        self.hull_area
+    }
+
+    pub fn materials(&self) -> f64 {
+        self.materials
+    }
+
+    pub fn rig(&self) -> f64 {
+        self.rig
+    }
+
+    pub fn hardware(&self) -> f64 {
+        self.hardware
+    }
+
+    /// Building hours.
+    pub fn labor_hours(&self) -> f64 {
+        self.labor_hours
+    }
+
+    pub fn labor(&self) -> f64 {
+        self.labor
+    }
+
+    pub fn total(&self) -> f64 {
+        self.materials + self.rig + self.hardware + self.labor
+    }
+
+    /// Budget range, low and high.
+    pub fn range(&self) -> (f64, f64) {
+        (self.total() * COST_RANGE.0, self.total() * COST_RANGE.1)
+    }
+}
+
+impl fmt::Display for CostEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (low, high) = self.range();
+        write!(
+            f,
+            "[Cost estimate]\n\
+            \tHull area: {:>10.1}m2\n\
+            \tMaterials: {:>10.0}\n\
+            \tRig:       {:>10.0}\n\
+            \tHardware:  {:>10.0}\n\
+            \tLabor:     {:>10.0} ({:.0}h)\n\
+            \tTotal:     {:>10.0} ({:.0} to {:.0})",
+            self.hull_area.to_meter2(),
+            self.materials,
+            self.rig,
+            self.hardware,
+            self.labor,
+            self.labor_hours,
+            self.total(),
+            low,
+            high
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::si::{Length, Weight};
+
+    #[test]
+    fn cost_estimate() {
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(Length::from_meter(6.0));
+        boat.set_b_max(Length::from_meter(2.4));
+        boat.set_displacement(Weight::from_kilogram(1000.0));
+        boat.set_wetted_surface(Area::from_meter2(10.0));
+
+        let estimate = CostEstimate::new(&boat, &UnitCosts::amateur());
+        // 10 * 1.5 + 6 * 2.4 * 0.75
+        assert_eq!(format!("{:.1}", estimate.hull_area().to_meter2()), "25.8");
+        assert_eq!(format!("{:.0}", estimate.materials()), "3096");
+        assert_eq!(format!("{:.0}", estimate.labor_hours()), "1102");
+        assert_eq!(estimate.labor(), 0.0);
+        // 3096 + 8000 + 6000
+        assert_eq!(format!("{:.0}", estimate.total()), "17096");
+        assert_eq!(format!("{:.0}", estimate.range().1), "23934");
+
+        let estimate = CostEstimate::new(&boat, &UnitCosts::professional());
+        assert_eq!(format!("{:.0}", estimate.labor()), "39683");
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+use std::io;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// CSV
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// CSV file error.
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    /// Line number (starting at 1) and message.
+    Parse(usize, String),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(err) => write!(f, "CSV file error: {}", err),
+            CsvError::Parse(line, message) => write!(f, "CSV line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> CsvError {
+        CsvError::Io(err)
+    }
+}
+
+/// Split a CSV line into trimmed fields.
+/// Fields may be quoted to contain commas, a doubled quote inside a quoted field is a quote.
+pub(crate) fn split_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Non empty lines with their line number (starting at 1), skipping # comments.
+pub(crate) fn lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn split_line() {
+        assert_eq!(super::split_line("a, b ,c"), vec!["a", "b", "c"]);
+        assert_eq!(
+            super::split_line(r#""Catalina 27, tall rig",27.0,"5"" board""#),
+            vec!["Catalina 27, tall rig", "27.0", "5\" board"]
+        );
+        assert_eq!(super::split_line("a,,"), vec!["a", "", ""]);
+
+        let text = "# comment\n\nname\n  x  \n";
+        let lines: Vec<(usize, &str)> = lines(text).collect();
+        assert_eq!(lines, vec![(3, "name"), (4, "x")]);
+    }
+}
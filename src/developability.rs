@@ -0,0 +1,296 @@
+use super::hull::{Hull, HullPoint};
+use super::si::{Area, Length};
+use std::fmt;
+
+/// Largest curvature of a bay for the plate to wrap from a flat sheet, the Gauss curvature
+/// times the area (radian), the angle the plate would have to stretch.
+pub const DEVELOPABILITY_TOLERANCE: f64 = 0.005;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DEVELOPABILITY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Edge of a panel, an offset point of the sections counted from the keel, or the sheer.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PanelEdge {
+    Point(usize),
+    Sheer,
+}
+
+impl PanelEdge {
+    fn point(self, points: &[(Length, Length)]) -> Option<(Length, Length)> {
+        match self {
+            PanelEdge::Point(i) => points.get(i).copied(),
+            PanelEdge::Sheer => points.last().copied(),
+        }
+    }
+}
+
+impl fmt::Display for PanelEdge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PanelEdge::Point(0) => write!(f, "Keel"),
+            PanelEdge::Point(i) => write!(f, "Chine {}", i),
+            PanelEdge::Sheer => write!(f, "Sheer"),
+        }
+    }
+}
+
+/// Bay of a panel between two stations.
+#[derive(Debug, Copy, Clone)]
+pub struct PanelBay {
+    pub start: Length,
+    pub end: Length,
+    /// Angle between the two halves of the bay split by a diagonal (degree).
+    pub twist: f64,
+    /// Gauss curvature at the middle of the bay (1/m2), negative for a twisted bay.
+    pub gaussian: f64,
+    pub area: Area,
+}
+
+impl PanelBay {
+    /// Curvature the plate would have to stretch to.
+    pub fn angle_defect(&self) -> f64 {
+        self.gaussian.abs() * self.area.to_meter2()
+    }
+
+    pub fn is_developable(&self) -> bool {
+        self.angle_defect() <= DEVELOPABILITY_TOLERANCE
+    }
+}
+
+/// Panel
+///
+/// Plate between two edges of a chined hull, lofted as straight lines between the edges at
+/// the stations. Each bay between two stations is the bilinear surface of its four corners,
+/// flat when the corners are on a plane and twisted otherwise, the twist is the Gauss
+/// curvature K = -(d.n)^2 / |ru x rv|^2 at the middle of the bay. Developable plates, cylinders
+/// and cones, have no Gauss curvature. The rulings of a cone fanning across the stations show
+/// as a small twist, the tolerance is on the curvature times the bay area.
+#[derive(Debug, Clone)]
+pub struct Panel {
+    lower: PanelEdge,
+    upper: PanelEdge,
+    bays: Vec<PanelBay>,
+}
+
+#[allow(dead_code)]
+impl Panel {
+    pub fn new(hull: &Hull, lower: PanelEdge, upper: PanelEdge) -> Panel {
+        let bays = rulings(hull, lower, upper)
+            .windows(2)
+            .map(|w| bay(w[0].0, w[1].0, w[0].1, w[1].1))
+            .collect();
+        Panel { lower, upper, bays }
+    }
+
+    pub fn lower(&self) -> PanelEdge {
+        self.lower
+    }
+
+    pub fn upper(&self) -> PanelEdge {
+        self.upper
+    }
+
+    pub fn bays(&self) -> &[PanelBay] {
+        &self.bays
+    }
+
+    pub fn area(&self) -> Area {
+        Area::from_meter2(self.bays.iter().map(|b| b.area.to_meter2()).sum())
+    }
+
+    /// Largest twist of the bays (degree).
+    pub fn maximum_twist(&self) -> f64 {
+        self.bays.iter().fold(0.0, |max, b| f64::max(max, b.twist))
+    }
+
+    /// Bays that do not wrap from a flat sheet.
+    pub fn violations(&self) -> Vec<&PanelBay> {
+        self.bays.iter().filter(|b| !b.is_developable()).collect()
+    }
+
+    pub fn is_developable(&self) -> bool {
+        self.bays.iter().all(|b| b.is_developable())
+    }
+}
+
+/// Straight lines of a panel at the stations, from the lower to the upper edge. Sections
+/// without both edges are skipped.
+pub fn rulings(hull: &Hull, lower: PanelEdge, upper: PanelEdge) -> Vec<(HullPoint, HullPoint)> {
+    hull.sections()
+        .iter()
+        .filter_map(|s| {
+            let points = s.points();
+            let (y0, z0) = lower.point(&points)?;
+            let (y1, z1) = upper.point(&points)?;
+            Some((
+                HullPoint::new(s.position(), y0, z0),
+                HullPoint::new(s.position(), y1, z1),
+            ))
+        })
+        .collect()
+}
+
+/// Panel edges of a chined hull, the keel, the chines and the sheer.
+pub fn panel_edges(chines: &[usize]) -> Vec<PanelEdge> {
+    let mut edges = vec![PanelEdge::Point(0)];
+    edges.extend(chines.iter().map(|&i| PanelEdge::Point(i)));
+    edges.push(PanelEdge::Sheer);
+    edges
+}
+
+/// Bay of the corners, 00 and 10 on the lower edge, 01 and 11 on the upper edge.
+fn bay(p00: HullPoint, p10: HullPoint, p01: HullPoint, p11: HullPoint) -> PanelBay {
+    let v = |p: HullPoint| [p.x.to_meter(), p.y.to_meter(), p.z.to_meter()];
+    let (a, b, c, e) = (v(p00), v(p10), v(p01), v(p11));
+    let sub = |p: [f64; 3], q: [f64; 3]| [p[0] - q[0], p[1] - q[1], p[2] - q[2]];
+    let cross = |p: [f64; 3], q: [f64; 3]| {
+        [
+            p[1] * q[2] - p[2] * q[1],
+            p[2] * q[0] - p[0] * q[2],
+            p[0] * q[1] - p[1] * q[0],
+        ]
+    };
+    let dot = |p: [f64; 3], q: [f64; 3]| p[0] * q[0] + p[1] * q[1] + p[2] * q[2];
+    let norm = |p: [f64; 3]| dot(p, p).sqrt();
+
+    let along = sub(b, a);
+    let across = sub(c, a);
+    let d = sub(sub(e, b), across);
+    let ru = [
+        along[0] + d[0] / 2.0,
+        along[1] + d[1] / 2.0,
+        along[2] + d[2] / 2.0,
+    ];
+    let rv = [
+        across[0] + d[0] / 2.0,
+        across[1] + d[1] / 2.0,
+        across[2] + d[2] / 2.0,
+    ];
+    let normal = cross(ru, rv);
+    let area = norm(normal);
+    let gaussian = if area > 1e-12 {
+        -(dot(d, normal) / area).powi(2) / (area * area)
+    } else {
+        0.0
+    };
+    let n1 = cross(sub(b, a), sub(c, a));
+    let n2 = cross(sub(c, e), sub(b, e));
+    let twist = if norm(n1) > 1e-12 && norm(n2) > 1e-12 {
+        (dot(n1, n2) / (norm(n1) * norm(n2)))
+            .clamp(-1.0, 1.0)
+            .acos()
+            .to_degrees()
+    } else {
+        0.0
+    };
+    PanelBay {
+        start: p00.x,
+        end: p10.x,
+        twist,
+        gaussian,
+        area: Area::from_meter2(area),
+    }
+}
+
+/// Developability
+///
+/// Panels of a chined hull, from the keel to the first chine, between the chines and from the
+/// last chine to the sheer.
+#[derive(Debug, Clone)]
+pub struct Developability {
+    panels: Vec<Panel>,
+}
+
+#[allow(dead_code)]
+impl Developability {
+    /// Chines as the offset points of the sections, counted from the keel.
+    pub fn new(hull: &Hull, chines: &[usize]) -> Developability {
+        Developability {
+            panels: panel_edges(chines)
+                .windows(2)
+                .map(|w| Panel::new(hull, w[0], w[1]))
+                .collect(),
+        }
+    }
+
+    pub fn panels(&self) -> &[Panel] {
+        &self.panels
+    }
+
+    pub fn is_developable(&self) -> bool {
+        self.panels.iter().all(|p| p.is_developable())
+    }
+}
+
+impl fmt::Display for Developability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Developability]")?;
+        for panel in &self.panels {
+            write!(
+                f,
+                "\n\t{} to {}: {:.2}m2, twist {:.1}deg, {}",
+                panel.lower,
+                panel.upper,
+                panel.area().to_meter2(),
+                panel.maximum_twist(),
+                if panel.is_developable() {
+                    "developable".to_string()
+                } else {
+                    format!("{} bays not developable", panel.violations().len())
+                }
+            )?;
+            for bay in panel.violations() {
+                write!(
+                    f,
+                    "\n\t\t{:.3}m to {:.3}m: K {:.4}1/m2",
+                    bay.start.to_meter(),
+                    bay.end.to_meter(),
+                    bay.gaussian
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn developability() {
+        use crate::parent::ParentHull;
+
+        // Chine at 20 degree deadrise along the hull, flat panels.
+        let hull = |deadrise: &dyn Fn(f64) -> f64| {
+            crate::hull::test::chine_hull(
+                &|x| (0.0, 0.8 * deadrise(x).to_radians().tan()),
+                0.8,
+                (1.0, 1.0),
+            )
+        };
+        let flat = Developability::new(&hull(&|_| 20.0), &[1]);
+        assert_eq!(flat.panels().len(), 2);
+        assert_eq!(flat.panels()[0].bays().len(), 4);
+        assert_eq!(format!("{:.4}", flat.panels()[0].maximum_twist()), "0.0000");
+        assert!(flat.is_developable());
+
+        // Deadrise from 40 degree forward to 0 aft, the bottom twists.
+        let twisted = Developability::new(&hull(&|x| 40.0 - 10.0 * x), &[1]);
+        let bottom = &twisted.panels()[0];
+        assert!(!bottom.is_developable());
+        assert!(bottom.bays()[0].gaussian < 0.0);
+        assert!(bottom.maximum_twist() > 5.0);
+        assert!(!twisted.is_developable());
+        assert_eq!(twisted.panels()[1].upper(), PanelEdge::Sheer);
+
+        let parent = ParentHull::HardChineDinghy;
+        let dinghy = Developability::new(&parent.hull(), parent.chines());
+        assert_eq!(dinghy.panels().len(), 2);
+        assert!(dinghy.panels()[0].area().to_meter2() > 0.0);
+    }
+}
@@ -0,0 +1,342 @@
+use super::developability::{panel_edges, rulings, PanelEdge};
+use super::hull::{Hull, HullPoint};
+use super::si::{Area, Length};
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DEVELOPMENT
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Flat panel
+///
+/// Panel of a chined hull unrolled to its flat shape, to mark and cut from the sheets for
+/// stitch and glue. Each bay between two stations is split in two triangles by a diagonal and
+/// the triangles are laid flat one after the other keeping the lengths of their sides, so the
+/// edges and the station lines have the lengths of the hull. A panel that is not developable
+/// unrolls anyway, its diagonals take the strain.
+/// The flat panel starts at the forward station, the lower edge from the origin along x.
+#[derive(Debug, Clone)]
+pub struct FlatPanel {
+    lower_edge: PanelEdge,
+    upper_edge: PanelEdge,
+    stations: Vec<Length>,
+    lower: Vec<(f64, f64)>,
+    upper: Vec<(f64, f64)>,
+}
+
+#[allow(dead_code)]
+impl FlatPanel {
+    /// None when the panel has less than two stations.
+    pub fn new(hull: &Hull, lower_edge: PanelEdge, upper_edge: PanelEdge) -> Option<FlatPanel> {
+        let rulings = rulings(hull, lower_edge, upper_edge);
+        if rulings.len() < 2 {
+            return None;
+        }
+        let distance = |p: HullPoint, q: HullPoint| {
+            ((p.x.to_meter() - q.x.to_meter()).powi(2)
+                + (p.y.to_meter() - q.y.to_meter()).powi(2)
+                + (p.z.to_meter() - q.z.to_meter()).powi(2))
+            .sqrt()
+        };
+        let (l0, u0) = rulings[0];
+        let mut lower = vec![(0.0, 0.0)];
+        let mut upper = vec![(0.0, distance(l0, u0))];
+        // Known point across the edge from the next triangle, none at the first station.
+        let mut behind: Option<(f64, f64)> = None;
+        for w in rulings.windows(2) {
+            let ((l, u), (next_l, next_u)) = (w[0], w[1]);
+            let (fl, fu) = (*lower.last().unwrap(), *upper.last().unwrap());
+            let flat_l = place(fl, fu, distance(l, next_l), distance(u, next_l), behind);
+            let flat_u = place(
+                flat_l,
+                fu,
+                distance(next_l, next_u),
+                distance(u, next_u),
+                Some(fl),
+            );
+            behind = Some(fu);
+            lower.push(flat_l);
+            upper.push(flat_u);
+        }
+
+        // Lower edge chord along x.
+        let (ex, ey) = *lower.last().unwrap();
+        let angle = -ey.atan2(ex);
+        let (sin, cos) = angle.sin_cos();
+        let rotate = |p: &(f64, f64)| (p.0 * cos - p.1 * sin, p.0 * sin + p.1 * cos);
+        Some(FlatPanel {
+            lower_edge,
+            upper_edge,
+            stations: rulings.iter().map(|r| r.0.x).collect(),
+            lower: lower.iter().map(rotate).collect(),
+            upper: upper.iter().map(rotate).collect(),
+        })
+    }
+
+    pub fn lower_edge(&self) -> PanelEdge {
+        self.lower_edge
+    }
+
+    pub fn upper_edge(&self) -> PanelEdge {
+        self.upper_edge
+    }
+
+    /// Stations of the station lines marked on the panel.
+    pub fn stations(&self) -> &[Length] {
+        &self.stations
+    }
+
+    /// Flat lower edge points, at the stations.
+    pub fn lower(&self) -> Vec<(Length, Length)> {
+        to_length(&self.lower)
+    }
+
+    /// Flat upper edge points, at the stations.
+    pub fn upper(&self) -> Vec<(Length, Length)> {
+        to_length(&self.upper)
+    }
+
+    /// Closed outline, the lower edge forward to aft and the upper edge back.
+    pub fn outline(&self) -> Vec<(Length, Length)> {
+        let mut points = self.lower.clone();
+        points.extend(self.upper.iter().rev());
+        to_length(&points)
+    }
+
+    /// Station lines, from the lower to the upper edge.
+    pub fn station_lines(&self) -> Vec<((Length, Length), (Length, Length))> {
+        self.lower().into_iter().zip(self.upper()).collect()
+    }
+
+    pub fn lower_length(&self) -> Length {
+        Length::from_meter(polyline_length(&self.lower))
+    }
+
+    pub fn upper_length(&self) -> Length {
+        Length::from_meter(polyline_length(&self.upper))
+    }
+
+    /// Length of the station line at a station index.
+    pub fn width(&self, station: usize) -> Option<Length> {
+        let l = self.lower.get(station)?;
+        let u = self.upper.get(station)?;
+        Some(Length::from_meter((u.0 - l.0).hypot(u.1 - l.1)))
+    }
+
+    /// Bounding box, the length and the width of the sheet to cut the panel from.
+    pub fn extent(&self) -> (Length, Length) {
+        let points: Vec<&(f64, f64)> = self.lower.iter().chain(self.upper.iter()).collect();
+        let range = |f: &dyn Fn(&(f64, f64)) -> f64| {
+            let min = points.iter().map(|p| f(p)).fold(f64::INFINITY, f64::min);
+            let max = points
+                .iter()
+                .map(|p| f(p))
+                .fold(f64::NEG_INFINITY, f64::max);
+            Length::from_meter(max - min)
+        };
+        (range(&|p| p.0), range(&|p| p.1))
+    }
+
+    /// Flat area, by the shoelace formula of the outline.
+    pub fn area(&self) -> Area {
+        let mut points = self.lower.clone();
+        points.extend(self.upper.iter().rev());
+        let twice: f64 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(p, q)| p.0 * q.1 - q.0 * p.1)
+            .sum();
+        Area::from_meter2(twice.abs() / 2.0)
+    }
+}
+
+/// Third point of a triangle from the points of a side and the distances to them, on the
+/// side of the line away from a known point, or to the right of p to q without one.
+fn place(
+    p: (f64, f64),
+    q: (f64, f64),
+    distance_p: f64,
+    distance_q: f64,
+    away: Option<(f64, f64)>,
+) -> (f64, f64) {
+    let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+    let base = dx.hypot(dy);
+    if base < 1e-12 {
+        return (p.0 + distance_p, p.1);
+    }
+    let (ux, uy) = (dx / base, dy / base);
+    let along = (distance_p * distance_p - distance_q * distance_q + base * base) / (2.0 * base);
+    let across = (distance_p * distance_p - along * along).max(0.0).sqrt();
+    let side = match away {
+        Some(a) => {
+            let cross = ux * (a.1 - p.1) - uy * (a.0 - p.0);
+            if cross > 0.0 {
+                -1.0
+            } else {
+                1.0
+            }
+        }
+        None => -1.0,
+    };
+    (
+        p.0 + along * ux - side * across * uy,
+        p.1 + along * uy + side * across * ux,
+    )
+}
+
+fn polyline_length(points: &[(f64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|w| (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))
+        .sum()
+}
+
+fn to_length(points: &[(f64, f64)]) -> Vec<(Length, Length)> {
+    points
+        .iter()
+        .map(|&(x, y)| (Length::from_meter(x), Length::from_meter(y)))
+        .collect()
+}
+
+impl fmt::Display for FlatPanel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (length, width) = self.extent();
+        write!(
+            f,
+            "[Panel {} to {}]\n\
+            \tExtent:     {:>7.3}m x {:.3}m\n\
+            \tArea:       {:>7.3}m2\n\
+            \tLower edge: {:>7.3}m\n\
+            \tUpper edge: {:>7.3}m\n\
+            \tStation      Lower x  Lower y  Upper x  Upper y",
+            self.lower_edge,
+            self.upper_edge,
+            length.to_meter(),
+            width.to_meter(),
+            self.area().to_meter2(),
+            self.lower_length().to_meter(),
+            self.upper_length().to_meter()
+        )?;
+        for (i, station) in self.stations.iter().enumerate() {
+            write!(
+                f,
+                "\n\t{:>7.3}m {:>8.3} {:>8.3} {:>8.3} {:>8.3}",
+                station.to_meter(),
+                self.lower[i].0,
+                self.lower[i].1,
+                self.upper[i].0,
+                self.upper[i].1
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Development
+///
+/// Flat panels of a chined hull, from the keel to the first chine, between the chines and
+/// from the last chine to the sheer, for one side of the hull.
+#[derive(Debug, Clone)]
+pub struct Development {
+    panels: Vec<FlatPanel>,
+}
+
+#[allow(dead_code)]
+impl Development {
+    /// Chines as the offset points of the sections, counted from the keel.
+    pub fn new(hull: &Hull, chines: &[usize]) -> Development {
+        Development {
+            panels: panel_edges(chines)
+                .windows(2)
+                .filter_map(|w| FlatPanel::new(hull, w[0], w[1]))
+                .collect(),
+        }
+    }
+
+    pub fn panels(&self) -> &[FlatPanel] {
+        &self.panels
+    }
+
+    /// Flat area of the panels of one side.
+    pub fn area(&self) -> Area {
+        Area::from_meter2(self.panels.iter().map(|p| p.area().to_meter2()).sum())
+    }
+}
+
+impl fmt::Display for Development {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Development]\n\tPanels: {}", self.panels.len())?;
+        for panel in &self.panels {
+            write!(f, "\n{}", panel)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn development() {
+        use crate::parent::ParentHull;
+
+        // V bottom 0.6m wide and 0.2m deep with a vertical side, the keel and the chine rising
+        // aft.
+        let hull = crate::hull::test::chine_hull(&|x| (0.1 * x, 0.1 * x + 0.2), 0.6, (0.6, 1.0));
+        let development = Development::new(&hull, &[1]);
+        assert_eq!(development.panels().len(), 2);
+
+        // The flat bottom is a parallelogram, the keel as long as on the hull and the station
+        // lines leaning 0.02m aft.
+        let bottom = &development.panels()[0];
+        assert_eq!(bottom.lower_edge(), PanelEdge::Point(0));
+        assert_eq!(format!("{:.4}", bottom.lower_length().to_meter()), "4.0200");
+        assert_eq!(
+            format!("{:.4}", bottom.width(2).unwrap().to_meter()),
+            "0.6325"
+        );
+        let (length, width) = bottom.extent();
+        assert_eq!(format!("{:.4}", length.to_meter()), "4.0399");
+        assert_eq!(format!("{:.4}", width.to_meter()), "0.6321");
+        assert_eq!(format!("{:.4}", bottom.area().to_meter2()), "2.5412");
+        assert_eq!(bottom.outline().len(), 10);
+
+        // The side, 0.8m deep forward to 0.4m aft.
+        let side = &development.panels()[1];
+        assert_eq!(
+            format!("{:.4}", side.width(0).unwrap().to_meter()),
+            "0.8000"
+        );
+        assert_eq!(
+            format!("{:.4}", side.width(4).unwrap().to_meter()),
+            "0.4000"
+        );
+        assert_eq!(format!("{:.4}", side.lower_length().to_meter()), "4.0200");
+        assert_eq!(format!("{:.4}", side.upper_length().to_meter()), "4.0000");
+        assert_eq!(format!("{:.4}", side.area().to_meter2()), "2.4000");
+
+        // The edges keep their length on the hull.
+        let parent = ParentHull::PlaningPowerboat;
+        let hull = parent.hull();
+        let development = Development::new(&hull, parent.chines());
+        let chine: f64 = rulings(&hull, PanelEdge::Point(1), PanelEdge::Sheer)
+            .windows(2)
+            .map(|w| {
+                let (p, q) = (w[0].0, w[1].0);
+                ((p.x.to_meter() - q.x.to_meter()).powi(2)
+                    + (p.y.to_meter() - q.y.to_meter()).powi(2)
+                    + (p.z.to_meter() - q.z.to_meter()).powi(2))
+                .sqrt()
+            })
+            .sum();
+        let side = &development.panels()[1];
+        assert_eq!(
+            format!("{:.4}", side.lower_length().to_meter()),
+            format!("{:.4}", chine)
+        );
+        assert!(development.area().to_meter2() > 0.0);
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DIAGNOSTICS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Diagnostic severity.
+/// Errors make the computed ratios meaningless, warnings point to values that are unusual
+/// but possible.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Warning => write!(f, "Warning"),
+            Severity::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// Diagnostic.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+}
+
+#[allow(dead_code)]
+impl Diagnostic {
+    pub fn new(severity: Severity, message: String) -> Diagnostic {
+        Diagnostic { severity, message }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Diagnostics report.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+#[allow(dead_code)]
+impl Diagnostics {
+    pub fn new() -> Diagnostics {
+        Diagnostics { items: Vec::new() }
+    }
+
+    pub fn error(&mut self, message: String) {
+        self.items.push(Diagnostic::new(Severity::Error, message));
+    }
+
+    pub fn warning(&mut self, message: String) {
+        self.items.push(Diagnostic::new(Severity::Warning, message));
+    }
+
+    /// Append the diagnostics of another check.
+    pub fn append(&mut self, other: Diagnostics) {
+        self.items.extend(other.items);
+    }
+
+    /// All diagnostics, in the order they were found.
+    pub fn items(&self) -> &[Diagnostic] {
+        &self.items
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Diagnostics]")?;
+        if self.items.is_empty() {
+            return write!(f, "\n\tNo issues found");
+        }
+        for item in &self.items {
+            write!(f, "\n\t{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn diagnostics() {
+        let mut diagnostics = Diagnostics::new();
+        assert!(diagnostics.is_empty());
+        assert_eq!(diagnostics.to_string(), "[Diagnostics]\n\tNo issues found");
+
+        diagnostics.warning("Sail area is high".to_string());
+        assert!(!diagnostics.has_errors());
+        diagnostics.error("DWL exceeds LOA".to_string());
+        assert!(diagnostics.has_errors());
+        assert_eq!(diagnostics.errors().count(), 1);
+        assert_eq!(diagnostics.warnings().count(), 1);
+        assert_eq!(
+            diagnostics.to_string(),
+            "[Diagnostics]\n\tWarning: Sail area is high\n\tError: DWL exceeds LOA"
+        );
+    }
+}
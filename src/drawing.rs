@@ -0,0 +1,3831 @@
+use super::appendage::AppendageGeometry;
+use super::boat::{Boat, Ratios};
+use super::development::FlatPanel;
+use super::foil::Foil;
+use super::hull::{Hull, SECTION_SAMPLES};
+use super::hydrostatics::Hydrostatics;
+use super::offsets::{format_offset, OffsetKind, OffsetLine, OffsetUnits, OffsetsTable};
+use super::rig::{Rig, RigPlacement, SailPoint};
+use super::si::{Length, UnitSystem, Weight};
+use cairo::{
+    Context, FontSlant, FontWeight, Format, ImageSurface, PdfSurface, Surface, SvgSurface,
+};
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Cairo points per inch, the resolution of an image one pixel to the point.
+pub const POINTS_PER_INCH: f64 = 72.0;
+
+/// Cairo points per meter, full size.
+pub const POINTS_PER_METER: f64 = POINTS_PER_INCH / 0.0254;
+
+/// Dash of the grid lines (points).
+pub const GRID_DASH: [f64; 2] = [6.0, 4.0];
+
+/// Label font size (points).
+pub const LABEL_SIZE: f64 = 8.0;
+
+/// Points sampled along the longitudinal lines of the hull.
+pub const LINE_SAMPLES: usize = 60;
+
+/// Length of the dimension and leader arrow heads (points).
+pub const ARROW_SIZE: f64 = 6.0;
+
+/// Gap between a dimensioned point and its extension line, and run of the extension line
+/// past the dimension line (points).
+pub const EXTENSION_GAP: f64 = 2.0;
+
+/// Default margin of a sheet (m), full size.
+pub const SHEET_MARGIN_M: f64 = 0.01;
+
+/// Gap between the views of a lines plan sheet, room for the line labels (points).
+pub const VIEW_GAP: f64 = 40.0;
+
+/// Overlap of the adjacent pages of a tiled pattern, to tape them together over (m).
+pub const TILE_OVERLAP: f64 = 0.01;
+
+/// Length of the scale check bar printed on each tile (m), to measure the print is full size.
+pub const SCALE_BAR: f64 = 0.1;
+
+/// Title block width and height (points).
+pub const TITLE_BLOCK_SIZE: (f64, f64) = (200.0, 84.0);
+
+/// Offsets table row height (points).
+pub const TABLE_ROW: f64 = 12.0;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DRAWING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Drawing error.
+#[derive(Debug)]
+pub enum DrawingError {
+    /// The surface could not be created.
+    Cairo(cairo::Error),
+    /// The output could not be written.
+    Io(io::Error),
+    /// No drawing format for the file extension.
+    Format(PathBuf),
+}
+
+impl fmt::Display for DrawingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DrawingError::Cairo(err) => write!(f, "drawing surface error: {}", err),
+            DrawingError::Io(err) => write!(f, "drawing output error: {}", err),
+            DrawingError::Format(path) => {
+                write!(f, "unknown drawing format: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DrawingError {}
+
+impl From<cairo::Error> for DrawingError {
+    fn from(err: cairo::Error) -> DrawingError {
+        DrawingError::Cairo(err)
+    }
+}
+
+impl From<io::Error> for DrawingError {
+    fn from(err: io::Error) -> DrawingError {
+        DrawingError::Io(err)
+    }
+}
+
+/// Paint the whole surface white and set the source to black, to draw on.
+pub fn blank(context: &Context) {
+    context.set_source_rgb(1.0, 1.0, 1.0);
+    context.paint();
+    context.set_source_rgb(0.0, 0.0, 0.0);
+}
+
+/// Where a drawing target writes to.
+#[derive(Debug, Clone)]
+pub enum Output {
+    File(PathBuf),
+    /// Bytes kept in memory, empty until rendered.
+    Memory(Vec<u8>),
+}
+
+impl Output {
+    fn store(&mut self, bytes: Vec<u8>) -> Result<(), DrawingError> {
+        match self {
+            Output::File(path) => fs::write(path, bytes)?,
+            Output::Memory(memory) => *memory = bytes,
+        }
+        Ok(())
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            Output::File(_) => None,
+            Output::Memory(memory) => Some(memory),
+        }
+    }
+}
+
+/// Drawing target
+///
+/// Backend a drawing is rendered to, so the same drawing routine can write a PDF, an SVG or a
+/// PNG, to a file or to memory. The drawing gets the context and the target width and height.
+pub trait DrawTarget {
+    /// Drawing size (points), the images scaled to their resolution.
+    fn size(&self) -> (f64, f64);
+
+    /// Render a drawing on a blank surface and write it out.
+    fn render(&mut self, draw: &dyn Fn(&Context, f64, f64)) -> Result<(), DrawingError>;
+
+    /// Bytes of a target in memory, None for a file.
+    fn bytes(&self) -> Option<&[u8]>;
+}
+
+/// Render on a vector surface writing to memory and take the bytes out.
+fn stream_bytes(
+    surface: &Surface,
+    (width, height): (f64, f64),
+    draw: &dyn Fn(&Context, f64, f64),
+) -> Result<Vec<u8>, DrawingError> {
+    {
+        let context = Context::new(surface);
+        blank(&context);
+        draw(&context, width, height);
+    }
+    let stream = surface
+        .finish_output_stream()
+        .map_err(|err| DrawingError::Io(err.error))?;
+    Ok(*stream
+        .downcast::<Vec<u8>>()
+        .map_err(|_| io::Error::other("unexpected drawing stream"))?)
+}
+
+/// PDF page of width by height points.
+pub struct PdfTarget {
+    width: f64,
+    height: f64,
+    output: Output,
+}
+
+#[allow(dead_code)]
+impl PdfTarget {
+    pub fn file<P: AsRef<Path>>(path: P, width: f64, height: f64) -> PdfTarget {
+        PdfTarget {
+            width,
+            height,
+            output: Output::File(path.as_ref().to_path_buf()),
+        }
+    }
+
+    pub fn memory(width: f64, height: f64) -> PdfTarget {
+        PdfTarget {
+            width,
+            height,
+            output: Output::Memory(Vec::new()),
+        }
+    }
+}
+
+impl DrawTarget for PdfTarget {
+    fn size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn render(&mut self, draw: &dyn Fn(&Context, f64, f64)) -> Result<(), DrawingError> {
+        let surface = PdfSurface::for_stream(self.width, self.height, Vec::<u8>::new())?;
+        let bytes = stream_bytes(&surface, self.size(), draw)?;
+        self.output.store(bytes)
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        self.output.bytes()
+    }
+}
+
+/// SVG image of width by height points.
+pub struct SvgTarget {
+    width: f64,
+    height: f64,
+    output: Output,
+}
+
+#[allow(dead_code)]
+impl SvgTarget {
+    pub fn file<P: AsRef<Path>>(path: P, width: f64, height: f64) -> SvgTarget {
+        SvgTarget {
+            width,
+            height,
+            output: Output::File(path.as_ref().to_path_buf()),
+        }
+    }
+
+    pub fn memory(width: f64, height: f64) -> SvgTarget {
+        SvgTarget {
+            width,
+            height,
+            output: Output::Memory(Vec::new()),
+        }
+    }
+}
+
+impl DrawTarget for SvgTarget {
+    fn size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn render(&mut self, draw: &dyn Fn(&Context, f64, f64)) -> Result<(), DrawingError> {
+        let surface = SvgSurface::for_stream(self.width, self.height, Vec::<u8>::new())?;
+        let bytes = stream_bytes(&surface, self.size(), draw)?;
+        self.output.store(bytes)
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        self.output.bytes()
+    }
+}
+
+/// PNG image of width by height pixels.
+pub struct PngTarget {
+    width: f64,
+    height: f64,
+    dpi: f64,
+    output: Output,
+}
+
+#[allow(dead_code)]
+impl PngTarget {
+    /// PNG of width by height points, one pixel to the point until the resolution is set.
+    pub fn file<P: AsRef<Path>>(path: P, width: f64, height: f64) -> PngTarget {
+        PngTarget {
+            width,
+            height,
+            dpi: POINTS_PER_INCH,
+            output: Output::File(path.as_ref().to_path_buf()),
+        }
+    }
+
+    pub fn memory(width: f64, height: f64) -> PngTarget {
+        PngTarget {
+            width,
+            height,
+            dpi: POINTS_PER_INCH,
+            output: Output::Memory(Vec::new()),
+        }
+    }
+
+    /// Resolution, pixels per inch.
+    pub fn dpi(&self) -> f64 {
+        self.dpi
+    }
+    pub fn set_dpi(&mut self, val: f64) {
+        self.dpi = val;
+    }
+
+    /// Set the resolution for an image of a width in pixels.
+    pub fn set_pixel_width(&mut self, pixels: i32) {
+        self.dpi = pixels as f64 * POINTS_PER_INCH / self.width;
+    }
+
+    /// Image width and height (pixels).
+    pub fn pixel_size(&self) -> (i32, i32) {
+        let factor = self.dpi / POINTS_PER_INCH;
+        (
+            (self.width * factor).round() as i32,
+            (self.height * factor).round() as i32,
+        )
+    }
+}
+
+impl DrawTarget for PngTarget {
+    fn size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn render(&mut self, draw: &dyn Fn(&Context, f64, f64)) -> Result<(), DrawingError> {
+        let (width, height) = self.size();
+        let (pixel_width, pixel_height) = self.pixel_size();
+        let factor = self.dpi / POINTS_PER_INCH;
+        let surface = to_image(pixel_width, pixel_height, |context| {
+            context.scale(factor, factor);
+            draw(context, width, height)
+        })?;
+        let mut bytes = Vec::new();
+        surface
+            .write_to_png(&mut bytes)
+            .map_err(|err| io::Error::other(err.to_string()))?;
+        self.output.store(bytes)
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        self.output.bytes()
+    }
+}
+
+/// Drawing file formats.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum FileFormat {
+    Pdf,
+    /// Scalable, to edit in Inkscape.
+    Svg,
+    Png,
+}
+
+#[allow(dead_code)]
+impl FileFormat {
+    /// Format of a file by its extension, None when unknown.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<FileFormat> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "pdf" => Some(FileFormat::Pdf),
+            "svg" => Some(FileFormat::Svg),
+            "png" => Some(FileFormat::Png),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            FileFormat::Pdf => "pdf",
+            FileFormat::Svg => "svg",
+            FileFormat::Png => "png",
+        }
+    }
+}
+
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FileFormat::Pdf => write!(f, "PDF"),
+            FileFormat::Svg => write!(f, "SVG"),
+            FileFormat::Png => write!(f, "PNG"),
+        }
+    }
+}
+
+/// Target writing a file of width by height points in the format of its extension, the PNG
+/// one pixel to the point.
+pub fn file_target<P: AsRef<Path>>(
+    path: P,
+    width: f64,
+    height: f64,
+) -> Result<Box<dyn DrawTarget>, DrawingError> {
+    let path = path.as_ref();
+    Ok(match FileFormat::from_path(path) {
+        Some(FileFormat::Pdf) => Box::new(PdfTarget::file(path, width, height)),
+        Some(FileFormat::Svg) => Box::new(SvgTarget::file(path, width, height)),
+        Some(FileFormat::Png) => Box::new(PngTarget::file(path, width, height)),
+        None => return Err(DrawingError::Format(path.to_path_buf())),
+    })
+}
+
+/// Render a single page to a target, the drawing called once.
+fn render_once<F>(target: &mut dyn DrawTarget, draw: F) -> Result<(), DrawingError>
+where
+    F: FnOnce(&Context),
+{
+    let draw = Cell::new(Some(draw));
+    target.render(&|context, _, _| {
+        if let Some(draw) = draw.take() {
+            draw(context);
+        }
+    })
+}
+
+/// Draw a width by height (points) page to a PDF file.
+pub fn to_pdf<P, F>(path: P, width: f64, height: f64, draw: F) -> Result<(), DrawingError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&Context),
+{
+    render_once(&mut PdfTarget::file(path, width, height), draw)
+}
+
+/// Draw a width by height (points) page to an SVG file.
+pub fn to_svg<P, F>(path: P, width: f64, height: f64, draw: F) -> Result<(), DrawingError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&Context),
+{
+    render_once(&mut SvgTarget::file(path, width, height), draw)
+}
+
+/// Draw a width by height (points) page to PDF bytes in memory.
+pub fn to_pdf_bytes<F>(width: f64, height: f64, draw: F) -> Result<Vec<u8>, DrawingError>
+where
+    F: FnOnce(&Context),
+{
+    let mut target = PdfTarget::memory(width, height);
+    render_once(&mut target, draw)?;
+    Ok(target.bytes().unwrap_or_default().to_vec())
+}
+
+/// Draw on a width by height (pixels) image in memory.
+pub fn to_image<F>(width: i32, height: i32, draw: F) -> Result<ImageSurface, DrawingError>
+where
+    F: FnOnce(&Context),
+{
+    let surface = ImageSurface::create(Format::ARgb32, width, height)?;
+    {
+        let context = Context::new(&surface);
+        blank(&context);
+        draw(&context);
+    }
+    surface.flush();
+    Ok(surface)
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LAYERS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Drawing layers, each with its own visibility, color and line weight.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Layer {
+    /// Stations, waterlines and buttocks straight, the baseline and the centerline.
+    Grid,
+    Hull,
+    Appendages,
+    /// Spars, stays and sails.
+    Rig,
+    /// Dimensions, leaders, notes and marks.
+    Annotations,
+    /// Construction lines, the station marks of the patterns.
+    Construction,
+}
+
+#[allow(dead_code)]
+impl Layer {
+    pub const ALL: [Layer; 6] = [
+        Layer::Grid,
+        Layer::Hull,
+        Layer::Appendages,
+        Layer::Rig,
+        Layer::Annotations,
+        Layer::Construction,
+    ];
+
+    /// Default style, black, the lines of the boat heavier than the grid and the annotations.
+    pub fn default_style(self) -> LayerStyle {
+        let line_width = match self {
+            Layer::Grid | Layer::Annotations | Layer::Construction => 0.5,
+            Layer::Hull | Layer::Appendages | Layer::Rig => 1.0,
+        };
+        LayerStyle {
+            visible: true,
+            color: (0.0, 0.0, 0.0),
+            line_width,
+        }
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Layer::Grid => write!(f, "Grid"),
+            Layer::Hull => write!(f, "Hull"),
+            Layer::Appendages => write!(f, "Appendages"),
+            Layer::Rig => write!(f, "Rig"),
+            Layer::Annotations => write!(f, "Annotations"),
+            Layer::Construction => write!(f, "Construction"),
+        }
+    }
+}
+
+/// Style of a layer, the color RGB from 0 to 1 and the line weight (points).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct LayerStyle {
+    pub visible: bool,
+    pub color: (f64, f64, f64),
+    pub line_width: f64,
+}
+
+/// Layers
+///
+/// Styles of the drawing layers, read by the renderers through the DrawingStyle as they draw
+/// each layer. A hidden layer is left out, the others drawn in their color, the line weights
+/// of a renderer relative to the layer line weight.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Layers {
+    styles: [LayerStyle; 6],
+}
+
+impl Default for Layers {
+    fn default() -> Layers {
+        Layers {
+            styles: Layer::ALL.map(Layer::default_style),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Layers {
+    pub fn new() -> Layers {
+        Layers::default()
+    }
+
+    /// Only some layers visible.
+    pub fn only(layers: &[Layer]) -> Layers {
+        let mut only = Layers::new();
+        for &layer in Layer::ALL.iter() {
+            only.set_visible(layer, layers.contains(&layer));
+        }
+        only
+    }
+
+    pub fn style(&self, layer: Layer) -> LayerStyle {
+        self.styles[layer as usize]
+    }
+    pub fn set_style(&mut self, layer: Layer, val: LayerStyle) {
+        self.styles[layer as usize] = val;
+    }
+
+    pub fn is_visible(&self, layer: Layer) -> bool {
+        self.style(layer).visible
+    }
+    pub fn set_visible(&mut self, layer: Layer, val: bool) {
+        self.styles[layer as usize].visible = val;
+    }
+
+    pub fn set_color(&mut self, layer: Layer, val: (f64, f64, f64)) {
+        self.styles[layer as usize].color = val;
+    }
+
+    pub fn set_line_width(&mut self, layer: Layer, val: f64) {
+        self.styles[layer as usize].line_width = val;
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// STYLE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Kinds of line drawn, each on a layer with a weight of its own.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum LineKind {
+    Grid,
+    Stations,
+    Waterlines,
+    Buttocks,
+    /// Sheer, keel, stem and transom, the deck outline in plan.
+    Outline,
+    Appendages,
+    /// Mast and booms.
+    Spars,
+    Stays,
+    Sails,
+    /// Dimensions, leaders and marks.
+    Dimensions,
+    /// Station marks of the patterns.
+    Construction,
+    /// Cut lines of the full size patterns, kept fine.
+    Pattern,
+    /// Curves of the plots, the GZ curve and the polar traces.
+    Curves,
+    /// Rules of the tables, the offsets and the particulars, the title block and the border.
+    Tables,
+}
+
+#[allow(dead_code)]
+impl LineKind {
+    pub const ALL: [LineKind; 14] = [
+        LineKind::Grid,
+        LineKind::Stations,
+        LineKind::Waterlines,
+        LineKind::Buttocks,
+        LineKind::Outline,
+        LineKind::Appendages,
+        LineKind::Spars,
+        LineKind::Stays,
+        LineKind::Sails,
+        LineKind::Dimensions,
+        LineKind::Construction,
+        LineKind::Pattern,
+        LineKind::Curves,
+        LineKind::Tables,
+    ];
+
+    /// Layer the lines are drawn on.
+    pub fn layer(self) -> Layer {
+        match self {
+            LineKind::Grid => Layer::Grid,
+            LineKind::Stations
+            | LineKind::Waterlines
+            | LineKind::Buttocks
+            | LineKind::Outline
+            | LineKind::Curves => Layer::Hull,
+            LineKind::Appendages => Layer::Appendages,
+            LineKind::Spars | LineKind::Stays | LineKind::Sails => Layer::Rig,
+            LineKind::Dimensions | LineKind::Tables => Layer::Annotations,
+            LineKind::Construction | LineKind::Pattern => Layer::Construction,
+        }
+    }
+
+    /// Default weight over the layer line weight, the waterlines and buttocks finer than the
+    /// stations, the spars heavier than the sails, the plot curves and the table frames heavy.
+    pub fn default_weight(self) -> f64 {
+        match self {
+            LineKind::Waterlines | LineKind::Buttocks | LineKind::Stays => 0.5,
+            LineKind::Spars => 1.5,
+            LineKind::Curves | LineKind::Tables => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+impl fmt::Display for LineKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LineKind::Grid => write!(f, "Grid"),
+            LineKind::Stations => write!(f, "Stations"),
+            LineKind::Waterlines => write!(f, "Waterlines"),
+            LineKind::Buttocks => write!(f, "Buttocks"),
+            LineKind::Outline => write!(f, "Outline"),
+            LineKind::Appendages => write!(f, "Appendages"),
+            LineKind::Spars => write!(f, "Spars"),
+            LineKind::Stays => write!(f, "Stays"),
+            LineKind::Sails => write!(f, "Sails"),
+            LineKind::Dimensions => write!(f, "Dimensions"),
+            LineKind::Construction => write!(f, "Construction"),
+            LineKind::Pattern => write!(f, "Pattern"),
+            LineKind::Curves => write!(f, "Curves"),
+            LineKind::Tables => write!(f, "Tables"),
+        }
+    }
+}
+
+/// Color mode of a drawing.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ColorMode {
+    /// Everything black, for print and the traditional plans.
+    Monochrome,
+    /// The layer colors, for the screen.
+    Color,
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ColorMode::Monochrome => write!(f, "Monochrome"),
+            ColorMode::Color => write!(f, "Color"),
+        }
+    }
+}
+
+/// Drawing style
+///
+/// How the renderers draw, the layers, the weight of each kind of line over the line weight
+/// of its layer, the label font and size, and the color mode. Monochrome draws every layer
+/// black whatever its color. The default draws the plans as they have always been, black lines
+/// in the Sans font at LABEL_SIZE.
+#[derive(PartialEq, Debug, Clone)]
+pub struct DrawingStyle {
+    layers: Layers,
+    weights: [f64; 14],
+    font: String,
+    label_size: f64,
+    mode: ColorMode,
+}
+
+impl Default for DrawingStyle {
+    fn default() -> DrawingStyle {
+        DrawingStyle {
+            layers: Layers::default(),
+            weights: LineKind::ALL.map(LineKind::default_weight),
+            font: "Sans".to_string(),
+            label_size: LABEL_SIZE,
+            mode: ColorMode::Monochrome,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl DrawingStyle {
+    pub fn new() -> DrawingStyle {
+        DrawingStyle::default()
+    }
+
+    /// Traditional plan conventions, monochrome in a serif font, the outline heavy over fine
+    /// construction lines.
+    pub fn traditional() -> DrawingStyle {
+        let mut style = DrawingStyle::new();
+        style.set_font("Serif".to_string());
+        style.set_weight(LineKind::Outline, 1.4);
+        style.set_weight(LineKind::Stations, 0.7);
+        style.layers.set_line_width(Layer::Grid, 0.25);
+        style
+    }
+
+    /// Screen friendly, a color for each layer and heavier lines in a larger font.
+    pub fn screen() -> DrawingStyle {
+        let mut style = DrawingStyle::new();
+        style.set_mode(ColorMode::Color);
+        style.set_label_size(1.25 * LABEL_SIZE);
+        let colors = [
+            (Layer::Grid, (0.6, 0.6, 0.6)),
+            (Layer::Hull, (0.0, 0.2, 0.6)),
+            (Layer::Appendages, (0.6, 0.1, 0.1)),
+            (Layer::Rig, (0.0, 0.45, 0.2)),
+            (Layer::Annotations, (0.3, 0.3, 0.3)),
+            (Layer::Construction, (0.6, 0.0, 0.6)),
+        ];
+        for &(layer, color) in colors.iter() {
+            let width = style.layers.style(layer).line_width;
+            style.layers.set_color(layer, color);
+            style.layers.set_line_width(layer, 1.5 * width);
+        }
+        style
+    }
+
+    pub fn layers(&self) -> &Layers {
+        &self.layers
+    }
+    pub fn set_layers(&mut self, val: Layers) {
+        self.layers = val;
+    }
+
+    /// Weight of a kind of line over the line weight of its layer.
+    pub fn weight(&self, kind: LineKind) -> f64 {
+        self.weights[kind as usize]
+    }
+    pub fn set_weight(&mut self, kind: LineKind, val: f64) {
+        self.weights[kind as usize] = val;
+    }
+
+    /// Label font family.
+    pub fn font(&self) -> &str {
+        &self.font
+    }
+    pub fn set_font(&mut self, val: String) {
+        self.font = val;
+    }
+
+    /// Label font size (points).
+    pub fn label_size(&self) -> f64 {
+        self.label_size
+    }
+    pub fn set_label_size(&mut self, val: f64) {
+        self.label_size = val;
+    }
+
+    pub fn mode(&self) -> ColorMode {
+        self.mode
+    }
+    pub fn set_mode(&mut self, val: ColorMode) {
+        self.mode = val;
+    }
+
+    pub fn is_visible(&self, layer: Layer) -> bool {
+        self.layers.is_visible(layer)
+    }
+
+    /// Color of a layer in the color mode.
+    pub fn color(&self, layer: Layer) -> (f64, f64, f64) {
+        match self.mode {
+            ColorMode::Monochrome => (0.0, 0.0, 0.0),
+            ColorMode::Color => self.layers.style(layer).color,
+        }
+    }
+
+    /// Line width of a kind of line (points).
+    pub fn line_width(&self, kind: LineKind) -> f64 {
+        self.layers.style(kind.layer()).line_width * self.weight(kind)
+    }
+
+    /// Set the color and the line width of a kind of line on the context. False when its layer
+    /// is hidden, nothing to draw.
+    pub fn apply(&self, context: &Context, kind: LineKind) -> bool {
+        let (r, g, b) = self.color(kind.layer());
+        context.set_source_rgb(r, g, b);
+        context.set_line_width(self.line_width(kind));
+        self.is_visible(kind.layer())
+    }
+
+    /// Set the label font and size on the context.
+    pub fn select_font(&self, context: &Context) {
+        context.select_font_face(&self.font, FontSlant::Normal, FontWeight::Normal);
+        context.set_font_size(self.label_size);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SHEET
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Standard paper sizes.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PaperSize {
+    A4,
+    A3,
+    A2,
+    A1,
+    Letter,
+    Tabloid,
+}
+
+#[allow(dead_code)]
+impl PaperSize {
+    pub const ALL: [PaperSize; 6] = [
+        PaperSize::A4,
+        PaperSize::A3,
+        PaperSize::A2,
+        PaperSize::A1,
+        PaperSize::Letter,
+        PaperSize::Tabloid,
+    ];
+
+    /// Width and height, portrait.
+    pub fn size(self) -> (Length, Length) {
+        let (width, height) = match self {
+            PaperSize::A4 => (210.0, 297.0),
+            PaperSize::A3 => (297.0, 420.0),
+            PaperSize::A2 => (420.0, 594.0),
+            PaperSize::A1 => (594.0, 841.0),
+            PaperSize::Letter => (215.9, 279.4),
+            PaperSize::Tabloid => (279.4, 431.8),
+        };
+        (
+            Length::from_millimeter(width),
+            Length::from_millimeter(height),
+        )
+    }
+}
+
+impl fmt::Display for PaperSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaperSize::A4 => write!(f, "A4"),
+            PaperSize::A3 => write!(f, "A3"),
+            PaperSize::A2 => write!(f, "A2"),
+            PaperSize::A1 => write!(f, "A1"),
+            PaperSize::Letter => write!(f, "Letter"),
+            PaperSize::Tabloid => write!(f, "Tabloid"),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+/// Drawing scale, the size on paper over the real size.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum DrawingScale {
+    FullSize,
+    OneToTen,
+    OneToTwenty,
+    /// One to any reduction, 50.0 for 1:50, full size when the reduction is not positive.
+    OneTo(f64),
+}
+
+#[allow(dead_code)]
+impl DrawingScale {
+    /// One to a reduction, none when the reduction is not positive and finite.
+    pub fn one_to(reduction: f64) -> Option<DrawingScale> {
+        if reduction.is_finite() && reduction > 0.0 {
+            Some(DrawingScale::OneTo(reduction))
+        } else {
+            None
+        }
+    }
+
+    /// Size on paper over the real size, 0.1 for 1:10.
+    pub fn factor(self) -> f64 {
+        match self {
+            DrawingScale::FullSize => 1.0,
+            DrawingScale::OneToTen => 0.1,
+            DrawingScale::OneToTwenty => 0.05,
+            DrawingScale::OneTo(reduction) => match DrawingScale::one_to(reduction) {
+                Some(_) => 1.0 / reduction,
+                None => 1.0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for DrawingScale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "1:{:.0}", 1.0 / self.factor())
+    }
+}
+
+/// Sheet
+///
+/// Paper a drawing is laid out on, the paper size and orientation, the margin and the drawing
+/// scale. Model lengths go to points at the scale, 72 points to the inch, and the targets of
+/// the sheet are created with its size.
+#[derive(Debug, Copy, Clone)]
+pub struct Sheet {
+    paper: PaperSize,
+    orientation: Orientation,
+    margin: Length,
+    scale: DrawingScale,
+}
+
+#[allow(dead_code)]
+impl Sheet {
+    pub fn new(paper: PaperSize, orientation: Orientation, scale: DrawingScale) -> Sheet {
+        Sheet {
+            paper,
+            orientation,
+            margin: Length::from_meter(SHEET_MARGIN_M),
+            scale,
+        }
+    }
+
+    pub fn paper(&self) -> PaperSize {
+        self.paper
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn margin(&self) -> Length {
+        self.margin
+    }
+    pub fn set_margin(&mut self, val: Length) {
+        self.margin = val;
+    }
+
+    pub fn scale(&self) -> DrawingScale {
+        self.scale
+    }
+    pub fn set_scale(&mut self, val: DrawingScale) {
+        self.scale = val;
+    }
+
+    /// Paper width and height in the orientation.
+    pub fn paper_size(&self) -> (Length, Length) {
+        let (width, height) = self.paper.size();
+        match self.orientation {
+            Orientation::Portrait => (width, height),
+            Orientation::Landscape => (height, width),
+        }
+    }
+
+    /// Sheet width and height (points).
+    pub fn size(&self) -> (f64, f64) {
+        let (width, height) = self.paper_size();
+        (points(width, 1.0), points(height, 1.0))
+    }
+
+    /// Margin (points).
+    pub fn margin_points(&self) -> f64 {
+        points(self.margin, 1.0)
+    }
+
+    /// Area inside the margin, x, y, width and height (points).
+    pub fn drawing_area(&self) -> (f64, f64, f64, f64) {
+        let (width, height) = self.size();
+        let margin = self.margin_points();
+        (margin, margin, width - 2.0 * margin, height - 2.0 * margin)
+    }
+
+    /// Model length on the sheet at the scale (points).
+    pub fn to_points(&self, length: Length) -> f64 {
+        points(length, self.scale.factor())
+    }
+
+    /// Whether a model width and height fit inside the margin at the scale.
+    pub fn fits(&self, width: Length, height: Length) -> bool {
+        let (_, _, area_width, area_height) = self.drawing_area();
+        self.to_points(width) <= area_width && self.to_points(height) <= area_height
+    }
+
+    /// Border along the margin, on the annotations layer.
+    pub fn draw_border(&self, context: &Context, style: &DrawingStyle) {
+        let (x, y, width, height) = self.drawing_area();
+        context.save();
+        if style.apply(context, LineKind::Tables) {
+            context.rectangle(x, y, width, height);
+            context.stroke();
+        }
+        context.restore();
+    }
+
+    pub fn pdf<P: AsRef<Path>>(&self, path: P) -> PdfTarget {
+        let (width, height) = self.size();
+        PdfTarget::file(path, width, height)
+    }
+
+    pub fn svg<P: AsRef<Path>>(&self, path: P) -> SvgTarget {
+        let (width, height) = self.size();
+        SvgTarget::file(path, width, height)
+    }
+
+    /// PNG of the sheet at a resolution (pixels per inch).
+    pub fn png<P: AsRef<Path>>(&self, path: P, dpi: f64) -> PngTarget {
+        let (width, height) = self.size();
+        let mut target = PngTarget::file(path, width, height);
+        target.set_dpi(dpi);
+        target
+    }
+
+    /// Target of the sheet in the format of the file extension.
+    pub fn target<P: AsRef<Path>>(&self, path: P) -> Result<Box<dyn DrawTarget>, DrawingError> {
+        let (width, height) = self.size();
+        file_target(path, width, height)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LINES PLAN
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Paper points of a length at a scale, the paper length over the true length.
+fn points(length: Length, scale: f64) -> f64 {
+    length.to_meter() * scale * POINTS_PER_METER
+}
+
+/// Lines plan view.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum View {
+    Profile,
+    HalfBreadth,
+    BodyPlan,
+}
+
+/// Grid
+///
+/// Reference lines shared by the lines plan views, the stations, the waterlines, the buttocks,
+/// the baseline and the centerline. Each view draws the lines it shows, the ones it cuts
+/// straight, dashed over the length of the hull, up to the highest sheer and out to the widest
+/// half-breadth, and labeled at the edge, stations by number.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    stations: Vec<Length>,
+    waterlines: Vec<Length>,
+    buttocks: Vec<Length>,
+    range: (Length, Length),
+    height: Length,
+    half_breadth: Length,
+    labels: bool,
+}
+
+#[allow(dead_code)]
+impl Grid {
+    /// Grid over a hull, the stations at the sections.
+    pub fn new(hull: &Hull, waterlines: &[Length], buttocks: &[Length]) -> Grid {
+        let positions = hull.positions(LINE_SAMPLES);
+        let highest = |values: Vec<f64>| Length::from_meter(values.into_iter().fold(0.0, f64::max));
+        Grid {
+            stations: hull.sections().iter().map(|s| s.position()).collect(),
+            waterlines: waterlines.to_vec(),
+            buttocks: buttocks.to_vec(),
+            range: hull.range(),
+            height: highest(
+                positions
+                    .iter()
+                    .filter_map(|&x| hull.sheer(x))
+                    .map(|z| z.to_meter())
+                    .collect(),
+            ),
+            half_breadth: highest(
+                positions
+                    .iter()
+                    .filter_map(|&x| hull.half_breadth(x, hull.sheer(x)?))
+                    .chain(
+                        hull.sections()
+                            .iter()
+                            .flat_map(|s| s.points())
+                            .map(|(y, _)| y),
+                    )
+                    .map(|y| y.to_meter())
+                    .collect(),
+            ),
+            labels: true,
+        }
+    }
+
+    /// Grid over a hull evenly spaced, the stations from the foremost section, the waterlines
+    /// up from the baseline and the buttocks out from the centerline.
+    pub fn spaced(
+        hull: &Hull,
+        station_spacing: Length,
+        waterline_spacing: Length,
+        buttock_spacing: Length,
+    ) -> Grid {
+        let mut grid = Grid::new(hull, &[], &[]);
+        let steps = |start: f64, end: f64, spacing: Length| -> Vec<Length> {
+            let spacing = spacing.to_meter();
+            if spacing <= 0.0 {
+                return Vec::new();
+            }
+            (0..)
+                .map(|i| start + spacing * i as f64)
+                .take_while(|&v| v <= end + 1e-9)
+                .map(Length::from_meter)
+                .collect()
+        };
+        let (first, last) = grid.range;
+        grid.stations = steps(first.to_meter(), last.to_meter(), station_spacing);
+        let mut waterlines = steps(0.0, grid.height.to_meter(), waterline_spacing);
+        let mut buttocks = steps(0.0, grid.half_breadth.to_meter(), buttock_spacing);
+        // The baseline and the centerline are drawn apart.
+        grid.waterlines = waterlines.split_off(1.min(waterlines.len()));
+        grid.buttocks = buttocks.split_off(1.min(buttocks.len()));
+        grid
+    }
+
+    pub fn stations(&self) -> &[Length] {
+        &self.stations
+    }
+
+    pub fn waterlines(&self) -> &[Length] {
+        &self.waterlines
+    }
+
+    pub fn buttocks(&self) -> &[Length] {
+        &self.buttocks
+    }
+
+    /// Highest sheer.
+    pub fn height(&self) -> Length {
+        self.height
+    }
+
+    /// Widest half-breadth.
+    pub fn half_breadth(&self) -> Length {
+        self.half_breadth
+    }
+
+    pub fn labels(&self) -> bool {
+        self.labels
+    }
+    pub fn set_labels(&mut self, val: bool) {
+        self.labels = val;
+    }
+
+    /// Draw the grid lines of a view at a scale, the origin of the view at the context origin,
+    /// on the grid layer.
+    pub fn draw(&self, context: &Context, view: View, scale: f64, style: &DrawingStyle) {
+        if !style.is_visible(Layer::Grid) {
+            return;
+        }
+        let (x0, x1) = (points(self.range.0, scale), points(self.range.1, scale));
+        let (height, breadth) = (points(self.height, scale), points(self.half_breadth, scale));
+        let horizontals: &[Length] = match view {
+            View::Profile | View::BodyPlan => &self.waterlines,
+            View::HalfBreadth => &self.buttocks,
+        };
+        let (left, right) = match view {
+            View::Profile | View::HalfBreadth => (x0, x1),
+            View::BodyPlan => (-breadth, breadth),
+        };
+        let prefix = if view == View::HalfBreadth {
+            "BL"
+        } else {
+            "WL"
+        };
+
+        let size = style.label_size();
+        context.save();
+        style.apply(context, LineKind::Grid);
+        style.select_font(context);
+        // Baseline, or the centerline in plan.
+        context.move_to(left, 0.0);
+        context.line_to(right, 0.0);
+        if view == View::BodyPlan {
+            context.move_to(0.0, 0.0);
+            context.line_to(0.0, -height);
+        }
+        context.stroke();
+
+        context.set_dash(&GRID_DASH, 0.0);
+        for &value in horizontals {
+            let y = -points(value, scale);
+            context.move_to(left, y);
+            context.line_to(right, y);
+            context.stroke();
+            if self.labels {
+                context.move_to(right + 2.0, y + size / 3.0);
+                context.show_text(&format!("{} {:.2}", prefix, value.to_meter()));
+            }
+        }
+        match view {
+            View::Profile | View::HalfBreadth => {
+                let top = if view == View::Profile {
+                    height
+                } else {
+                    breadth
+                };
+                for (i, &station) in self.stations.iter().enumerate() {
+                    let x = points(station, scale);
+                    context.move_to(x, 0.0);
+                    context.line_to(x, -top);
+                    context.stroke();
+                    if self.labels {
+                        let label = format!("{}", i);
+                        let width = context.text_extents(&label).width;
+                        context.move_to(x - width / 2.0, size + 2.0);
+                        context.show_text(&label);
+                    }
+                }
+            }
+            View::BodyPlan => {
+                for &offset in &self.buttocks {
+                    let x = points(offset, scale);
+                    for &x in &[-x, x] {
+                        context.move_to(x, 0.0);
+                        context.line_to(x, -height);
+                        context.stroke();
+                    }
+                    if self.labels {
+                        let label = format!("BL {:.2}", offset.to_meter());
+                        let width = context.text_extents(&label).width;
+                        context.move_to(x - width / 2.0, size + 2.0);
+                        context.show_text(&label);
+                    }
+                }
+            }
+        }
+        context.restore();
+    }
+}
+
+/// Body plan
+///
+/// Station sections of the hull at a scale (0.1 for 1:10), the centerline at the context origin
+/// and the baseline on it, heights up. Looking from ahead, the forward stations on the right and
+/// the aft stations, from the aftmost section to amidships, mirrored on the left. The waterlines
+/// and the buttocks of the grid across both halves, the stations labeled by their number at the
+/// sheer.
+pub fn body_plan(context: &Context, hull: &Hull, scale: f64, grid: &Grid, style: &DrawingStyle) {
+    let (first, last) = hull.range();
+    let midship = (first.to_meter() + last.to_meter()) / 2.0;
+    let sections = hull.sections();
+    grid.draw(context, View::BodyPlan, scale, style);
+    if !style.is_visible(Layer::Hull) {
+        return;
+    }
+
+    context.save();
+    style.apply(context, LineKind::Stations);
+    style.select_font(context);
+    for (i, section) in sections.iter().enumerate() {
+        let side = if section.position().to_meter() <= midship {
+            1.0
+        } else {
+            -1.0
+        };
+        let (bottom, top) = (section.bottom().to_meter(), section.top().to_meter());
+        let mut end = (0.0, 0.0);
+        for step in 0..=SECTION_SAMPLES {
+            let z = bottom + (top - bottom) * step as f64 / SECTION_SAMPLES as f64;
+            let z = Length::from_meter(z);
+            let y = section
+                .half_breadth(z)
+                .unwrap_or_else(|| Length::from_meter(0.0));
+            end = (side * points(y, scale), -points(z, scale));
+            if step == 0 {
+                context.move_to(end.0, end.1);
+            } else {
+                context.line_to(end.0, end.1);
+            }
+        }
+        context.stroke();
+        let label = format!("{}", i);
+        let width = context.text_extents(&label).width;
+        let x = if side > 0.0 {
+            end.0 + 2.0
+        } else {
+            end.0 - width - 2.0
+        };
+        context.move_to(x, end.1 - 2.0);
+        context.show_text(&label);
+    }
+    context.restore();
+}
+
+/// Polyline of (x, y) points in context coordinates.
+fn polyline(context: &Context, points: &[(f64, f64)]) {
+    for (i, &(x, y)) in points.iter().enumerate() {
+        if i == 0 {
+            context.move_to(x, y);
+        } else {
+            context.line_to(x, y);
+        }
+    }
+}
+
+/// Profile outline of a hull, (position, height) along the sheer forward to aft and back along
+/// the keel, to close down the transom and up the stem.
+fn hull_outline(hull: &Hull) -> Vec<(Length, Length)> {
+    let positions = hull.positions(LINE_SAMPLES);
+    let mut outline: Vec<(Length, Length)> = positions
+        .iter()
+        .filter_map(|&x| Some((x, hull.sheer(x)?)))
+        .collect();
+    outline.extend(
+        positions
+            .iter()
+            .rev()
+            .filter_map(|&x| Some((x, hull.keel(x)?))),
+    );
+    outline
+}
+
+/// Profile
+///
+/// Sheer plan of the hull at a scale (0.1 for 1:10), the forward perpendicular at the context
+/// origin and the baseline on it, the bow on the left and heights up. The outline runs along
+/// the sheer, down the transom, forward along the keel and up the stem. The stations and
+/// waterlines of the grid straight, the buttocks of the grid as curves, labeled, and the
+/// appendages in outline, labeled at the tip.
+pub fn profile(
+    context: &Context,
+    hull: &Hull,
+    scale: f64,
+    grid: &Grid,
+    appendages: &[(&str, AppendageGeometry)],
+    style: &DrawingStyle,
+) {
+    let point = |x: Length, z: Length| (points(x, scale), -points(z, scale));
+    grid.draw(context, View::Profile, scale, style);
+
+    context.save();
+    style.select_font(context);
+    if style.apply(context, LineKind::Buttocks) {
+        for &offset in grid.buttocks() {
+            let line: Vec<(f64, f64)> = hull
+                .buttock(offset, LINE_SAMPLES)
+                .into_iter()
+                .map(|(x, z)| point(x, z))
+                .collect();
+            if let Some(&(x, y)) = line.last() {
+                polyline(context, &line);
+                context.stroke();
+                context.move_to(x + 2.0, y);
+                context.show_text(&format!("BL {:.2}", offset.to_meter()));
+            }
+        }
+
+        style.apply(context, LineKind::Outline);
+        let outline: Vec<(f64, f64)> = hull_outline(hull)
+            .into_iter()
+            .map(|(x, z)| point(x, z))
+            .collect();
+        polyline(context, &outline);
+        context.close_path();
+        context.stroke();
+    }
+
+    if style.apply(context, LineKind::Appendages) {
+        for (label, appendage) in appendages {
+            let outline: Vec<(f64, f64)> = appendage
+                .profile()
+                .into_iter()
+                .map(|(x, z)| point(x, z))
+                .collect();
+            polyline(context, &outline);
+            context.stroke();
+            // Right of the trailing edge at the tip.
+            if let Some(&(x, y)) = outline.get(2) {
+                context.move_to(x + 2.0, y);
+                context.show_text(label);
+            }
+        }
+    }
+    context.restore();
+}
+
+/// Half-breadth plan
+///
+/// Plan of the hull at a scale, the forward perpendicular at the context origin and the
+/// centerline on it, the bow on the left and the half-breadths up. The deck outline at the
+/// sheer and the waterlines of the grid as curves, labeled at the widest point, with the
+/// stations and buttocks of the grid straight.
+pub fn half_breadth_plan(
+    context: &Context,
+    hull: &Hull,
+    scale: f64,
+    grid: &Grid,
+    style: &DrawingStyle,
+) {
+    let point = |x: Length, y: Length| (points(x, scale), -points(y, scale));
+    let widest = |line: &[(f64, f64)]| line.iter().cloned().min_by(|a, b| a.1.total_cmp(&b.1));
+
+    grid.draw(context, View::HalfBreadth, scale, style);
+    if !style.is_visible(Layer::Hull) {
+        return;
+    }
+
+    context.save();
+    style.apply(context, LineKind::Waterlines);
+    style.select_font(context);
+    for &height in grid.waterlines() {
+        let line: Vec<(f64, f64)> = hull
+            .waterline(height, LINE_SAMPLES)
+            .into_iter()
+            .map(|(x, y)| point(x, y))
+            .collect();
+        polyline(context, &line);
+        context.stroke();
+        if let Some((x, y)) = widest(&line) {
+            context.move_to(x, y + style.label_size());
+            context.show_text(&format!("WL {:.2}", height.to_meter()));
+        }
+    }
+
+    style.apply(context, LineKind::Outline);
+    let deck: Vec<(f64, f64)> = hull
+        .positions(LINE_SAMPLES)
+        .into_iter()
+        .filter_map(|x| Some(point(x, hull.half_breadth(x, hull.sheer(x)?)?)))
+        .collect();
+    polyline(context, &deck);
+    context.stroke();
+    context.restore();
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LINES PLAN SHEET
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Title block
+///
+/// Identification of a drawing sheet, the boat name and principal dimensions, the scale, the
+/// date and the drawing number, drawn boxed in the bottom right corner of the sheet. On a page
+/// of a document, the sheet title and the page out of the count under the number.
+pub struct TitleBlock {
+    name: String,
+    loa: Length,
+    dwl: Length,
+    beam: Length,
+    draft: Length,
+    displacement: Weight,
+    scale: DrawingScale,
+    date: String,
+    number: String,
+    /// Sheet title, page and page count in a document.
+    page: Option<(String, usize, usize)>,
+}
+
+#[allow(dead_code)]
+impl TitleBlock {
+    pub fn from_boat(boat: &Boat, sheet: &Sheet, date: String, number: String) -> TitleBlock {
+        TitleBlock {
+            name: boat.name().to_string(),
+            loa: boat.loa(),
+            dwl: boat.dwl(),
+            beam: boat.b_max(),
+            draft: boat.draft(),
+            displacement: boat.displacement(),
+            scale: sheet.scale(),
+            date,
+            number,
+            page: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scale(&self) -> DrawingScale {
+        self.scale
+    }
+
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    /// Sheet title, page and page count in a document.
+    pub fn page(&self) -> Option<(&str, usize, usize)> {
+        self.page
+            .as_ref()
+            .map(|(title, page, count)| (title.as_str(), *page, *count))
+    }
+
+    pub fn set_page(&mut self, title: String, page: usize, count: usize) {
+        self.page = Some((title, page, count));
+    }
+
+    /// Lines of the block, the boat name first.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            self.name.clone(),
+            format!(
+                "LOA {:.2}m  DWL {:.2}m",
+                self.loa.to_meter(),
+                self.dwl.to_meter()
+            ),
+            format!(
+                "Beam {:.2}m  Draft {:.2}m",
+                self.beam.to_meter(),
+                self.draft.to_meter()
+            ),
+            format!("Displacement {:.0}kg", self.displacement.to_kilogram()),
+            format!("Scale {}  Date {}", self.scale, self.date),
+            format!("Drawing {}", self.number),
+        ];
+        if let Some((title, page, count)) = &self.page {
+            lines.push(format!("{}  Sheet {} of {}", title, page, count));
+        }
+        lines
+    }
+
+    /// Draw the block with its top left corner at the context origin, on the annotations layer.
+    pub fn draw(&self, context: &Context, style: &DrawingStyle) {
+        let (width, height) = TITLE_BLOCK_SIZE;
+        let lines = self.lines();
+        let row = height / lines.len() as f64;
+        let size = style.label_size();
+        context.save();
+        if !style.apply(context, LineKind::Tables) {
+            context.restore();
+            return;
+        }
+        style.select_font(context);
+        context.rectangle(0.0, 0.0, width, height);
+        context.stroke();
+        context.set_line_width(style.line_width(LineKind::Tables) / 2.0);
+        for (i, line) in lines.iter().enumerate() {
+            let y = row * i as f64;
+            if i > 0 {
+                context.move_to(0.0, y);
+                context.line_to(width, y);
+                context.stroke();
+            }
+            context.set_font_size(if i == 0 { 1.5 * size } else { size });
+            context.move_to(4.0, y + row - 4.0);
+            context.show_text(line);
+        }
+        context.restore();
+    }
+}
+
+/// Title block in the bottom right corner of a sheet, inside the margin.
+fn place_title(context: &Context, sheet: &Sheet, title: &TitleBlock, style: &DrawingStyle) {
+    let (width, height) = sheet.size();
+    let margin = sheet.margin_points();
+    let (block_width, block_height) = TITLE_BLOCK_SIZE;
+    context.save();
+    context.translate(width - margin - block_width, height - margin - block_height);
+    title.draw(context, style);
+    context.restore();
+}
+
+/// Lines plan sheet
+///
+/// The three views of the lines plan on a sheet, at the sheet scale. The profile on the top
+/// left with the half-breadth plan under it, the stations aligned, and the body plan on the
+/// right, on the profile baseline. A border all around and the title block in the bottom right
+/// corner.
+pub fn lines_plan(
+    context: &Context,
+    sheet: &Sheet,
+    hull: &Hull,
+    grid: &Grid,
+    appendages: &[(&str, AppendageGeometry)],
+    style: &DrawingStyle,
+    title: &TitleBlock,
+) {
+    let scale = sheet.scale().factor();
+    let margin = sheet.margin_points();
+    let depth = appendages
+        .iter()
+        .flat_map(|(_, a)| a.profile())
+        .map(|(_, z)| -z.to_meter())
+        .fold(0.0, f64::max);
+    let m = |meter: f64| points(Length::from_meter(meter), scale);
+    let (first, last) = hull.range();
+    let beam = points(grid.half_breadth(), scale);
+
+    let x0 = margin + VIEW_GAP / 2.0 - points(first, scale);
+    let baseline = margin + VIEW_GAP / 2.0 + points(grid.height(), scale);
+    let centerline = baseline + m(depth) + VIEW_GAP + beam;
+    let body = x0 + points(last, scale) + 2.0 * VIEW_GAP + beam;
+
+    sheet.draw_border(context, style);
+    context.save();
+    context.translate(x0, baseline);
+    profile(context, hull, scale, grid, appendages, style);
+    context.translate(0.0, centerline - baseline);
+    half_breadth_plan(context, hull, scale, grid, style);
+    context.translate(body - x0, baseline - centerline);
+    body_plan(context, hull, scale, grid, style);
+    context.restore();
+
+    place_title(context, sheet, title, style);
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// ANNOTATION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Filled arrow head with the tip on a point, pointing in a direction (radian).
+fn arrow(context: &Context, (x, y): (f64, f64), angle: f64) {
+    let spread = 0.3;
+    context.move_to(x, y);
+    context.line_to(
+        x - ARROW_SIZE * (angle - spread).cos(),
+        y - ARROW_SIZE * (angle - spread).sin(),
+    );
+    context.line_to(
+        x - ARROW_SIZE * (angle + spread).cos(),
+        y - ARROW_SIZE * (angle + spread).sin(),
+    );
+    context.close_path();
+    context.fill();
+}
+
+/// Font size set on the context (points).
+fn font_size(context: &Context) -> f64 {
+    context.get_font_matrix().yy
+}
+
+/// Linear dimension
+///
+/// Dimension of the distance between two points of a drawing at a scale, in context
+/// coordinates. Extension lines from the points to the dimension line, drawn parallel to the
+/// points at an offset (points, to the left going from the first point to the second), arrows
+/// on both ends and the length in the units over the line. The annotations are drawn in the
+/// context color, line width and font, set by the style of the caller.
+pub fn linear_dimension(
+    context: &Context,
+    from: (f64, f64),
+    to: (f64, f64),
+    offset: f64,
+    scale: f64,
+    units: UnitSystem,
+) {
+    let distance = (to.0 - from.0).hypot(to.1 - from.1);
+    let text = units.length(Length::from_meter(distance / (scale * POINTS_PER_METER)));
+    labeled_dimension(context, from, to, offset, &text);
+}
+
+/// Linear dimension with a text of its own over the line, a name with the length.
+pub fn labeled_dimension(
+    context: &Context,
+    from: (f64, f64),
+    to: (f64, f64),
+    offset: f64,
+    text: &str,
+) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let distance = dx.hypot(dy);
+    if distance == 0.0 {
+        return;
+    }
+    let angle = dy.atan2(dx);
+    // Unit normal to the left, times a distance from the points.
+    let side = offset.signum();
+    let normal = |d: f64| (dy / distance * d, -dx / distance * d);
+    let along = |(x, y): (f64, f64), d: f64| (x + normal(d).0, y + normal(d).1);
+    let (start, end) = (along(from, offset), along(to, offset));
+
+    context.save();
+    for &point in &[from, to] {
+        let (x, y) = along(point, side * EXTENSION_GAP);
+        context.move_to(x, y);
+        let (x, y) = along(point, offset + side * EXTENSION_GAP);
+        context.line_to(x, y);
+    }
+    context.move_to(start.0, start.1);
+    context.line_to(end.0, end.1);
+    context.stroke();
+    arrow(context, start, angle + std::f64::consts::PI);
+    arrow(context, end, angle);
+
+    let extents = context.text_extents(text);
+    context.translate((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+    // Text upright, left to right.
+    let upright = if angle.abs() > std::f64::consts::FRAC_PI_2 {
+        angle + std::f64::consts::PI
+    } else {
+        angle
+    };
+    context.rotate(upright);
+    context.move_to(-extents.width / 2.0 - extents.x_bearing, -EXTENSION_GAP);
+    context.show_text(text);
+    context.restore();
+}
+
+/// Leader from a point of the drawing, the tip with an arrow, to a text at a position, in
+/// context coordinates. A short landing, away from the tip, runs under the start of the text.
+pub fn leader(context: &Context, tip: (f64, f64), position: (f64, f64), text: &str) {
+    context.save();
+    let extents = context.text_extents(text);
+    let direction = if position.0 >= tip.0 { 1.0 } else { -1.0 };
+    let landing = position.0 + direction * ARROW_SIZE;
+    context.move_to(tip.0, tip.1);
+    context.line_to(position.0, position.1);
+    context.line_to(landing, position.1);
+    context.stroke();
+    arrow(context, tip, (tip.1 - position.1).atan2(tip.0 - position.0));
+    let x = if direction > 0.0 {
+        landing + EXTENSION_GAP
+    } else {
+        landing - EXTENSION_GAP - extents.x_advance
+    };
+    context.move_to(x, position.1 + font_size(context) / 3.0);
+    context.show_text(text);
+    context.restore();
+}
+
+/// Note, lines of text from a position, the first line baseline on it.
+pub fn note(context: &Context, position: (f64, f64), lines: &[&str]) {
+    context.save();
+    let size = font_size(context);
+    for (i, line) in lines.iter().enumerate() {
+        context.move_to(position.0, position.1 + 1.25 * size * i as f64);
+        context.show_text(line);
+    }
+    context.restore();
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HATCHING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Closest spacing of the hatch lines (points), a closer hatching is a fill anyway.
+pub const HATCH_MIN_SPACING: f64 = 0.5;
+
+/// Hatching of a region, drawn in the context color and line width.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Hatch {
+    /// Flat fill of the context color at an opacity from 0 to 1, light for the areas under the
+    /// waterline.
+    Fill(f64),
+    /// Parallel lines at an angle (degree, counterclockwise from the x axis) and a spacing
+    /// (points), the section of a cut part.
+    Lines { angle: f64, spacing: f64 },
+    /// Lines crossed at right angles, the section of a second material.
+    Cross { angle: f64, spacing: f64 },
+}
+
+#[allow(dead_code)]
+impl Hatch {
+    /// Standard section hatching, lines at 45 degree.
+    pub const SECTION: Hatch = Hatch::Lines {
+        angle: 45.0,
+        spacing: 4.0,
+    };
+
+    /// Light fill of the areas under the waterline.
+    pub const UNDERWATER: Hatch = Hatch::Fill(0.15);
+}
+
+/// Hatch lines across a clipped region at an angle and a spacing, on the multiples of the
+/// spacing from the context origin so the hatching of adjacent regions lines up. The spacing is
+/// at least the minimum.
+fn hatch_lines(context: &Context, outline: &[(f64, f64)], angle: f64, spacing: f64) {
+    if spacing.is_nan() || spacing <= 0.0 {
+        return;
+    }
+    let spacing = spacing.max(HATCH_MIN_SPACING);
+    // Along the lines, up to the right with y down, and across them.
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let along = |&(x, y): &(f64, f64)| x * cos - y * sin;
+    let across = |&(x, y): &(f64, f64)| x * sin + y * cos;
+    let range = |f: &dyn Fn(&(f64, f64)) -> f64| {
+        outline
+            .iter()
+            .map(f)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), v| {
+                (low.min(v), high.max(v))
+            })
+    };
+    let (start, end) = range(&along);
+    let (low, high) = range(&across);
+    let point = |a: f64, d: f64| (a * cos + d * sin, d * cos - a * sin);
+    let mut d = (low / spacing).floor() * spacing;
+    while d <= high {
+        let (x, y) = point(start, d);
+        context.move_to(x, y);
+        let (x, y) = point(end, d);
+        context.line_to(x, y);
+        d += spacing;
+    }
+    context.stroke();
+}
+
+/// Hatch the region inside a closed outline, in context coordinates. The outline itself is
+/// left to the caller, to draw on its own layer.
+pub fn hatch(context: &Context, outline: &[(f64, f64)], hatch: Hatch) {
+    if outline.len() < 3 {
+        return;
+    }
+    context.save();
+    context.new_path();
+    polyline(context, outline);
+    context.close_path();
+    context.clip();
+    match hatch {
+        Hatch::Fill(opacity) => context.paint_with_alpha(opacity),
+        Hatch::Lines { angle, spacing } => hatch_lines(context, outline, angle, spacing),
+        Hatch::Cross { angle, spacing } => {
+            hatch_lines(context, outline, angle, spacing);
+            hatch_lines(context, outline, angle + 90.0, spacing);
+        }
+    }
+    context.restore();
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SAIL PLAN
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Sail plan
+///
+/// Rig on the hull profile at a scale, the forward perpendicular at the context origin on the
+/// DWL, the bow on the left and heights up. The hull outline with its baseline a height under
+/// the DWL, the mast from the sheer to the masthead, the boom, the forestay and the mainsail
+/// and the mizzen outlines, with the sails of the inventory that are positioned dashed over.
+/// The I, J, P and E dimensioned in the units and the CE marked. The DWL on the grid layer and
+/// the spars and sails on the rig layer. Needs the rig placement, draws nothing without.
+pub fn sail_plan(
+    context: &Context,
+    rig: &Rig,
+    hull: Option<(&Hull, Length)>,
+    scale: f64,
+    units: UnitSystem,
+    style: &DrawingStyle,
+) {
+    let place = match rig.placement() {
+        Some(place) => place,
+        None => return,
+    };
+    let point = |p: SailPoint| (points(p.x, scale), -points(p.z, scale));
+    let at = |x: Length, z: Length| point(SailPoint::new(x, z));
+    let mast = place.mast;
+    let masthead = if rig.has_foretriangle() {
+        (place.sheer + rig.i())
+            .to_meter()
+            .max((place.boom + rig.p()).to_meter())
+    } else {
+        (place.boom + rig.p()).to_meter()
+    };
+
+    let size = style.label_size();
+    context.save();
+    style.select_font(context);
+    if let Some((hull, dwl)) = hull {
+        if style.apply(context, LineKind::Outline) {
+            // Lighter than on the lines plan, the hull under the rig.
+            context.set_line_width(0.5 * style.line_width(LineKind::Outline));
+            let outline: Vec<(f64, f64)> = hull_outline(hull)
+                .into_iter()
+                .map(|(x, z)| at(x, z - dwl))
+                .collect();
+            polyline(context, &outline);
+            context.close_path();
+            context.stroke();
+        }
+        if style.apply(context, LineKind::Grid) {
+            let (first, last) = hull.range();
+            context.set_dash(&GRID_DASH, 0.0);
+            context.move_to(points(first, scale), 0.0);
+            context.line_to(points(last, scale), 0.0);
+            context.stroke();
+            context.set_dash(&[], 0.0);
+            context.move_to(points(last, scale) + 2.0, size / 3.0);
+            context.show_text("DWL");
+        }
+    }
+    if !style.is_visible(Layer::Rig) {
+        context.restore();
+        return;
+    }
+
+    // Spars and the forestay.
+    style.apply(context, LineKind::Spars);
+    let (x, y) = at(mast, place.sheer);
+    context.move_to(x, y);
+    let (x, y) = at(mast, Length::from_meter(masthead));
+    context.line_to(x, y);
+    let (x, y) = at(mast, place.boom);
+    context.move_to(x, y);
+    let (x, y) = at(mast + rig.e(), place.boom);
+    context.line_to(x, y);
+    let mizzen = rig.counted_mizzen().zip(place.mizzen);
+    if let Some((sail, mast)) = mizzen {
+        let (x, y) = at(mast, place.sheer);
+        context.move_to(x, y);
+        let (x, y) = at(mast, place.boom + sail.p);
+        context.line_to(x, y);
+        let (x, y) = at(mast, place.boom);
+        context.move_to(x, y);
+        let (x, y) = at(mast + sail.e, place.boom);
+        context.line_to(x, y);
+    }
+    context.stroke();
+    style.apply(context, LineKind::Stays);
+    if rig.has_foretriangle() {
+        let (x, y) = at(mast - rig.j(), place.sheer);
+        context.move_to(x, y);
+        let (x, y) = at(mast, place.sheer + rig.i());
+        context.line_to(x, y);
+        context.stroke();
+    }
+
+    // Mainsail and mizzen outlines.
+    style.apply(context, LineKind::Sails);
+    let mut outlines = vec![[
+        at(mast, place.boom),
+        at(mast, place.boom + rig.p()),
+        at(mast + rig.e(), place.boom),
+    ]];
+    if let Some((sail, mast)) = mizzen {
+        outlines.push([
+            at(mast, place.boom),
+            at(mast, place.boom + sail.p),
+            at(mast + sail.e, place.boom),
+        ]);
+    }
+    for outline in &outlines {
+        polyline(context, outline);
+        context.close_path();
+        context.stroke();
+    }
+    context.set_dash(&GRID_DASH, 0.0);
+    for sail in rig.sails() {
+        if let Some(corners) = sail.corners() {
+            let outline: Vec<(f64, f64)> = corners.iter().map(|&p| point(p)).collect();
+            polyline(context, &outline);
+            context.close_path();
+            context.stroke();
+        }
+    }
+    context.set_dash(&[], 0.0);
+    context.restore();
+    if !style.is_visible(Layer::Annotations) {
+        return;
+    }
+
+    // Rig dimensions, I forward of the forestay, J under the sheer, P aft of the clew and E
+    // under the boom.
+    context.save();
+    style.apply(context, LineKind::Dimensions);
+    style.select_font(context);
+    let gap = 2.0 * size;
+    let label = |name: &str, length: Length| format!("{} {}", name, units.length(length));
+    if rig.has_foretriangle() {
+        labeled_dimension(
+            context,
+            at(mast, place.sheer),
+            at(mast, place.sheer + rig.i()),
+            points(rig.j(), scale) + gap,
+            &label("I", rig.i()),
+        );
+        labeled_dimension(
+            context,
+            at(mast - rig.j(), place.sheer),
+            at(mast, place.sheer),
+            -gap,
+            &label("J", rig.j()),
+        );
+    }
+    labeled_dimension(
+        context,
+        at(mast, place.boom),
+        at(mast, place.boom + rig.p()),
+        -(points(rig.e(), scale) + gap),
+        &label("P", rig.p()),
+    );
+    labeled_dimension(
+        context,
+        at(mast, place.boom),
+        at(mast + rig.e(), place.boom),
+        -gap,
+        &label("E", rig.e()),
+    );
+
+    if let Some(ce) = rig.center_of_effort() {
+        let (x, y) = point(ce);
+        let radius = ARROW_SIZE / 2.0;
+        context.new_path();
+        context.arc(x, y, radius, 0.0, 2.0 * std::f64::consts::PI);
+        context.move_to(x - 2.0 * radius, y);
+        context.line_to(x + 2.0 * radius, y);
+        context.move_to(x, y - 2.0 * radius);
+        context.line_to(x, y + 2.0 * radius);
+        context.stroke();
+        context.move_to(x + 2.0 * radius + 2.0, y - 2.0);
+        context.show_text("CE");
+    }
+    context.restore();
+}
+
+/// Height of the highest masthead above the DWL (m), the mizzen of a ketch or yawl included.
+fn rig_top(rig: &Rig, place: &RigPlacement) -> f64 {
+    let mut top = (place.sheer + rig.i())
+        .to_meter()
+        .max((place.boom + rig.p()).to_meter());
+    if let (Some(mizzen), Some(_)) = (rig.counted_mizzen(), place.mizzen) {
+        top = top.max((place.boom + mizzen.p).to_meter());
+    }
+    top
+}
+
+/// Sail plan sheet
+///
+/// Sail plan of the boat on a sheet at the sheet scale, the hull profile when given with the
+/// DWL at the hull draft above the baseline, and the title block in the bottom right corner.
+/// Needs the rig and its placement, only the border and the title block without.
+pub fn sail_plan_sheet(
+    context: &Context,
+    sheet: &Sheet,
+    boat: &Boat,
+    hull: Option<&Hull>,
+    units: UnitSystem,
+    style: &DrawingStyle,
+    title: &TitleBlock,
+) {
+    let scale = sheet.scale().factor();
+    let margin = sheet.margin_points();
+    sheet.draw_border(context, style);
+    if let Some((rig, place)) = boat.rig().and_then(|r| Some((r, r.placement()?))) {
+        let mut forward = (place.mast - rig.j()).to_meter().min(0.0);
+        if let Some(hull) = hull {
+            forward = forward.min(hull.range().0.to_meter());
+        }
+        let top = rig_top(rig, &place);
+        // Room forward for the I dimension.
+        let x0 = margin + 2.0 * VIEW_GAP - points(Length::from_meter(forward), scale);
+        let y0 = margin + VIEW_GAP + points(Length::from_meter(top), scale);
+        context.save();
+        context.translate(x0, y0);
+        let hull = hull.map(|h| (h, boat.draft()));
+        sail_plan(context, rig, hull, scale, units, style);
+        context.restore();
+    }
+    place_title(context, sheet, title, style);
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// OFFSETS TABLE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Text padding in the cells of the offsets table (points).
+const TABLE_PADDING: f64 = 4.0;
+
+/// Row of the offsets table, the name and the values, a group heading without values.
+type OffsetRow = (String, Option<Vec<String>>);
+
+/// Rows of the offsets table in a notation, the station numbers and positions, then the heights
+/// and the half-breadths, each group under its heading.
+fn offset_rows(table: &OffsetsTable, units: OffsetUnits) -> Vec<OffsetRow> {
+    let stations = table.stations();
+    let mut rows = vec![
+        (
+            "Station".to_string(),
+            Some((0..stations.len()).map(|i| i.to_string()).collect()),
+        ),
+        (
+            "Aft of FP".to_string(),
+            Some(stations.iter().map(|&s| format_offset(s, units)).collect()),
+        ),
+    ];
+    let groups = [
+        (OffsetKind::Height, "Heights above base"),
+        (OffsetKind::HalfBreadth, "Half-breadths"),
+    ];
+    for &(kind, heading) in groups.iter() {
+        let lines: Vec<&OffsetLine> = table.lines().iter().filter(|l| l.kind() == kind).collect();
+        if lines.is_empty() {
+            continue;
+        }
+        rows.push((heading.to_string(), None));
+        for line in lines {
+            let name = match line.position() {
+                Some(position) => format!("{} {}", line.name(), format_offset(position, units)),
+                None => line.name().to_string(),
+            };
+            let values = line
+                .values()
+                .iter()
+                .map(|v| v.map_or("-".to_string(), |v| format_offset(v, units)))
+                .collect();
+            rows.push((name, Some(values)));
+        }
+    }
+    rows
+}
+
+/// Width of the name column and of the station columns, to the widest text (points).
+fn offset_columns(context: &Context, rows: &[OffsetRow], style: &DrawingStyle) -> (f64, f64) {
+    context.save();
+    style.select_font(context);
+    let width = |text: &str| context.text_extents(text).x_advance + 2.0 * TABLE_PADDING;
+    let name = rows
+        .iter()
+        .filter(|(_, values)| values.is_some())
+        .map(|(name, _)| width(name))
+        .fold(0.0, f64::max);
+    let column = rows
+        .iter()
+        .filter_map(|(_, values)| values.as_ref())
+        .flatten()
+        .map(|value| width(value))
+        .fold(0.0, f64::max);
+    context.restore();
+    (name, column)
+}
+
+/// Size of the offsets table in a notation, width and height (points).
+pub fn offsets_table_size(
+    context: &Context,
+    table: &OffsetsTable,
+    units: OffsetUnits,
+    style: &DrawingStyle,
+) -> (f64, f64) {
+    let rows = offset_rows(table, units);
+    let (name, column) = offset_columns(context, &rows, style);
+    (
+        name + column * table.stations().len() as f64,
+        TABLE_ROW * rows.len() as f64,
+    )
+}
+
+/// Offsets table
+///
+/// Offsets of the table in a notation, feet-inches-eighths or millimeters, laid out as on the
+/// published plans, a column for each station and a row for each line, the station numbers and
+/// positions on top and the heights and half-breadths under their headings. The values right
+/// aligned and a dash where the line does not cross the station. Top left corner at the origin,
+/// on the annotations layer.
+pub fn offsets_table(
+    context: &Context,
+    table: &OffsetsTable,
+    units: OffsetUnits,
+    style: &DrawingStyle,
+) {
+    let rows = offset_rows(table, units);
+    let (name_width, column) = offset_columns(context, &rows, style);
+    let stations = table.stations().len();
+    let width = name_width + column * stations as f64;
+    let rule = style.line_width(LineKind::Tables);
+    context.save();
+    if !style.apply(context, LineKind::Tables) {
+        context.restore();
+        return;
+    }
+    style.select_font(context);
+    context.rectangle(0.0, 0.0, width, TABLE_ROW * rows.len() as f64);
+    context.stroke();
+    for (i, (name, values)) in rows.iter().enumerate() {
+        let y = TABLE_ROW * i as f64;
+        if i > 0 {
+            // Heavier rule under the station rows.
+            context.set_line_width(if i == 2 { rule } else { rule / 2.0 });
+            context.move_to(0.0, y);
+            context.line_to(width, y);
+            context.stroke();
+        }
+        let baseline = y + TABLE_ROW - TABLE_PADDING;
+        context.move_to(TABLE_PADDING, baseline);
+        context.show_text(name);
+        if let Some(values) = values {
+            for (j, value) in values.iter().enumerate() {
+                let right = name_width + column * (j + 1) as f64 - TABLE_PADDING;
+                context.move_to(right - context.text_extents(value).x_advance, baseline);
+                context.show_text(value);
+            }
+            // Column rules, broken across the headings.
+            context.set_line_width(rule / 2.0);
+            for j in 0..stations {
+                let x = name_width + column * j as f64;
+                context.move_to(x, y);
+                context.line_to(x, y + TABLE_ROW);
+            }
+            context.stroke();
+        }
+    }
+    context.restore();
+}
+
+/// Offsets sheet
+///
+/// Offsets table on a sheet, at its size or scaled down to fit over the title block, with a note
+/// of the notation under it, a border all around and the title block in the bottom right corner.
+pub fn offsets_sheet(
+    context: &Context,
+    sheet: &Sheet,
+    table: &OffsetsTable,
+    units: OffsetUnits,
+    style: &DrawingStyle,
+    title: &TitleBlock,
+) {
+    let (x, y, width, height) = sheet.drawing_area();
+    sheet.draw_border(context, style);
+    let (table_width, table_height) = offsets_table_size(context, table, units, style);
+    let room_width = width - 2.0 * VIEW_GAP;
+    let room_height = height - TITLE_BLOCK_SIZE.1 - 3.0 * VIEW_GAP;
+    let fit = (room_width / table_width)
+        .min(room_height / table_height)
+        .min(1.0);
+    context.save();
+    context.translate(x + VIEW_GAP, y + VIEW_GAP);
+    context.scale(fit, fit);
+    offsets_table(context, table, units, style);
+    context.restore();
+    let notation = match units {
+        OffsetUnits::FeetInchesEighths => "Offsets in feet-inches-eighths, + a sixteenth over",
+        OffsetUnits::Millimeter => "Offsets in millimeters",
+    };
+    context.save();
+    if style.apply(context, LineKind::Dimensions) {
+        style.select_font(context);
+        note(
+            context,
+            (
+                x + VIEW_GAP,
+                y + VIEW_GAP + fit * table_height + 2.0 * style.label_size(),
+            ),
+            &[
+                notation,
+                "Heights above the baseline, half-breadths from the centerline",
+            ],
+        );
+    }
+    context.restore();
+    place_title(context, sheet, title, style);
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PARTICULARS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Row of the particulars, the name, the value with its units and the character of a ratio.
+pub type ParticularsRow = [String; 3];
+
+/// Design particulars
+///
+/// Dimensions, ratios with their character and hydrostatics of a boat, in groups of rows to lay
+/// out on a sheet as the shareable summary of the design. The hydrostatics group, and the
+/// initial stability from the hull geometry, when the hull hydrostatics are given. The values
+/// not informed are left out.
+#[derive(Debug, Clone)]
+pub struct Particulars {
+    name: String,
+    groups: Vec<(String, Vec<ParticularsRow>)>,
+}
+
+#[allow(dead_code)]
+impl Particulars {
+    pub fn from_boat(
+        boat: &Boat,
+        hydrostatics: Option<&Hydrostatics>,
+        units: UnitSystem,
+    ) -> Particulars {
+        let row =
+            |name: &str, value: String, character: String| [name.to_string(), value, character];
+        let plain = |name: &str, value: String| row(name, value, String::new());
+
+        let mut dimensions = vec![
+            plain("LOA", units.length(boat.loa())),
+            plain("DWL", units.length(boat.dwl())),
+            plain("Beam", units.length(boat.b_max())),
+            plain("BWL", units.length(boat.bwl())),
+            plain("Draft", units.length(boat.draft())),
+        ];
+        if boat.keel().is_some() {
+            dimensions.push(plain("Total draft", units.length(boat.total_draft())));
+        }
+        dimensions.push(plain("Displacement", units.weight(boat.displacement())));
+        if let Some(ratio) = boat.ballast_ratio() {
+            dimensions.push(plain("Ballast ratio", format!("{:.1}%", ratio * 100.0)));
+        }
+        dimensions.push(plain("Sail area", units.area(boat.sail_area())));
+        dimensions.push(plain("Wetted surface", units.area(boat.wetted_surface())));
+
+        let mut ratios = Ratios::new(boat);
+        if let Some(hydrostatics) = hydrostatics {
+            ratios = ratios.with_hydrostatics(boat, hydrostatics);
+        }
+        let r = &ratios;
+        let mut rows = vec![
+            row(
+                "L/B",
+                format!("{:.2}", r.length_beam().value()),
+                r.length_beam().character().to_string(),
+            ),
+            row(
+                "D/L",
+                format!("{:.0}", r.displacement_length().value()),
+                r.displacement_length().character().to_string(),
+            ),
+            row(
+                "SA/D",
+                format!("{:.1}", r.sail_area_displacement().value()),
+                r.sail_area_displacement().character().to_string(),
+            ),
+            row(
+                "SA/WS",
+                format!("{:.2}", r.sail_area_wetted_surface().value()),
+                r.sail_area_wetted_surface().character().to_string(),
+            ),
+            row(
+                "S#",
+                format!("{:.2}", r.s_number().value()),
+                r.s_number().character().to_string(),
+            ),
+            row(
+                "BN",
+                format!("{:.2}", r.bruce_number().value()),
+                r.bruce_number().character().to_string(),
+            ),
+        ];
+        if let Some(ssv) = r.screening_stability() {
+            rows.push(plain("SSV", format!("{:.2}", ssv.value())));
+        }
+        if let Some(cp) = r.prismatic_coefficient() {
+            rows.push(row(
+                "Cp",
+                format!("{:.3}", cp.value()),
+                format!("{} (ideal {:.3})", cp.character(), cp.ideal()),
+            ));
+        }
+        for (name, center) in &[("LCB", r.lcb()), ("LCF", r.lcf())] {
+            if let Some(center) = center {
+                rows.push(row(
+                    name,
+                    format!("{:.1}%", center.value()),
+                    center.character().to_string(),
+                ));
+            }
+        }
+        if let Some(stability) = r.initial_stability() {
+            rows.push(row(
+                "GM",
+                units.length(stability.gm()),
+                stability.character().to_string(),
+            ));
+        }
+
+        let mut groups = vec![
+            ("Dimensions".to_string(), dimensions),
+            ("Ratios".to_string(), rows),
+        ];
+        if let Some(h) = hydrostatics {
+            let optional =
+                |length: Option<Length>| length.map_or("-".to_string(), |l| units.length(l));
+            let immersion = match units {
+                UnitSystem::Metric => units.weight(h.immersion().weight_per_centimeter()) + "/cm",
+                UnitSystem::Imperial => units.weight(h.immersion().weight_per_inch()) + "/in",
+            };
+            let mut rows = vec![
+                plain("Waterline", units.length(h.waterline())),
+                plain("Volume", units.volume(h.volume())),
+                plain("Displacement", units.weight(h.displacement())),
+                plain("LCB aft of FP", optional(h.lcb())),
+                plain("KB", optional(h.kb())),
+                plain("Waterplane area", units.area(h.waterplane_area())),
+                plain("LCF aft of FP", optional(h.lcf())),
+                plain("BM", optional(h.bm())),
+                plain("BML", optional(h.bml())),
+                plain("Wetted surface", units.area(h.wetted_surface())),
+                plain("Immersion", immersion),
+            ];
+            if let Some(c) = h.form_coefficients() {
+                rows.push(plain("Cp", format!("{:.3}", c.cp)));
+                rows.push(plain("Cb", format!("{:.3}", c.cb)));
+                rows.push(plain("Cm", format!("{:.3}", c.cm)));
+                rows.push(plain("Cw", format!("{:.3}", c.cw)));
+            }
+            groups.push((format!("Hydrostatics - {}", h.water()), rows));
+        }
+        Particulars {
+            name: boat.name().to_string(),
+            groups,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Groups of rows under their headings.
+    pub fn groups(&self) -> &[(String, Vec<ParticularsRow>)] {
+        &self.groups
+    }
+
+    /// Row by group heading and row name.
+    pub fn row(&self, group: &str, name: &str) -> Option<&ParticularsRow> {
+        self.groups
+            .iter()
+            .find(|(heading, _)| heading == group)?
+            .1
+            .iter()
+            .find(|row| row[0] == name)
+    }
+
+    /// Width of the name, value and character columns, to the widest text (points).
+    fn columns(&self, context: &Context, style: &DrawingStyle) -> [f64; 3] {
+        context.save();
+        style.select_font(context);
+        let mut columns = [0.0_f64; 3];
+        for row in self.groups.iter().flat_map(|(_, rows)| rows) {
+            for (column, text) in columns.iter_mut().zip(row.iter()) {
+                *column = column.max(context.text_extents(text).x_advance + 2.0 * TABLE_PADDING);
+            }
+        }
+        context.restore();
+        columns
+    }
+
+    /// Size of a group, its heading and rows, width and height (points).
+    fn group_size(&self, context: &Context, group: usize, style: &DrawingStyle) -> (f64, f64) {
+        let width: f64 = self.columns(context, style).iter().sum();
+        (width, TABLE_ROW * (self.groups[group].1.len() + 1) as f64)
+    }
+
+    /// Draw a group with its top left corner at the origin, the heading over a rule, the names
+    /// and characters left aligned and the values right aligned, on the annotations layer.
+    fn draw_group(&self, context: &Context, group: usize, style: &DrawingStyle) {
+        let columns = self.columns(context, style);
+        let (width, _) = self.group_size(context, group, style);
+        let (heading, rows) = &self.groups[group];
+        let rule = style.line_width(LineKind::Tables);
+        context.save();
+        if !style.apply(context, LineKind::Tables) {
+            context.restore();
+            return;
+        }
+        style.select_font(context);
+        context.set_font_size(1.25 * style.label_size());
+        context.move_to(TABLE_PADDING, TABLE_ROW - TABLE_PADDING);
+        context.show_text(heading);
+        context.move_to(0.0, TABLE_ROW);
+        context.line_to(width, TABLE_ROW);
+        context.stroke();
+        context.set_font_size(style.label_size());
+        context.set_line_width(rule / 4.0);
+        for (i, row) in rows.iter().enumerate() {
+            let y = TABLE_ROW * (i + 2) as f64;
+            let baseline = y - TABLE_PADDING;
+            context.move_to(TABLE_PADDING, baseline);
+            context.show_text(&row[0]);
+            let right = columns[0] + columns[1] - TABLE_PADDING;
+            context.move_to(right - context.text_extents(&row[1]).x_advance, baseline);
+            context.show_text(&row[1]);
+            context.move_to(columns[0] + columns[1] + TABLE_PADDING, baseline);
+            context.show_text(&row[2]);
+            context.move_to(0.0, y);
+            context.line_to(width, y);
+            context.stroke();
+        }
+        context.restore();
+    }
+
+    /// Size of the groups one under the other, a row apart, width and height (points).
+    pub fn size(&self, context: &Context, style: &DrawingStyle) -> (f64, f64) {
+        let width: f64 = self.columns(context, style).iter().sum();
+        let height: f64 = (0..self.groups.len())
+            .map(|g| self.group_size(context, g, style).1 + TABLE_ROW)
+            .sum();
+        (width, (height - TABLE_ROW).max(0.0))
+    }
+
+    /// Draw the groups one under the other, a row apart, top left corner at the origin.
+    pub fn draw(&self, context: &Context, style: &DrawingStyle) {
+        let mut y = 0.0;
+        for group in 0..self.groups.len() {
+            context.save();
+            context.translate(0.0, y);
+            self.draw_group(context, group, style);
+            context.restore();
+            y += self.group_size(context, group, style).1 + TABLE_ROW;
+        }
+    }
+}
+
+/// Particulars sheet
+///
+/// Design particulars of a boat on a sheet, under a heading, the groups flowing down the sheet
+/// and on to a next column when they don't fit over the title block, a border all around and
+/// the title block in the bottom right corner.
+pub fn particulars_sheet(
+    context: &Context,
+    sheet: &Sheet,
+    particulars: &Particulars,
+    style: &DrawingStyle,
+    title: &TitleBlock,
+) {
+    let (x, y, _, height) = sheet.drawing_area();
+    sheet.draw_border(context, style);
+    context.save();
+    if style.apply(context, LineKind::Tables) {
+        style.select_font(context);
+        context.set_font_size(2.0 * style.label_size());
+        context.move_to(x + VIEW_GAP, y + VIEW_GAP);
+        context.show_text(&format!("Design particulars - {}", particulars.name()));
+    }
+    context.restore();
+
+    let top = y + VIEW_GAP + TABLE_ROW;
+    let bottom = y + height - TITLE_BLOCK_SIZE.1 - VIEW_GAP;
+    let (width, _) = particulars.size(context, style);
+    let (mut column, mut row) = (x + VIEW_GAP, top);
+    for group in 0..particulars.groups().len() {
+        let (_, group_height) = particulars.group_size(context, group, style);
+        if row > top && row + group_height > bottom {
+            column += width + VIEW_GAP;
+            row = top;
+        }
+        context.save();
+        context.translate(column, row);
+        particulars.draw_group(context, group, style);
+        context.restore();
+        row += group_height + TABLE_ROW;
+    }
+    place_title(context, sheet, title, style);
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DOCUMENT
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Routine drawing a page of a document, on its sheet with its title block.
+type PageDrawing<'a> = Box<dyn Fn(&Context, &Sheet, &TitleBlock) + 'a>;
+
+/// Page of a document, its sheet and title and the routine drawing it.
+struct Page<'a> {
+    sheet: Sheet,
+    title: String,
+    draw: PageDrawing<'a>,
+}
+
+/// Document
+///
+/// Sheets of a design, the lines plan, the sail plan, the offsets and the report pages, in a
+/// single PDF, a page each in the order added, each on its own paper and orientation. The
+/// title blocks share the boat, the date and the drawing number, the pages numbered from it as
+/// 12-1, 12-2 and so on, with the sheet title and the page out of the count.
+pub struct Document<'a> {
+    boat: &'a Boat,
+    date: String,
+    number: String,
+    pages: Vec<Page<'a>>,
+}
+
+#[allow(dead_code)]
+impl<'a> Document<'a> {
+    pub fn new(boat: &'a Boat, date: String, number: String) -> Document<'a> {
+        Document {
+            boat,
+            date,
+            number,
+            pages: Vec::new(),
+        }
+    }
+
+    /// Add a sheet, drawn on its sheet with the title block of its page, as the sheet drawings
+    /// take them.
+    pub fn add_page<F>(&mut self, sheet: Sheet, title: &str, draw: F)
+    where
+        F: Fn(&Context, &Sheet, &TitleBlock) + 'a,
+    {
+        self.pages.push(Page {
+            sheet,
+            title: title.to_string(),
+            draw: Box::new(draw),
+        });
+    }
+
+    /// Number of pages.
+    pub fn count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Title block of a page.
+    pub fn title_block(&self, page: usize) -> Option<TitleBlock> {
+        let sheet = self.pages.get(page)?;
+        let mut title = TitleBlock::from_boat(
+            self.boat,
+            &sheet.sheet,
+            self.date.clone(),
+            format!("{}-{}", self.number, page + 1),
+        );
+        title.set_page(sheet.title.clone(), page + 1, self.pages.len());
+        Some(title)
+    }
+
+    /// Write the document to a PDF file.
+    pub fn to_pdf<P: AsRef<Path>>(&self, path: P) -> Result<(), DrawingError> {
+        fs::write(path, self.to_pdf_bytes()?)?;
+        Ok(())
+    }
+
+    /// Write the document to PDF bytes in memory.
+    pub fn to_pdf_bytes(&self) -> Result<Vec<u8>, DrawingError> {
+        let (width, height) = self.pages.first().map_or_else(
+            || Sheet::new(PaperSize::A4, Orientation::Portrait, DrawingScale::FullSize).size(),
+            |p| p.sheet.size(),
+        );
+        let surface = PdfSurface::for_stream(width, height, Vec::<u8>::new())?;
+        {
+            let context = Context::new(&surface);
+            for (i, page) in self.pages.iter().enumerate() {
+                let (width, height) = page.sheet.size();
+                surface.set_size(width, height)?;
+                blank(&context);
+                if let Some(title) = self.title_block(i) {
+                    context.save();
+                    (page.draw)(&context, &page.sheet, &title);
+                    context.restore();
+                }
+                context.show_page();
+            }
+        }
+        let stream = surface
+            .finish_output_stream()
+            .map_err(|err| DrawingError::Io(err.error))?;
+        Ok(*stream
+            .downcast::<Vec<u8>>()
+            .map_err(|_| io::Error::other("unexpected drawing stream"))?)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TILING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Tiling
+///
+/// Full size pattern of a width and a height printed across pages of a paper size, for the
+/// builders without a plotter. The pages overlap by TILE_OVERLAP inside their margin, crosses
+/// in the middle of each overlap line the pages up, and each page carries its tile name, the
+/// row letter and the column number, and a SCALE_BAR to check the printer kept the size.
+/// The pattern is drawn with its top left corner at the context origin, y down, full size.
+#[derive(Debug, Copy, Clone)]
+pub struct Tiling {
+    sheet: Sheet,
+    width: Length,
+    height: Length,
+}
+
+#[allow(dead_code)]
+impl Tiling {
+    pub fn new(
+        paper: PaperSize,
+        orientation: Orientation,
+        width: Length,
+        height: Length,
+    ) -> Tiling {
+        Tiling {
+            sheet: Sheet::new(paper, orientation, DrawingScale::FullSize),
+            width,
+            height,
+        }
+    }
+
+    pub fn sheet(&self) -> &Sheet {
+        &self.sheet
+    }
+
+    /// Pattern width and height.
+    pub fn size(&self) -> (Length, Length) {
+        (self.width, self.height)
+    }
+
+    /// Pattern advance from a page to the next across and down (points).
+    fn steps(&self) -> (f64, f64) {
+        let (_, _, width, height) = self.sheet.drawing_area();
+        let overlap = points(Length::from_meter(TILE_OVERLAP), 1.0);
+        (width - overlap, height - overlap)
+    }
+
+    /// Pages across a length (points) with an advance.
+    fn count(length: f64, step: f64) -> usize {
+        let overlap = points(Length::from_meter(TILE_OVERLAP), 1.0);
+        (((length - overlap) / step).ceil() as usize).max(1)
+    }
+
+    pub fn columns(&self) -> usize {
+        Tiling::count(points(self.width, 1.0), self.steps().0)
+    }
+
+    pub fn rows(&self) -> usize {
+        Tiling::count(points(self.height, 1.0), self.steps().1)
+    }
+
+    pub fn pages(&self) -> usize {
+        self.columns() * self.rows()
+    }
+
+    /// Tile name of a row and a column, A1 on the top left, B1 under it, AA1 after Z1.
+    pub fn name(row: usize, column: usize) -> String {
+        let mut letters = Vec::new();
+        let mut row = row + 1;
+        while row > 0 {
+            row -= 1;
+            letters.insert(0, (b'A' + (row % 26) as u8) as char);
+            row /= 26;
+        }
+        format!("{}{}", letters.into_iter().collect::<String>(), column + 1)
+    }
+
+    /// Render the tiles on the pages of a PDF surface, the crosses on the construction layer and
+    /// the page frame, the tile labels and the scale bar on the annotations layer.
+    fn render_pages(&self, surface: &Surface, draw: &dyn Fn(&Context), style: &DrawingStyle) {
+        let context = Context::new(surface);
+        let (x, y, width, height) = self.sheet.drawing_area();
+        let (step_x, step_y) = self.steps();
+        let overlap = points(Length::from_meter(TILE_OVERLAP), 1.0);
+        let (pattern_width, pattern_height) = (points(self.width, 1.0), points(self.height, 1.0));
+        let (columns, rows) = (self.columns(), self.rows());
+        let (_, page_height) = self.sheet.size();
+        for row in 0..rows {
+            for column in 0..columns {
+                blank(&context);
+                context.save();
+                context.rectangle(x, y, width, height);
+                context.clip();
+                context.translate(x - step_x * column as f64, y - step_y * row as f64);
+                context.save();
+                draw(&context);
+                context.restore();
+                // Crosses along the middle of the overlaps, shared by the pages either side.
+                if style.apply(&context, LineKind::Construction) {
+                    let cross = |cx: f64, cy: f64| {
+                        context.move_to(cx - ARROW_SIZE, cy);
+                        context.line_to(cx + ARROW_SIZE, cy);
+                        context.move_to(cx, cy - ARROW_SIZE);
+                        context.line_to(cx, cy + ARROW_SIZE);
+                    };
+                    for seam in 1..columns {
+                        let cx = step_x * seam as f64 + overlap / 2.0;
+                        for tile in 0..rows {
+                            let first = step_y * tile as f64 + overlap;
+                            let last =
+                                (step_y * tile as f64 + height - overlap).min(pattern_height);
+                            cross(cx, first + ARROW_SIZE);
+                            cross(cx, last.max(first + ARROW_SIZE) - ARROW_SIZE);
+                        }
+                    }
+                    for seam in 1..rows {
+                        let cy = step_y * seam as f64 + overlap / 2.0;
+                        for tile in 0..columns {
+                            let first = step_x * tile as f64 + overlap;
+                            let last = (step_x * tile as f64 + width - overlap).min(pattern_width);
+                            cross(first + ARROW_SIZE, cy);
+                            cross(last.max(first + ARROW_SIZE) - ARROW_SIZE, cy);
+                        }
+                    }
+                    context.stroke();
+                }
+                context.restore();
+
+                context.save();
+                if style.apply(&context, LineKind::Dimensions) {
+                    context.rectangle(x, y, width, height);
+                    context.stroke();
+                    style.select_font(&context);
+                    let baseline = page_height - y / 2.0;
+                    context.move_to(x, baseline);
+                    context.show_text(&format!(
+                        "Tile {} - page {} of {}, full size, {:.0}mm overlap",
+                        Tiling::name(row, column),
+                        row * columns + column + 1,
+                        rows * columns,
+                        TILE_OVERLAP * 1000.0
+                    ));
+                    let bar = points(Length::from_meter(SCALE_BAR), 1.0);
+                    let end = x + width;
+                    context.move_to(end - bar, baseline);
+                    context.line_to(end, baseline);
+                    context.move_to(end - bar, baseline - 3.0);
+                    context.line_to(end - bar, baseline + 3.0);
+                    context.move_to(end, baseline - 3.0);
+                    context.line_to(end, baseline + 3.0);
+                    context.stroke();
+                    let label = format!("{:.0}mm", SCALE_BAR * 1000.0);
+                    let extents = context.text_extents(&label);
+                    context.move_to(end - bar / 2.0 - extents.width / 2.0, baseline - 3.0);
+                    context.show_text(&label);
+                }
+                context.restore();
+                context.show_page();
+            }
+        }
+    }
+
+    /// Print the tiles to a PDF file, one page each.
+    pub fn to_pdf<P: AsRef<Path>>(
+        &self,
+        path: P,
+        draw: &dyn Fn(&Context),
+        style: &DrawingStyle,
+    ) -> Result<(), DrawingError> {
+        fs::write(path, self.to_pdf_bytes(draw, style)?)?;
+        Ok(())
+    }
+
+    /// Print the tiles to PDF bytes in memory.
+    pub fn to_pdf_bytes(
+        &self,
+        draw: &dyn Fn(&Context),
+        style: &DrawingStyle,
+    ) -> Result<Vec<u8>, DrawingError> {
+        let (width, height) = self.sheet.size();
+        let surface = PdfSurface::for_stream(width, height, Vec::<u8>::new())?;
+        self.render_pages(&surface, draw, style);
+        let stream = surface
+            .finish_output_stream()
+            .map_err(|err| DrawingError::Io(err.error))?;
+        Ok(*stream
+            .downcast::<Vec<u8>>()
+            .map_err(|_| io::Error::other("unexpected drawing stream"))?)
+    }
+}
+
+/// Room around a full size pattern (points).
+const PATTERN_PADDING: f64 = 10.0;
+
+/// Full size developed panel tiled across pages, its outline and the station lines, as flat
+/// as laid out, the lower edge at the bottom, the outline a pattern line to cut to.
+pub fn panel_pattern<P: AsRef<Path>>(
+    path: P,
+    paper: PaperSize,
+    orientation: Orientation,
+    panel: &FlatPanel,
+    style: &DrawingStyle,
+) -> Result<Tiling, DrawingError> {
+    let outline = panel.outline();
+    let bounds = outline.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        |(left, right, top), (x, y)| {
+            (
+                left.min(x.to_meter()),
+                right.max(x.to_meter()),
+                top.max(y.to_meter()),
+            )
+        },
+    );
+    let (left, right, top) = bounds;
+    let (_, width) = panel.extent();
+    let padding = PATTERN_PADDING / POINTS_PER_METER;
+    let tiling = Tiling::new(
+        paper,
+        orientation,
+        Length::from_meter(right - left + 2.0 * padding),
+        Length::from_meter(width.to_meter() + 2.0 * padding),
+    );
+    let point = |(x, y): (Length, Length)| {
+        (
+            (x.to_meter() - left) * POINTS_PER_METER + PATTERN_PADDING,
+            (top - y.to_meter()) * POINTS_PER_METER + PATTERN_PADDING,
+        )
+    };
+    let draw = |context: &Context| {
+        if style.apply(context, LineKind::Pattern) {
+            let outline: Vec<(f64, f64)> = panel.outline().into_iter().map(point).collect();
+            polyline(context, &outline);
+            context.close_path();
+            context.stroke();
+        }
+        if style.apply(context, LineKind::Construction) {
+            context.set_dash(&GRID_DASH, 0.0);
+            for (lower, upper) in panel.station_lines() {
+                let (a, b) = (point(lower), point(upper));
+                context.move_to(a.0, a.1);
+                context.line_to(b.0, b.1);
+            }
+            context.stroke();
+        }
+    };
+    tiling.to_pdf(path, &draw, style)?;
+    Ok(tiling)
+}
+
+/// Full size foil template at a chord tiled across pages, the chord along x, a pattern line.
+pub fn foil_pattern<P: AsRef<Path>>(
+    path: P,
+    paper: PaperSize,
+    orientation: Orientation,
+    foil: &Foil,
+    chord: Length,
+    style: &DrawingStyle,
+) -> Result<Tiling, DrawingError> {
+    let (low, high) = foil.depth(chord);
+    let (low, high) = (low.to_meter(), high.to_meter());
+    let padding = PATTERN_PADDING / POINTS_PER_METER;
+    // Room under the lower side for the label of the template.
+    let label = 2.0 * LABEL_SIZE / POINTS_PER_METER;
+    let tiling = Tiling::new(
+        paper,
+        orientation,
+        Length::from_meter(chord.to_meter() + 2.0 * padding),
+        Length::from_meter(high - low + 2.0 * padding + label),
+    );
+    let draw = |context: &Context| {
+        if !style.apply(context, LineKind::Pattern) {
+            return;
+        }
+        style.select_font(context);
+        context.translate(PATTERN_PADDING, PATTERN_PADDING + high * POINTS_PER_METER);
+        foil.draw(context, chord);
+    };
+    tiling.to_pdf(path, &draw, style)?;
+    Ok(tiling)
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn drawing() {
+        let lines = |context: &Context| {
+            context.move_to(10.0, 10.0);
+            context.line_to(90.0, 10.0);
+            context.line_to(90.0, 90.0);
+            context.stroke();
+        };
+        let path =
+            std::env::temp_dir().join(format!("boat_plan_drawing_{}.pdf", std::process::id()));
+        to_pdf(&path, 100.0, 100.0, lines).unwrap();
+        let file = std::fs::read(&path).unwrap();
+        assert!(file.starts_with(b"%PDF"));
+        std::fs::remove_file(&path).unwrap();
+
+        let bytes = to_pdf_bytes(100.0, 100.0, lines).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+
+        let mut image = to_image(100, 100, lines).unwrap();
+        assert_eq!(image.get_width(), 100);
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        // White background, black line along the top.
+        assert_eq!(
+            data[50 * stride + 50 * 4..50 * stride + 50 * 4 + 3],
+            [255, 255, 255]
+        );
+        assert_eq!(
+            data[10 * stride + 50 * 4..10 * stride + 50 * 4 + 3],
+            [0, 0, 0]
+        );
+
+        // The same drawing on every backend.
+        let frame = |context: &Context, width: f64, height: f64| {
+            context.rectangle(1.0, 1.0, width - 2.0, height - 2.0);
+            context.stroke();
+        };
+        let mut targets: Vec<(Box<dyn DrawTarget>, &[u8])> = vec![
+            (Box::new(PdfTarget::memory(100.0, 50.0)), b"%PDF"),
+            (Box::new(SvgTarget::memory(100.0, 50.0)), b"<?xml"),
+            (Box::new(PngTarget::memory(100.0, 50.0)), b"\x89PNG"),
+        ];
+        for (target, magic) in targets.iter_mut() {
+            assert_eq!(target.size(), (100.0, 50.0));
+            target.render(&frame).unwrap();
+            assert!(target.bytes().unwrap().starts_with(magic));
+        }
+        let path =
+            std::env::temp_dir().join(format!("boat_plan_drawing_{}.svg", std::process::id()));
+        let mut svg = SvgTarget::file(&path, 100.0, 50.0);
+        svg.render(&frame).unwrap();
+        assert!(svg.bytes().is_none());
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<svg"));
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = std::env::temp_dir()
+            .join("boat_plan_missing")
+            .join("out.pdf");
+        assert!(to_pdf(&missing, 100.0, 100.0, lines).is_err());
+
+        // Format by the extension.
+        assert_eq!(FileFormat::from_path("plan.SVG"), Some(FileFormat::Svg));
+        assert_eq!(FileFormat::from_path("plan"), None);
+        assert!(file_target("plan.dwg", 100.0, 50.0).is_err());
+        let path =
+            std::env::temp_dir().join(format!("boat_plan_target_{}.svg", std::process::id()));
+        let mut target = file_target(&path, 100.0, 50.0).unwrap();
+        target.render(&frame).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        assert!(svg.contains(r#"width="100pt" height="50pt""#));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Probe of the pixels of an image, true when the pixel or one around it is dark.
+    #[allow(dead_code)]
+    fn dark_probe(image: &mut ImageSurface) -> impl Fn(usize, usize) -> bool + '_ {
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        move |x, y| (x - 1..=x + 1).any(|x| (y - 1..=y + 1).any(|y| data[y * stride + x * 4] < 224))
+    }
+
+    /// Flaring hull, the widest station forward of amidships.
+    #[allow(dead_code)]
+    fn flaring_hull() -> Hull {
+        let m = Length::from_meter;
+        let sections = [(0.0, 0.2), (1.0, 0.8), (2.0, 1.0), (3.0, 0.9), (4.0, 0.7)]
+            .iter()
+            .map(|&(x, y)| {
+                crate::section::Section::new(
+                    m(x),
+                    &[(m(0.0), m(0.0)), (m(y), m(0.5)), (m(y * 1.1), m(1.0))],
+                    crate::spline::Interpolation::Linear,
+                )
+                .unwrap()
+            })
+            .collect();
+        Hull::new(sections, crate::spline::Interpolation::Linear).unwrap()
+    }
+
+    #[test]
+    fn body_plan_view() {
+        let m = Length::from_meter;
+        let hull = flaring_hull();
+        // At 1:50.
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        let mut image = to_image(400, 400, |context| {
+            context.translate(200.0, 350.0);
+            body_plan(
+                context,
+                &hull,
+                0.02,
+                &Grid::new(&hull, &[m(0.5)], &[]),
+                &DrawingStyle::new(),
+            );
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        // Station 2 forward on the right at the 0.5m waterline, 1.0m out, not on the left.
+        let y = 350 - points(0.5);
+        assert!(dark(200 + points(1.0), y));
+        assert!(!dark(200 - points(1.0) - 1, y - 3));
+        // Station 3 aft mirrored on the left, 0.9m out.
+        assert!(dark(200 - points(0.9), y));
+        // Centerline.
+        assert!(dark(200, 350 - points(0.7)));
+    }
+
+    #[test]
+    fn drawing_style() {
+        let mut style = DrawingStyle::new();
+        assert_eq!(style.mode(), ColorMode::Monochrome);
+        assert_eq!(style.line_width(LineKind::Outline), 1.0);
+        assert_eq!(style.line_width(LineKind::Waterlines), 0.5);
+        assert_eq!(style.line_width(LineKind::Spars), 1.5);
+        assert_eq!(style.line_width(LineKind::Grid), 0.5);
+        assert_eq!(LineKind::Sails.layer(), Layer::Rig);
+        // Monochrome draws the layer colors black.
+        let mut layers = Layers::new();
+        layers.set_color(Layer::Hull, (0.0, 0.0, 1.0));
+        style.set_layers(layers);
+        assert_eq!(style.color(Layer::Hull), (0.0, 0.0, 0.0));
+        style.set_mode(ColorMode::Color);
+        assert_eq!(style.color(Layer::Hull), (0.0, 0.0, 1.0));
+        style.set_weight(LineKind::Stations, 2.0);
+        assert_eq!(style.line_width(LineKind::Stations), 2.0);
+
+        let traditional = DrawingStyle::traditional();
+        assert_eq!(traditional.font(), "Serif");
+        assert!(
+            traditional.line_width(LineKind::Outline) > traditional.line_width(LineKind::Stations)
+        );
+        assert!(
+            traditional.line_width(LineKind::Grid) < DrawingStyle::new().line_width(LineKind::Grid)
+        );
+        let screen = DrawingStyle::screen();
+        assert_eq!(screen.mode(), ColorMode::Color);
+        assert_eq!(screen.label_size(), 10.0);
+        assert_ne!(screen.color(Layer::Hull), screen.color(Layer::Rig));
+
+        // Wider labels in the larger font, in the font of the style.
+        let surface = ImageSurface::create(Format::ARgb32, 10, 10).unwrap();
+        let context = Context::new(&surface);
+        let width = |style: &DrawingStyle| {
+            style.select_font(&context);
+            assert_eq!(font_size(&context), style.label_size());
+            context.text_extents("WL 0.50").x_advance
+        };
+        assert!(width(&screen) > width(&DrawingStyle::new()));
+    }
+
+    #[test]
+    fn hatching_spacing() {
+        // A vanishing spacing draws at the minimum, dark all across, and returns.
+        let square = [(2.0, 2.0), (8.0, 2.0), (8.0, 8.0), (2.0, 8.0)];
+        let mut image = to_image(10, 10, |context| {
+            context.set_line_width(1.0);
+            hatch(
+                context,
+                &square,
+                Hatch::Lines {
+                    angle: 0.0,
+                    spacing: 1e-12,
+                },
+            );
+        })
+        .unwrap();
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        assert!((3..7).all(|y| data[y * stride + 5 * 4] < 128));
+        assert_eq!(data[stride + 5 * 4], 255);
+    }
+
+    #[test]
+    fn hatching() {
+        let square = [(20.0, 20.0), (80.0, 20.0), (80.0, 80.0), (20.0, 80.0)];
+        let mut image = to_image(100, 200, |context| {
+            context.set_line_width(1.0);
+            hatch(context, &square, Hatch::SECTION);
+            context.translate(0.0, 100.0);
+            hatch(context, &square, Hatch::UNDERWATER);
+        })
+        .unwrap();
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        let value = |x: usize, y: usize| data[y * stride + x * 4];
+        // Lines across the square, 4 points apart, none outside.
+        let row: Vec<bool> = (20..80).map(|x| value(x, 50) < 128).collect();
+        let lines = row.windows(2).filter(|w| w[1] && !w[0]).count();
+        assert!((lines as f64 - 60.0 / (4.0 * std::f64::consts::SQRT_2)).abs() <= 2.0);
+        assert!((0..100).all(|x| value(x, 10) == 255 && value(x, 90) == 255));
+        // Even light fill.
+        assert_eq!(value(50, 150), 217);
+        assert_eq!(value(10, 150), 255);
+    }
+
+    #[test]
+    fn layers() {
+        let m = Length::from_meter;
+        let mut layers = Layers::new();
+        assert_eq!(layers.style(Layer::Hull).line_width, 1.0);
+        assert_eq!(layers.style(Layer::Grid).line_width, 0.5);
+        layers.set_color(Layer::Hull, (1.0, 0.0, 0.0));
+        layers.set_line_width(Layer::Hull, 2.0);
+        let grid_only = Layers::only(&[Layer::Grid]);
+        assert!(grid_only.is_visible(Layer::Grid));
+        assert!(!grid_only.is_visible(Layer::Hull));
+        assert_eq!(Layer::Annotations.to_string(), "Annotations");
+
+        let hull = flaring_hull();
+        let grid = Grid::new(&hull, &[m(0.5)], &[]);
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        let render = |layers: &Layers| {
+            let mut style = DrawingStyle::new();
+            style.set_mode(ColorMode::Color);
+            style.set_layers(layers.clone());
+            to_image(400, 400, |context| {
+                context.translate(200.0, 350.0);
+                body_plan(context, &hull, 0.02, &grid, &style);
+            })
+            .unwrap()
+        };
+        // Station 2 forward halfway up the topside, 1.05m out, and the centerline.
+        let (x, y) = (200 + points(1.05), 350 - points(0.75));
+        let (cx, cy) = (200, 350 - points(0.7));
+        for &(layers, station) in &[(&layers, true), (&grid_only, false)] {
+            let mut image = render(layers);
+            let stride = image.get_stride() as usize;
+            let data = image.get_data().unwrap();
+            // Blue and red of the pixels around, BGRA in memory.
+            let around = |x: usize, y: usize, channel: usize| {
+                (x - 1..=x + 1)
+                    .flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y)))
+                    .map(|(x, y)| data[y * stride + x * 4 + channel])
+                    .collect::<Vec<u8>>()
+            };
+            let dark = |x: usize, y: usize| around(x, y, 0).iter().any(|&v| v < 224);
+            assert_eq!(dark(x, y), station);
+            assert!(dark(cx, cy));
+            if station {
+                // Red, the blue gone and the red kept.
+                assert!(around(x, y, 0)
+                    .iter()
+                    .zip(around(x, y, 2))
+                    .any(|(&b, r)| b < 64 && r > 192));
+            }
+        }
+        let mut hidden = Layers::new();
+        hidden.set_visible(Layer::Grid, false);
+        let mut image = render(&hidden);
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        assert!(data[cy * stride + cx * 4] > 224);
+    }
+
+    #[test]
+    fn profile_view() {
+        use crate::appendage::{Keel, KeelType};
+        use crate::si::Weight;
+
+        let m = Length::from_meter;
+        let hull = flaring_hull();
+        let mut keel = Keel::new(
+            KeelType::Fin,
+            m(1.0),
+            m(0.6),
+            m(1.0),
+            Weight::from_kilogram(500.0),
+        );
+        keel.set_leading_edge(m(1.5));
+        let keel = keel.geometry().unwrap();
+
+        // At 1:50, the forward perpendicular 20 points in and the baseline 100 points up.
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        let mut image = to_image(400, 300, |context| {
+            context.translate(20.0, 200.0);
+            profile(
+                context,
+                &hull,
+                0.02,
+                &Grid::new(&hull, &[m(0.5)], &[m(0.4)]),
+                &[("Keel", keel)],
+                &DrawingStyle::new(),
+            );
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        // Sheer 1m up amidships, the stem and transom, the keel tip 1m down.
+        assert!(dark(20 + points(2.0), 200 - points(1.0)));
+        assert!(dark(20, 200 - points(0.5)));
+        assert!(dark(20 + points(4.0), 200 - points(0.5)));
+        assert!(dark(20 + points(1.8), 200 + points(1.0)));
+        assert!(!dark(20 + points(2.5), 200 - points(0.75)));
+        // The keel label right of the tip.
+        assert!((0..20).any(|x| dark(24 + points(2.1) + x, 200 + points(1.0) - 3)));
+        assert!(!(0..20).any(|x| dark(24 + points(2.1) + x, 200 + points(1.0) + 6)));
+    }
+
+    #[test]
+    fn half_breadth_view() {
+        let m = Length::from_meter;
+        let hull = flaring_hull();
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        let mut image = to_image(400, 200, |context| {
+            context.translate(20.0, 150.0);
+            half_breadth_plan(
+                context,
+                &hull,
+                0.02,
+                &Grid::new(&hull, &[m(0.5)], &[m(0.4)]),
+                &DrawingStyle::new(),
+            );
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        let half_breadth = |z: f64| hull.half_breadth(m(2.0), m(z)).unwrap().to_meter();
+        // Deck and waterline amidships, the deck beyond the waterline on a flaring hull.
+        assert!(half_breadth(1.0) > half_breadth(0.5));
+        assert!(dark(20 + points(2.0), 150 - points(half_breadth(1.0))));
+        assert!(dark(20 + points(2.0), 150 - points(half_breadth(0.5))));
+        assert!(dark(20 + points(2.0), 150 - points(0.4)));
+        assert!(dark(20 + points(2.0), 150));
+    }
+
+    #[test]
+    fn lines_plan_sheet() {
+        let m = Length::from_meter;
+        let hull = flaring_hull();
+        let mut boat = Boat::new("Skiff".to_string());
+        boat.set_loa(m(4.0));
+        let sheet = Sheet::new(
+            PaperSize::A4,
+            Orientation::Landscape,
+            DrawingScale::OneTo(50.0),
+        );
+        let title =
+            TitleBlock::from_boat(&boat, &sheet, "2020-11-21".to_string(), "LP-1".to_string());
+        let lines = title.lines();
+        assert_eq!(lines[0], "Skiff");
+        assert_eq!(lines[1], "LOA 4.00m  DWL 3.80m");
+        assert_eq!(lines[4], "Scale 1:50  Date 2020-11-21");
+        assert_eq!(lines[5], "Drawing LP-1");
+
+        let mut image = to_image(842, 595, |context| {
+            let grid = Grid::new(&hull, &[m(0.5)], &[m(0.4)]);
+            lines_plan(
+                context,
+                &sheet,
+                &hull,
+                &grid,
+                &[],
+                &DrawingStyle::new(),
+                &title,
+            );
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        // Border 10mm in, title block and the stem of the profile from the baseline to the
+        // sheer.
+        assert!(dark(28, 300));
+        assert!(dark(842 - 28 - 200, 595 - 28 - 84));
+        let baseline = 28 + 20 + points(1.0);
+        assert!(dark(48, baseline));
+        assert!(dark(48, baseline - points(0.5)));
+        assert!(!dark(38, baseline - points(0.5)));
+
+        // No border and no title block with the annotations layer hidden.
+        let mut hidden = DrawingStyle::new();
+        hidden.set_layers(Layers::only(&[Layer::Grid, Layer::Hull]));
+        let mut image = to_image(842, 595, |context| {
+            let grid = Grid::new(&hull, &[m(0.5)], &[m(0.4)]);
+            lines_plan(context, &sheet, &hull, &grid, &[], &hidden, &title);
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        assert!(!dark(842 - 28 - 200, 595 - 28 - 84));
+        assert!(!dark(28, 300));
+    }
+
+    #[test]
+    fn annotation() {
+        let mut image = to_image(300, 200, |context| {
+            let style = DrawingStyle::new();
+            style.apply(context, LineKind::Dimensions);
+            style.select_font(context);
+            // 4m at 1:50, 40 points above.
+            let length = 4.0 * 0.02 * POINTS_PER_METER;
+            linear_dimension(
+                context,
+                (20.0, 100.0),
+                (20.0 + length, 100.0),
+                40.0,
+                0.02,
+                UnitSystem::Metric,
+            );
+            leader(context, (100.0, 150.0), (140.0, 180.0), "Chainplate");
+            note(context, (200.0, 20.0), &["Scantlings", "ISO 12215"]);
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        // Extension lines clear of the points, the dimension line and arrow heads.
+        assert!(!dark(20, 99));
+        assert!(dark(20, 80));
+        assert!(dark(247, 80));
+        assert!(dark(24, 60));
+        assert!(dark(60, 60));
+        assert!(!dark(60, 70));
+        // Leader line and landing.
+        assert!(dark(120, 165));
+        assert!(dark(144, 180));
+    }
+
+    #[test]
+    fn drawing_scale() {
+        assert_eq!(DrawingScale::one_to(50.0), Some(DrawingScale::OneTo(50.0)));
+        assert_eq!(DrawingScale::OneTo(50.0).factor(), 0.02);
+        assert_eq!(DrawingScale::OneTo(50.0).to_string(), "1:50");
+        for &reduction in &[0.0, -10.0, f64::INFINITY, f64::NAN] {
+            assert!(DrawingScale::one_to(reduction).is_none());
+            assert_eq!(DrawingScale::OneTo(reduction).factor(), 1.0);
+        }
+    }
+
+    #[test]
+    fn sheet() {
+        let mut sheet = Sheet::new(
+            PaperSize::A3,
+            Orientation::Landscape,
+            DrawingScale::OneToTen,
+        );
+        let (width, height) = sheet.size();
+        assert_eq!(format!("{:.1} {:.1}", width, height), "1190.6 841.9");
+        assert_eq!(sheet.scale().to_string(), "1:10");
+        assert_eq!(DrawingScale::FullSize.to_string(), "1:1");
+        // 1m at 1:10 is 100mm.
+        assert_eq!(
+            format!("{:.3}", sheet.to_points(Length::from_meter(1.0))),
+            format!("{:.3}", points(Length::from_millimeter(100.0), 1.0))
+        );
+        let m = Length::from_meter;
+        assert!(sheet.fits(m(3.5), m(2.0)));
+        assert!(!sheet.fits(m(3.5), m(3.0)));
+        sheet.set_scale(DrawingScale::OneToTwenty);
+        assert!(sheet.fits(m(3.5), m(3.0)));
+        let portrait = Sheet::new(
+            PaperSize::Letter,
+            Orientation::Portrait,
+            DrawingScale::FullSize,
+        );
+        assert_eq!(
+            format!("{:.0} {:.0}", portrait.size().0, portrait.size().1),
+            "612 792"
+        );
+        let (x, _, area_width, _) = portrait.drawing_area();
+        assert_eq!(format!("{:.2} {:.2}", x, area_width), "28.35 555.31");
+    }
+
+    #[test]
+    fn grid() {
+        let m = Length::from_meter;
+        let hull = flaring_hull();
+        let grid = Grid::new(&hull, &[m(0.5)], &[]);
+        assert_eq!(grid.stations().len(), 5);
+        assert_eq!(format!("{:.2}", grid.height().to_meter()), "1.00");
+        assert_eq!(format!("{:.2}", grid.half_breadth().to_meter()), "1.10");
+        let mut grid = Grid::spaced(&hull, m(0.5), m(0.25), m(0.3));
+        assert_eq!(grid.stations().len(), 9);
+        let meters = |values: &[Length]| {
+            values
+                .iter()
+                .map(|v| format!("{:.2}", v.to_meter()))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        assert_eq!(meters(grid.waterlines()), "0.25 0.50 0.75 1.00");
+        assert_eq!(meters(grid.buttocks()), "0.30 0.60 0.90");
+
+        // Station 1 in the profile at 0.5m, without the waterline labels.
+        grid.set_labels(false);
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        let mut image = to_image(300, 100, |context| {
+            context.translate(20.0, 80.0);
+            grid.draw(context, View::Profile, 0.02, &DrawingStyle::new());
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        assert!(dark(20 + points(0.5), 78));
+        assert!(dark(20 + points(0.25), 80));
+        assert!(!dark(20 + points(4.0) + 10, 80 - points(0.5)));
+    }
+
+    #[test]
+    fn png_resolution() {
+        let frame = |context: &Context, width: f64, height: f64| {
+            context.rectangle(10.0, 10.0, width - 20.0, height - 20.0);
+            context.fill();
+        };
+        let mut target = PngTarget::memory(100.0, 50.0);
+        target.set_dpi(144.0);
+        assert_eq!(target.pixel_size(), (200, 100));
+        assert_eq!(target.size(), (100.0, 50.0));
+        target.render(&frame).unwrap();
+        let image =
+            ImageSurface::create_from_png(&mut std::io::Cursor::new(target.bytes().unwrap()))
+                .unwrap();
+        assert_eq!((image.get_width(), image.get_height()), (200, 100));
+        let mut image = image;
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        // The 10 points border is 20 pixels wide.
+        assert_eq!(data[50 * stride + 19 * 4], 255);
+        assert_eq!(data[50 * stride + 21 * 4], 0);
+
+        // A sheet for a forum post 1200 pixels wide.
+        let sheet = Sheet::new(
+            PaperSize::A4,
+            Orientation::Landscape,
+            DrawingScale::OneToTen,
+        );
+        let mut png = sheet.png("sheet.png", 150.0);
+        assert_eq!(png.pixel_size(), (1754, 1240));
+        png.set_pixel_width(1200);
+        assert_eq!(png.pixel_size(), (1200, 848));
+    }
+
+    #[test]
+    fn tiling() {
+        let m = Length::from_meter;
+        // A4 portrait, 190 by 277mm inside the margin, 180 by 267mm advance.
+        let tiling = Tiling::new(PaperSize::A4, Orientation::Portrait, m(2.0), m(0.6));
+        assert_eq!((tiling.columns(), tiling.rows()), (12, 3));
+        assert_eq!(tiling.pages(), 36);
+        let small = Tiling::new(PaperSize::A4, Orientation::Portrait, m(0.19), m(0.1));
+        assert_eq!(small.pages(), 1);
+        assert_eq!(Tiling::name(1, 2), "B3");
+        assert_eq!(Tiling::name(26, 0), "AA1");
+        assert_eq!(Tiling::name(0, 10), "A11");
+        assert_eq!(Tiling::name(702, 0), "AAA1");
+
+        let style = DrawingStyle::new();
+        let bytes = tiling
+            .to_pdf_bytes(
+                &|context| {
+                    context.rectangle(0.0, 0.0, 2.0 * POINTS_PER_METER, 0.6 * POINTS_PER_METER);
+                    context.stroke();
+                },
+                &style,
+            )
+            .unwrap();
+        let text = String::from_utf8_lossy(&bytes);
+        assert_eq!(text.matches("/Type /Page ").count(), 36);
+
+        // The scale bar on the bottom right of the page, gone with the annotations layer hidden.
+        let render = |style: &DrawingStyle| {
+            let mut image = to_image(595, 842, |_| {}).unwrap();
+            small.render_pages(&image, &|_| {}, style);
+            let (x, y, width, _) = small.sheet().drawing_area();
+            let dark = dark_probe(&mut image);
+            dark((x + width) as usize, (842.0 - y / 2.0) as usize)
+        };
+        assert!(render(&style));
+        let mut hidden = DrawingStyle::new();
+        hidden.set_layers(Layers::only(&[Layer::Hull]));
+        assert!(!render(&hidden));
+
+        let foil = Foil::naca4(0.0, 0.0, 0.12);
+        let path =
+            std::env::temp_dir().join(format!("boat_plan_foil_pattern_{}.pdf", std::process::id()));
+        let tiling = foil_pattern(
+            &path,
+            PaperSize::Letter,
+            Orientation::Landscape,
+            &foil,
+            m(0.4),
+            &DrawingStyle::new(),
+        )
+        .unwrap();
+        assert_eq!(tiling.pages(), 2);
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+        std::fs::remove_file(&path).unwrap();
+
+        // A flat bottom panel 4m long and 0.6m wide across A3 landscape pages.
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.2), 0.6, (0.6, 1.0));
+        let development = crate::development::Development::new(&hull, &[1]);
+        let path = std::env::temp_dir().join(format!(
+            "boat_plan_panel_pattern_{}.pdf",
+            std::process::id()
+        ));
+        let tiling = panel_pattern(
+            &path,
+            PaperSize::A3,
+            Orientation::Landscape,
+            &development.panels()[0],
+            &DrawingStyle::new(),
+        )
+        .unwrap();
+        assert_eq!((tiling.columns(), tiling.rows()), (11, 3));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn sail_plan_view() {
+        let m = Length::from_meter;
+        let mut rig = Rig::new(m(6.0), m(2.0), m(5.5), m(2.5));
+        let mut image = to_image(300, 300, |context| {
+            sail_plan(
+                context,
+                &rig,
+                None,
+                0.02,
+                UnitSystem::Metric,
+                &DrawingStyle::new(),
+            );
+        })
+        .unwrap();
+        // Nothing without the placement.
+        assert!(image.get_data().unwrap().iter().all(|&v| v == 255));
+
+        rig.set_placement(RigPlacement {
+            mast: m(1.5),
+            sheer: m(0.6),
+            boom: m(1.2),
+            mizzen: None,
+        });
+        let hull = flaring_hull();
+        let points = |meter: f64| (meter * 0.02 * POINTS_PER_METER).round() as usize;
+        // The FP 60 points in and the DWL 40 points up.
+        let mut image = to_image(400, 500, |context| {
+            context.translate(60.0, 460.0);
+            sail_plan(
+                context,
+                &rig,
+                Some((&hull, m(0.3))),
+                0.02,
+                UnitSystem::Metric,
+                &DrawingStyle::new(),
+            );
+        })
+        .unwrap();
+        let dark = dark_probe(&mut image);
+        // Mast at the masthead, boom end, forestay halfway, the sheer amidships and the CE.
+        assert!(dark(60 + points(1.5), 460 - points(6.5)));
+        assert!(dark(60 + points(4.0), 460 - points(1.2)));
+        assert!(dark(60 + points(0.5), 460 - points(3.6)));
+        assert!(dark(60 + points(2.0), 460 - points(0.7)));
+        let ce = rig.center_of_effort().unwrap();
+        assert!(dark(
+            60 + points(ce.x.to_meter()),
+            460 - points(ce.z.to_meter())
+        ));
+        assert!(!dark(60 + points(3.0), 460 - points(5.0)));
+
+        let mut boat = Boat::new("Sloop".to_string());
+        boat.set_rig(rig);
+        let sheet = Sheet::new(
+            PaperSize::A4,
+            Orientation::Portrait,
+            DrawingScale::OneTo(50.0),
+        );
+        let title = TitleBlock::from_boat(&boat, &sheet, String::new(), "SP-1".to_string());
+        let bytes = to_pdf_bytes(595.0, 842.0, |context| {
+            sail_plan_sheet(
+                context,
+                &sheet,
+                &boat,
+                Some(&hull),
+                UnitSystem::Imperial,
+                &DrawingStyle::new(),
+                &title,
+            );
+        })
+        .unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn sail_plan_top() {
+        use crate::rig::{Mizzen, RigType};
+
+        let m = Length::from_meter;
+        let mut rig = Rig::new(m(6.0), m(2.0), m(5.5), m(2.5));
+        let mut place = RigPlacement {
+            mast: m(1.5),
+            sheer: m(0.6),
+            boom: m(1.2),
+            mizzen: Some(m(5.0)),
+        };
+        assert_eq!(format!("{:.1}", rig_top(&rig, &place)), "6.7");
+        // Taller mizzen, only counted on a ketch or yawl with its mast placed.
+        rig.set_mizzen(Mizzen {
+            p: m(7.0),
+            e: m(2.0),
+        });
+        assert_eq!(format!("{:.1}", rig_top(&rig, &place)), "6.7");
+        rig.set_rig_type(RigType::Yawl);
+        assert_eq!(format!("{:.1}", rig_top(&rig, &place)), "8.2");
+        place.mizzen = None;
+        assert_eq!(format!("{:.1}", rig_top(&rig, &place)), "6.7");
+    }
+
+    #[test]
+    fn offsets_table_sheet() {
+        let fie = OffsetUnits::FeetInchesEighths;
+        let text = "\
+            line, kind, position, 0-0-0, 5-0-0, 10-0-0\n\
+            Sheer, height, , 3-6-0, 3-0-4, 2-9-0\n\
+            Keel, height, , 2-0-0, 0-3-0, 0-0-0\n\
+            Sheer, half-breadth, , 0-0-0, 2-9-2+, 3-4-0\n\
+            WL 12, waterline, 1-0-0, -, 2-1-0, 2-8-0\n";
+        let table = OffsetsTable::parse_csv(text, fie).unwrap();
+        let rows = offset_rows(&table, fie);
+        // Stations, positions, two headings and four lines.
+        assert_eq!(rows.len(), 8);
+        assert_eq!(rows[1].1.as_ref().unwrap()[1], "5-0-0");
+        assert_eq!(rows[2], ("Heights above base".to_string(), None));
+        assert_eq!(rows[6].1.as_ref().unwrap()[1], "2-9-2+");
+        assert_eq!(rows[7].0, "WL 12 1-0-0");
+        assert_eq!(rows[7].1.as_ref().unwrap()[0], "-");
+        let mm = offset_rows(&table, OffsetUnits::Millimeter);
+        assert_eq!(mm[4].1.as_ref().unwrap()[1], "76");
+
+        let style = DrawingStyle::new();
+        let mut size = (0.0, 0.0);
+        let mut columns = (0.0, 0.0);
+        let mut image = to_image(300, 200, |context| {
+            size = offsets_table_size(context, &table, fie, &style);
+            columns = offset_columns(context, &rows, &style);
+            context.translate(10.0, 10.0);
+            offsets_table(context, &table, fie, &style);
+        })
+        .unwrap();
+        assert_eq!(size.1, 8.0 * TABLE_ROW);
+        let dark = dark_probe(&mut image);
+        // Top and right rules, and the column rules broken across the headings.
+        assert!(dark(10 + size.0 as usize / 2, 10));
+        assert!(dark(10 + size.0 as usize, 10 + size.1 as usize / 2));
+        let rule = 10 + (columns.0 + columns.1).round() as usize;
+        assert!(dark(rule, 16));
+        assert!(!dark(rule, 10 + (2.5 * TABLE_ROW) as usize));
+
+        let boat = Boat::new("Skiff".to_string());
+        let sheet = Sheet::new(
+            PaperSize::A4,
+            Orientation::Landscape,
+            DrawingScale::FullSize,
+        );
+        let title = TitleBlock::from_boat(&boat, &sheet, String::new(), "OT-1".to_string());
+        let bytes = to_pdf_bytes(842.0, 595.0, |context| {
+            offsets_sheet(context, &sheet, &table, fie, &style, &title);
+        })
+        .unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn particulars() {
+        use crate::hydrostatics::Water;
+
+        let m = Length::from_meter;
+        let mut boat = Boat::new("Skiff".to_string());
+        boat.set_loa(m(4.0));
+        boat.set_dwl(m(3.8));
+        boat.set_b_max(m(1.2));
+        boat.set_bwl(m(1.0));
+        boat.set_draft(m(0.15));
+        boat.set_displacement(Weight::from_kilogram(250.0));
+        let particulars = Particulars::from_boat(&boat, None, UnitSystem::Metric);
+        assert_eq!(particulars.groups().len(), 2);
+        assert_eq!(particulars.row("Dimensions", "LOA").unwrap()[1], "4.000m");
+        assert_eq!(
+            particulars.row("Dimensions", "Displacement").unwrap()[1],
+            "250kg"
+        );
+        let lb = particulars.row("Ratios", "L/B").unwrap();
+        assert_eq!(lb[1], "3.33");
+        assert_eq!(
+            lb[2],
+            Ratios::new(&boat).length_beam().character().to_string()
+        );
+        assert!(particulars.row("Ratios", "LCB").is_none());
+
+        let hull = flaring_hull();
+        let hydrostatics = Hydrostatics::new(&hull, m(0.3), Water::Salt);
+        let particulars = Particulars::from_boat(&boat, Some(&hydrostatics), UnitSystem::Imperial);
+        assert_eq!(particulars.groups().len(), 3);
+        assert_eq!(
+            particulars.row("Dimensions", "LOA").unwrap()[1],
+            "13' 1.5\""
+        );
+        let heading = &particulars.groups()[2].0;
+        assert!(heading.starts_with("Hydrostatics"));
+        assert!(particulars.row(heading, "Cb").is_some());
+        assert!(particulars.row(heading, "Immersion").unwrap()[1].ends_with("lb/in"));
+
+        let style = DrawingStyle::new();
+        let mut size = (0.0, 0.0);
+        let mut image = to_image(300, 600, |context| {
+            size = particulars.size(context, &style);
+            context.translate(10.0, 10.0);
+            particulars.draw(context, &style);
+        })
+        .unwrap();
+        let rows: usize = particulars.groups().iter().map(|(_, r)| r.len() + 2).sum();
+        assert_eq!(size.1, (rows - 1) as f64 * TABLE_ROW);
+        let dark = dark_probe(&mut image);
+        // Rule under the first heading, none in the gap before the second.
+        let rule = 10 + TABLE_ROW as usize;
+        assert!(dark(10 + size.0 as usize / 2, rule));
+        let gap = 10.0 + (particulars.groups()[0].1.len() + 1) as f64 * TABLE_ROW + TABLE_ROW / 2.0;
+        assert!(!dark(10 + size.0 as usize / 2, gap as usize));
+
+        // Nothing with the annotations layer hidden.
+        let mut hidden = DrawingStyle::new();
+        hidden.set_layers(Layers::only(&[Layer::Hull]));
+        let mut image = to_image(300, 600, |context| {
+            context.translate(10.0, 10.0);
+            particulars.draw(context, &hidden);
+        })
+        .unwrap();
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        assert!(!data.chunks(stride).any(|row| row.iter().any(|&v| v < 224)));
+
+        let sheet = Sheet::new(PaperSize::A4, Orientation::Portrait, DrawingScale::FullSize);
+        let title = TitleBlock::from_boat(&boat, &sheet, String::new(), "DP-1".to_string());
+        let bytes = to_pdf_bytes(595.0, 842.0, |context| {
+            particulars_sheet(context, &sheet, &particulars, &style, &title);
+        })
+        .unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn document() {
+        let m = Length::from_meter;
+        let hull = flaring_hull();
+        let mut boat = Boat::new("Skiff".to_string());
+        boat.set_loa(m(4.0));
+        let grid = Grid::new(&hull, &[m(0.5)], &[m(0.4)]);
+        let particulars = Particulars::from_boat(&boat, None, UnitSystem::Metric);
+        let mut document = Document::new(&boat, "2020-11-21".to_string(), "12".to_string());
+        let landscape = Sheet::new(
+            PaperSize::A4,
+            Orientation::Landscape,
+            DrawingScale::OneTo(50.0),
+        );
+        document.add_page(landscape, "Lines plan", |context, sheet, title| {
+            lines_plan(
+                context,
+                sheet,
+                &hull,
+                &grid,
+                &[],
+                &DrawingStyle::new(),
+                title,
+            );
+        });
+        let portrait = Sheet::new(PaperSize::A4, Orientation::Portrait, DrawingScale::FullSize);
+        document.add_page(portrait, "Particulars", |context, sheet, title| {
+            particulars_sheet(context, sheet, &particulars, &DrawingStyle::new(), title);
+        });
+        assert_eq!(document.count(), 2);
+        let title = document.title_block(1).unwrap();
+        assert_eq!(title.number(), "12-2");
+        assert_eq!(title.scale(), DrawingScale::FullSize);
+        assert_eq!(title.page(), Some(("Particulars", 2, 2)));
+        assert_eq!(title.lines().last().unwrap(), "Particulars  Sheet 2 of 2");
+        assert!(document.title_block(2).is_none());
+
+        let bytes = document.to_pdf_bytes().unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+        let text = String::from_utf8_lossy(&bytes);
+        assert_eq!(text.matches("/Type /Page ").count(), 2);
+        // Each page on its own paper.
+        assert!(text.contains("/MediaBox [ 0 0 841.889764 595.275591 ]"));
+        assert!(text.contains("/MediaBox [ 0 0 595.275591 841.889764 ]"));
+        let path =
+            std::env::temp_dir().join(format!("boat_plan_document_{}.pdf", std::process::id()));
+        document.to_pdf(&path).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
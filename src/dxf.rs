@@ -0,0 +1,443 @@
+use super::development::Development;
+use super::drawing::{Layer, LayerStyle, Layers};
+use super::hull::{Hull, HullPoint};
+use super::si::Length;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Drawing units per meter, the DXF in millimeters as most CAD and CNC cutting services expect.
+pub const DXF_UNITS_PER_METER: f64 = 1000.0;
+
+/// Gap between the developed panels laid out side by side (m).
+pub const PANEL_GAP: f64 = 0.1;
+
+/// RGB of the AutoCAD color indexes 1 to 9, the standard colors, 7 black on white.
+pub const ACI_COLORS: [(f64, f64, f64); 9] = [
+    (1.0, 0.0, 0.0),
+    (1.0, 1.0, 0.0),
+    (0.0, 1.0, 0.0),
+    (0.0, 1.0, 1.0),
+    (0.0, 0.0, 1.0),
+    (1.0, 0.0, 1.0),
+    (0.0, 0.0, 0.0),
+    (0.5, 0.5, 0.5),
+    (0.75, 0.75, 0.75),
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DXF
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// DXF layer, one per curve type and one per drawing layer.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum DxfLayer {
+    Stations,
+    Waterlines,
+    Buttocks,
+    Chines,
+    /// Keel and sheer lines.
+    Outline,
+    /// Outlines of the developed panels.
+    Panels,
+    /// Station marks of the developed panels.
+    PanelStations,
+    /// Lines of a drawing layer, under the layer name.
+    Drawing(Layer),
+}
+
+#[allow(dead_code)]
+impl DxfLayer {
+    pub const ALL: [DxfLayer; 13] = [
+        DxfLayer::Stations,
+        DxfLayer::Waterlines,
+        DxfLayer::Buttocks,
+        DxfLayer::Chines,
+        DxfLayer::Outline,
+        DxfLayer::Panels,
+        DxfLayer::PanelStations,
+        DxfLayer::Drawing(Layer::Grid),
+        DxfLayer::Drawing(Layer::Hull),
+        DxfLayer::Drawing(Layer::Appendages),
+        DxfLayer::Drawing(Layer::Rig),
+        DxfLayer::Drawing(Layer::Annotations),
+        DxfLayer::Drawing(Layer::Construction),
+    ];
+
+    /// Layer name in the DXF.
+    pub fn name(self) -> &'static str {
+        match self {
+            DxfLayer::Stations => "STATIONS",
+            DxfLayer::Waterlines => "WATERLINES",
+            DxfLayer::Buttocks => "BUTTOCKS",
+            DxfLayer::Chines => "CHINES",
+            DxfLayer::Outline => "OUTLINE",
+            DxfLayer::Panels => "PANELS",
+            DxfLayer::PanelStations => "PANEL_STATIONS",
+            DxfLayer::Drawing(Layer::Grid) => "GRID",
+            DxfLayer::Drawing(Layer::Hull) => "HULL",
+            DxfLayer::Drawing(Layer::Appendages) => "APPENDAGES",
+            DxfLayer::Drawing(Layer::Rig) => "RIG",
+            DxfLayer::Drawing(Layer::Annotations) => "ANNOTATIONS",
+            DxfLayer::Drawing(Layer::Construction) => "CONSTRUCTION",
+        }
+    }
+
+    /// AutoCAD color index.
+    pub fn color(self) -> u8 {
+        match self {
+            DxfLayer::Drawing(layer) => aci(&layer.default_style()) as u8,
+            DxfLayer::Stations => 1,
+            DxfLayer::Waterlines => 5,
+            DxfLayer::Buttocks => 3,
+            DxfLayer::Chines => 6,
+            DxfLayer::Outline => 7,
+            DxfLayer::Panels => 7,
+            DxfLayer::PanelStations => 8,
+        }
+    }
+
+    /// Drawing layer of the curves.
+    pub fn layer(self) -> Layer {
+        match self {
+            DxfLayer::PanelStations => Layer::Construction,
+            DxfLayer::Drawing(layer) => layer,
+            _ => Layer::Hull,
+        }
+    }
+}
+
+/// AutoCAD color index of a layer style, the standard color nearest to its color, negative
+/// for a hidden layer, the R12 mark of a layer turned off.
+pub fn aci(style: &LayerStyle) -> i16 {
+    let (r, g, b) = style.color;
+    let index = ACI_COLORS
+        .iter()
+        .map(|&(cr, cg, cb)| (r - cr).powi(2) + (g - cg).powi(2) + (b - cb).powi(2))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(7, |(i, _)| i as i16 + 1);
+    if style.visible {
+        index
+    } else {
+        -index
+    }
+}
+
+impl fmt::Display for DxfLayer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Polyline of a DXF, the points in meters.
+#[derive(Debug, Clone)]
+struct Polyline {
+    layer: DxfLayer,
+    points: Vec<[f64; 3]>,
+    closed: bool,
+    three_d: bool,
+}
+
+/// DXF
+///
+/// AutoCAD R12 DXF of polylines on layers, read by about every CAD program and CNC cutting
+/// service. R12 has no splines, the curves go as polylines through their sampled points. The
+/// hull lines as 3D polylines, x aft of the forward perpendicular, y the half-breadth and z up,
+/// and the developed panels as flat closed polylines, in DXF_UNITS_PER_METER. Each drawing layer
+/// has a DXF layer of its name for the lines added on it. With the drawing layers set, each DXF
+/// layer takes the color and the visibility of its drawing layer.
+#[derive(Debug, Clone, Default)]
+pub struct Dxf {
+    polylines: Vec<Polyline>,
+    layers: Option<Layers>,
+}
+
+#[allow(dead_code)]
+impl Dxf {
+    pub fn new() -> Dxf {
+        Dxf::default()
+    }
+
+    /// Lines of a hull, the stations, the waterlines and the buttocks sampled at a number of
+    /// points, the chines and the keel and sheer through the offset points of the sections.
+    pub fn from_hull(
+        hull: &Hull,
+        waterlines: &[Length],
+        buttocks: &[Length],
+        samples: usize,
+    ) -> Dxf {
+        let mut dxf = Dxf::new();
+        let sections = hull.sections();
+        for section in sections {
+            let points: Vec<HullPoint> = section
+                .points()
+                .into_iter()
+                .map(|(y, z)| HullPoint::new(section.position(), y, z))
+                .collect();
+            dxf.add_curve(DxfLayer::Stations, &points);
+        }
+        for &height in waterlines {
+            let points: Vec<HullPoint> = hull
+                .waterline(height, samples)
+                .into_iter()
+                .map(|(x, y)| HullPoint::new(x, y, height))
+                .collect();
+            dxf.add_curve(DxfLayer::Waterlines, &points);
+        }
+        for &offset in buttocks {
+            let points: Vec<HullPoint> = hull
+                .buttock(offset, samples)
+                .into_iter()
+                .map(|(x, z)| HullPoint::new(x, offset, z))
+                .collect();
+            dxf.add_curve(DxfLayer::Buttocks, &points);
+        }
+        // Longitudinal lines through the offset points all the sections have.
+        let count = sections.iter().map(|s| s.points().len()).min().unwrap_or(0);
+        for i in 0..count {
+            let points: Vec<HullPoint> = sections
+                .iter()
+                .map(|s| {
+                    let points = s.points();
+                    let (y, z) = if i + 1 == count {
+                        points[points.len() - 1]
+                    } else {
+                        points[i]
+                    };
+                    HullPoint::new(s.position(), y, z)
+                })
+                .collect();
+            let layer = if i == 0 || i + 1 == count {
+                DxfLayer::Outline
+            } else {
+                DxfLayer::Chines
+            };
+            dxf.add_curve(layer, &points);
+        }
+        dxf
+    }
+
+    /// 3D polyline through hull points, left out with less than two points.
+    pub fn add_curve(&mut self, layer: DxfLayer, points: &[HullPoint]) {
+        if points.len() < 2 {
+            return;
+        }
+        self.polylines.push(Polyline {
+            layer,
+            points: points
+                .iter()
+                .map(|p| [p.x.to_meter(), p.y.to_meter(), p.z.to_meter()])
+                .collect(),
+            closed: false,
+            three_d: true,
+        });
+    }
+
+    /// Flat polyline through (x, y) points, closed back to the first point or open.
+    pub fn add_polyline(&mut self, layer: DxfLayer, points: &[(Length, Length)], closed: bool) {
+        if points.len() < 2 {
+            return;
+        }
+        self.polylines.push(Polyline {
+            layer,
+            points: points
+                .iter()
+                .map(|(x, y)| [x.to_meter(), y.to_meter(), 0.0])
+                .collect(),
+            closed,
+            three_d: false,
+        });
+    }
+
+    /// Panels of a development side by side across y, PANEL_GAP apart, the outlines closed and
+    /// the station lines marked, ready to nest and cut.
+    pub fn add_development(&mut self, development: &Development) {
+        let mut cursor = 0.0;
+        for panel in development.panels() {
+            let outline = panel.outline();
+            let (low, high) = outline
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), (_, y)| {
+                    (low.min(y.to_meter()), high.max(y.to_meter()))
+                });
+            let shift =
+                |(x, y): (Length, Length)| (x, Length::from_meter(y.to_meter() - low + cursor));
+            let outline: Vec<(Length, Length)> = outline.into_iter().map(shift).collect();
+            self.add_polyline(DxfLayer::Panels, &outline, true);
+            for (lower, upper) in panel.station_lines() {
+                self.add_polyline(
+                    DxfLayer::PanelStations,
+                    &[shift(lower), shift(upper)],
+                    false,
+                );
+            }
+            cursor += high - low + PANEL_GAP;
+        }
+    }
+
+    pub fn layers(&self) -> Option<&Layers> {
+        self.layers.as_ref()
+    }
+    pub fn set_layers(&mut self, val: Layers) {
+        self.layers = Some(val);
+    }
+
+    /// AutoCAD color index of a layer, that of its drawing layer when the layers are set.
+    pub fn color(&self, layer: DxfLayer) -> i16 {
+        match &self.layers {
+            Some(layers) => aci(&layers.style(layer.layer())),
+            None => layer.color() as i16,
+        }
+    }
+
+    /// Number of polylines on a layer.
+    pub fn count(&self, layer: DxfLayer) -> usize {
+        self.polylines.iter().filter(|p| p.layer == layer).count()
+    }
+
+    /// Write the DXF.
+    pub fn write<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "{}", self)
+    }
+
+    /// Save the DXF to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+/// Group code and value pair.
+fn group(f: &mut fmt::Formatter, code: u16, value: &dyn fmt::Display) -> fmt::Result {
+    write!(f, "{:>3}\n{}\n", code, value)
+}
+
+/// Coordinate group codes of a point, 10, 20 and 30 for the first point of an entity.
+fn point(f: &mut fmt::Formatter, code: u16, p: [f64; 3]) -> fmt::Result {
+    for (i, v) in p.iter().enumerate() {
+        group(
+            f,
+            code + 10 * i as u16,
+            &format!("{:.4}", v * DXF_UNITS_PER_METER),
+        )?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Dxf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        group(f, 0, &"SECTION")?;
+        group(f, 2, &"HEADER")?;
+        group(f, 9, &"$ACADVER")?;
+        group(f, 1, &"AC1009")?;
+        group(f, 0, &"ENDSEC")?;
+
+        group(f, 0, &"SECTION")?;
+        group(f, 2, &"TABLES")?;
+        group(f, 0, &"TABLE")?;
+        group(f, 2, &"LAYER")?;
+        group(f, 70, &DxfLayer::ALL.len())?;
+        for layer in DxfLayer::ALL.iter() {
+            group(f, 0, &"LAYER")?;
+            group(f, 2, &layer.name())?;
+            group(f, 70, &0)?;
+            group(f, 62, &self.color(*layer))?;
+            group(f, 6, &"CONTINUOUS")?;
+        }
+        group(f, 0, &"ENDTAB")?;
+        group(f, 0, &"ENDSEC")?;
+
+        group(f, 0, &"SECTION")?;
+        group(f, 2, &"ENTITIES")?;
+        for polyline in &self.polylines {
+            let flags = if polyline.three_d { 8 } else { 0 } + if polyline.closed { 1 } else { 0 };
+            group(f, 0, &"POLYLINE")?;
+            group(f, 8, &polyline.layer.name())?;
+            group(f, 66, &1)?;
+            point(f, 10, [0.0, 0.0, 0.0])?;
+            group(f, 70, &flags)?;
+            for &p in &polyline.points {
+                group(f, 0, &"VERTEX")?;
+                group(f, 8, &polyline.layer.name())?;
+                point(f, 10, p)?;
+                if polyline.three_d {
+                    group(f, 70, &32)?;
+                }
+            }
+            group(f, 0, &"SEQEND")?;
+            group(f, 8, &polyline.layer.name())?;
+        }
+        group(f, 0, &"ENDSEC")?;
+        group(f, 0, &"EOF")
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn dxf() {
+        let m = Length::from_meter;
+        // Hard chine hull, the chine 0.6m out and 0.2m up.
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.2), 0.6, (0.6, 1.0));
+        let mut dxf = Dxf::from_hull(&hull, &[m(0.5)], &[m(0.3)], 20);
+        assert_eq!(dxf.count(DxfLayer::Stations), 5);
+        assert_eq!(dxf.count(DxfLayer::Waterlines), 1);
+        assert_eq!(dxf.count(DxfLayer::Buttocks), 1);
+        assert_eq!(dxf.count(DxfLayer::Chines), 1);
+        assert_eq!(dxf.count(DxfLayer::Outline), 2);
+        dxf.add_development(&Development::new(&hull, &[1]));
+        assert_eq!(dxf.count(DxfLayer::Panels), 2);
+        assert_eq!(dxf.count(DxfLayer::PanelStations), 10);
+
+        let text = dxf.to_string();
+        assert!(text.starts_with("  0\nSECTION\n  2\nHEADER\n  9\n$ACADVER\n  1\nAC1009\n"));
+        assert!(text.ends_with("  0\nEOF\n"));
+        assert!(text.contains("  2\nPANEL_STATIONS\n"));
+        assert_eq!(text.matches("\nPOLYLINE\n").count(), 22);
+        assert_eq!(
+            text.matches("\nPOLYLINE\n").count(),
+            text.matches("\nSEQEND\n").count()
+        );
+        // The chine at station 1 in millimeters.
+        assert!(text.contains(" 10\n1000.0000\n 20\n600.0000\n 30\n200.0000\n"));
+        let path = std::env::temp_dir().join(format!("boat_plan_hull_{}.dxf", std::process::id()));
+        dxf.save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+        std::fs::remove_file(&path).unwrap();
+
+        // Drawing layers, the hull lines blue and the construction lines turned off.
+        assert!(text.contains("  2\nSTATIONS\n 70\n0\n 62\n1\n"));
+        let mut layers = Layers::new();
+        layers.set_color(Layer::Hull, (0.1, 0.2, 0.9));
+        layers.set_visible(Layer::Construction, false);
+        dxf.set_layers(layers);
+        assert_eq!(dxf.color(DxfLayer::Stations), 5);
+        assert_eq!(dxf.color(DxfLayer::Panels), 5);
+        assert_eq!(dxf.color(DxfLayer::PanelStations), -7);
+        let text = dxf.to_string();
+        assert!(text.contains("  2\nSTATIONS\n 70\n0\n 62\n5\n"));
+        assert!(text.contains("  2\nPANEL_STATIONS\n 70\n0\n 62\n-7\n"));
+        assert!(text.contains("  2\nCONSTRUCTION\n 70\n0\n 62\n-7\n"));
+
+        // A baseline on the grid layer, under its name.
+        dxf.add_polyline(
+            DxfLayer::Drawing(Layer::Grid),
+            &[(m(0.0), m(0.0)), (m(4.0), m(0.0))],
+            false,
+        );
+        assert_eq!(dxf.count(DxfLayer::Drawing(Layer::Grid)), 1);
+        let text = dxf.to_string();
+        assert!(text.contains("  2\nGRID\n 70\n0\n 62\n7\n"));
+        assert!(text.contains("POLYLINE\n  8\nGRID\n"));
+
+        // No panic on a color that is not a number.
+        let mut layers = Layers::new();
+        layers.set_color(Layer::Rig, (f64::NAN, 0.0, 0.0));
+        assert!(aci(&layers.style(Layer::Rig)) > 0);
+    }
+}
@@ -0,0 +1,353 @@
+use super::hull::Hull;
+use super::si::Length;
+use super::spline::Spline;
+use std::fmt;
+
+/// Points sampled along a hull line for the fairness check.
+pub const FAIRNESS_SAMPLES: usize = 60;
+
+/// Curvature (1/m) below which the line is considered straight, sign changes are ignored.
+pub const CURVATURE_TOLERANCE: f64 = 1e-3;
+
+/// A point curvier than its neighbors and than the mean of the line by this factor is a spike.
+pub const SPIKE_FACTOR: f64 = 3.0;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FAIRNESS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// View of a hull line.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum View {
+    /// Position and height.
+    Profile,
+    /// Position and half-breadth.
+    Plan,
+}
+
+impl fmt::Display for View {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            View::Profile => write!(f, "Profile"),
+            View::Plan => write!(f, "Plan"),
+        }
+    }
+}
+
+/// Kind of unfair spot.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Unfairness {
+    /// The curvature changes sign, a hollow or a bump.
+    Inflection,
+    /// The curvature jumps at a point, a hard spot.
+    Spike,
+}
+
+impl fmt::Display for Unfairness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Unfairness::Inflection => write!(f, "Inflection"),
+            Unfairness::Spike => write!(f, "Spike"),
+        }
+    }
+}
+
+/// Point of a line and its curvature (1/m), positive turning counterclockwise.
+#[derive(Debug, Copy, Clone)]
+pub struct CurvaturePoint {
+    pub x: Length,
+    pub y: Length,
+    pub curvature: f64,
+}
+
+/// Unfair spot of a line, at a point of the line.
+#[derive(Debug, Copy, Clone)]
+pub struct UnfairSpot {
+    pub x: Length,
+    pub y: Length,
+    pub kind: Unfairness,
+}
+
+/// Fairness
+///
+/// Curvature along a line, from the circle through each point and its neighbors, so the end
+/// points have no curvature. Unfair spots are the sign changes of the curvature and the
+/// spikes, a fair line bends smoothly one way. Some inflections are intended, like the hollow
+/// of a waterline at the bow, the designer judges. The comb of the curvature, in the
+/// coordinates of the line, overlays the line of a drawing like the porcupine of a fairing
+/// batten.
+#[derive(Debug, Clone)]
+pub struct Fairness {
+    name: String,
+    points: Vec<CurvaturePoint>,
+    spots: Vec<UnfairSpot>,
+}
+
+#[allow(dead_code)]
+impl Fairness {
+    /// Fairness of a line of (x, y) points in order along the line.
+    pub fn new(name: String, line: &[(Length, Length)]) -> Fairness {
+        let points: Vec<CurvaturePoint> = line
+            .windows(3)
+            .map(|w| CurvaturePoint {
+                x: w[1].0,
+                y: w[1].1,
+                curvature: curvature(w[0], w[1], w[2]),
+            })
+            .collect();
+        let mean =
+            points.iter().map(|p| p.curvature.abs()).sum::<f64>() / points.len().max(1) as f64;
+        let mut spots = Vec::new();
+        for (i, point) in points.iter().enumerate() {
+            let k = point.curvature;
+            if i > 0 {
+                let previous = points[i - 1].curvature;
+                if previous.abs() > CURVATURE_TOLERANCE
+                    && k.abs() > CURVATURE_TOLERANCE
+                    && previous.signum() != k.signum()
+                {
+                    spots.push(UnfairSpot {
+                        x: point.x,
+                        y: point.y,
+                        kind: Unfairness::Inflection,
+                    });
+                }
+            }
+            if i > 0 && i + 1 < points.len() {
+                let neighbors = points[i - 1]
+                    .curvature
+                    .abs()
+                    .max(points[i + 1].curvature.abs());
+                if k.abs() > CURVATURE_TOLERANCE
+                    && k.abs() > neighbors
+                    && k.abs() > SPIKE_FACTOR * mean
+                {
+                    spots.push(UnfairSpot {
+                        x: point.x,
+                        y: point.y,
+                        kind: Unfairness::Spike,
+                    });
+                }
+            }
+        }
+        Fairness {
+            name,
+            points,
+            spots,
+        }
+    }
+
+    /// Sheer, sampled from the foremost to the aftmost section.
+    pub fn sheer(hull: &Hull, view: View) -> Fairness {
+        let line: Vec<(Length, Length)> = hull
+            .positions(FAIRNESS_SAMPLES)
+            .into_iter()
+            .filter_map(|x| {
+                let z = hull.sheer(x)?;
+                match view {
+                    View::Profile => Some((x, z)),
+                    View::Plan => hull.half_breadth(x, z).map(|y| (x, y)),
+                }
+            })
+            .collect();
+        Fairness::new(format!("Sheer {}", view), &line)
+    }
+
+    /// Waterline at a height, in plan.
+    pub fn waterline(hull: &Hull, height: Length) -> Fairness {
+        Fairness::new(
+            format!("WL {:.3}m", height.to_meter()),
+            &hull.waterline(height, FAIRNESS_SAMPLES),
+        )
+    }
+
+    /// Chine through the offset point of the same index on every section, counted from the
+    /// keel, lofted with the hull interpolation. A hard chine hull has its chines as offset
+    /// points.
+    pub fn chine(hull: &Hull, index: usize, view: View) -> Fairness {
+        let name = format!("Chine {} {}", index, view);
+        let stations: Vec<(f64, f64)> = hull
+            .sections()
+            .iter()
+            .filter_map(|s| {
+                let (y, z) = *s.points().get(index)?;
+                let value = match view {
+                    View::Profile => z,
+                    View::Plan => y,
+                };
+                Some((s.position().to_meter(), value.to_meter()))
+            })
+            .collect();
+        let line: Vec<(Length, Length)> = match Spline::new(&stations, hull.interpolation()) {
+            Some(spline) => hull
+                .positions(FAIRNESS_SAMPLES)
+                .into_iter()
+                .filter_map(|x| {
+                    spline
+                        .eval(x.to_meter())
+                        .map(|v| (x, Length::from_meter(v)))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Fairness::new(name, &line)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Curvature at the inner points of the line.
+    pub fn points(&self) -> &[CurvaturePoint] {
+        &self.points
+    }
+
+    pub fn spots(&self) -> &[UnfairSpot] {
+        &self.spots
+    }
+
+    pub fn inflections(&self) -> usize {
+        self.count(Unfairness::Inflection)
+    }
+
+    pub fn spikes(&self) -> usize {
+        self.count(Unfairness::Spike)
+    }
+
+    fn count(&self, kind: Unfairness) -> usize {
+        self.spots.iter().filter(|s| s.kind == kind).count()
+    }
+
+    /// Largest curvature (1/m), zero for a straight line.
+    pub fn maximum_curvature(&self) -> f64 {
+        self.points
+            .iter()
+            .fold(0.0, |max, p| f64::max(max, p.curvature.abs()))
+    }
+
+    /// No unfair spot.
+    pub fn is_fair(&self) -> bool {
+        self.spots.is_empty()
+    }
+
+    /// Curvature comb, a segment from each point of the line along the normal, the length
+    /// the curvature times the scale (m2), on the inside of the bend.
+    pub fn comb(&self, scale: f64) -> Vec<((Length, Length), (Length, Length))> {
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let before = if i > 0 { &self.points[i - 1] } else { p };
+                let after = self.points.get(i + 1).unwrap_or(p);
+                let dx = after.x.to_meter() - before.x.to_meter();
+                let dy = after.y.to_meter() - before.y.to_meter();
+                let length = dx.hypot(dy);
+                let (nx, ny) = if length > 0.0 {
+                    (-dy / length, dx / length)
+                } else {
+                    (0.0, 0.0)
+                };
+                let tooth = p.curvature * scale;
+                (
+                    (p.x, p.y),
+                    (
+                        Length::from_meter(p.x.to_meter() + nx * tooth),
+                        Length::from_meter(p.y.to_meter() + ny * tooth),
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Signed curvature of the circle through three points, zero when aligned.
+fn curvature(a: (Length, Length), b: (Length, Length), c: (Length, Length)) -> f64 {
+    let (ax, ay) = (a.0.to_meter(), a.1.to_meter());
+    let (bx, by) = (b.0.to_meter(), b.1.to_meter());
+    let (cx, cy) = (c.0.to_meter(), c.1.to_meter());
+    let cross = (bx - ax) * (cy - by) - (by - ay) * (cx - bx);
+    let sides = (bx - ax).hypot(by - ay) * (cx - bx).hypot(cy - by) * (cx - ax).hypot(cy - ay);
+    if sides == 0.0 {
+        0.0
+    } else {
+        2.0 * cross / sides
+    }
+}
+
+impl fmt::Display for Fairness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Fairness {}]\n\
+            \tPoints:        {:>9}\n\
+            \tMax curvature: {:>9.4}1/m\n\
+            \tInflections:   {:>9}\n\
+            \tSpikes:        {:>9}",
+            self.name,
+            self.points.len(),
+            self.maximum_curvature(),
+            self.inflections(),
+            self.spikes()
+        )?;
+        for spot in &self.spots {
+            write!(
+                f,
+                "\n\t{:<10} at {:.3}m, {:.3}m",
+                spot.kind,
+                spot.x.to_meter(),
+                spot.y.to_meter()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn fairness() {
+        let m = Length::from_meter;
+        // Arc of a 10m radius circle.
+        let arc: Vec<(Length, Length)> = (0..21)
+            .map(|i| {
+                let angle = (i as f64 * 2.0).to_radians();
+                (m(10.0 * angle.sin()), m(10.0 - 10.0 * angle.cos()))
+            })
+            .collect();
+        let fairness = Fairness::new("Arc".to_string(), &arc);
+        assert_eq!(fairness.points().len(), 19);
+        assert_eq!(format!("{:.4}", fairness.maximum_curvature()), "0.1000");
+        assert!(fairness.is_fair());
+        // Teeth toward the center of the circle.
+        let comb = fairness.comb(1.0);
+        assert!(comb[9].1 .1.to_meter() > comb[9].0 .1.to_meter());
+
+        // A point pushed out of the arc.
+        let mut bumped = arc.clone();
+        bumped[10].1 = m(bumped[10].1.to_meter() - 0.05);
+        let fairness = Fairness::new("Bumped".to_string(), &bumped);
+        assert!(!fairness.is_fair());
+        // Reversed on each side of the bump.
+        assert_eq!(fairness.inflections(), 4);
+        assert_eq!(fairness.spikes(), 1);
+        let spike = fairness
+            .spots()
+            .iter()
+            .find(|s| s.kind == Unfairness::Spike)
+            .unwrap();
+        assert_eq!(spike.x.to_meter(), bumped[10].0.to_meter());
+
+        // Straight sheer and chines of a box.
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.1), 1.0, (1.0, 1.0));
+        let sheer = Fairness::sheer(&hull, View::Profile);
+        assert_eq!(sheer.points().len(), FAIRNESS_SAMPLES - 2);
+        assert_eq!(sheer.maximum_curvature(), 0.0);
+        assert!(Fairness::chine(&hull, 1, View::Plan).is_fair());
+        assert!(Fairness::chine(&hull, 5, View::Plan).points().is_empty());
+        assert!(Fairness::waterline(&hull, m(0.5)).is_fair());
+    }
+}
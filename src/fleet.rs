@@ -0,0 +1,395 @@
+use super::boat::{Boat, Metric};
+use super::csv::{self, CsvError};
+use super::si::{Area, Length, Weight};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// CRITERION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Filter criterion, a metric inside an inclusive range.
+/// Dimensions are in SI units (m, m2, kg).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Criterion {
+    metric: Metric,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+#[allow(dead_code)]
+impl Criterion {
+    pub fn between(metric: Metric, min: f64, max: f64) -> Criterion {
+        Criterion {
+            metric,
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+
+    pub fn at_least(metric: Metric, min: f64) -> Criterion {
+        Criterion {
+            metric,
+            min: Some(min),
+            max: None,
+        }
+    }
+
+    pub fn at_most(metric: Metric, max: f64) -> Criterion {
+        Criterion {
+            metric,
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    pub fn matches(&self, boat: &Boat) -> bool {
+        let value = self.metric.value(boat);
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SUMMARY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Summary of a metric across the fleet.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct MetricSummary {
+    metric: Metric,
+    min: f64,
+    max: f64,
+    median: f64,
+}
+
+#[allow(dead_code)]
+impl MetricSummary {
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn median(&self) -> f64 {
+        self.median
+    }
+}
+
+impl fmt::Display for MetricSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<20}{:>12.2}{:>12.2}{:>12.2}",
+            format!("{} {}", self.metric, self.metric.unit()).trim_end(),
+            self.min,
+            self.median,
+            self.max
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FLEET
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Fleet
+///
+/// Collection of boats, usually reference designs to compare a new design against.
+#[derive(Debug, Clone, Default)]
+pub struct Fleet {
+    boats: Vec<Boat>,
+}
+
+#[allow(dead_code)]
+impl Fleet {
+    pub fn new() -> Fleet {
+        Fleet { boats: Vec::new() }
+    }
+
+    pub fn from_boats(boats: Vec<Boat>) -> Fleet {
+        Fleet { boats }
+    }
+
+    pub fn push(&mut self, boat: Boat) {
+        self.boats.push(boat);
+    }
+
+    pub fn boats(&self) -> &[Boat] {
+        &self.boats
+    }
+
+    pub fn len(&self) -> usize {
+        self.boats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boats.is_empty()
+    }
+
+    /// Import boats from a CSV file.
+    ///
+    /// The first line is the header, columns are matched by name (case insensitive) and may
+    /// be in any order:
+    ///
+    /// name          Boat name (required).
+    /// loa           LOA (required).
+    /// lwl           DWL (required), "dwl" is also accepted.
+    /// beam          Maximum beam (required).
+    /// displacement  Displacement (required).
+    /// sail_area     Sail area (required).
+    /// draft         Hull draft (optional).
+    /// units         "imperial" (ft, lb, ft2) or "metric" (m, kg, m2), per boat (optional,
+    ///               metric when missing).
+    ///
+    /// Other columns are ignored, empty lines and lines starting with # are skipped.
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Fleet, CsvError> {
+        Fleet::parse_csv(&fs::read_to_string(path)?)
+    }
+
+    /// Import boats from CSV text, see from_csv.
+    pub fn parse_csv(text: &str) -> Result<Fleet, CsvError> {
+        let mut lines = csv::lines(text);
+        let (header_line, header) = match lines.next() {
+            Some((n, l)) => (n, csv::split_line(l)),
+            None => return Ok(Fleet::new()),
+        };
+        let column = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)))
+        };
+        let required = |name: &str, names: &[&str]| {
+            column(names)
+                .ok_or_else(|| CsvError::Parse(header_line, format!("missing column \"{}\"", name)))
+        };
+        let name_col = required("name", &["name"])?;
+        let loa_col = required("loa", &["loa"])?;
+        let lwl_col = required("lwl", &["lwl", "dwl"])?;
+        let beam_col = required("beam", &["beam"])?;
+        let displacement_col = required("displacement", &["displacement"])?;
+        let sail_area_col = required("sail_area", &["sail_area"])?;
+        let draft_col = column(&["draft"]);
+        let units_col = column(&["units"]);
+
+        let mut fleet = Fleet::new();
+        for (n, line) in lines {
+            let fields = csv::split_line(line);
+            let field = |col: usize| fields.get(col).map(|f| f.as_str()).unwrap_or("");
+            let number = |col: usize| {
+                field(col).parse::<f64>().map_err(|_| {
+                    CsvError::Parse(n, format!("invalid {} \"{}\"", header[col], field(col)))
+                })
+            };
+            let imperial = match units_col.map(field) {
+                None | Some("") => false,
+                Some(u) if u.eq_ignore_ascii_case("metric") => false,
+                Some(u) if u.eq_ignore_ascii_case("imperial") => true,
+                Some(u) => return Err(CsvError::Parse(n, format!("invalid units \"{}\"", u))),
+            };
+            let length = |col: usize| -> Result<Length, CsvError> {
+                let val = number(col)?;
+                Ok(if imperial {
+                    Length::from_foot(val)
+                } else {
+                    Length::from_meter(val)
+                })
+            };
+
+            let mut boat = Boat::new(field(name_col).to_string());
+            boat.set_loa(length(loa_col)?);
+            boat.set_dwl(length(lwl_col)?);
+            boat.set_b_max(length(beam_col)?);
+            boat.set_bwl(length(beam_col)?);
+            if let Some(col) = draft_col {
+                if !field(col).is_empty() {
+                    boat.set_draft(length(col)?);
+                }
+            }
+            let displacement = number(displacement_col)?;
+            boat.set_displacement(if imperial {
+                Weight::from_pound(displacement)
+            } else {
+                Weight::from_kilogram(displacement)
+            });
+            let sail_area = number(sail_area_col)?;
+            boat.set_sail_area(if imperial {
+                Area::from_foot2(sail_area)
+            } else {
+                Area::from_meter2(sail_area)
+            });
+            fleet.push(boat);
+        }
+        Ok(fleet)
+    }
+
+    /// Sort the boats by a metric.
+    pub fn sort_by(&mut self, metric: Metric, ascending: bool) {
+        self.boats.sort_by(|a, b| {
+            let ordering = metric
+                .value(a)
+                .partial_cmp(&metric.value(b))
+                .unwrap_or(std::cmp::Ordering::Equal);
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+
+    /// Boats matching all the criteria.
+    pub fn filter(&self, criteria: &[Criterion]) -> Fleet {
+        Fleet {
+            boats: self
+                .boats
+                .iter()
+                .filter(|b| criteria.iter().all(|c| c.matches(b)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Min, max and median of a metric, none for an empty fleet.
+    pub fn summary(&self, metric: Metric) -> Option<MetricSummary> {
+        let mut values: Vec<f64> = self.boats.iter().map(|b| metric.value(b)).collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let n = values.len();
+        let median = if n.is_multiple_of(2) {
+            (values[n / 2 - 1] + values[n / 2]) / 2.0
+        } else {
+            values[n / 2]
+        };
+        Some(MetricSummary {
+            metric,
+            min: values[0],
+            max: values[n - 1],
+            median,
+        })
+    }
+
+    /// Summary of every metric.
+    pub fn summaries(&self) -> Vec<MetricSummary> {
+        Metric::ALL
+            .iter()
+            .filter_map(|&m| self.summary(m))
+            .collect()
+    }
+}
+
+impl fmt::Display for Fleet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Fleet - {} boats]\n{:<20}{:>12}{:>12}{:>12}",
+            self.boats.len(),
+            "",
+            "Min",
+            "Median",
+            "Max"
+        )?;
+        for summary in self.summaries() {
+            write!(f, "\n{}", summary)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::si::{Length, Weight};
+
+    #[allow(dead_code)]
+    fn boat(name: &str, loa: f64, displacement: f64) -> Boat {
+        let mut boat = Boat::new(name.to_string());
+        boat.set_loa(Length::from_foot(loa));
+        boat.set_dwl(Length::from_foot(loa * 0.85));
+        boat.set_b_max(Length::from_foot(loa / 3.0));
+        boat.set_displacement(Weight::from_pound(displacement));
+        boat
+    }
+
+    #[test]
+    fn fleet() {
+        let mut fleet = Fleet::from_boats(vec![
+            boat("A", 30.0, 8000.0),
+            boat("B", 24.0, 3000.0),
+            boat("C", 28.0, 4500.0),
+            boat("D", 36.0, 16000.0),
+        ]);
+        assert!(Fleet::new().summary(Metric::Loa).is_none());
+
+        fleet.sort_by(Metric::Loa, true);
+        let names: Vec<&str> = fleet.boats().iter().map(|b| b.name()).collect();
+        assert_eq!(names, vec!["B", "C", "A", "D"]);
+        fleet.sort_by(Metric::Displacement, false);
+        assert_eq!(fleet.boats()[0].name(), "D");
+
+        // D/L below 200 and LOA between 25ft and 32ft.
+        let light = fleet.filter(&[
+            Criterion::at_most(Metric::DisplacementLength, 200.0),
+            Criterion::between(
+                Metric::Loa,
+                Length::from_foot(25.0).to_meter(),
+                Length::from_foot(32.0).to_meter(),
+            ),
+        ]);
+        let names: Vec<&str> = light.boats().iter().map(|b| b.name()).collect();
+        assert_eq!(names, vec!["C"]);
+
+        let loa = fleet.summary(Metric::Loa).unwrap();
+        assert_eq!(
+            format!("{:.1}", Length::from_meter(loa.min()).to_foot()),
+            "24.0"
+        );
+        assert_eq!(
+            format!("{:.1}", Length::from_meter(loa.median()).to_foot()),
+            "29.0"
+        );
+        assert_eq!(
+            format!("{:.1}", Length::from_meter(loa.max()).to_foot()),
+            "36.0"
+        );
+        assert_eq!(fleet.summaries().len(), Metric::ALL.len());
+    }
+
+    #[test]
+    fn from_csv() {
+        let text = "\
+            Name,LOA,LWL,Beam,Displacement,Sail_Area,Units,Designer\n\
+            # Reference boats.\n\
+            \"Catalina 27, tall rig\",27.0,21.75,8.83,6850,340,imperial,Butler\n\
+            Metric boat,8.0,7.0,2.6,2500,32,metric,\n";
+        let fleet = Fleet::parse_csv(text).unwrap();
+        assert_eq!(fleet.len(), 2);
+        let catalina = &fleet.boats()[0];
+        assert_eq!(catalina.name(), "Catalina 27, tall rig");
+        assert_eq!(format!("{:.2}", catalina.dwl().to_foot()), "21.75");
+        assert_eq!(format!("{:.0}", catalina.displacement().to_pound()), "6850");
+        assert_eq!(format!("{:.0}", catalina.sail_area().to_foot2()), "340");
+        assert_eq!(fleet.boats()[1].loa().to_meter(), 8.0);
+
+        let path = std::env::temp_dir().join(format!("boat_plan_fleet_{}.csv", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        let fleet = Fleet::from_csv(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(fleet.len(), 2);
+
+        let err = Fleet::parse_csv("name,loa\nA,1").unwrap_err();
+        assert_eq!(err.to_string(), "CSV line 1: missing column \"lwl\"");
+        let err =
+            Fleet::parse_csv("name,loa,lwl,beam,displacement,sail_area\nA,x,1,1,1,1").unwrap_err();
+        assert_eq!(err.to_string(), "CSV line 2: invalid loa \"x\"");
+    }
+}
@@ -0,0 +1,402 @@
+pub use super::drawing::POINTS_PER_METER;
+use super::si::{Area, Length};
+use super::spline::{Interpolation, Spline};
+use cairo::Context;
+use std::fmt;
+
+/// Points of a foil on each side, from the leading to the trailing edge.
+pub const FOIL_RESOLUTION: usize = 40;
+
+/// NACA 63-010 half-thickness ordinates (% of the chord).
+/// Abbott, I. H. and von Doenhoff, A. E. (1959) Theory of wing sections. Dover.
+const NACA_63_010: [(f64, f64); 26] = [
+    (0.0, 0.0),
+    (0.5, 0.829),
+    (0.75, 1.004),
+    (1.25, 1.275),
+    (2.5, 1.756),
+    (5.0, 2.440),
+    (7.5, 2.950),
+    (10.0, 3.362),
+    (15.0, 3.994),
+    (20.0, 4.445),
+    (25.0, 4.753),
+    (30.0, 4.938),
+    (35.0, 5.000),
+    (40.0, 4.938),
+    (45.0, 4.766),
+    (50.0, 4.496),
+    (55.0, 4.140),
+    (60.0, 3.715),
+    (65.0, 3.237),
+    (70.0, 2.720),
+    (75.0, 2.177),
+    (80.0, 1.626),
+    (85.0, 1.091),
+    (90.0, 0.604),
+    (95.0, 0.214),
+    (100.0, 0.0),
+];
+
+/// NACA 65-010 half-thickness ordinates (% of the chord).
+/// Abbott, I. H. and von Doenhoff, A. E. (1959) Theory of wing sections. Dover.
+const NACA_65_010: [(f64, f64); 26] = [
+    (0.0, 0.0),
+    (0.5, 0.772),
+    (0.75, 0.932),
+    (1.25, 1.169),
+    (2.5, 1.574),
+    (5.0, 2.177),
+    (7.5, 2.647),
+    (10.0, 3.040),
+    (15.0, 3.666),
+    (20.0, 4.143),
+    (25.0, 4.503),
+    (30.0, 4.760),
+    (35.0, 4.924),
+    (40.0, 4.996),
+    (45.0, 4.963),
+    (50.0, 4.812),
+    (55.0, 4.530),
+    (60.0, 4.146),
+    (65.0, 3.682),
+    (70.0, 3.156),
+    (75.0, 2.584),
+    (80.0, 1.987),
+    (85.0, 1.385),
+    (90.0, 0.810),
+    (95.0, 0.306),
+    (100.0, 0.0),
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FOIL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Foil series.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum FoilSeries {
+    /// Maximum camber and its position, fractions of the chord.
+    Naca4 { camber: f64, position: f64 },
+    /// Symmetrical 63 series, the 63-010 thickness scaled.
+    Naca63,
+    /// Symmetrical 65 series, the 65-010 thickness scaled.
+    Naca65,
+}
+
+/// Foil
+///
+/// NACA section of a thickness as a fraction of the chord. The 4-digit thickness is
+/// yt = 5t (0.2969 x^0.5 - 0.1260 x - 0.3516 x^2 + 0.2843 x^3 - 0.1036 x^4), with the trailing
+/// edge closed to cut templates, laid on each side of the camber line. The 6 series
+/// ordinates are interpolated and scaled to the thickness.
+/// Coordinates from the trailing edge over the upper side to the leading edge and back under
+/// the lower side, closer at the leading edge.
+#[derive(Debug, Copy, Clone)]
+pub struct Foil {
+    series: FoilSeries,
+    thickness: f64,
+}
+
+#[allow(dead_code)]
+impl Foil {
+    /// NACA 4-digit section, the camber and its position in tenths for the second digit.
+    pub fn naca4(camber: f64, position: f64, thickness: f64) -> Foil {
+        Foil {
+            series: FoilSeries::Naca4 { camber, position },
+            thickness,
+        }
+    }
+
+    /// Symmetrical NACA 4-digit section, 00xx.
+    pub fn symmetrical(thickness: f64) -> Foil {
+        Foil::naca4(0.0, 0.0, thickness)
+    }
+
+    pub fn naca63(thickness: f64) -> Foil {
+        Foil {
+            series: FoilSeries::Naca63,
+            thickness,
+        }
+    }
+
+    pub fn naca65(thickness: f64) -> Foil {
+        Foil {
+            series: FoilSeries::Naca65,
+            thickness,
+        }
+    }
+
+    /// Foil from its designation, like "NACA 0012", "2412" or "63-012".
+    pub fn from_designation(designation: &str) -> Option<Foil> {
+        let code = designation.trim().trim_start_matches("NACA").trim();
+        let thickness = |digits: &str| -> Option<f64> {
+            if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            digits.parse::<f64>().ok().map(|t| t / 100.0)
+        };
+        let series = |prefix: &str| {
+            code.strip_prefix(prefix)?
+                .strip_prefix('0')
+                .and_then(thickness)
+        };
+        if let Some(thickness) = series("63-") {
+            return Some(Foil::naca63(thickness));
+        }
+        if let Some(thickness) = series("65-") {
+            return Some(Foil::naca65(thickness));
+        }
+        if code.len() != 4 || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let digit = |i: usize| code[i..i + 1].parse::<f64>().ok();
+        Some(Foil::naca4(
+            digit(0)? / 100.0,
+            digit(1)? / 10.0,
+            thickness(&code[2..])?,
+        ))
+    }
+
+    pub fn series(&self) -> FoilSeries {
+        self.series
+    }
+
+    /// Thickness as a fraction of the chord.
+    pub fn thickness(&self) -> f64 {
+        self.thickness
+    }
+
+    /// Half-thickness at a fraction of the chord, as a fraction of the chord.
+    pub fn half_thickness(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        match self.series {
+            FoilSeries::Naca4 { .. } => (5.0
+                * self.thickness
+                * (0.2969 * x.sqrt() - 0.1260 * x - 0.3516 * x.powi(2) + 0.2843 * x.powi(3)
+                    - 0.1036 * x.powi(4)))
+            .max(0.0),
+            FoilSeries::Naca63 => ordinate(&NACA_63_010, x) * self.thickness / 0.1,
+            FoilSeries::Naca65 => ordinate(&NACA_65_010, x) * self.thickness / 0.1,
+        }
+    }
+
+    /// Camber line height and slope at a fraction of the chord.
+    pub fn camber_line(&self, x: f64) -> (f64, f64) {
+        match self.series {
+            FoilSeries::Naca4 { camber, position } if camber > 0.0 && position > 0.0 => {
+                let (m, p) = (camber, position);
+                if x < p {
+                    (
+                        m / (p * p) * (2.0 * p * x - x * x),
+                        2.0 * m / (p * p) * (p - x),
+                    )
+                } else {
+                    (
+                        m / ((1.0 - p) * (1.0 - p)) * (1.0 - 2.0 * p + 2.0 * p * x - x * x),
+                        2.0 * m / ((1.0 - p) * (1.0 - p)) * (p - x),
+                    )
+                }
+            }
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Fractions of the chord of the points, cosine spaced.
+    fn stations(points: usize) -> Vec<f64> {
+        let points = points.max(2);
+        (0..=points)
+            .map(|i| (1.0 - (std::f64::consts::PI * i as f64 / points as f64).cos()) / 2.0)
+            .collect()
+    }
+
+    /// Side of the foil, upper or lower, from the leading to the trailing edge.
+    fn side(&self, chord: Length, points: usize, upper: bool) -> Vec<(Length, Length)> {
+        let chord = chord.to_meter();
+        let sign = if upper { 1.0 } else { -1.0 };
+        Foil::stations(points)
+            .into_iter()
+            .map(|x| {
+                let (yc, slope) = self.camber_line(x);
+                let yt = self.half_thickness(x);
+                let angle = slope.atan();
+                (
+                    Length::from_meter((x - sign * yt * angle.sin()) * chord),
+                    Length::from_meter((yc + sign * yt * angle.cos()) * chord),
+                )
+            })
+            .collect()
+    }
+
+    /// Upper side at a chord, from the leading to the trailing edge.
+    pub fn upper(&self, chord: Length, points: usize) -> Vec<(Length, Length)> {
+        self.side(chord, points, true)
+    }
+
+    /// Lower side at a chord, from the leading to the trailing edge.
+    pub fn lower(&self, chord: Length, points: usize) -> Vec<(Length, Length)> {
+        self.side(chord, points, false)
+    }
+
+    /// Closed outline at a chord, from the trailing edge over the upper side and back under.
+    pub fn coordinates(&self, chord: Length, points: usize) -> Vec<(Length, Length)> {
+        let mut coordinates = self.upper(chord, points);
+        coordinates.reverse();
+        coordinates.extend(self.lower(chord, points).into_iter().skip(1));
+        coordinates
+    }
+
+    /// Lowest and highest ordinates of the outline at a chord, the camber included.
+    pub fn depth(&self, chord: Length) -> (Length, Length) {
+        self.coordinates(chord, FOIL_RESOLUTION).iter().fold(
+            (Length::from_meter(0.0), Length::from_meter(0.0)),
+            |(low, high), &(_, y)| {
+                (
+                    Length::from_meter(low.to_meter().min(y.to_meter())),
+                    Length::from_meter(high.to_meter().max(y.to_meter())),
+                )
+            },
+        )
+    }
+
+    /// Section area at a chord.
+    pub fn area(&self, chord: Length) -> Area {
+        let coordinates = self.coordinates(chord, FOIL_RESOLUTION);
+        let twice: f64 = coordinates
+            .windows(2)
+            .map(|w| w[0].0.to_meter() * w[1].1.to_meter() - w[1].0.to_meter() * w[0].1.to_meter())
+            .sum();
+        Area::from_meter2(twice.abs() / 2.0)
+    }
+
+    /// Position of the maximum thickness, fraction of the chord.
+    pub fn maximum_thickness_position(&self) -> f64 {
+        (0..=1000)
+            .map(|i| i as f64 / 1000.0)
+            .fold((0.0, 0.0), |max, x| {
+                let t = self.half_thickness(x);
+                if t > max.1 {
+                    (x, t)
+                } else {
+                    max
+                }
+            })
+            .0
+    }
+
+    /// Full size ordinates in millimeters, one "x y" line per point, from the trailing edge
+    /// over the upper side and back under.
+    pub fn template(&self, chord: Length, points: usize) -> String {
+        self.coordinates(chord, points)
+            .iter()
+            .map(|(x, y)| format!("{:.1} {:.1}\n", x.to_millimiter(), y.to_millimiter()))
+            .collect()
+    }
+
+    /// Draw the full size template at the origin of the context, the chord along x and the
+    /// upper side up, with the chord line and the maximum thickness marked.
+    pub fn draw(&self, context: &Context, chord: Length) {
+        let scale = POINTS_PER_METER;
+        let coordinates = self.coordinates(chord, FOIL_RESOLUTION);
+        for (i, (x, y)) in coordinates.iter().enumerate() {
+            let (px, py) = (x.to_meter() * scale, -y.to_meter() * scale);
+            if i == 0 {
+                context.move_to(px, py);
+            } else {
+                context.line_to(px, py);
+            }
+        }
+        context.close_path();
+        context.stroke();
+        context.move_to(0.0, 0.0);
+        context.line_to(chord.to_meter() * scale, 0.0);
+        let position = self.maximum_thickness_position();
+        let thickest = position * chord.to_meter() * scale;
+        let half = self.half_thickness(position) * chord.to_meter();
+        let camber = self.camber_line(position).0 * chord.to_meter();
+        context.move_to(thickest, -(camber + half) * scale);
+        context.line_to(thickest, -(camber - half) * scale);
+        context.stroke();
+        let (low, _) = self.depth(chord);
+        context.move_to(0.0, -low.to_meter() * scale + 12.0);
+        context.show_text(&format!("{} - chord {:.0}mm", self, chord.to_millimiter()));
+    }
+}
+
+/// Ordinate of a series table at a fraction of the chord, as a fraction of the chord.
+fn ordinate(table: &[(f64, f64)], x: f64) -> f64 {
+    Spline::new(table, Interpolation::CubicSpline)
+        .and_then(|s| s.eval(x * 100.0))
+        .map_or(0.0, |y| y.max(0.0) / 100.0)
+}
+
+impl fmt::Display for Foil {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let thickness = (self.thickness * 100.0).round();
+        match self.series {
+            FoilSeries::Naca4 { camber, position } => write!(
+                f,
+                "NACA {:.0}{:.0}{:02.0}",
+                (camber * 100.0).round(),
+                (position * 10.0).round(),
+                thickness
+            ),
+            FoilSeries::Naca63 => write!(f, "NACA 63-0{:02.0}", thickness),
+            FoilSeries::Naca65 => write!(f, "NACA 65-0{:02.0}", thickness),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn foil() {
+        let foil = Foil::from_designation("NACA 0012").unwrap();
+        assert_eq!(foil.to_string(), "NACA 0012");
+        assert_eq!(format!("{:.4}", foil.half_thickness(0.3)), "0.0600");
+        assert_eq!(foil.half_thickness(1.0), 0.0);
+        assert_eq!(format!("{:.2}", foil.maximum_thickness_position()), "0.30");
+        // About 0.685 t c^2.
+        let area = foil.area(Length::from_meter(1.0)).to_meter2();
+        assert_eq!(format!("{:.3}", area), "0.082");
+
+        let cambered = Foil::from_designation("2412").unwrap();
+        assert_eq!(cambered.to_string(), "NACA 2412");
+        assert_eq!(cambered.camber_line(0.4).0, 0.02);
+        let coordinates = cambered.coordinates(Length::from_meter(0.5), 20);
+        assert_eq!(coordinates.len(), 41);
+        assert_eq!(format!("{:.3}", coordinates[0].0.to_meter()), "0.500");
+        assert_eq!(format!("{:.3}", coordinates[20].0.to_meter()), "0.000");
+        // The camber lifts the upper side above the half thickness.
+        let (low, high) = cambered.depth(Length::from_meter(1.0));
+        assert_eq!(format!("{:.3}", low.to_meter()), "-0.042");
+        assert_eq!(format!("{:.3}", high.to_meter()), "0.079");
+
+        let series = Foil::from_designation("65-012").unwrap();
+        assert_eq!(series.series(), FoilSeries::Naca65);
+        assert_eq!(series.to_string(), "NACA 65-012");
+        assert_eq!(format!("{:.4}", series.half_thickness(0.4)), "0.0600");
+        assert_eq!(
+            format!("{:.2}", series.maximum_thickness_position()),
+            "0.41"
+        );
+        let series = Foil::from_designation("63-010").unwrap();
+        assert_eq!(format!("{:.4}", series.half_thickness(0.35)), "0.0500");
+
+        assert!(Foil::from_designation("NACA 23012").is_none());
+        assert!(Foil::from_designation("64-012").is_none());
+
+        let template = foil.template(Length::from_millimeter(300.0), 10);
+        assert_eq!(template.lines().next(), Some("300.0 0.0"));
+        assert_eq!(template.lines().count(), 21);
+
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 900, 200).unwrap();
+        let context = Context::new(&surface);
+        context.translate(10.0, 100.0);
+        foil.draw(&context, Length::from_millimeter(300.0));
+    }
+}
@@ -0,0 +1,209 @@
+use super::boat::{Boat, DisplacementLengthRatio, SailAreaDisplacementRatio};
+use super::fleet::Fleet;
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HANDICAP MODEL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Handicap model
+///
+/// Linear regression of the PHRF rating (seconds per mile) on the ratios:
+/// PHRF = intercept + lwl * DWL(ft) exp 0.5 + sail_area_displacement * SA/D
+///        + displacement_length * D/L
+/// There are no default coefficients, fit the model to boats of known rating of the local fleet.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct HandicapModel {
+    pub intercept: f64,
+    pub lwl: f64,
+    pub sail_area_displacement: f64,
+    pub displacement_length: f64,
+}
+
+#[allow(dead_code)]
+impl HandicapModel {
+    /// Fit the model to boats of known rating, by least squares.
+    /// None with less than four boats or when the ratios do not vary enough.
+    pub fn fit(fleet: &Fleet, ratings: &[f64]) -> Option<HandicapModel> {
+        let boats = fleet.boats();
+        if boats.len() != ratings.len() || boats.len() < 4 {
+            return None;
+        }
+        // Normal equations, XtX c = Xty.
+        let mut matrix = [[0.0; 5]; 4];
+        for (boat, rating) in boats.iter().zip(ratings) {
+            let x = features(boat);
+            for i in 0..4 {
+                for j in 0..4 {
+                    matrix[i][j] += x[i] * x[j];
+                }
+                matrix[i][4] += x[i] * rating;
+            }
+        }
+        let c = solve(matrix)?;
+        Some(HandicapModel {
+            intercept: c[0],
+            lwl: c[1],
+            sail_area_displacement: c[2],
+            displacement_length: c[3],
+        })
+    }
+
+    /// Estimated rating (seconds per mile), lower is faster.
+    pub fn rating(&self, boat: &Boat) -> f64 {
+        let x = features(boat);
+        self.intercept
+            + self.lwl * x[1]
+            + self.sail_area_displacement * x[2]
+            + self.displacement_length * x[3]
+    }
+
+    /// Estimated rating of the boat placed among the fleet.
+    pub fn estimate(&self, boat: &Boat, fleet: &Fleet) -> HandicapEstimate {
+        let mut fleet: Vec<(String, f64)> = fleet
+            .boats()
+            .iter()
+            .map(|b| (b.name().to_string(), self.rating(b)))
+            .collect();
+        fleet.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        HandicapEstimate {
+            name: boat.name().to_string(),
+            rating: self.rating(boat),
+            fleet,
+        }
+    }
+}
+
+/// Regression features, the constant term first.
+fn features(boat: &Boat) -> [f64; 4] {
+    [
+        1.0,
+        boat.dwl().to_foot().sqrt(),
+        SailAreaDisplacementRatio::from_boat(boat).value(),
+        DisplacementLengthRatio::from_boat(boat).value(),
+    ]
+}
+
+/// Gauss-Jordan elimination with partial pivoting of an augmented 4x4 system.
+fn solve(mut m: [[f64; 5]; 4]) -> Option<[f64; 4]> {
+    for i in 0..4 {
+        let pivot = (i..4).max_by(|&a, &b| {
+            m[a][i]
+                .abs()
+                .partial_cmp(&m[b][i].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        if m[pivot][i].abs() < 1e-9 {
+            return None;
+        }
+        m.swap(i, pivot);
+        let row = m[i];
+        for (r, other) in m.iter_mut().enumerate() {
+            if r != i {
+                let factor = other[i] / row[i];
+                for (val, pivot_val) in other.iter_mut().zip(row.iter()).skip(i) {
+                    *val -= factor * pivot_val;
+                }
+            }
+        }
+    }
+    let mut x = [0.0; 4];
+    for (i, row) in m.iter().enumerate() {
+        x[i] = row[4] / row[i];
+    }
+    Some(x)
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HANDICAP ESTIMATE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Handicap estimate of a boat, with the fleet ratings by the same model, fastest first.
+pub struct HandicapEstimate {
+    name: String,
+    rating: f64,
+    fleet: Vec<(String, f64)>,
+}
+
+#[allow(dead_code)]
+impl HandicapEstimate {
+    /// Estimated rating (seconds per mile).
+    pub fn rating(&self) -> f64 {
+        self.rating
+    }
+
+    /// Fleet boats and their estimated ratings, fastest first.
+    pub fn fleet(&self) -> &[(String, f64)] {
+        &self.fleet
+    }
+
+    /// Number of fleet boats faster than the boat.
+    pub fn position(&self) -> usize {
+        self.fleet.iter().filter(|(_, r)| *r < self.rating).count()
+    }
+}
+
+impl fmt::Display for HandicapEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Handicap estimate]\n\t{:<20} {:>6.0}",
+            self.name, self.rating
+        )?;
+        for (name, rating) in &self.fleet {
+            write!(f, "\n\t{:<20} {:>6.0}", name, rating)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::si::{Area, Length, Weight};
+
+    #[allow(dead_code)]
+    fn boat(name: &str, dwl: f64, sail_area: f64, displacement: f64) -> Boat {
+        let mut boat = Boat::new(name.to_string());
+        boat.set_dwl(Length::from_foot(dwl));
+        boat.set_sail_area(Area::from_foot2(sail_area));
+        boat.set_displacement(Weight::from_pound(displacement));
+        boat
+    }
+
+    #[test]
+    fn handicap() {
+        // Ratings made up from known coefficients, not published ratings.
+        let model = HandicapModel {
+            intercept: 880.0,
+            lwl: -132.0,
+            sail_area_displacement: -4.5,
+            displacement_length: 0.1,
+        };
+        let j24 = boat("J/24", 20.0, 261.0, 3100.0);
+        let fleet = Fleet::from_boats(vec![
+            boat("Catalina 22", 19.3, 212.0, 2250.0),
+            boat("Olson 30", 25.0, 414.0, 3600.0),
+            boat("Catalina 30", 25.0, 444.0, 10200.0),
+            boat("Tartan 10", 27.0, 463.0, 7400.0),
+            boat("J/35", 28.3, 590.0, 10000.0),
+        ]);
+
+        // Fit recovers the model the ratings come from.
+        let ratings: Vec<f64> = fleet.boats().iter().map(|b| model.rating(b)).collect();
+        let fitted = HandicapModel::fit(&fleet, &ratings).unwrap();
+        assert_eq!(format!("{:.3}", fitted.lwl), "-132.000");
+        assert_eq!(format!("{:.3}", fitted.displacement_length), "0.100");
+        assert!(HandicapModel::fit(&fleet, &ratings[..3]).is_none());
+
+        let estimate = fitted.estimate(&j24, &fleet);
+        assert_eq!(
+            format!("{:.0}", estimate.rating()),
+            format!("{:.0}", model.rating(&j24))
+        );
+        assert_eq!(estimate.fleet()[0].0, "Olson 30");
+        assert_eq!(estimate.position(), 4);
+    }
+}
@@ -0,0 +1,543 @@
+use super::offsets::OffsetsTable;
+use super::section::Section;
+use super::si::Length;
+use super::spline::{Interpolation, Spline};
+use std::fmt;
+
+/// Heights sampled on a lofted section, from the keel to the sheer.
+pub const SECTION_SAMPLES: usize = 24;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HULL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Point of the hull surface, on the starboard side.
+#[derive(Debug, Copy, Clone)]
+pub struct HullPoint {
+    /// Aft of the forward perpendicular.
+    pub x: Length,
+    /// Half-breadth.
+    pub y: Length,
+    /// Above the baseline.
+    pub z: Length,
+}
+
+impl HullPoint {
+    pub fn new(x: Length, y: Length, z: Length) -> HullPoint {
+        HullPoint { x, y, z }
+    }
+}
+
+/// Kind of a longitudinal curve of the hull.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum CurveKind {
+    Keel,
+    /// Chine through the offset point of the sections counted from the keel.
+    Chine(usize),
+    Sheer,
+}
+
+impl fmt::Display for CurveKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CurveKind::Keel => write!(f, "Keel"),
+            CurveKind::Chine(i) => write!(f, "Chine {}", i),
+            CurveKind::Sheer => write!(f, "Sheer"),
+        }
+    }
+}
+
+/// Hull curve
+///
+/// Longitudinal curve of the hull through its own control points, the half-breadth and the
+/// height as curves of the position. Edited apart from the sections, the hull moves the
+/// offset point of the curve on every section to the curve.
+#[derive(Debug, Clone)]
+pub struct HullCurve {
+    kind: CurveKind,
+    points: Vec<HullPoint>,
+    interpolation: Interpolation,
+    breadth: Spline,
+    height: Spline,
+}
+
+#[allow(dead_code)]
+impl HullCurve {
+    /// Curve of control points, needs two points at different positions.
+    pub fn new(
+        kind: CurveKind,
+        mut points: Vec<HullPoint>,
+        interpolation: Interpolation,
+    ) -> Option<HullCurve> {
+        points.sort_by(|a, b| {
+            a.x.to_meter()
+                .partial_cmp(&b.x.to_meter())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let curve = |f: &dyn Fn(&HullPoint) -> Length| {
+            let values: Vec<(f64, f64)> = points
+                .iter()
+                .map(|p| (p.x.to_meter(), f(p).to_meter()))
+                .collect();
+            Spline::new(&values, interpolation)
+        };
+        let breadth = curve(&|p| p.y)?;
+        let height = curve(&|p| p.z)?;
+        Some(HullCurve {
+            kind,
+            points,
+            interpolation,
+            breadth,
+            height,
+        })
+    }
+
+    pub fn kind(&self) -> CurveKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> String {
+        self.kind.to_string()
+    }
+
+    /// Control points, sorted from the bow.
+    pub fn points(&self) -> &[HullPoint] {
+        &self.points
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Curve with a control point moved, None when there is no such point.
+    pub fn with_point(&self, index: usize, point: HullPoint) -> Option<HullCurve> {
+        let mut points = self.points.clone();
+        *points.get_mut(index)? = point;
+        HullCurve::new(self.kind, points, self.interpolation)
+    }
+
+    /// Foremost and aftmost control points position.
+    pub fn range(&self) -> (Length, Length) {
+        (self.points[0].x, self.points[self.points.len() - 1].x)
+    }
+
+    /// Half-breadth and height at a position, None outside the control points.
+    pub fn at(&self, x: Length) -> Option<(Length, Length)> {
+        Some((
+            Length::from_meter(self.breadth.eval(x.to_meter())?.max(0.0)),
+            Length::from_meter(self.height.eval(x.to_meter())?),
+        ))
+    }
+
+    /// Index of the offset point of the curve in the points of a section.
+    fn index(&self, points: usize) -> Option<usize> {
+        match self.kind {
+            CurveKind::Keel => Some(0),
+            CurveKind::Chine(i) if i + 1 < points => Some(i),
+            CurveKind::Chine(_) => None,
+            CurveKind::Sheer => points.checked_sub(1),
+        }
+    }
+}
+
+/// Hull
+///
+/// Surface lofted between the station sections. At a height the half-breadths of the sections
+/// are joined longitudinally by the same interpolation, zero where the height is below the keel
+/// of a section, so waterlines, buttocks and sections can be taken anywhere and agree.
+/// The keel, the chines and the sheer can be defined as curves of their own, the sections
+/// follow them and the keel and sheer heights are taken from them.
+#[derive(Debug, Clone)]
+pub struct Hull {
+    sections: Vec<Section>,
+    interpolation: Interpolation,
+    curves: Vec<HullCurve>,
+}
+
+#[allow(dead_code)]
+impl Hull {
+    /// Hull of sections sorted from the bow, needs two sections at least.
+    pub fn new(mut sections: Vec<Section>, interpolation: Interpolation) -> Option<Hull> {
+        if sections.len() < 2 {
+            return None;
+        }
+        sections.sort_by(|a, b| {
+            a.position()
+                .to_meter()
+                .partial_cmp(&b.position().to_meter())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Some(Hull {
+            sections,
+            interpolation,
+            curves: Vec::new(),
+        })
+    }
+
+    /// Hull of the stations of the offsets table, stations without two points are skipped.
+    pub fn from_offsets(table: &OffsetsTable, interpolation: Interpolation) -> Option<Hull> {
+        let sections = (0..table.stations().len())
+            .filter_map(|i| Section::from_offsets(table, i, interpolation))
+            .collect();
+        Hull::new(sections, interpolation)
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Foremost and aftmost sections position.
+    pub fn range(&self) -> (Length, Length) {
+        (
+            self.sections[0].position(),
+            self.sections[self.sections.len() - 1].position(),
+        )
+    }
+
+    pub fn curves(&self) -> &[HullCurve] {
+        &self.curves
+    }
+
+    pub fn curve(&self, kind: CurveKind) -> Option<&HullCurve> {
+        self.curves.iter().find(|c| c.kind == kind)
+    }
+
+    /// Curve through the offset points of the sections, to start editing from.
+    pub fn curve_from_sections(&self, kind: CurveKind) -> Option<HullCurve> {
+        let points = self
+            .sections
+            .iter()
+            .filter_map(|s| {
+                let points = s.points();
+                let i = match kind {
+                    CurveKind::Keel => 0,
+                    CurveKind::Chine(i) if i + 1 < points.len() => i,
+                    CurveKind::Chine(_) => return None,
+                    CurveKind::Sheer => points.len() - 1,
+                };
+                let (y, z) = points[i];
+                Some(HullPoint::new(s.position(), y, z))
+            })
+            .collect();
+        HullCurve::new(kind, points, self.interpolation)
+    }
+
+    /// Set a curve, replacing the curve of the same kind, and move the offset point of the
+    /// curve on the sections it spans to the curve.
+    pub fn set_curve(&mut self, curve: HullCurve) {
+        for section in self.sections.iter_mut() {
+            let mut points = section.points();
+            let (index, (y, z)) = match (curve.index(points.len()), curve.at(section.position())) {
+                (Some(i), Some(point)) => (i, point),
+                _ => continue,
+            };
+            points[index] = (y, z);
+            if let Some(moved) = Section::new(section.position(), &points, section.interpolation())
+            {
+                *section = moved;
+            }
+        }
+        self.curves.retain(|c| c.kind != curve.kind);
+        self.curves.push(curve);
+    }
+
+    /// Remove a curve, the sections keep their points.
+    pub fn remove_curve(&mut self, kind: CurveKind) {
+        self.curves.retain(|c| c.kind != kind);
+    }
+
+    /// Longitudinal curve through a value of the sections, None where the value is not defined.
+    fn longitudinal<F>(&self, value: F) -> Option<Spline>
+    where
+        F: Fn(&Section) -> Option<f64>,
+    {
+        let points: Vec<(f64, f64)> = self
+            .sections
+            .iter()
+            .filter_map(|s| value(s).map(|v| (s.position().to_meter(), v)))
+            .collect();
+        Spline::new(&points, self.interpolation)
+    }
+
+    /// Keel height at a position.
+    pub fn keel(&self, x: Length) -> Option<Length> {
+        if let Some(point) = self.curve(CurveKind::Keel).and_then(|c| c.at(x)) {
+            return Some(point.1);
+        }
+        self.longitudinal(|s| Some(s.bottom().to_meter()))?
+            .eval(x.to_meter())
+            .map(Length::from_meter)
+    }
+
+    /// Sheer height at a position.
+    pub fn sheer(&self, x: Length) -> Option<Length> {
+        if let Some(point) = self.curve(CurveKind::Sheer).and_then(|c| c.at(x)) {
+            return Some(point.1);
+        }
+        self.longitudinal(|s| Some(s.top().to_meter()))?
+            .eval(x.to_meter())
+            .map(Length::from_meter)
+    }
+
+    /// Half-breadth at a position and height, None above the sheer or outside the stations.
+    pub fn half_breadth(&self, x: Length, z: Length) -> Option<Length> {
+        if z.to_meter() > self.sheer(x)?.to_meter() + 1e-9 {
+            return None;
+        }
+        let height = z.to_meter();
+        self.longitudinal(|s| {
+            if height < s.bottom().to_meter() {
+                Some(0.0)
+            } else {
+                s.half_breadth(z).map(|y| y.to_meter())
+            }
+        })?
+        .eval(x.to_meter())
+        .map(|y| Length::from_meter(y.max(0.0)))
+    }
+
+    /// Height of a buttock at a position, None where the hull is narrower.
+    pub fn buttock_height(&self, x: Length, offset: Length) -> Option<Length> {
+        let spline = self.longitudinal(|s| s.height_at(offset).map(|z| z.to_meter()))?;
+        spline.eval(x.to_meter()).map(Length::from_meter)
+    }
+
+    /// Positions evenly spaced from the foremost to the aftmost section.
+    pub(crate) fn positions(&self, samples: usize) -> Vec<Length> {
+        let (first, last) = self.range();
+        let samples = samples.max(2);
+        (0..samples)
+            .map(|i| {
+                Length::from_meter(
+                    first.to_meter()
+                        + (last.to_meter() - first.to_meter()) * i as f64 / (samples - 1) as f64,
+                )
+            })
+            .collect()
+    }
+
+    /// Waterline at a height, (position, half-breadth) points.
+    pub fn waterline(&self, height: Length, samples: usize) -> Vec<(Length, Length)> {
+        self.positions(samples)
+            .into_iter()
+            .filter_map(|x| self.half_breadth(x, height).map(|y| (x, y)))
+            .collect()
+    }
+
+    /// Buttock at an offset from the centerline, (position, height) points.
+    pub fn buttock(&self, offset: Length, samples: usize) -> Vec<(Length, Length)> {
+        self.positions(samples)
+            .into_iter()
+            .filter_map(|x| self.buttock_height(x, offset).map(|z| (x, z)))
+            .collect()
+    }
+
+    /// Lofted section at any position, sampled from the keel to the sheer.
+    pub fn section(&self, x: Length) -> Option<Section> {
+        let keel = self.keel(x)?.to_meter();
+        let sheer = self.sheer(x)?.to_meter();
+        let points: Vec<(Length, Length)> = (0..=SECTION_SAMPLES)
+            .filter_map(|i| {
+                let z =
+                    Length::from_meter(keel + (sheer - keel) * i as f64 / SECTION_SAMPLES as f64);
+                self.half_breadth(x, z).map(|y| (y, z))
+            })
+            .collect();
+        Section::new(x, &points, self.interpolation)
+    }
+
+    /// Wireframe of the hull, the station sections, the waterlines and the buttocks as
+    /// polylines of points.
+    pub fn wireframe(
+        &self,
+        waterlines: &[Length],
+        buttocks: &[Length],
+        samples: usize,
+    ) -> Vec<Vec<HullPoint>> {
+        let mut lines: Vec<Vec<HullPoint>> = self
+            .sections
+            .iter()
+            .map(|s| {
+                s.points()
+                    .into_iter()
+                    .map(|(y, z)| HullPoint::new(s.position(), y, z))
+                    .collect()
+            })
+            .collect();
+        for &height in waterlines {
+            lines.push(
+                self.waterline(height, samples)
+                    .into_iter()
+                    .map(|(x, y)| HullPoint::new(x, y, height))
+                    .collect(),
+            );
+        }
+        for &offset in buttocks {
+            lines.push(
+                self.buttock(offset, samples)
+                    .into_iter()
+                    .map(|(x, z)| HullPoint::new(x, offset, z))
+                    .collect(),
+            );
+        }
+        lines.retain(|line| line.len() > 1);
+        lines
+    }
+}
+
+impl fmt::Display for Hull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (first, last) = self.range();
+        write!(
+            f,
+            "[Hull]\n\
+            \tSections:      {:>9}\n\
+            \tFrom:          {:>9.2}m\n\
+            \tTo:            {:>9.2}m\n\
+            \tInterpolation: {:>9}",
+            self.sections.len(),
+            first.to_meter(),
+            last.to_meter(),
+            self.interpolation
+        )?;
+        for curve in &self.curves {
+            write!(
+                f,
+                "\n\t{:<14} {:>9} points",
+                curve.name(),
+                curve.points().len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+pub(crate) mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    /// Hard chine hull from 0 to 4m, a section each meter from the keel on the center line to
+    /// the chine and up to the sheer, (half-breadth, height). The bottom gives the keel and the
+    /// chine heights at a position, to raise or twist the bottom along the hull, a flat bottom
+    /// starting at the chine.
+    #[allow(dead_code)]
+    pub(crate) fn chine_hull(
+        bottom: &dyn Fn(f64) -> (f64, f64),
+        chine: f64,
+        sheer: (f64, f64),
+    ) -> Hull {
+        let m = Length::from_meter;
+        let sections = (0..5)
+            .map(|i| {
+                let x = i as f64;
+                let (keel, height) = bottom(x);
+                let points = [
+                    (m(0.0), m(keel)),
+                    (m(chine), m(height)),
+                    (m(sheer.0), m(sheer.1)),
+                ];
+                let flat = if height > keel { 0 } else { 1 };
+                Section::new(m(x), &points[flat..], Interpolation::Linear).unwrap()
+            })
+            .collect();
+        Hull::new(sections, Interpolation::Linear).unwrap()
+    }
+
+    /// Box hull, 1m half-breadth and 1m deep, from 0 to 4m.
+    #[allow(dead_code)]
+    pub(crate) fn box_hull() -> Hull {
+        chine_hull(&|_| (0.0, 0.001), 1.0, (1.0, 1.0))
+    }
+
+    #[test]
+    fn hull() {
+        let m = Length::from_meter;
+        let hull = box_hull();
+        assert_eq!(hull.range().1.to_meter(), 4.0);
+        assert_eq!(hull.half_breadth(m(1.5), m(0.5)).unwrap().to_meter(), 1.0);
+        assert!(hull.half_breadth(m(1.5), m(1.5)).is_none());
+        assert!(hull.half_breadth(m(4.5), m(0.5)).is_none());
+        assert_eq!(hull.waterline(m(0.5), 9).len(), 9);
+        let buttock = hull.buttock(m(0.5), 5);
+        assert_eq!(format!("{:.4}", buttock[2].1.to_meter()), "0.0005");
+        assert!(hull.buttock(m(1.5), 5).is_empty());
+
+        let section = hull.section(m(2.5)).unwrap();
+        assert_eq!(section.position().to_meter(), 2.5);
+        assert_eq!(section.top().to_meter(), 1.0);
+
+        // 5 sections, 1 waterline, 1 buttock.
+        assert_eq!(hull.wireframe(&[m(0.5)], &[m(0.5)], 5).len(), 7);
+
+        // Keel rising toward the bow, the waterline ends forward.
+        let sections = vec![
+            Section::new(
+                m(0.0),
+                &[(m(0.0), m(0.6)), (m(0.2), m(1.0))],
+                Interpolation::Linear,
+            )
+            .unwrap(),
+            Section::new(
+                m(2.0),
+                &[(m(0.0), m(0.0)), (m(1.0), m(1.0))],
+                Interpolation::Linear,
+            )
+            .unwrap(),
+        ];
+        let hull = Hull::new(sections, Interpolation::Linear).unwrap();
+        assert_eq!(hull.keel(m(1.0)).unwrap().to_meter(), 0.3);
+        // Half of the 0.5m of the aft section.
+        assert_eq!(hull.half_breadth(m(1.0), m(0.5)).unwrap().to_meter(), 0.25);
+    }
+
+    #[test]
+    fn hull_curves() {
+        let m = Length::from_meter;
+        let mut hull = box_hull();
+        let sheer = hull.curve_from_sections(CurveKind::Sheer).unwrap();
+        assert_eq!(sheer.points().len(), 5);
+        assert_eq!(sheer.name(), "Sheer");
+        assert!(hull.curve_from_sections(CurveKind::Chine(2)).is_none());
+
+        // Sheer sprung up 0.2m at the bow.
+        let sheer = sheer
+            .with_point(0, HullPoint::new(m(0.0), m(1.0), m(1.2)))
+            .unwrap();
+        assert!(sheer
+            .with_point(5, HullPoint::new(m(0.0), m(1.0), m(1.2)))
+            .is_none());
+        hull.set_curve(sheer);
+        assert_eq!(hull.curves().len(), 1);
+        assert_eq!(hull.sections()[0].top().to_meter(), 1.2);
+        assert_eq!(
+            format!("{:.2}", hull.sheer(m(0.5)).unwrap().to_meter()),
+            "1.10"
+        );
+
+        // Chine pulled in to 0.8m amidships, only where the curve spans.
+        let chine = HullCurve::new(
+            CurveKind::Chine(1),
+            vec![
+                HullPoint::new(m(1.0), m(1.0), m(0.001)),
+                HullPoint::new(m(2.0), m(0.8), m(0.001)),
+                HullPoint::new(m(3.0), m(1.0), m(0.001)),
+            ],
+            Interpolation::Linear,
+        )
+        .unwrap();
+        hull.set_curve(chine);
+        assert_eq!(hull.sections()[2].points()[1].0.to_meter(), 0.8);
+        assert_eq!(hull.sections()[0].points()[1].0.to_meter(), 1.0);
+        assert_eq!(hull.curve(CurveKind::Chine(1)).unwrap().points().len(), 3);
+
+        hull.remove_curve(CurveKind::Sheer);
+        assert!(hull.curve(CurveKind::Sheer).is_none());
+        assert_eq!(hull.sections()[0].top().to_meter(), 1.2);
+    }
+}
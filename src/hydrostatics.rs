@@ -0,0 +1,1369 @@
+use super::boat::{ideal_prismatic, Boat, PRISMATIC_TOLERANCE, SEA_WATER_DENSITY};
+use super::drawing::{DrawingStyle, LineKind, GRID_DASH};
+use super::hull::Hull;
+use super::section::Section;
+use super::si::{Area, Density, Length, Volume, Weight};
+use cairo::Context;
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// WATER
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Water the boat floats in.
+#[allow(dead_code)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Water {
+    Salt,
+    Fresh,
+}
+
+impl Water {
+    pub fn density(self) -> Density {
+        match self {
+            Water::Salt => Density::from_kilogram_meter3(SEA_WATER_DENSITY),
+            Water::Fresh => Density::from_kilogram_meter3(1000.0),
+        }
+    }
+
+    /// Kinematic viscosity at 15 degree Celsius (m2/s), ITTC.
+    pub fn kinematic_viscosity(self) -> f64 {
+        match self {
+            Water::Salt => 1.18831e-6,
+            Water::Fresh => 1.13902e-6,
+        }
+    }
+}
+
+impl fmt::Display for Water {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Water::Salt => write!(f, "Salt water"),
+            Water::Fresh => write!(f, "Fresh water"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// IMMERSION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Immersion
+///
+/// How much the boat sinks when loaded, assuming wall sided hull near the waterline,
+/// valid for loads small compared to the displacement.
+pub struct Immersion {
+    waterplane_area: Area,
+    water: Water,
+}
+
+#[allow(dead_code)]
+impl Immersion {
+    pub fn new(waterplane_area: Area, water: Water) -> Immersion {
+        Immersion {
+            waterplane_area,
+            water,
+        }
+    }
+
+    /// Needs Cw to be informed.
+    pub fn from_boat(boat: &Boat, water: Water) -> Option<Immersion> {
+        Some(Immersion::new(boat.waterplane_area()?, water))
+    }
+
+    /// Weight needed to sink the boat a length.
+    pub fn weight_per(&self, length: Length) -> Weight {
+        Weight::from_kilogram(
+            self.waterplane_area.to_meter2()
+                * length.to_meter()
+                * self.water.density().to_kilogram_meter3(),
+        )
+    }
+
+    /// Weight needed to sink the boat one centimeter.
+    pub fn weight_per_centimeter(&self) -> Weight {
+        self.weight_per(Length::from_millimeter(10.0))
+    }
+
+    /// Weight needed to sink the boat one inch (PPI).
+    pub fn weight_per_inch(&self) -> Weight {
+        self.weight_per(Length::from_inch(1.0))
+    }
+
+    /// Sinkage when loading a weight (crew, gear, stores).
+    pub fn sinkage(&self, load: Weight) -> Length {
+        Length::from_meter(
+            load.to_kilogram()
+                / (self.waterplane_area.to_meter2() * self.water.density().to_kilogram_meter3()),
+        )
+    }
+}
+
+impl fmt::Display for Immersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Immersion - {}]\n\
+            \tkg/cm: {:>9.1}\n\
+            \tlb/in: {:>9.1}",
+            self.water,
+            self.weight_per_centimeter().to_kilogram(),
+            self.weight_per_inch().to_pound()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HYDROSTATICS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Integral of y over x by Simpson's rule, x increasing, not necessarily evenly spaced.
+/// Pairs of intervals by the parabola through three points, the last interval by the trapezoid
+/// when the number of intervals is odd.
+pub fn simpson(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len().min(y.len());
+    let mut integral = 0.0;
+    let mut i = 0;
+    while i + 2 < n {
+        let h0 = x[i + 1] - x[i];
+        let h1 = x[i + 2] - x[i + 1];
+        if h0 > 0.0 && h1 > 0.0 {
+            integral += (h0 + h1) / 6.0
+                * ((2.0 - h1 / h0) * y[i]
+                    + (h0 + h1).powi(2) / (h0 * h1) * y[i + 1]
+                    + (2.0 - h0 / h1) * y[i + 2]);
+        }
+        i += 2;
+    }
+    if i + 1 < n {
+        integral += (x[i + 1] - x[i]) * (y[i] + y[i + 1]) / 2.0;
+    }
+    integral
+}
+
+/// Hydrostatics
+///
+/// Hull geometry integrated below a waterline, the sectional areas of the stations integrated
+/// along the length by Simpson's rule. The LCB is the centroid of the sectional areas and the
+/// VCB the centroid of their vertical moments. The waterplane is integrated the same way from
+/// the half-breadths of the stations at the waterline, its moments of inertia in m4:
+/// It = 2/3 * Integral(y^3 dx), about the centerline
+/// IL = 2 * Integral(y * x^2 dx) - Aw * LCF^2, about the LCF
+/// The wetted surface integrates the girths of the stations, neglecting the slope of the surface
+/// along the length, small on the middle body, it underestimates the ends a little.
+/// Trimmed, each station is taken to its own waterline height and the waterplane is the one
+/// projected on the baseline, fine for the small trims of a sailing yacht.
+pub struct Hydrostatics {
+    waterline: Length,
+    trim: f64,
+    reference: Length,
+    water: Water,
+    stations: Vec<Length>,
+    sectional_areas: Vec<Area>,
+    volume: Volume,
+    lcb: Option<Length>,
+    vcb: Option<Length>,
+    bottom: Length,
+    waterplane_area: Area,
+    waterplane_start: Length,
+    waterplane_length: Length,
+    waterline_beam: Length,
+    lcf: Option<Length>,
+    transverse_inertia: f64,
+    longitudinal_inertia: f64,
+    wetted_surface: Area,
+}
+
+#[allow(dead_code)]
+impl Hydrostatics {
+    /// Level waterline, at a height above the baseline.
+    pub fn new(hull: &Hull, waterline: Length, water: Water) -> Hydrostatics {
+        Hydrostatics::with_trim(hull, waterline, 0.0, water)
+    }
+
+    /// Trimmed waterline, at a height above the baseline midway between the end sections and
+    /// a trim angle in degrees, positive by the stern.
+    pub fn with_trim(hull: &Hull, waterline: Length, trim: f64, water: Water) -> Hydrostatics {
+        let (first, last) = hull.range();
+        let reference = (first.to_meter() + last.to_meter()) / 2.0;
+        let slope = trim.to_radians().tan();
+        let level = |s: &Section| {
+            Length::from_meter(waterline.to_meter() + (s.position().to_meter() - reference) * slope)
+        };
+        let stations: Vec<Length> = hull.sections().iter().map(|s| s.position()).collect();
+        let sectional_areas: Vec<Area> = hull
+            .sections()
+            .iter()
+            .map(|s| s.area_below(level(s)))
+            .collect();
+        let x: Vec<f64> = stations.iter().map(|x| x.to_meter()).collect();
+        let areas: Vec<f64> = sectional_areas.iter().map(|a| a.to_meter2()).collect();
+        let volume = simpson(&x, &areas);
+        let longitudinal: Vec<f64> = x.iter().zip(&areas).map(|(x, a)| x * a).collect();
+        let vertical: Vec<f64> = hull
+            .sections()
+            .iter()
+            .zip(&areas)
+            .map(|(s, a)| s.centroid_below(level(s)).map_or(0.0, |z| z.to_meter() * a))
+            .collect();
+        let half_breadths: Vec<f64> = hull
+            .sections()
+            .iter()
+            .map(|s| {
+                let waterline = level(s);
+                if waterline.to_meter() < s.bottom().to_meter() {
+                    0.0
+                } else if waterline.to_meter() > s.top().to_meter() {
+                    s.half_breadth(s.top()).map_or(0.0, |y| y.to_meter())
+                } else {
+                    s.half_breadth(waterline).map_or(0.0, |y| y.to_meter())
+                }
+            })
+            .collect();
+        let breadths: Vec<f64> = half_breadths.iter().map(|y| 2.0 * y).collect();
+        let waterplane_area = simpson(&x, &breadths);
+        let first_moments: Vec<f64> = x.iter().zip(&breadths).map(|(x, b)| x * b).collect();
+        let second_moments: Vec<f64> = x.iter().zip(&breadths).map(|(x, b)| x * x * b).collect();
+        let cubes: Vec<f64> = half_breadths
+            .iter()
+            .map(|y| 2.0 / 3.0 * y.powi(3))
+            .collect();
+        let lcf = if waterplane_area > 0.0 {
+            Some(simpson(&x, &first_moments) / waterplane_area)
+        } else {
+            None
+        };
+        // From the station before the first with beam to the one after the last, where the
+        // waterline ends.
+        let (waterplane_start, waterplane_length) = match (
+            half_breadths.iter().position(|y| *y > 0.0),
+            half_breadths.iter().rposition(|y| *y > 0.0),
+        ) {
+            (Some(first), Some(last)) => {
+                let start = x[first.saturating_sub(1)];
+                (start, x[(last + 1).min(x.len() - 1)] - start)
+            }
+            _ => (0.0, 0.0),
+        };
+        let girths: Vec<f64> = hull
+            .sections()
+            .iter()
+            .map(|s| s.girth_below(level(s)).to_meter())
+            .collect();
+        let centroid = |moments: &[f64]| {
+            if volume > 0.0 {
+                Some(Length::from_meter(simpson(&x, moments) / volume))
+            } else {
+                None
+            }
+        };
+        Hydrostatics {
+            waterline,
+            trim,
+            reference: Length::from_meter(reference),
+            water,
+            stations,
+            sectional_areas,
+            volume: Volume::from_meter3(volume),
+            lcb: centroid(&longitudinal),
+            vcb: centroid(&vertical),
+            bottom: hull
+                .sections()
+                .iter()
+                .map(|s| s.bottom())
+                .fold(waterline, |low, z| {
+                    if z.to_meter() < low.to_meter() {
+                        z
+                    } else {
+                        low
+                    }
+                }),
+            waterplane_area: Area::from_meter2(waterplane_area),
+            waterplane_start: Length::from_meter(waterplane_start),
+            waterplane_length: Length::from_meter(waterplane_length),
+            waterline_beam: Length::from_meter(breadths.iter().cloned().fold(0.0, f64::max)),
+            lcf: lcf.map(Length::from_meter),
+            transverse_inertia: simpson(&x, &cubes),
+            longitudinal_inertia: lcf.map_or(0.0, |lcf| {
+                simpson(&x, &second_moments) - waterplane_area * lcf * lcf
+            }),
+            wetted_surface: Area::from_meter2(simpson(&x, &girths)),
+        }
+    }
+
+    /// Waterline height above the baseline, midway between the end sections when trimmed.
+    pub fn waterline(&self) -> Length {
+        self.waterline
+    }
+
+    /// Trim angle in degrees, positive by the stern.
+    pub fn trim(&self) -> f64 {
+        self.trim
+    }
+
+    /// Waterline height at a position aft of the forward perpendicular.
+    pub fn waterline_at(&self, x: Length) -> Length {
+        Length::from_meter(
+            self.waterline.to_meter()
+                + (x.to_meter() - self.reference.to_meter()) * self.trim.to_radians().tan(),
+        )
+    }
+
+    pub fn water(&self) -> Water {
+        self.water
+    }
+
+    pub fn stations(&self) -> &[Length] {
+        &self.stations
+    }
+
+    /// Immersed area of the stations, both sides.
+    pub fn sectional_areas(&self) -> &[Area] {
+        &self.sectional_areas
+    }
+
+    /// Displaced volume.
+    pub fn volume(&self) -> Volume {
+        self.volume
+    }
+
+    /// Displacement, the weight of the displaced water.
+    pub fn displacement(&self) -> Weight {
+        self.water.density().weight(self.volume)
+    }
+
+    /// LCB (longitudinal center of buoyancy) aft of the forward perpendicular.
+    pub fn lcb(&self) -> Option<Length> {
+        self.lcb
+    }
+
+    /// LCB as % of DWL aft of the forward perpendicular, the way the boat enters it.
+    pub fn lcb_percent(&self, dwl: Length) -> Option<f64> {
+        Some(self.lcb?.to_meter() / dwl.to_meter() * 100.0)
+    }
+
+    /// VCB (vertical center of buoyancy) above the baseline.
+    pub fn vcb(&self) -> Option<Length> {
+        self.vcb
+    }
+
+    /// KB (height of the center of buoyancy) above the bottom of the canoe body.
+    pub fn kb(&self) -> Option<Length> {
+        Some(self.vcb? - self.bottom)
+    }
+
+    /// Set the boat LCB computed from the geometry, instead of the entered one.
+    pub fn update_lcb(&self, boat: &mut Boat) {
+        if let Some(lcb) = self.lcb_percent(boat.dwl()) {
+            boat.set_lcb(lcb);
+        }
+    }
+
+    /// Waterplane area.
+    pub fn waterplane_area(&self) -> Area {
+        self.waterplane_area
+    }
+
+    /// Forward end of the waterplane, aft of the forward perpendicular.
+    pub fn waterplane_start(&self) -> Length {
+        self.waterplane_start
+    }
+
+    /// Length of the waterplane, to the stations where it ends, next to the ones with beam.
+    pub fn waterplane_length(&self) -> Length {
+        self.waterplane_length
+    }
+
+    /// Beam at the waterline, the widest station.
+    pub fn waterline_beam(&self) -> Length {
+        self.waterline_beam
+    }
+
+    /// Canoe body draft, the waterline above the lowest keel point.
+    pub fn draft(&self) -> Length {
+        self.waterline - self.bottom
+    }
+
+    /// Largest sectional area, Am.
+    pub fn maximum_sectional_area(&self) -> Area {
+        Area::from_meter2(
+            self.sectional_areas
+                .iter()
+                .map(|a| a.to_meter2())
+                .fold(0.0, f64::max),
+        )
+    }
+
+    /// Form coefficients of the immersed geometry, None when nothing is immersed.
+    pub fn form_coefficients(&self) -> Option<FormCoefficients> {
+        let l = self.waterplane_length.to_meter();
+        let b = self.waterline_beam.to_meter();
+        let t = self.draft().to_meter();
+        let am = self.maximum_sectional_area().to_meter2();
+        let v = self.volume.to_meter3();
+        if l <= 0.0 || b <= 0.0 || t <= 0.0 || am <= 0.0 {
+            return None;
+        }
+        Some(FormCoefficients {
+            cp: v / (am * l),
+            cb: v / (l * b * t),
+            cm: am / (b * t),
+            cw: self.waterplane_area.to_meter2() / (l * b),
+        })
+    }
+
+    /// Set the boat form coefficients computed from the geometry, instead of the entered ones.
+    pub fn update_coefficients(&self, boat: &mut Boat) {
+        if let Some(coefficients) = self.form_coefficients() {
+            boat.set_cp(coefficients.cp);
+            boat.set_cb(coefficients.cb);
+            boat.set_cm(coefficients.cm);
+            boat.set_cw(coefficients.cw);
+        }
+    }
+
+    /// SAC (sectional area curve) against the ideal Cp for the boat target speed.
+    pub fn sectional_area_curve(&self, boat: &Boat) -> Option<SectionalAreaCurve> {
+        SectionalAreaCurve::new(self, boat.target_slr())
+    }
+
+    /// LCF (longitudinal center of flotation) aft of the forward perpendicular.
+    pub fn lcf(&self) -> Option<Length> {
+        self.lcf
+    }
+
+    /// LCF as % of DWL aft of the forward perpendicular, the way the boat enters it.
+    pub fn lcf_percent(&self, dwl: Length) -> Option<f64> {
+        Some(self.lcf?.to_meter() / dwl.to_meter() * 100.0)
+    }
+
+    /// Set the boat LCF computed from the geometry, instead of the entered one.
+    pub fn update_lcf(&self, boat: &mut Boat) {
+        if let Some(lcf) = self.lcf_percent(boat.dwl()) {
+            boat.set_lcf(lcf);
+        }
+    }
+
+    /// Waterplane transverse moment of inertia about the centerline (m4).
+    pub fn transverse_inertia(&self) -> f64 {
+        self.transverse_inertia
+    }
+
+    /// Waterplane longitudinal moment of inertia about the LCF (m4).
+    pub fn longitudinal_inertia(&self) -> f64 {
+        self.longitudinal_inertia
+    }
+
+    /// BM (transverse metacentric radius), It / V.
+    pub fn bm(&self) -> Option<Length> {
+        self.metacentric_radius(self.transverse_inertia)
+    }
+
+    /// BML (longitudinal metacentric radius), IL / V.
+    pub fn bml(&self) -> Option<Length> {
+        self.metacentric_radius(self.longitudinal_inertia)
+    }
+
+    fn metacentric_radius(&self, inertia: f64) -> Option<Length> {
+        if self.volume.to_meter3() <= 0.0 {
+            return None;
+        }
+        Some(Length::from_meter(inertia / self.volume.to_meter3()))
+    }
+
+    /// Immersion of the waterplane.
+    pub fn immersion(&self) -> Immersion {
+        Immersion::new(self.waterplane_area, self.water)
+    }
+
+    /// MCT (moment to change trim one centimeter) in kg.m, approximating GML by BML.
+    ///
+    /// MCT = Displacement * BML / (100 * LWL)
+    pub fn moment_to_trim_centimeter(&self) -> Option<f64> {
+        if self.waterplane_length.to_meter() <= 0.0 {
+            return None;
+        }
+        Some(
+            self.displacement().to_kilogram() * self.bml()?.to_meter()
+                / (100.0 * self.waterplane_length.to_meter()),
+        )
+    }
+
+    /// Wetted surface of the canoe body.
+    pub fn wetted_surface(&self) -> Area {
+        self.wetted_surface
+    }
+
+    /// Set the boat wetted surface computed from the geometry plus the keel, instead of the
+    /// entered or estimated one, for SA/WS and the resistance. The keel and the rudder count
+    /// from their foil geometry when positioned.
+    pub fn update_wetted_surface(&self, boat: &mut Boat) {
+        let keel = boat.keel().map_or(0.0, |k| match k.geometry() {
+            Some(geometry) => geometry.wetted_surface().to_meter2() * k.count() as f64,
+            None => k.wetted_surface().to_meter2(),
+        });
+        let rudder = boat
+            .rudder()
+            .and_then(|r| r.geometry())
+            .map_or(0.0, |g| g.wetted_surface().to_meter2());
+        boat.set_wetted_surface(Area::from_meter2(
+            self.wetted_surface.to_meter2() + keel + rudder,
+        ));
+    }
+
+    /// Relative difference between the displacement of the geometry and the entered one.
+    pub fn displacement_discrepancy(&self, boat: &Boat) -> Option<f64> {
+        let entered = boat.displacement().to_kilogram();
+        if entered <= 0.0 {
+            return None;
+        }
+        Some((self.displacement().to_kilogram() - entered) / entered)
+    }
+}
+
+impl fmt::Display for Hydrostatics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Hydrostatics - {}]\n\
+            \tWaterline:    {:>9.3}m\n\
+            \tVolume:       {:>9.3}m3\n\
+            \tDisplacement: {:>9.0}kg\n\
+            \tLCB:          {:>9.3}m\n\
+            \tKB:           {:>9.3}m\n\
+            \tAw:           {:>9.3}m2\n\
+            \tLCF:          {:>9.3}m\n\
+            \tBM:           {:>9.3}m\n\
+            \tBML:          {:>9.3}m\n\
+            \tWS:           {:>9.3}m2",
+            self.water,
+            self.waterline.to_meter(),
+            self.volume.to_meter3(),
+            self.displacement().to_kilogram(),
+            self.lcb.map_or(0.0, |x| x.to_meter()),
+            self.kb().map_or(0.0, |z| z.to_meter()),
+            self.waterplane_area.to_meter2(),
+            self.lcf.map_or(0.0, |x| x.to_meter()),
+            self.bm().map_or(0.0, |z| z.to_meter()),
+            self.bml().map_or(0.0, |z| z.to_meter()),
+            self.wetted_surface.to_meter2()
+        )
+    }
+}
+
+/// Form coefficients
+///
+/// Cp = V / (Am * LWL)
+/// Cb = V / (LWL * BWL * T)
+/// Cm = Am / (BWL * T)
+/// Cw = Aw / (LWL * BWL)
+#[derive(Debug, Copy, Clone)]
+pub struct FormCoefficients {
+    pub cp: f64,
+    pub cb: f64,
+    pub cm: f64,
+    pub cw: f64,
+}
+
+impl fmt::Display for FormCoefficients {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Form coefficients]\n\
+            \tCp: {:>7.3}\n\
+            \tCb: {:>7.3}\n\
+            \tCm: {:>7.3}\n\
+            \tCw: {:>7.3}",
+            self.cp, self.cb, self.cm, self.cw
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// EQUILIBRIUM
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Iterations of the equilibrium solver.
+pub const EQUILIBRIUM_ITERATIONS: usize = 50;
+
+/// Equilibrium tolerance, relative for the volume, meters for the LCB over the LCG.
+pub const EQUILIBRIUM_TOLERANCE: (f64, f64) = (1e-5, 1e-4);
+
+/// Equilibrium
+///
+/// Floating waterline of the hull for a displacement and LCG. Each iteration sinks the hull
+/// by the missing volume over the waterplane area, about the LCF, and trims it by the distance
+/// of the LCB to the LCG over the BML, until the volume and the LCB match.
+pub struct Equilibrium {
+    hydrostatics: Hydrostatics,
+    iterations: usize,
+    converged: bool,
+}
+
+#[allow(dead_code)]
+impl Equilibrium {
+    /// Level when the LCG is not known. None when the hull can not float the displacement,
+    /// the hull deeper than its sheer.
+    pub fn solve(
+        hull: &Hull,
+        displacement: Weight,
+        lcg: Option<Length>,
+        water: Water,
+    ) -> Option<Equilibrium> {
+        let target = water.density().volume(displacement).to_meter3();
+        if target <= 0.0 {
+            return None;
+        }
+        let bottom = hull
+            .sections()
+            .iter()
+            .map(|s| s.bottom().to_meter())
+            .fold(f64::MAX, f64::min);
+        let top = hull
+            .sections()
+            .iter()
+            .map(|s| s.top().to_meter())
+            .fold(f64::MIN, f64::max);
+        // Start a tenth of the depth up.
+        let mut waterline = bottom + (top - bottom) / 10.0;
+        let mut trim: f64 = 0.0;
+        let mut hydrostatics =
+            Hydrostatics::with_trim(hull, Length::from_meter(waterline), trim, water);
+        for iteration in 1..=EQUILIBRIUM_ITERATIONS {
+            let volume = hydrostatics.volume().to_meter3();
+            let area = hydrostatics.waterplane_area().to_meter2();
+            if area <= 0.0 {
+                // Below the hull, move up.
+                waterline += (top - bottom) / 10.0;
+            } else {
+                let sinkage = (target - volume) / area;
+                let mut trimming = 0.0;
+                if let (Some(lcg), Some(lcb), Some(bml), Some(lcf)) = (
+                    lcg,
+                    hydrostatics.lcb(),
+                    hydrostatics.bml(),
+                    hydrostatics.lcf(),
+                ) {
+                    if bml.to_meter() > 0.0 {
+                        trimming = ((lcg.to_meter() - lcb.to_meter()) / bml.to_meter()).atan();
+                        // Trim about the LCF, keeping the volume.
+                        let pivot = lcf.to_meter() - hydrostatics.reference.to_meter();
+                        let new_trim = trim.to_radians() + trimming;
+                        waterline -= pivot * (new_trim.tan() - trim.to_radians().tan());
+                    }
+                }
+                waterline += sinkage;
+                trim += trimming.to_degrees();
+            }
+            hydrostatics =
+                Hydrostatics::with_trim(hull, Length::from_meter(waterline), trim, water);
+            let volume_error = (hydrostatics.volume().to_meter3() - target).abs() / target;
+            let lcb_error = match (lcg, hydrostatics.lcb()) {
+                (Some(lcg), Some(lcb)) => (lcg.to_meter() - lcb.to_meter()).abs(),
+                _ => 0.0,
+            };
+            if volume_error < EQUILIBRIUM_TOLERANCE.0 && lcb_error < EQUILIBRIUM_TOLERANCE.1 {
+                return Equilibrium::floating(hull, hydrostatics, iteration, true);
+            }
+        }
+        Equilibrium::floating(hull, hydrostatics, EQUILIBRIUM_ITERATIONS, false)
+    }
+
+    /// None when the sheer is immersed at a station.
+    fn floating(
+        hull: &Hull,
+        hydrostatics: Hydrostatics,
+        iterations: usize,
+        converged: bool,
+    ) -> Option<Equilibrium> {
+        let immersed = hull.sections().iter().any(|s| {
+            hydrostatics.waterline_at(s.position()).to_meter() > s.top().to_meter() + 1e-9
+        });
+        if immersed {
+            return None;
+        }
+        Some(Equilibrium {
+            hydrostatics,
+            iterations,
+            converged,
+        })
+    }
+
+    /// Hydrostatics at the floating waterline.
+    pub fn hydrostatics(&self) -> &Hydrostatics {
+        &self.hydrostatics
+    }
+
+    /// Waterline height above the baseline, midway between the end sections.
+    pub fn waterline(&self) -> Length {
+        self.hydrostatics.waterline()
+    }
+
+    /// Trim angle in degrees, positive by the stern.
+    pub fn trim(&self) -> f64 {
+        self.hydrostatics.trim()
+    }
+
+    /// Waterline height above the baseline at the foremost section.
+    pub fn forward_waterline(&self) -> Length {
+        self.hydrostatics
+            .waterline_at(self.hydrostatics.stations()[0])
+    }
+
+    /// Waterline height above the baseline at the aftmost section.
+    pub fn aft_waterline(&self) -> Length {
+        let stations = self.hydrostatics.stations();
+        self.hydrostatics.waterline_at(stations[stations.len() - 1])
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// The volume and LCB matched inside the tolerance.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl fmt::Display for Equilibrium {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Equilibrium]\n\
+            \tWaterline:    {:>9.3}m\n\
+            \tForward:      {:>9.3}m\n\
+            \tAft:          {:>9.3}m\n\
+            \tTrim:         {:>9.2}deg\n\
+            \tDisplacement: {:>9.0}kg ({} iterations{})",
+            self.waterline().to_meter(),
+            self.forward_waterline().to_meter(),
+            self.aft_waterline().to_meter(),
+            self.trim(),
+            self.hydrostatics.displacement().to_kilogram(),
+            self.iterations,
+            if self.converged {
+                ""
+            } else {
+                ", not converged"
+            }
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SECTIONAL AREA CURVE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// SAC (sectional area curve)
+///
+/// Sectional areas along the length, nondimensional by the waterplane length and the largest
+/// area. The area under the curve is the Cp, compared with the ideal Cp for the target SLR.
+pub struct SectionalAreaCurve {
+    points: Vec<(f64, f64)>,
+    cp: f64,
+    ideal: f64,
+    target_slr: f64,
+}
+
+#[allow(dead_code)]
+impl SectionalAreaCurve {
+    /// None when nothing is immersed.
+    pub fn new(hydrostatics: &Hydrostatics, target_slr: f64) -> Option<SectionalAreaCurve> {
+        let coefficients = hydrostatics.form_coefficients()?;
+        let am = hydrostatics.maximum_sectional_area().to_meter2();
+        let stations = hydrostatics.stations();
+        let first = hydrostatics.waterplane_start().to_meter();
+        let length = hydrostatics.waterplane_length().to_meter();
+        Some(SectionalAreaCurve {
+            points: stations
+                .iter()
+                .zip(hydrostatics.sectional_areas())
+                .map(|(x, a)| ((x.to_meter() - first) / length, a.to_meter2() / am))
+                .collect(),
+            cp: coefficients.cp,
+            ideal: ideal_prismatic(target_slr),
+            target_slr,
+        })
+    }
+
+    /// (Position / LWL, Area / Am) points.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    pub fn cp(&self) -> f64 {
+        self.cp
+    }
+
+    /// Ideal Cp for the target SLR.
+    pub fn ideal(&self) -> f64 {
+        self.ideal
+    }
+
+    pub fn target_slr(&self) -> f64 {
+        self.target_slr
+    }
+
+    /// Cp minus the ideal Cp, positive when the ends are fuller than ideal.
+    pub fn difference(&self) -> f64 {
+        self.cp - self.ideal
+    }
+
+    /// Cp inside the tolerance around the ideal.
+    pub fn is_ideal(&self) -> bool {
+        self.difference().abs() <= PRISMATIC_TOLERANCE
+    }
+
+    /// Plot the curve in a width by height box at the context origin, bow on the left,
+    /// with the Cp and the ideal Cp as the heights of rectangles of the same area (dashed).
+    /// The frame on the grid layer, the curve on the hull layer and the Cp lines and their
+    /// labels on the annotations layer.
+    pub fn draw(&self, context: &Context, width: f64, height: f64, style: &DrawingStyle) {
+        let to_x = |x: f64| x * width;
+        let to_y = |y: f64| height * (1.0 - y);
+        context.save();
+        style.select_font(context);
+        if style.apply(context, LineKind::Grid) {
+            context.rectangle(0.0, 0.0, width, height);
+            context.stroke();
+        }
+
+        if style.apply(context, LineKind::Curves) {
+            for (i, &(x, y)) in self.points.iter().enumerate() {
+                if i == 0 {
+                    context.move_to(to_x(x), to_y(y));
+                } else {
+                    context.line_to(to_x(x), to_y(y));
+                }
+            }
+            context.stroke();
+        }
+
+        if style.apply(context, LineKind::Dimensions) {
+            context.set_dash(&GRID_DASH, 0.0);
+            for (label, value) in &[("Cp", self.cp), ("Ideal", self.ideal)] {
+                context.move_to(0.0, to_y(*value));
+                context.line_to(width, to_y(*value));
+                context.stroke();
+                context.move_to(4.0, to_y(*value) - 4.0);
+                context.show_text(&format!("{} {:.3}", label, value));
+            }
+            context.set_dash(&[], 0.0);
+        }
+        context.restore();
+    }
+}
+
+impl fmt::Display for SectionalAreaCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Sectional area curve]\n\
+            \tCp:    {:>7.3}\n\
+            \tIdeal: {:>7.3} (SLR {:.2})",
+            self.cp, self.ideal, self.target_slr
+        )?;
+        for (x, y) in &self.points {
+            write!(f, "\n\t{:>7.3} {:>7.3}", x, y)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// BONJEAN CURVES
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Heights of the Bonjean curves, from the lowest keel point to the highest sheer.
+pub const BONJEAN_HEIGHTS: usize = 20;
+
+/// Bonjean curves
+///
+/// Sectional area of each station against the height of the waterline, computed once from the
+/// hull, the displacement at any waterline, level or trimmed, then is read off the curves.
+pub struct BonjeanCurves {
+    stations: Vec<Length>,
+    heights: Vec<Length>,
+    /// Areas by station, then by height.
+    areas: Vec<Vec<Area>>,
+}
+
+#[allow(dead_code)]
+impl BonjeanCurves {
+    pub fn new(hull: &Hull) -> BonjeanCurves {
+        let bottom = hull
+            .sections()
+            .iter()
+            .map(|s| s.bottom().to_meter())
+            .fold(f64::MAX, f64::min);
+        let top = hull
+            .sections()
+            .iter()
+            .map(|s| s.top().to_meter())
+            .fold(f64::MIN, f64::max);
+        let heights: Vec<Length> = (0..=BONJEAN_HEIGHTS)
+            .map(|i| {
+                Length::from_meter(bottom + (top - bottom) * i as f64 / BONJEAN_HEIGHTS as f64)
+            })
+            .collect();
+        BonjeanCurves {
+            stations: hull.sections().iter().map(|s| s.position()).collect(),
+            areas: hull
+                .sections()
+                .iter()
+                .map(|s| heights.iter().map(|h| s.area_below(*h)).collect())
+                .collect(),
+            heights,
+        }
+    }
+
+    pub fn stations(&self) -> &[Length] {
+        &self.stations
+    }
+
+    pub fn heights(&self) -> &[Length] {
+        &self.heights
+    }
+
+    /// Curve of a station, (height, area) points.
+    pub fn curve(&self, station: usize) -> Option<Vec<(Length, Area)>> {
+        Some(
+            self.heights
+                .iter()
+                .copied()
+                .zip(self.areas.get(station)?.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// Sectional area of a station at a waterline height, interpolated, zero below the curves
+    /// and the area to the sheer above them.
+    pub fn area(&self, station: usize, height: Length) -> Option<Area> {
+        let areas = self.areas.get(station)?;
+        let z = height.to_meter();
+        let last = self.heights.len() - 1;
+        if z <= self.heights[0].to_meter() {
+            return Some(Area::from_meter2(0.0));
+        }
+        if z >= self.heights[last].to_meter() {
+            return Some(areas[last]);
+        }
+        let i = self.heights.windows(2).position(|h| z <= h[1].to_meter())?;
+        let (z0, z1) = (self.heights[i].to_meter(), self.heights[i + 1].to_meter());
+        let (a0, a1) = (areas[i].to_meter2(), areas[i + 1].to_meter2());
+        Some(Area::from_meter2(a0 + (a1 - a0) * (z - z0) / (z1 - z0)))
+    }
+
+    /// Displaced volume read off the curves, a waterline at a height midway between the end
+    /// stations and a trim in degrees, positive by the stern.
+    pub fn volume(&self, waterline: Length, trim: f64) -> Volume {
+        let x: Vec<f64> = self.stations.iter().map(|x| x.to_meter()).collect();
+        let reference = (x[0] + x[x.len() - 1]) / 2.0;
+        let slope = trim.to_radians().tan();
+        let areas: Vec<f64> = x
+            .iter()
+            .enumerate()
+            .map(|(i, position)| {
+                let height = waterline.to_meter() + (position - reference) * slope;
+                self.area(i, Length::from_meter(height))
+                    .map_or(0.0, |a| a.to_meter2())
+            })
+            .collect();
+        Volume::from_meter3(simpson(&x, &areas))
+    }
+
+    /// Displacement read off the curves.
+    pub fn displacement(&self, waterline: Length, trim: f64, water: Water) -> Weight {
+        water.density().weight(self.volume(waterline, trim))
+    }
+
+    /// Plot in a width by height box at the context origin, bow on the left and the baseline
+    /// at the bottom. Each curve starts on its station line, the areas scaled so the largest
+    /// one spans a station spacing. The frame and the station lines on the grid layer and the
+    /// curves on the hull layer.
+    pub fn draw(&self, context: &Context, width: f64, height: f64, style: &DrawingStyle) {
+        let first = self.stations[0].to_meter();
+        let length = self.stations[self.stations.len() - 1].to_meter() - first;
+        let bottom = self.heights[0].to_meter();
+        let depth = self.heights[self.heights.len() - 1].to_meter() - bottom;
+        if length <= 0.0 || depth <= 0.0 {
+            return;
+        }
+        let to_x = |x: f64| (x - first) / length * width;
+        let to_y = |z: f64| height * (1.0 - (z - bottom) / depth);
+        let largest = self
+            .areas
+            .iter()
+            .flatten()
+            .map(|a| a.to_meter2())
+            .fold(0.0, f64::max);
+        let spacing = length / (self.stations.len() - 1) as f64;
+        let scale = if largest > 0.0 {
+            spacing / largest
+        } else {
+            0.0
+        };
+        context.save();
+        if style.apply(context, LineKind::Grid) {
+            context.rectangle(0.0, 0.0, width, height);
+            for station in &self.stations {
+                context.move_to(to_x(station.to_meter()), to_y(bottom));
+                context.line_to(to_x(station.to_meter()), to_y(bottom + depth));
+            }
+            context.stroke();
+        }
+
+        if style.apply(context, LineKind::Curves) {
+            for (station, areas) in self.stations.iter().zip(&self.areas) {
+                let x = station.to_meter();
+                for (i, (z, area)) in self.heights.iter().zip(areas).enumerate() {
+                    let point = (to_x(x + area.to_meter2() * scale), to_y(z.to_meter()));
+                    if i == 0 {
+                        context.move_to(point.0, point.1);
+                    } else {
+                        context.line_to(point.0, point.1);
+                    }
+                }
+                context.stroke();
+            }
+        }
+        context.restore();
+    }
+}
+
+impl fmt::Display for BonjeanCurves {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Bonjean curves]\n\t{:>7}", "m")?;
+        for station in &self.stations {
+            write!(f, " {:>7.2}", station.to_meter())?;
+        }
+        for (i, height) in self.heights.iter().enumerate() {
+            write!(f, "\n\t{:>7.3}", height.to_meter())?;
+            for areas in &self.areas {
+                write!(f, " {:>7.3}", areas[i].to_meter2())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::spline::Interpolation;
+
+    #[test]
+    fn immersion() {
+        let immersion = Immersion::new(Area::from_meter2(20.0), Water::Salt);
+        assert_eq!(
+            format!("{:.1}", immersion.weight_per_centimeter().to_kilogram()),
+            "205.0"
+        );
+        assert_eq!(
+            format!(
+                "{:.0}",
+                immersion
+                    .sinkage(Weight::from_kilogram(410.0))
+                    .to_millimiter()
+            ),
+            "20"
+        );
+
+        let immersion = Immersion::new(Area::from_meter2(20.0), Water::Fresh);
+        assert_eq!(
+            format!("{:.1}", immersion.weight_per_centimeter().to_kilogram()),
+            "200.0"
+        );
+        assert_eq!(
+            format!("{:.1}", immersion.weight_per_inch().to_kilogram()),
+            "508.0"
+        );
+    }
+
+    #[test]
+    fn hydrostatics() {
+        // Parabola, exact by Simpson even unevenly spaced.
+        let x = [0.0, 1.0, 3.0];
+        let y: Vec<f64> = x.iter().map(|x| x * x).collect();
+        assert_eq!(format!("{:.6}", simpson(&x, &y)), "9.000000");
+
+        // V sections, 2m wide at the 1m waterline, over 4m.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 1.0), 1.0, (1.0, 1.2));
+        let hydrostatics = Hydrostatics::new(&hull, m(1.0), Water::Salt);
+        assert_eq!(
+            format!("{:.3}", hydrostatics.sectional_areas()[2].to_meter2()),
+            "1.000"
+        );
+        assert_eq!(format!("{:.3}", hydrostatics.volume().to_meter3()), "4.000");
+        assert_eq!(
+            format!("{:.0}", hydrostatics.displacement().to_kilogram()),
+            "4100"
+        );
+        let fresh = Hydrostatics::new(&hull, m(1.0), Water::Fresh);
+        assert_eq!(format!("{:.0}", fresh.displacement().to_kilogram()), "4000");
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_displacement(Weight::from_kilogram(0.0));
+        assert!(hydrostatics.displacement_discrepancy(&boat).is_none());
+        boat.set_displacement(Weight::from_kilogram(4000.0));
+        assert_eq!(
+            format!(
+                "{:.3}",
+                hydrostatics.displacement_discrepancy(&boat).unwrap()
+            ),
+            "0.025"
+        );
+    }
+
+    #[test]
+    fn center_of_buoyancy() {
+        // V sections 1m deep growing aft, areas 0, 0.25, 0.5, 0.75 and 1 m2.
+        let m = Length::from_meter;
+        let sections = (0..5)
+            .map(|i| {
+                let half_breadth = i as f64 / 4.0;
+                Section::new(
+                    m(i as f64),
+                    &[(m(0.0), m(0.0)), (m(half_breadth), m(1.0))],
+                    Interpolation::Linear,
+                )
+                .unwrap()
+            })
+            .collect();
+        let hull = Hull::new(sections, Interpolation::Linear).unwrap();
+        let hydrostatics = Hydrostatics::new(&hull, m(1.0), Water::Salt);
+        // Triangle distribution, centroid at 2/3 of the length.
+        assert_eq!(
+            format!("{:.4}", hydrostatics.volume().to_meter3()),
+            "2.0000"
+        );
+        assert_eq!(
+            format!("{:.3}", hydrostatics.lcb().unwrap().to_meter()),
+            "2.667"
+        );
+        assert_eq!(
+            format!("{:.1}", hydrostatics.lcb_percent(m(4.0)).unwrap()),
+            "66.7"
+        );
+        // V section centroid at 2/3 of the depth.
+        assert_eq!(
+            format!("{:.3}", hydrostatics.kb().unwrap().to_meter()),
+            "0.667"
+        );
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(m(4.0));
+        hydrostatics.update_lcb(&mut boat);
+        assert_eq!(format!("{:.1}", boat.lcb().unwrap()), "66.7");
+
+        let dry = Hydrostatics::new(&hull, m(-1.0), Water::Salt);
+        assert!(dry.lcb().is_none() && dry.kb().is_none());
+    }
+
+    #[test]
+    fn waterplane() {
+        // Box 4m long, 2m wide, floating at 0.5m.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.0), 1.0, (1.0, 1.0));
+        let hydrostatics = Hydrostatics::new(&hull, m(0.5), Water::Salt);
+        assert_eq!(
+            format!("{:.3}", hydrostatics.waterplane_area().to_meter2()),
+            "8.000"
+        );
+        assert_eq!(hydrostatics.waterplane_length().to_meter(), 4.0);
+        assert_eq!(
+            format!("{:.3}", hydrostatics.lcf().unwrap().to_meter()),
+            "2.000"
+        );
+        // L * B^3 / 12 and B * L^3 / 12.
+        assert_eq!(
+            format!("{:.4}", hydrostatics.transverse_inertia()),
+            "2.6667"
+        );
+        assert_eq!(
+            format!("{:.4}", hydrostatics.longitudinal_inertia()),
+            "10.6667"
+        );
+        // B^2 / (12 * T)
+        assert_eq!(
+            format!("{:.4}", hydrostatics.bm().unwrap().to_meter()),
+            "0.6667"
+        );
+        assert_eq!(
+            format!("{:.4}", hydrostatics.bml().unwrap().to_meter()),
+            "2.6667"
+        );
+        assert_eq!(
+            format!(
+                "{:.1}",
+                hydrostatics
+                    .immersion()
+                    .weight_per_centimeter()
+                    .to_kilogram()
+            ),
+            "82.0"
+        );
+        // 4100 * 2.6667 / 400
+        assert_eq!(
+            format!("{:.2}", hydrostatics.moment_to_trim_centimeter().unwrap()),
+            "27.33"
+        );
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(m(4.0));
+        hydrostatics.update_lcf(&mut boat);
+        assert_eq!(format!("{:.1}", boat.lcf().unwrap()), "50.0");
+
+        // Girth B + 2T over the length, the transom and bow ends not counted.
+        assert_eq!(
+            format!("{:.3}", hydrostatics.wetted_surface().to_meter2()),
+            "12.000"
+        );
+        hydrostatics.update_wetted_surface(&mut boat);
+        assert_eq!(
+            format!("{:.3}", boat.wetted_surface().to_meter2()),
+            "12.000"
+        );
+    }
+
+    #[test]
+    fn sectional_area_curve() {
+        use crate::drawing::{to_image, Layer, Layers};
+
+        // Diamond waterplane, V sections, 4m long and 2m wide at the 1m waterline.
+        let m = Length::from_meter;
+        let sections = (0..5)
+            .map(|i| {
+                let half_breadth = 1.0 - (i as f64 - 2.0).abs() / 2.0;
+                Section::new(
+                    m(i as f64),
+                    &[(m(0.0), m(0.0)), (m(half_breadth), m(1.0))],
+                    Interpolation::Linear,
+                )
+                .unwrap()
+            })
+            .collect();
+        let hull = Hull::new(sections, Interpolation::Linear).unwrap();
+        let hydrostatics = Hydrostatics::new(&hull, m(1.0), Water::Salt);
+        let coefficients = hydrostatics.form_coefficients().unwrap();
+        assert_eq!(format!("{:.3}", coefficients.cp), "0.500");
+        assert_eq!(format!("{:.3}", coefficients.cb), "0.250");
+        assert_eq!(format!("{:.3}", coefficients.cm), "0.500");
+        assert_eq!(format!("{:.3}", coefficients.cw), "0.500");
+
+        let mut boat = Boat::new("".to_string());
+        hydrostatics.update_coefficients(&mut boat);
+        assert_eq!(format!("{:.3}", boat.cb().unwrap()), "0.250");
+
+        let sac = hydrostatics.sectional_area_curve(&boat).unwrap();
+        assert_eq!(sac.points()[2], (0.5, 1.0));
+        assert_eq!(format!("{:.3}", sac.ideal()), "0.602");
+        assert!(!sac.is_ideal());
+
+        let mut style = DrawingStyle::new();
+        let plot = |style: &DrawingStyle| {
+            let mut image =
+                to_image(200, 100, |context| sac.draw(context, 200.0, 100.0, style)).unwrap();
+            let data = image.get_data().unwrap();
+            data.iter().any(|&v| v < 128)
+        };
+        assert!(plot(&style));
+        // Nothing with the layers of the plot hidden.
+        style.set_layers(Layers::only(&[Layer::Rig]));
+        assert!(!plot(&style));
+    }
+
+    #[test]
+    fn equilibrium() {
+        // Box 4m long, 2m wide and 1m deep.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.0), 1.0, (1.0, 1.0));
+
+        // 4 m3, level at 0.5m.
+        let level =
+            Equilibrium::solve(&hull, Weight::from_kilogram(4100.0), None, Water::Salt).unwrap();
+        assert!(level.converged());
+        assert_eq!(format!("{:.4}", level.waterline().to_meter()), "0.5000");
+        assert_eq!(level.trim(), 0.0);
+
+        // LCG 0.2m aft, trimmed by the stern with the LCB over the LCG.
+        let trimmed = Equilibrium::solve(
+            &hull,
+            Weight::from_kilogram(4100.0),
+            Some(m(2.2)),
+            Water::Salt,
+        )
+        .unwrap();
+        assert!(trimmed.converged());
+        assert_eq!(
+            format!("{:.3}", trimmed.hydrostatics().lcb().unwrap().to_meter()),
+            "2.200"
+        );
+        assert!(trimmed.trim() > 0.0);
+        assert!(trimmed.aft_waterline().to_meter() > trimmed.forward_waterline().to_meter());
+        // Wall sided box, the trim keeps the mean draft.
+        assert_eq!(format!("{:.3}", trimmed.waterline().to_meter()), "0.500");
+
+        // Too heavy, deeper than the sheer.
+        assert!(
+            Equilibrium::solve(&hull, Weight::from_kilogram(9000.0), None, Water::Salt).is_none()
+        );
+    }
+
+    #[test]
+    fn bonjean_curves() {
+        use crate::drawing::{to_image, Layer, Layers};
+
+        // V sections, 2m wide at the 1m sheer, over 4m.
+        let m = Length::from_meter;
+        let sections = (0..5)
+            .map(|i| {
+                Section::new(
+                    m(i as f64),
+                    &[(m(0.0), m(0.0)), (m(1.0), m(1.0))],
+                    Interpolation::Linear,
+                )
+                .unwrap()
+            })
+            .collect();
+        let hull = Hull::new(sections, Interpolation::Linear).unwrap();
+        let bonjean = BonjeanCurves::new(&hull);
+        assert_eq!(bonjean.heights().len(), BONJEAN_HEIGHTS + 1);
+        assert_eq!(bonjean.curve(0).unwrap().len(), BONJEAN_HEIGHTS + 1);
+        // z^2 between the computed heights.
+        assert_eq!(
+            format!("{:.3}", bonjean.area(2, m(0.5)).unwrap().to_meter2()),
+            "0.250"
+        );
+        assert_eq!(
+            bonjean.area(2, m(2.0)).unwrap().to_meter2(),
+            bonjean.area(2, m(1.0)).unwrap().to_meter2()
+        );
+        assert!(bonjean.area(5, m(0.5)).is_none());
+
+        // Same as the full integration.
+        let level = Hydrostatics::new(&hull, m(0.5), Water::Salt);
+        assert_eq!(
+            format!("{:.3}", bonjean.volume(m(0.5), 0.0).to_meter3()),
+            format!("{:.3}", level.volume().to_meter3())
+        );
+        let trimmed = Hydrostatics::with_trim(&hull, m(0.5), 5.0, Water::Salt);
+        let difference = bonjean.displacement(m(0.5), 5.0, Water::Salt).to_kilogram()
+            - trimmed.displacement().to_kilogram();
+        assert!(difference.abs() < 5.0);
+
+        let mut style = DrawingStyle::new();
+        let plot = |style: &DrawingStyle| {
+            let mut image = to_image(400, 100, |context| {
+                bonjean.draw(context, 400.0, 100.0, style)
+            })
+            .unwrap();
+            let data = image.get_data().unwrap();
+            data.iter().any(|&v| v < 128)
+        };
+        assert!(plot(&style));
+        // Nothing with the layers of the plot hidden.
+        style.set_layers(Layers::only(&[Layer::Rig]));
+        assert!(!plot(&style));
+    }
+}
@@ -1,42 +1,123 @@
-mod boat;
-mod si;
+pub mod aero;
+pub mod appendage;
+pub mod balance;
+pub mod boat;
+pub mod comparison;
+pub mod cost;
+pub mod csv;
+pub mod developability;
+pub mod development;
+pub mod diagnostics;
+pub mod drawing;
+pub mod dxf;
+pub mod fairness;
+pub mod fleet;
+pub mod foil;
+pub mod handicap;
+pub mod hull;
+pub mod hydrostatics;
+pub mod loading;
+pub mod material;
+pub mod mesh;
+pub mod obj;
+pub mod offsets;
+pub mod parametric;
+pub mod parent;
+pub mod performance;
+pub mod polar;
+pub mod propeller;
+pub mod propulsion;
+pub mod resistance;
+pub mod rig;
+pub mod scantlings;
+pub mod seakeeping;
+pub mod section;
+pub mod si;
+pub mod spline;
+pub mod stability;
+pub mod trailer;
+pub mod transform;
+pub mod vpp;
+pub mod weights;
+pub mod wind;
 
-use cairo::{Context, PdfSurface};
+use boat::ProjectError;
+use drawing::DrawingError;
 use gio::prelude::*;
 use gtk::prelude::*;
 use gtk::{Application, ApplicationWindow, Button};
-// use cairo::{Context, Format, ImageSurface};
 use si::Length;
-// use std::fs::File;
-
-pub fn run() {
-    let mut boat = boat::Boat::new("Sail cruiser".to_string());
-    boat.set_loa(Length::from_foot(13.0));
-    boat.set_b_max(Length::from_foot(4.0));
-    println!("{}", boat);
-
-    let ratios = boat::Ratios::new(&boat);
-    println!("\n{}", ratios);
-
-    // let surface =
-    // ImageSurface::create(Format::ARgb32, 600, 600).expect("Couldn’t create a surface!");
-    let surface = PdfSurface::new(600., 600., "/home/douglasmg7/Downloads/output.pdf")
-        .expect("Couldn’t create a surface!");
-    let context = Context::new(&surface);
-    context.set_source_rgb(1.0, 1.0, 1.0);
-    //  Fills the context with the current source value.
-    context.paint();
-    // Set to black.
-    context.set_source_rgb(0.0, 0.0, 0.0);
-    context.line_to(100.0, 100.0);
-    context.line_to(500.0, 100.0);
-    context.line_to(500.0, 500.0);
-    context.stroke();
-
-    // let mut file =
-    // File::create("/home/douglasmg7/Downloads/output.png").expect("Couldn’t create file.");
-    // surface.write(&mut file).expect("Couldn’t write to png");
-    surface.flush();
+use std::fmt;
+
+/// Drawing output when not given on the command line, in the working directory.
+pub const OUTPUT_FILE: &str = "output.pdf";
+
+/// Run error.
+#[derive(Debug)]
+pub enum RunError {
+    /// The project file could not be loaded.
+    Project(ProjectError),
+    /// The drawing could not be written.
+    Drawing(DrawingError),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::Project(err) => write!(f, "{}", err),
+            RunError::Drawing(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<ProjectError> for RunError {
+    fn from(err: ProjectError) -> RunError {
+        RunError::Project(err)
+    }
+}
+
+impl From<DrawingError> for RunError {
+    fn from(err: DrawingError) -> RunError {
+        RunError::Drawing(err)
+    }
+}
+
+/// Project file and drawing output path, PDF, SVG or PNG by the extension, from the command line.
+/// The output is the design particulars sheet of the boat.
+pub fn run() -> Result<(), RunError> {
+    // Project file from the command line, or the example boat.
+    let boat = match std::env::args().nth(1) {
+        Some(path) => boat::Boat::load(&path)?,
+        None => {
+            let mut boat = boat::Boat::new("Sail cruiser".to_string());
+            boat.set_loa(Length::from_foot(13.0));
+            boat.set_b_max(Length::from_foot(4.0));
+            boat
+        }
+    };
+
+    // Design particulars sheet.
+    let output = std::env::args()
+        .nth(2)
+        .unwrap_or_else(|| OUTPUT_FILE.to_string());
+    let sheet = drawing::Sheet::new(
+        drawing::PaperSize::A4,
+        drawing::Orientation::Portrait,
+        drawing::DrawingScale::FullSize,
+    );
+    let particulars = drawing::Particulars::from_boat(&boat, None, si::UnitSystem::Metric);
+    let title = drawing::TitleBlock::from_boat(&boat, &sheet, String::new(), "1".to_string());
+    sheet.target(&output)?.render(&|context, _, _| {
+        drawing::particulars_sheet(
+            context,
+            &sheet,
+            &particulars,
+            &drawing::DrawingStyle::new(),
+            &title,
+        );
+    })?;
 
     let application =
         Application::new(Some("com.github.gtk-rs.examples.basic"), Default::default())
@@ -57,4 +138,5 @@ pub fn run() {
     });
 
     application.run(&[]);
+    Ok(())
 }
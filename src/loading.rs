@@ -0,0 +1,711 @@
+use super::boat::Boat;
+use super::hull::Hull;
+use super::hydrostatics::{Equilibrium, Immersion, Water};
+use super::si::{Density, Length, Volume, Weight};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PAYLOAD
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Payload kind.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum PayloadKind {
+    Crew,
+    Stores,
+    Gear,
+}
+
+impl fmt::Display for PayloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PayloadKind::Crew => write!(f, "Crew"),
+            PayloadKind::Stores => write!(f, "Stores"),
+            PayloadKind::Gear => write!(f, "Gear"),
+        }
+    }
+}
+
+/// Payload item
+///
+/// Crew, stores or gear carried aboard.
+/// Items without a position are assumed to be stowed at the CG of the boat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadItem {
+    name: String,
+    kind: PayloadKind,
+    weight: Weight,
+    /// VCG (height above the bottom of the canoe body).
+    vcg: Option<Length>,
+    /// LCG (% of DWL aft of forward perpendicular).
+    lcg: Option<f64>,
+}
+
+#[allow(dead_code)]
+impl PayloadItem {
+    pub fn new(name: String, kind: PayloadKind, weight: Weight) -> PayloadItem {
+        PayloadItem {
+            name,
+            kind,
+            weight,
+            vcg: None,
+            lcg: None,
+        }
+    }
+
+    /// Item positioned aboard.
+    pub fn at(mut self, vcg: Length, lcg: f64) -> PayloadItem {
+        self.vcg = Some(vcg);
+        self.lcg = Some(lcg);
+        self
+    }
+
+    /// Item name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Payload kind.
+    pub fn kind(&self) -> PayloadKind {
+        self.kind
+    }
+
+    /// Weight.
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+
+    /// VCG (height above the bottom of the canoe body).
+    pub fn vcg(&self) -> Option<Length> {
+        self.vcg
+    }
+
+    /// LCG (% of DWL aft of forward perpendicular).
+    pub fn lcg(&self) -> Option<f64> {
+        self.lcg
+    }
+}
+
+/// Payload
+///
+/// List of crew, stores and gear of a load condition.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Payload {
+    items: Vec<PayloadItem>,
+}
+
+#[allow(dead_code)]
+impl Payload {
+    pub fn new() -> Payload {
+        Payload::default()
+    }
+
+    pub fn push(&mut self, item: PayloadItem) {
+        self.items.push(item);
+    }
+
+    pub fn items(&self) -> &[PayloadItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Total weight.
+    pub fn weight(&self) -> Weight {
+        Weight::from_kilogram(self.items.iter().map(|i| i.weight.to_kilogram()).sum())
+    }
+
+    /// Total weight of a kind.
+    pub fn weight_of(&self, kind: PayloadKind) -> Weight {
+        Weight::from_kilogram(
+            self.items
+                .iter()
+                .filter(|i| i.kind == kind)
+                .map(|i| i.weight.to_kilogram())
+                .sum(),
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TANK
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Diesel density (kg/m3).
+pub const DIESEL_DENSITY: f64 = 840.0;
+/// Gasoline density (kg/m3).
+pub const GASOLINE_DENSITY: f64 = 740.0;
+/// Fresh water density (kg/m3).
+pub const FRESH_WATER_DENSITY: f64 = 1000.0;
+
+/// Fluid of a tank.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Fluid {
+    Diesel,
+    Gasoline,
+    Water,
+}
+
+impl Fluid {
+    /// Fuel, burned by the engine.
+    pub fn is_fuel(&self) -> bool {
+        matches!(self, Fluid::Diesel | Fluid::Gasoline)
+    }
+}
+
+impl fmt::Display for Fluid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Fluid::Diesel => write!(f, "Diesel"),
+            Fluid::Gasoline => write!(f, "Gasoline"),
+            Fluid::Water => write!(f, "Water"),
+        }
+    }
+}
+
+/// Tank
+///
+/// Fuel or water tank, the fluid weight is located at the tank center whatever the fill.
+/// The free surface, when informed, is the length and breadth of the fluid surface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tank {
+    name: String,
+    capacity: Volume,
+    fluid: Fluid,
+    density: Density,
+    /// Fill fraction, 0 empty to 1 full.
+    fill: f64,
+    /// VCG (height above the bottom of the canoe body).
+    vcg: Option<Length>,
+    /// LCG (% of DWL aft of forward perpendicular).
+    lcg: Option<f64>,
+    free_surface: Option<(Length, Length)>,
+}
+
+#[allow(dead_code)]
+impl Tank {
+    /// Full tank.
+    pub fn new(name: String, capacity: Volume, fluid: Fluid, density: Density) -> Tank {
+        Tank {
+            name,
+            capacity,
+            fluid,
+            density,
+            fill: 1.0,
+            vcg: None,
+            lcg: None,
+            free_surface: None,
+        }
+    }
+
+    /// Diesel tank.
+    pub fn diesel(name: String, capacity: Volume) -> Tank {
+        Tank::new(
+            name,
+            capacity,
+            Fluid::Diesel,
+            Density::from_kilogram_meter3(DIESEL_DENSITY),
+        )
+    }
+
+    /// Gasoline tank.
+    pub fn gasoline(name: String, capacity: Volume) -> Tank {
+        Tank::new(
+            name,
+            capacity,
+            Fluid::Gasoline,
+            Density::from_kilogram_meter3(GASOLINE_DENSITY),
+        )
+    }
+
+    /// Fresh water tank.
+    pub fn water(name: String, capacity: Volume) -> Tank {
+        Tank::new(
+            name,
+            capacity,
+            Fluid::Water,
+            Density::from_kilogram_meter3(FRESH_WATER_DENSITY),
+        )
+    }
+
+    /// Tank positioned aboard.
+    pub fn at(mut self, vcg: Length, lcg: f64) -> Tank {
+        self.vcg = Some(vcg);
+        self.lcg = Some(lcg);
+        self
+    }
+
+    /// Tank with the free surface dimensions.
+    pub fn with_free_surface(mut self, length: Length, breadth: Length) -> Tank {
+        self.free_surface = Some((length, breadth));
+        self
+    }
+
+    /// Tank name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Capacity.
+    pub fn capacity(&self) -> Volume {
+        self.capacity
+    }
+
+    /// Fluid.
+    pub fn fluid(&self) -> Fluid {
+        self.fluid
+    }
+
+    /// Fluid density.
+    pub fn density(&self) -> Density {
+        self.density
+    }
+
+    /// Fill fraction.
+    pub fn fill(&self) -> f64 {
+        self.fill
+    }
+    /// Fill fraction, limited to 0 - 1.
+    #[allow(dead_code)]
+    pub fn set_fill(&mut self, val: f64) {
+        self.fill = val.clamp(0.0, 1.0);
+    }
+
+    /// VCG (height above the bottom of the canoe body).
+    pub fn vcg(&self) -> Option<Length> {
+        self.vcg
+    }
+
+    /// LCG (% of DWL aft of forward perpendicular).
+    pub fn lcg(&self) -> Option<f64> {
+        self.lcg
+    }
+
+    /// Free surface length and breadth.
+    pub fn free_surface(&self) -> Option<(Length, Length)> {
+        self.free_surface
+    }
+
+    /// Diesel or gasoline tank.
+    pub fn is_fuel(&self) -> bool {
+        self.fluid.is_fuel()
+    }
+
+    /// Fluid volume.
+    pub fn contents(&self) -> Volume {
+        Volume::from_meter3(self.capacity.to_meter3() * self.fill)
+    }
+
+    /// Fluid weight.
+    pub fn weight(&self) -> Weight {
+        self.density.weight(self.contents())
+    }
+
+    /// Transverse moment of inertia of the free surface (m4), l * b^3 / 12.
+    /// Zero when the tank is empty or pressed full, as the fluid cannot move.
+    pub fn free_surface_inertia(&self) -> f64 {
+        match self.free_surface {
+            Some((length, breadth)) if self.fill > 0.0 && self.fill < 1.0 => {
+                length.to_meter() * breadth.to_meter().powi(3) / 12.0
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// LOAD CONDITION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Load condition kind.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum LoadConditionKind {
+    /// Empty boat, no crew, stores or liquids.
+    Lightship,
+    /// Condition the DWL was drawn for.
+    Design,
+    /// Fully loaded for cruising, tanks full.
+    Full,
+}
+
+impl fmt::Display for LoadConditionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadConditionKind::Lightship => write!(f, "Lightship"),
+            LoadConditionKind::Design => write!(f, "Design"),
+            LoadConditionKind::Full => write!(f, "Full load"),
+        }
+    }
+}
+
+/// Load condition
+///
+/// Displacement and center of gravity of the boat in a given trim, plus the payload and
+/// tanks carried.
+/// D/L and stability change a lot between the empty boat and the boat loaded for cruising.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadCondition {
+    kind: LoadConditionKind,
+    /// Displacement without the payload and tanks.
+    displacement: Weight,
+    /// VCG without the payload and tanks (height above the bottom of the canoe body).
+    vcg: Option<Length>,
+    /// LCG without the payload and tanks (% of DWL aft of forward perpendicular).
+    lcg: Option<f64>,
+    #[serde(default)]
+    payload: Payload,
+    #[serde(default)]
+    tanks: Vec<Tank>,
+}
+
+#[allow(dead_code)]
+impl LoadCondition {
+    pub fn new(kind: LoadConditionKind, displacement: Weight) -> LoadCondition {
+        LoadCondition {
+            kind,
+            displacement,
+            vcg: None,
+            lcg: None,
+            payload: Payload::new(),
+            tanks: Vec::new(),
+        }
+    }
+
+    /// Load condition kind.
+    pub fn kind(&self) -> LoadConditionKind {
+        self.kind
+    }
+
+    /// Displacement, including the payload and tanks.
+    pub fn displacement(&self) -> Weight {
+        Weight::from_kilogram(
+            self.displacement.to_kilogram()
+                + self.payload.weight().to_kilogram()
+                + self.tanks_weight().to_kilogram(),
+        )
+    }
+    /// Displacement without the payload and tanks.
+    #[allow(dead_code)]
+    pub fn set_displacement(&mut self, val: Weight) {
+        self.displacement = val;
+    }
+
+    /// VCG, including the payload and tanks (height above the bottom of the canoe body).
+    pub fn vcg(&self) -> Option<Length> {
+        let vcg = self.vcg?.to_meter();
+        Some(Length::from_meter(self.center(
+            vcg,
+            |i| i.vcg.map(|v| v.to_meter()),
+            |t| t.vcg.map(|v| v.to_meter()),
+        )))
+    }
+    /// VCG without the payload and tanks.
+    #[allow(dead_code)]
+    pub fn set_vcg(&mut self, val: Length) {
+        self.vcg = Some(val);
+    }
+
+    /// LCG, including the payload and tanks (% of DWL aft of forward perpendicular).
+    pub fn lcg(&self) -> Option<f64> {
+        let lcg = self.lcg?;
+        Some(self.center(lcg, |i| i.lcg, |t| t.lcg))
+    }
+    /// LCG without the payload and tanks.
+    #[allow(dead_code)]
+    pub fn set_lcg(&mut self, val: f64) {
+        self.lcg = Some(val);
+    }
+
+    /// Payload.
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+    #[allow(dead_code)]
+    pub fn set_payload(&mut self, val: Payload) {
+        self.payload = val;
+    }
+
+    /// Add a payload item.
+    pub fn add_payload(&mut self, item: PayloadItem) {
+        self.payload.push(item);
+    }
+
+    /// Tanks.
+    pub fn tanks(&self) -> &[Tank] {
+        &self.tanks
+    }
+
+    /// Tank by name.
+    pub fn tank_mut(&mut self, name: &str) -> Option<&mut Tank> {
+        self.tanks.iter_mut().find(|t| t.name == name)
+    }
+
+    /// Add a tank.
+    pub fn add_tank(&mut self, tank: Tank) {
+        self.tanks.push(tank);
+    }
+
+    /// Capacity of the fuel tanks.
+    pub fn fuel_capacity(&self) -> Volume {
+        Volume::from_meter3(
+            self.tanks
+                .iter()
+                .filter(|t| t.is_fuel())
+                .map(|t| t.capacity.to_meter3())
+                .sum(),
+        )
+    }
+
+    /// Weight of the fluids in the tanks.
+    pub fn tanks_weight(&self) -> Weight {
+        Weight::from_kilogram(self.tanks.iter().map(|t| t.weight().to_kilogram()).sum())
+    }
+
+    /// Center of the weights, unpositioned items and tanks at the center without them.
+    fn center<F, G>(&self, center: f64, item_position: F, tank_position: G) -> f64
+    where
+        F: Fn(&PayloadItem) -> Option<f64>,
+        G: Fn(&Tank) -> Option<f64>,
+    {
+        let mut weight = self.displacement.to_kilogram();
+        let mut moment = weight * center;
+        let items = self
+            .payload
+            .items()
+            .iter()
+            .map(|i| (i.weight, item_position(i)));
+        let tanks = self.tanks.iter().map(|t| (t.weight(), tank_position(t)));
+        for (w, position) in items.chain(tanks) {
+            let w = w.to_kilogram();
+            weight += w;
+            moment += w * position.unwrap_or(center);
+        }
+        moment / weight
+    }
+
+    /// Boat floating in this condition.
+    ///
+    /// The draft changes by the sinkage of the weight difference from the design displacement
+    /// (needs Cw, otherwise the design draft is kept). In equilibrium the LCB is over the LCG.
+    pub fn apply(&self, boat: &Boat) -> Boat {
+        let mut loaded = boat.clone();
+        let displacement = self.displacement();
+        let load = displacement.to_kilogram() - boat.displacement().to_kilogram();
+        if let Some(immersion) = Immersion::from_boat(boat, Water::Salt) {
+            let sinkage = immersion.sinkage(Weight::from_kilogram(load));
+            loaded.set_draft(boat.draft() + sinkage);
+        }
+        loaded.set_displacement(displacement);
+        if let Some(vcg) = self.vcg() {
+            loaded.set_vcg(vcg);
+        }
+        if let Some(lcg) = self.lcg() {
+            loaded.set_lcb(lcg);
+        }
+        loaded
+    }
+
+    /// Floating waterline of the hull in this condition, sinkage and trim from the geometry
+    /// instead of the wall sided approximation. Level when the LCG is not known.
+    pub fn equilibrium(&self, boat: &Boat, hull: &Hull) -> Option<Equilibrium> {
+        let lcg = self
+            .lcg()
+            .map(|lcg| Length::from_meter(lcg / 100.0 * boat.dwl().to_meter()));
+        Equilibrium::solve(hull, self.displacement(), lcg, Water::Salt)
+    }
+}
+
+impl fmt::Display for LoadCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Load condition - {}]\n\
+            \tDisplacement: {:>9.1} [kg]\n\
+            \tVCG:          {:>9} [m]\n\
+            \tLCG:          {:>9} [%]",
+            self.kind,
+            self.displacement().to_kilogram(),
+            self.vcg()
+                .map_or("-".to_string(), |v| format!("{:.3}", v.to_meter())),
+            self.lcg().map_or("-".to_string(), |v| format!("{:.1}", v))
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn load_condition() {
+        use super::super::boat::Ratios;
+
+        let mut boat = Boat::new("Cruiser".to_string());
+        boat.set_dwl(Length::from_meter(10.0));
+        boat.set_bwl(Length::from_meter(3.0));
+        boat.set_draft(Length::from_meter(0.5));
+        boat.set_displacement(Weight::from_kilogram(6000.0));
+        boat.set_cw(0.7);
+
+        let mut full = LoadCondition::new(LoadConditionKind::Full, Weight::from_kilogram(7076.25));
+        full.set_vcg(Length::from_meter(0.8));
+        full.set_lcg(54.0);
+        boat.set_load_condition(full);
+        boat.set_load_condition(LoadCondition::new(
+            LoadConditionKind::Lightship,
+            Weight::from_kilogram(4500.0),
+        ));
+        assert_eq!(boat.load_conditions().len(), 2);
+
+        // 1076.25 kg over 21 m2 of waterplane.
+        let loaded = boat.in_condition(LoadConditionKind::Full).unwrap();
+        assert_eq!(format!("{:.3}", loaded.draft().to_meter()), "0.550");
+        assert_eq!(format!("{:.3}", loaded.vcg().unwrap().to_meter()), "0.800");
+        assert_eq!(loaded.lcb(), Some(54.0));
+        assert!(boat.in_condition(LoadConditionKind::Design).is_none());
+
+        let light = boat.in_condition(LoadConditionKind::Lightship).unwrap();
+        assert!(
+            Ratios::new(&light).displacement_length().value()
+                < Ratios::new(&loaded).displacement_length().value()
+        );
+
+        // Replaces the condition of the same kind.
+        boat.set_load_condition(LoadCondition::new(
+            LoadConditionKind::Full,
+            Weight::from_kilogram(6500.0),
+        ));
+        assert_eq!(boat.load_conditions().len(), 2);
+        assert_eq!(
+            boat.load_condition(LoadConditionKind::Full)
+                .unwrap()
+                .displacement()
+                .to_kilogram(),
+            6500.0
+        );
+    }
+
+    #[test]
+    fn payload() {
+        let mut condition =
+            LoadCondition::new(LoadConditionKind::Design, Weight::from_kilogram(1000.0));
+        condition.set_vcg(Length::from_meter(0.5));
+        condition.set_lcg(55.0);
+        condition.add_payload(
+            PayloadItem::new(
+                "Skipper".to_string(),
+                PayloadKind::Crew,
+                Weight::from_kilogram(100.0),
+            )
+            .at(Length::from_meter(1.0), 80.0),
+        );
+        condition.add_payload(PayloadItem::new(
+            "Anchor".to_string(),
+            PayloadKind::Gear,
+            Weight::from_kilogram(150.0),
+        ));
+        assert_eq!(condition.payload().items().len(), 2);
+        assert_eq!(
+            condition
+                .payload()
+                .weight_of(PayloadKind::Crew)
+                .to_kilogram(),
+            100.0
+        );
+        assert_eq!(condition.displacement().to_kilogram(), 1250.0);
+        // (1150 * 0.5 + 100 * 1.0) / 1250
+        assert_eq!(
+            format!("{:.2}", condition.vcg().unwrap().to_meter()),
+            "0.54"
+        );
+        // (1150 * 55 + 100 * 80) / 1250
+        assert_eq!(format!("{:.1}", condition.lcg().unwrap()), "57.0");
+    }
+
+    #[test]
+    fn tanks() {
+        let mut condition =
+            LoadCondition::new(LoadConditionKind::Full, Weight::from_kilogram(1000.0));
+        condition.set_vcg(Length::from_meter(0.5));
+        condition.set_lcg(55.0);
+        condition.add_tank(
+            Tank::water("Water".to_string(), Volume::from_liter(200.0))
+                .at(Length::from_meter(0.2), 40.0)
+                .with_free_surface(Length::from_meter(1.0), Length::from_meter(0.6)),
+        );
+        condition.add_tank(
+            Tank::diesel("Fuel".to_string(), Volume::from_liter(50.0))
+                .at(Length::from_meter(0.3), 70.0),
+        );
+        // 200 kg of water and 42 kg of diesel.
+        assert_eq!(
+            format!("{:.1}", condition.displacement().to_kilogram()),
+            "1242.0"
+        );
+        assert_eq!(format!("{:.1}", condition.lcg().unwrap()), "53.1");
+        assert_eq!(condition.tanks()[0].free_surface_inertia(), 0.0);
+
+        condition.tank_mut("Water").unwrap().set_fill(0.5);
+        assert_eq!(
+            format!("{:.1}", condition.displacement().to_kilogram()),
+            "1142.0"
+        );
+        assert_eq!(
+            format!("{:.4}", condition.tanks()[0].free_surface_inertia()),
+            "0.0180"
+        );
+    }
+
+    #[test]
+    fn fuel_tanks() {
+        let mut condition =
+            LoadCondition::new(LoadConditionKind::Full, Weight::from_kilogram(1000.0));
+        condition.add_tank(Tank::new(
+            "Fuel".to_string(),
+            Volume::from_liter(60.0),
+            Fluid::Diesel,
+            Density::from_kilogram_meter3(832.5),
+        ));
+        condition.add_tank(Tank::gasoline(
+            "Outboard".to_string(),
+            Volume::from_liter(20.0),
+        ));
+        condition.add_tank(Tank::water("Water".to_string(), Volume::from_liter(200.0)));
+        assert!(condition.tanks()[0].is_fuel());
+        assert!(!condition.tanks()[2].is_fuel());
+        assert_eq!(
+            format!("{:.1}", condition.fuel_capacity().to_liter()),
+            "80.0"
+        );
+    }
+
+    #[test]
+    fn load_condition_equilibrium() {
+        // Box 4m long, 2m wide and 1m deep, 4.1t floats level at 0.5m.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.0), 1.0, (1.0, 1.0));
+        let mut boat = Boat::new("Box".to_string());
+        boat.set_dwl(m(4.0));
+
+        let mut full = LoadCondition::new(LoadConditionKind::Full, Weight::from_kilogram(4100.0));
+        full.add_payload(
+            PayloadItem::new(
+                "Crew".to_string(),
+                PayloadKind::Crew,
+                Weight::from_kilogram(410.0),
+            )
+            .at(m(0.5), 75.0),
+        );
+        let level = full.equilibrium(&boat, &hull).unwrap();
+        assert_eq!(format!("{:.3}", level.waterline().to_meter()), "0.550");
+        assert_eq!(level.trim(), 0.0);
+
+        // Crew aft trims by the stern.
+        full.set_lcg(50.0);
+        let trimmed = full.equilibrium(&boat, &hull).unwrap();
+        assert!(trimmed.trim() > 0.0);
+    }
+}
@@ -1,3 +1,6 @@
 fn main() {
-    boat_plan::run();
+    if let Err(err) = boat_plan::run() {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,176 @@
+use super::si::{Area, Density, Length, Pressure, Weight};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// MATERIAL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Material kind.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum MaterialKind {
+    Plywood,
+    Timber,
+    Composite,
+    Metal,
+}
+
+impl fmt::Display for MaterialKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MaterialKind::Plywood => write!(f, "Plywood"),
+            MaterialKind::Timber => write!(f, "Timber"),
+            MaterialKind::Composite => write!(f, "Composite"),
+            MaterialKind::Metal => write!(f, "Metal"),
+        }
+    }
+}
+
+/// Mechanical properties.
+///
+/// Typical values, along the grain for timber and plywood face grain. For metals the flexural
+/// strength is the yield strength.
+#[derive(Debug, Copy, Clone)]
+pub struct MaterialProperties {
+    pub density: Density,
+    pub tensile_strength: Pressure,
+    pub flexural_strength: Pressure,
+    pub elastic_modulus: Pressure,
+}
+
+/// Hull material
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Material {
+    /// Okoume marine plywood (BS 1088).
+    OkoumePlywood,
+    /// Meranti marine plywood (BS 1088).
+    MerantiPlywood,
+    /// Douglas fir marine plywood (APA marine grade).
+    DouglasFirPlywood,
+    DouglasFir,
+    WesternRedCedar,
+    WhiteOak,
+    SitkaSpruce,
+    Mahogany,
+    /// Polyester and chopped strand mat laminate, about 30% glass by weight.
+    GrpChoppedStrand,
+    /// Polyester and woven roving laminate, about 50% glass by weight.
+    GrpWovenRoving,
+    /// Aluminum 5083-H116.
+    Aluminum,
+}
+
+#[allow(dead_code)]
+impl Material {
+    /// Material catalogue.
+    pub const ALL: [Material; 11] = [
+        Material::OkoumePlywood,
+        Material::MerantiPlywood,
+        Material::DouglasFirPlywood,
+        Material::DouglasFir,
+        Material::WesternRedCedar,
+        Material::WhiteOak,
+        Material::SitkaSpruce,
+        Material::Mahogany,
+        Material::GrpChoppedStrand,
+        Material::GrpWovenRoving,
+        Material::Aluminum,
+    ];
+
+    /// Material by name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Material> {
+        Material::ALL
+            .iter()
+            .copied()
+            .find(|m| m.to_string().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn kind(self) -> MaterialKind {
+        match self {
+            Material::OkoumePlywood | Material::MerantiPlywood | Material::DouglasFirPlywood => {
+                MaterialKind::Plywood
+            }
+            Material::DouglasFir
+            | Material::WesternRedCedar
+            | Material::WhiteOak
+            | Material::SitkaSpruce
+            | Material::Mahogany => MaterialKind::Timber,
+            Material::GrpChoppedStrand | Material::GrpWovenRoving => MaterialKind::Composite,
+            Material::Aluminum => MaterialKind::Metal,
+        }
+    }
+
+    /// Density (kg/m3), tensile, flexural strengths and elastic modulus (MPa).
+    pub fn properties(self) -> MaterialProperties {
+        let (density, tensile, flexural, modulus) = match self {
+            Material::OkoumePlywood => (550.0, 35.0, 40.0, 7_000.0),
+            Material::MerantiPlywood => (650.0, 45.0, 55.0, 8_000.0),
+            Material::DouglasFirPlywood => (600.0, 40.0, 50.0, 9_000.0),
+            Material::DouglasFir => (530.0, 90.0, 85.0, 13_400.0),
+            Material::WesternRedCedar => (370.0, 45.0, 52.0, 7_700.0),
+            Material::WhiteOak => (750.0, 95.0, 105.0, 12_300.0),
+            Material::SitkaSpruce => (430.0, 75.0, 70.0, 10_800.0),
+            Material::Mahogany => (590.0, 75.0, 80.0, 10_300.0),
+            Material::GrpChoppedStrand => (1500.0, 85.0, 150.0, 7_000.0),
+            Material::GrpWovenRoving => (1700.0, 200.0, 250.0, 15_000.0),
+            Material::Aluminum => (2660.0, 275.0, 215.0, 70_000.0),
+        };
+        MaterialProperties {
+            density: Density::from_kilogram_meter3(density),
+            tensile_strength: Pressure::from_megapascal(tensile),
+            flexural_strength: Pressure::from_megapascal(flexural),
+            elastic_modulus: Pressure::from_megapascal(modulus),
+        }
+    }
+
+    pub fn density(self) -> Density {
+        self.properties().density
+    }
+
+    /// Weight of a panel of this material.
+    pub fn panel_weight(self, area: Area, thickness: Length) -> Weight {
+        Weight::from_kilogram(
+            self.density().to_kilogram_meter3() * area.to_meter2() * thickness.to_meter(),
+        )
+    }
+}
+
+impl fmt::Display for Material {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Material::OkoumePlywood => write!(f, "Okoume plywood"),
+            Material::MerantiPlywood => write!(f, "Meranti plywood"),
+            Material::DouglasFirPlywood => write!(f, "Douglas fir plywood"),
+            Material::DouglasFir => write!(f, "Douglas fir"),
+            Material::WesternRedCedar => write!(f, "Western red cedar"),
+            Material::WhiteOak => write!(f, "White oak"),
+            Material::SitkaSpruce => write!(f, "Sitka spruce"),
+            Material::Mahogany => write!(f, "Mahogany"),
+            Material::GrpChoppedStrand => write!(f, "GRP chopped strand"),
+            Material::GrpWovenRoving => write!(f, "GRP woven roving"),
+            Material::Aluminum => write!(f, "Aluminum"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn material() {
+        assert_eq!(
+            Material::from_name("okoume PLYWOOD"),
+            Some(Material::OkoumePlywood)
+        );
+        assert_eq!(Material::from_name("Balsa"), None);
+        assert_eq!(Material::WhiteOak.kind(), MaterialKind::Timber);
+        assert_eq!(Material::Aluminum.density().to_kilogram_meter3(), 2660.0);
+        // 2.44 x 1.22 sheet of 9mm okoume.
+        let weight = Material::OkoumePlywood
+            .panel_weight(Area::from_meter2(2.44 * 1.22), Length::from_millimeter(9.0));
+        assert_eq!(format!("{:.1}", weight.to_kilogram()), "14.7");
+    }
+}
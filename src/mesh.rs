@@ -0,0 +1,455 @@
+use super::appendage::{AppendageGeometry, APPENDAGE_STATIONS};
+use super::hull::{Hull, SECTION_SAMPLES};
+use super::si::Length;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Stations across the length of the hull mesh, by default.
+pub const MESH_STATIONS: usize = 40;
+
+/// Points up each side of a station of the hull mesh, keel to sheer, by default.
+pub const MESH_GIRTH: usize = SECTION_SAMPLES;
+
+/// STL units per meter, millimeters as the slicers of the 3D printers expect.
+pub const STL_UNITS_PER_METER: f64 = 1000.0;
+
+/// Vertices closer than this are welded into one (m).
+const WELD_TOLERANCE: f64 = 1e-6;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// MESH
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Mesh
+///
+/// Triangle mesh of a surface, the vertices in meters, x aft of the forward perpendicular, y
+/// to starboard and z up, the triangles counterclockwise seen from outside so their normals
+/// point out.
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    vertices: Vec<[f64; 3]>,
+    triangles: Vec<[usize; 3]>,
+}
+
+/// Difference of two points.
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Cross product.
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Rings of the hull at a number of stations evenly spaced from the foremost to the aftmost
+/// section, the port side from the sheer down to the keel and the starboard side back up,
+/// counterclockwise looking forward, the points evenly spaced in height.
+fn hull_rings(hull: &Hull, stations: usize, girth: usize) -> Vec<Vec<[f64; 3]>> {
+    let (first, last) = hull.range();
+    let (first, last) = (first.to_meter(), last.to_meter());
+    let (stations, girth) = (stations.max(2), girth.max(1));
+    (0..stations)
+        .filter_map(|i| {
+            let x = first + (last - first) * i as f64 / (stations - 1) as f64;
+            let position = Length::from_meter(x);
+            let keel = hull.keel(position)?.to_meter();
+            let sheer = hull.sheer(position)?.to_meter();
+            let side: Vec<(f64, f64)> = (0..=girth)
+                .map(|j| {
+                    let z = keel + (sheer - keel) * j as f64 / girth as f64;
+                    let y = hull
+                        .half_breadth(position, Length::from_meter(z))
+                        .map_or(0.0, |y| y.to_meter());
+                    (y, z)
+                })
+                .collect();
+            let port = side.iter().rev().map(|&(y, z)| [x, -y, z]);
+            let starboard = side.iter().map(|&(y, z)| [x, y, z]);
+            Some(port.chain(starboard).collect())
+        })
+        .collect()
+}
+
+#[allow(dead_code)]
+impl Mesh {
+    pub fn new() -> Mesh {
+        Mesh::default()
+    }
+
+    /// Mesh of the lofted hull surface, both sides, at a number of stations evenly spaced from
+    /// the foremost to the aftmost section and of points up each side from the keel to the
+    /// sheer. Closed, a flat deck at the sheer and the ends capped, the mesh is watertight, to
+    /// 3D print; open, only the wetted and topside surface.
+    pub fn from_hull(hull: &Hull, stations: usize, girth: usize, closed: bool) -> Mesh {
+        Mesh::loft(&hull_rings(hull, stations, girth), true, closed, closed)
+    }
+
+    /// Hull surface with the transom, and the bow when not pointed, capped, open at the deck.
+    pub fn shell(hull: &Hull, stations: usize, girth: usize) -> Mesh {
+        Mesh::loft(&hull_rings(hull, stations, girth), true, false, true)
+    }
+
+    /// Flat deck at the sheer, across from side to side.
+    pub fn deck(hull: &Hull, stations: usize) -> Mesh {
+        Mesh::loft(&hull_rings(hull, stations, 1), false, true, false)
+    }
+
+    /// Closed mesh of a keel or rudder blade, its foil sections down the span, the root and
+    /// the tip capped.
+    pub fn from_appendage(geometry: &AppendageGeometry) -> Mesh {
+        let span = geometry.span().to_meter();
+        let rings: Vec<Vec<[f64; 3]>> = (0..=APPENDAGE_STATIONS)
+            .map(|i| {
+                let depth = Length::from_meter(span * i as f64 / APPENDAGE_STATIONS as f64);
+                geometry
+                    .section(depth)
+                    .iter()
+                    .map(|p| [p.x.to_meter(), p.y.to_meter(), p.z.to_meter()])
+                    .collect()
+            })
+            .collect();
+        Mesh::loft(&rings, true, true, true)
+    }
+
+    /// Surface through rings of the same number of points, each counterclockwise around the
+    /// direction from the first ring to the last. The strips between the rings from each
+    /// point to the next, the closing strip from the last point back to the first, and the
+    /// end rings fanned from their center, each when asked for.
+    fn loft(rings: &[Vec<[f64; 3]>], strips: bool, closing: bool, ends: bool) -> Mesh {
+        let mut mesh = Mesh::new();
+        if rings.len() < 2 {
+            return mesh;
+        }
+        let size = rings[0].len();
+        for ring in rings {
+            mesh.vertices.extend(ring);
+        }
+        let index = |station: usize, point: usize| station * size + point % size;
+        let first = if strips { 0 } else { size - 1 };
+        let last = if closing { size } else { size - 1 };
+        for i in 0..rings.len() - 1 {
+            for j in first..last {
+                let (a, b) = (index(i, j), index(i, j + 1));
+                let (c, d) = (index(i + 1, j + 1), index(i + 1, j));
+                mesh.triangles.push([a, b, c]);
+                mesh.triangles.push([a, c, d]);
+            }
+        }
+        if ends {
+            // The first end facing back and the last ahead. An end closed to a line, as the
+            // stem, needs no cap.
+            for &(station, first) in &[(0, true), (rings.len() - 1, false)] {
+                let ring = &rings[station];
+                let beam = ring.iter().fold(0.0, |beam: f64, p| beam.max(p[1].abs()));
+                if beam < WELD_TOLERANCE {
+                    continue;
+                }
+                let center = ring
+                    .iter()
+                    .fold([0.0; 3], |c, p| [c[0] + p[0], c[1] + p[1], c[2] + p[2]]);
+                let n = size as f64;
+                mesh.vertices
+                    .push([center[0] / n, center[1] / n, center[2] / n]);
+                let center = mesh.vertices.len() - 1;
+                for j in 0..size {
+                    let (a, b) = (index(station, j), index(station, j + 1));
+                    mesh.triangles.push(if first {
+                        [center, b, a]
+                    } else {
+                        [center, a, b]
+                    });
+                }
+            }
+        }
+        mesh.weld();
+        mesh
+    }
+
+    /// Add the triangles of another mesh, the vertices at the same points welded.
+    pub fn append(&mut self, other: &Mesh) {
+        let offset = self.vertices.len();
+        self.vertices.extend(&other.vertices);
+        self.triangles.extend(
+            other
+                .triangles
+                .iter()
+                .map(|t| [t[0] + offset, t[1] + offset, t[2] + offset]),
+        );
+        self.weld();
+    }
+
+    /// Merge the vertices at the same point and drop the triangles left without area, the
+    /// two sides meeting on the centerline at the keel and the stem.
+    fn weld(&mut self) {
+        let key = |p: &[f64; 3]| {
+            let k = |v: f64| (v / WELD_TOLERANCE).round() as i64;
+            (k(p[0]), k(p[1]), k(p[2]))
+        };
+        let mut merged: HashMap<(i64, i64, i64), usize> = HashMap::new();
+        let mut vertices = Vec::new();
+        let map: Vec<usize> = self
+            .vertices
+            .iter()
+            .map(|p| {
+                *merged.entry(key(p)).or_insert_with(|| {
+                    vertices.push(*p);
+                    vertices.len() - 1
+                })
+            })
+            .collect();
+        self.triangles = self
+            .triangles
+            .iter()
+            .map(|t| [map[t[0]], map[t[1]], map[t[2]]])
+            .filter(|t| t[0] != t[1] && t[1] != t[2] && t[2] != t[0])
+            .collect();
+        self.vertices = vertices;
+    }
+
+    /// Vertices (m).
+    pub fn vertices(&self) -> &[[f64; 3]] {
+        &self.vertices
+    }
+
+    /// Triangles, the indices of their vertices.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Corner points of a triangle.
+    fn corners(&self, triangle: &[usize; 3]) -> [[f64; 3]; 3] {
+        [
+            self.vertices[triangle[0]],
+            self.vertices[triangle[1]],
+            self.vertices[triangle[2]],
+        ]
+    }
+
+    /// Outward unit normal of a triangle, zero for a triangle without area.
+    pub fn normal(&self, triangle: usize) -> [f64; 3] {
+        let [a, b, c] = self.corners(&self.triangles[triangle]);
+        let n = cross(sub(b, a), sub(c, a));
+        let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        if length == 0.0 {
+            return [0.0; 3];
+        }
+        [n[0] / length, n[1] / length, n[2] / length]
+    }
+
+    /// Surface area (m2).
+    pub fn area(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = self.corners(t);
+                let n = cross(sub(b, a), sub(c, a));
+                (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt() / 2.0
+            })
+            .sum()
+    }
+
+    /// Enclosed volume (m3), by the divergence theorem, only meaningful watertight.
+    pub fn volume(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = self.corners(t);
+                let n = cross(b, c);
+                (a[0] * n[0] + a[1] * n[1] + a[2] * n[2]) / 6.0
+            })
+            .sum()
+    }
+
+    /// Every edge shared by two triangles running it in opposite directions, no holes.
+    pub fn is_watertight(&self) -> bool {
+        // Uses of each edge, and the runs one way less the other.
+        let mut edges: HashMap<(usize, usize), (usize, i32)> = HashMap::new();
+        for t in &self.triangles {
+            for k in 0..3 {
+                let (a, b) = (t[k], t[(k + 1) % 3]);
+                let edge = edges.entry((a.min(b), a.max(b))).or_insert((0, 0));
+                edge.0 += 1;
+                edge.1 += if a < b { 1 } else { -1 };
+            }
+        }
+        !self.triangles.is_empty() && edges.values().all(|&edge| edge == (2, 0))
+    }
+
+    /// Write the mesh as STL, in millimeters at a model scale, 1 full size and 0.02 a 1:50
+    /// model.
+    pub fn write_stl<W: io::Write>(
+        &self,
+        out: &mut W,
+        format: StlFormat,
+        scale: f64,
+    ) -> io::Result<()> {
+        let factor = STL_UNITS_PER_METER * scale;
+        match format {
+            StlFormat::Ascii => {
+                writeln!(out, "solid hull")?;
+                for (i, t) in self.triangles.iter().enumerate() {
+                    let n = self.normal(i);
+                    writeln!(out, "  facet normal {:e} {:e} {:e}", n[0], n[1], n[2])?;
+                    writeln!(out, "    outer loop")?;
+                    for p in self.corners(t).iter() {
+                        writeln!(
+                            out,
+                            "      vertex {:e} {:e} {:e}",
+                            p[0] * factor,
+                            p[1] * factor,
+                            p[2] * factor
+                        )?;
+                    }
+                    writeln!(out, "    endloop")?;
+                    writeln!(out, "  endfacet")?;
+                }
+                writeln!(out, "endsolid hull")
+            }
+            StlFormat::Binary => {
+                let mut header = [0u8; 80];
+                let title = b"boat_plan hull";
+                header[..title.len()].copy_from_slice(title);
+                out.write_all(&header)?;
+                out.write_all(&(self.triangles.len() as u32).to_le_bytes())?;
+                for (i, t) in self.triangles.iter().enumerate() {
+                    for v in self.normal(i).iter() {
+                        out.write_all(&(*v as f32).to_le_bytes())?;
+                    }
+                    for p in self.corners(t).iter() {
+                        for v in p.iter() {
+                            out.write_all(&((v * factor) as f32).to_le_bytes())?;
+                        }
+                    }
+                    out.write_all(&0u16.to_le_bytes())?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// STL bytes of the mesh.
+    pub fn to_stl(&self, format: StlFormat, scale: f64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Writing to memory does not fail.
+        self.write_stl(&mut bytes, format, scale)
+            .unwrap_or_default();
+        bytes
+    }
+
+    /// Save the mesh to an STL file.
+    pub fn save_stl<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: StlFormat,
+        scale: f64,
+    ) -> io::Result<()> {
+        fs::write(path, self.to_stl(format, scale))
+    }
+}
+
+impl fmt::Display for Mesh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Mesh]\n\
+            \tVertices:  {:>9}\n\
+            \tTriangles: {:>9}\n\
+            \tArea:      {:>9.3}m2",
+            self.vertices.len(),
+            self.triangles.len(),
+            self.area()
+        )
+    }
+}
+
+/// STL file formats.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum StlFormat {
+    /// Text, readable and larger.
+    Ascii,
+    Binary,
+}
+
+impl fmt::Display for StlFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StlFormat::Ascii => write!(f, "ASCII"),
+            StlFormat::Binary => write!(f, "Binary"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn mesh() {
+        use crate::section::Section;
+        use crate::spline::Interpolation;
+
+        let m = Length::from_meter;
+        // Box hull, 1m half-breadth and 1m deep, 4m long, the bottom rounded up to the chine
+        // across the first girth step.
+        let hull = crate::hull::test::box_hull();
+        let closed = Mesh::from_hull(&hull, 5, 10, true);
+        assert!(closed.is_watertight());
+        // Section area 2 - 0.1, the bottom corners cut across the girth step.
+        assert!((closed.volume() - 4.0 * 1.9).abs() < 1e-9);
+        let open = Mesh::from_hull(&hull, 5, 10, false);
+        assert!(!open.is_watertight());
+        assert!(open.area() < closed.area());
+        // The starboard side normal points out and the deck up.
+        let find = |mesh: &Mesh, on: &dyn Fn(&[f64; 3]) -> bool| {
+            (0..mesh.triangles().len())
+                .find(|&i| mesh.corners(&mesh.triangles()[i]).iter().all(on))
+                .map(|i| mesh.normal(i))
+        };
+        assert_eq!(find(&open, &|p| p[1] == 1.0), Some([0.0, 1.0, 0.0]));
+        assert_eq!(find(&open, &|p| p[2] == 1.0), None);
+        assert_eq!(find(&closed, &|p| p[2] == 1.0), Some([0.0, 0.0, 1.0]));
+
+        // Pointed bow, the stem closed to a line without a cap.
+        let sections = [(0.0, 0.0), (2.0, 1.0), (4.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| {
+                Section::new(
+                    m(x),
+                    &[(m(0.0), m(0.0)), (m(y), m(1.0))],
+                    Interpolation::Linear,
+                )
+                .unwrap()
+            })
+            .collect();
+        let pointed = Hull::new(sections, Interpolation::Linear).unwrap();
+        let mesh = Mesh::from_hull(&pointed, 9, 6, true);
+        assert!(mesh.is_watertight());
+        assert!(mesh.volume() > 0.0);
+
+        let binary = closed.to_stl(StlFormat::Binary, 0.02);
+        assert_eq!(binary.len(), 84 + 50 * closed.triangles().len());
+        assert_eq!(
+            u32::from_le_bytes([binary[80], binary[81], binary[82], binary[83]]) as usize,
+            closed.triangles().len()
+        );
+        let ascii = String::from_utf8(closed.to_stl(StlFormat::Ascii, 1.0)).unwrap();
+        assert!(ascii.starts_with("solid hull\n  facet normal "));
+        assert!(ascii.ends_with("endsolid hull\n"));
+        assert_eq!(ascii.matches("endfacet").count(), closed.triangles().len());
+        // The transom corner in millimeters.
+        assert!(ascii.contains("vertex 4e3 1e3 1e3\n"));
+        let path = std::env::temp_dir().join(format!("boat_plan_hull_{}.stl", std::process::id()));
+        closed.save_stl(&path, StlFormat::Binary, 0.02).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), binary);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+use super::appendage::AppendageGeometry;
+use super::boat::Boat;
+use super::hull::Hull;
+use super::mesh::Mesh;
+use super::si::Length;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// OBJ
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Points along the root chord of an appendage to find the bottom of the hull.
+pub const ROOT_SAMPLES: usize = 10;
+
+/// Wavefront OBJ
+///
+/// Meshes of the parts of a boat as named groups, to render in Blender and the like. In
+/// meters, x aft of the forward perpendicular, y to starboard and z up, the faces
+/// counterclockwise seen from outside, each with its outward normal. Flat shaded, the smoothing
+/// left to the renderer.
+#[derive(Debug, Clone, Default)]
+pub struct Obj {
+    groups: Vec<(String, Mesh)>,
+}
+
+#[allow(dead_code)]
+impl Obj {
+    pub fn new() -> Obj {
+        Obj::default()
+    }
+
+    /// Hull and deck of the lofted hull at a number of stations and points up each side, with
+    /// the keel and the rudder when they are positioned, their root on the bottom of the hull.
+    pub fn from_boat(hull: &Hull, boat: &Boat, stations: usize, girth: usize) -> Obj {
+        let mut obj = Obj::new();
+        obj.add_group("hull", Mesh::shell(hull, stations, girth));
+        obj.add_group("deck", Mesh::deck(hull, stations));
+        if let Some(geometry) = boat.keel().and_then(|k| k.geometry()) {
+            obj.add_group("keel", Mesh::from_appendage(&on_bottom(hull, geometry)));
+        }
+        if let Some(geometry) = boat.rudder().and_then(|r| r.geometry()) {
+            obj.add_group("rudder", Mesh::from_appendage(&on_bottom(hull, geometry)));
+        }
+        obj
+    }
+
+    /// Group of a mesh, left out when empty. Spaces in the name are replaced, OBJ names are
+    /// one word.
+    pub fn add_group(&mut self, name: &str, mesh: Mesh) {
+        if mesh.triangles().is_empty() {
+            return;
+        }
+        self.groups.push((name.replace(' ', "_"), mesh));
+    }
+
+    /// Group names in order.
+    pub fn names(&self) -> Vec<&str> {
+        self.groups.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn group(&self, name: &str) -> Option<&Mesh> {
+        self.groups.iter().find(|(n, _)| n == name).map(|(_, m)| m)
+    }
+
+    /// Write the OBJ.
+    pub fn write<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "{}", self)
+    }
+
+    /// Save the OBJ to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+/// Appendage with the root at the highest point of the bottom of the hull along the root
+/// chord, no gap to the hull where the bottom rises. Past the ends of the hull, the bottom at
+/// the end, a rudder hung on the transom.
+fn on_bottom(hull: &Hull, mut geometry: AppendageGeometry) -> AppendageGeometry {
+    let start = geometry.leading_edge(Length::from_meter(0.0)).to_meter();
+    let chord = geometry.chord(Length::from_meter(0.0)).to_meter();
+    let (first, last) = hull.range();
+    let bottom = (0..=ROOT_SAMPLES)
+        .filter_map(|i| {
+            let x = start + chord * i as f64 / ROOT_SAMPLES as f64;
+            hull.keel(Length::from_meter(
+                x.clamp(first.to_meter(), last.to_meter()),
+            ))
+        })
+        .map(|z| z.to_meter())
+        .fold(None, |high: Option<f64>, z| {
+            Some(high.map_or(z, |h| h.max(z)))
+        });
+    if let Some(bottom) = bottom {
+        geometry.set_root(Length::from_meter(bottom));
+    }
+    geometry
+}
+
+impl fmt::Display for Obj {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# boat_plan")?;
+        // Vertex and normal indices count from 1 across the whole file.
+        let (mut vertices, mut normals) = (1, 1);
+        for (name, mesh) in &self.groups {
+            writeln!(f, "o {}", name)?;
+            writeln!(f, "g {}", name)?;
+            for v in mesh.vertices() {
+                writeln!(f, "v {:.6} {:.6} {:.6}", v[0], v[1], v[2])?;
+            }
+            for i in 0..mesh.triangles().len() {
+                let n = mesh.normal(i);
+                writeln!(f, "vn {:.6} {:.6} {:.6}", n[0], n[1], n[2])?;
+            }
+            writeln!(f, "s off")?;
+            for (i, t) in mesh.triangles().iter().enumerate() {
+                let n = normals + i;
+                writeln!(
+                    f,
+                    "f {}//{} {}//{} {}//{}",
+                    vertices + t[0],
+                    n,
+                    vertices + t[1],
+                    n,
+                    vertices + t[2],
+                    n
+                )?;
+            }
+            vertices += mesh.vertices().len();
+            normals += mesh.triangles().len();
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn obj() {
+        use crate::appendage::{Keel, KeelType, Rudder};
+        use crate::si::{Area, Weight};
+
+        let m = Length::from_meter;
+        // The keel rising aft, 0.1m a meter.
+        let hull = crate::hull::test::chine_hull(&|x| (0.1 * x, 0.1 * x + 0.5), 1.0, (1.0, 1.0));
+        let mut boat = Boat::new("Skiff".to_string());
+        let obj = Obj::from_boat(&hull, &boat, 5, 4);
+        assert_eq!(obj.names(), vec!["hull", "deck"]);
+
+        let mut keel = Keel::new(
+            KeelType::Fin,
+            m(1.0),
+            m(0.6),
+            m(1.2),
+            Weight::from_kilogram(500.0),
+        );
+        keel.set_leading_edge(m(1.5));
+        boat.set_keel(keel);
+        let mut rudder = Rudder::new(m(0.8), m(0.4), m(0.3), Area::from_meter2(0.02));
+        rudder.set_stock(m(3.8));
+        boat.set_rudder(rudder);
+        let obj = Obj::from_boat(&hull, &boat, 5, 4);
+        assert_eq!(obj.names(), vec!["hull", "deck", "keel", "rudder"]);
+        let keel = obj.group("keel").unwrap();
+        assert!(keel.is_watertight());
+        // The roots on the bottom, at its highest along the root chord, 2.5m aft for the keel
+        // and at the transom for the rudder, past the hull aft.
+        let top = |name: &str| {
+            obj.group(name)
+                .unwrap()
+                .vertices()
+                .iter()
+                .fold(f64::NEG_INFINITY, |high, v| high.max(v[2]))
+        };
+        assert_eq!(format!("{:.3}", top("keel")), "0.250");
+        assert_eq!(format!("{:.3}", top("rudder")), "0.400");
+        // Hull and deck together close the hull.
+        let mut closed = obj.group("hull").unwrap().clone();
+        closed.append(obj.group("deck").unwrap());
+        assert!(closed.is_watertight());
+        assert!(closed.volume() > 0.0);
+        // The deck faces up.
+        let deck = obj.group("deck").unwrap();
+        assert!((0..deck.triangles().len()).all(|i| deck.normal(i) == [0.0, 0.0, 1.0]));
+
+        let text = obj.to_string();
+        assert!(text.starts_with("# boat_plan\no hull\ng hull\nv "));
+        assert!(text.contains("\ng rudder\n"));
+        let count = |prefix: &str| text.lines().filter(|l| l.starts_with(prefix)).count();
+        let triangles: usize = obj
+            .names()
+            .iter()
+            .map(|n| obj.group(n).unwrap().triangles().len())
+            .sum();
+        assert_eq!(count("f "), triangles);
+        assert_eq!(count("vn "), triangles);
+        // The last face refers to the last vertex and normal at most.
+        let vertices = count("v ");
+        let last = text.lines().rfind(|l| l.starts_with("f ")).unwrap();
+        assert!(last.split_whitespace().skip(1).all(|i| i
+            .split("//")
+            .next()
+            .unwrap()
+            .parse::<usize>()
+            .unwrap()
+            <= vertices));
+        assert!(last.ends_with(&format!("//{}", triangles)));
+        let path = std::env::temp_dir().join(format!("boat_plan_boat_{}.obj", std::process::id()));
+        obj.save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,654 @@
+use super::csv;
+use super::si::Length;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// OFFSET LINE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Offset kind, what the values of a line are.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum OffsetKind {
+    /// Heights above the baseline (buttocks, sheer and keel profiles).
+    Height,
+    /// Half-breadths from the centerline (waterlines and sheer plan).
+    HalfBreadth,
+}
+
+impl fmt::Display for OffsetKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OffsetKind::Height => write!(f, "Height"),
+            OffsetKind::HalfBreadth => write!(f, "Half-breadth"),
+        }
+    }
+}
+
+/// Offset line
+///
+/// One row of the offsets table, a value for each station (None where the line does not cross
+/// the station). Waterlines are half-breadths at a height, buttocks are heights at an offset
+/// from the centerline, the sheer and keel lines have no position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffsetLine {
+    name: String,
+    kind: OffsetKind,
+    position: Option<Length>,
+    values: Vec<Option<Length>>,
+}
+
+#[allow(dead_code)]
+impl OffsetLine {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> OffsetKind {
+        self.kind
+    }
+
+    /// Waterline height or buttock offset, None for the sheer and keel lines.
+    pub fn position(&self) -> Option<Length> {
+        self.position
+    }
+
+    pub fn values(&self) -> &[Option<Length>] {
+        &self.values
+    }
+
+    /// Value at a station index.
+    pub fn value(&self, station: usize) -> Option<Length> {
+        self.values.get(station).copied().flatten()
+    }
+
+    pub fn is_waterline(&self) -> bool {
+        self.kind == OffsetKind::HalfBreadth && self.position.is_some()
+    }
+
+    pub fn is_buttock(&self) -> bool {
+        self.kind == OffsetKind::Height && self.position.is_some()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// OFFSETS TABLE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Sheer line name.
+pub const SHEER: &str = "Sheer";
+
+/// Keel profile line name, the height of the bottom of the hull at the centerline.
+pub const KEEL: &str = "Keel";
+
+/// Offsets table error.
+#[derive(Debug)]
+pub enum OffsetsError {
+    /// Unreadable offsets file.
+    Io(io::Error),
+    /// A line does not have a value for every station.
+    StationCount {
+        line: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Stations must go from forward to aft, without repeating.
+    NotMonotonic(usize),
+    /// Two lines with the same name and kind.
+    Duplicate(String),
+    /// Negative half-breadth, the table holds the starboard side only.
+    NotSymmetric { line: String, station: usize },
+    /// Buttock to port, the table holds the starboard side only.
+    PortButtock(String),
+    /// Unreadable value.
+    Parse { line: usize, value: String },
+    /// Unknown line kind.
+    Kind { line: usize, kind: String },
+}
+
+impl fmt::Display for OffsetsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OffsetsError::Io(err) => write!(f, "Offsets file error: {}", err),
+            OffsetsError::StationCount {
+                line,
+                expected,
+                found,
+            } => write!(f, "{} has {} values for {} stations", line, found, expected),
+            OffsetsError::NotMonotonic(i) => {
+                write!(f, "Station {} is not aft of the previous one", i)
+            }
+            OffsetsError::Duplicate(name) => write!(f, "Line {} is repeated", name),
+            OffsetsError::NotSymmetric { line, station } => write!(
+                f,
+                "{} has a negative half-breadth at station {}",
+                line, station
+            ),
+            OffsetsError::PortButtock(line) => write!(f, "{} is to port", line),
+            OffsetsError::Parse { line, value } => {
+                write!(f, "Line {}: invalid offset \"{}\"", line, value)
+            }
+            OffsetsError::Kind { line, kind } => {
+                write!(f, "Line {}: invalid kind \"{}\"", line, kind)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OffsetsError {}
+
+impl From<io::Error> for OffsetsError {
+    fn from(err: io::Error) -> OffsetsError {
+        OffsetsError::Io(err)
+    }
+}
+
+/// Section point, half-breadth and height of a line crossing a station.
+#[derive(Debug, Copy, Clone)]
+pub struct OffsetPoint {
+    pub half_breadth: Length,
+    pub height: Length,
+}
+
+/// Offsets table
+///
+/// Stations are positions aft of the forward perpendicular, heights are above the baseline
+/// and half-breadths from the centerline, starboard side only.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OffsetsTable {
+    stations: Vec<Length>,
+    lines: Vec<OffsetLine>,
+}
+
+#[allow(dead_code)]
+impl OffsetsTable {
+    pub fn new(stations: Vec<Length>) -> Result<OffsetsTable, OffsetsError> {
+        if let Some(i) =
+            (1..stations.len()).find(|&i| stations[i].to_meter() <= stations[i - 1].to_meter())
+        {
+            return Err(OffsetsError::NotMonotonic(i));
+        }
+        Ok(OffsetsTable {
+            stations,
+            lines: Vec::new(),
+        })
+    }
+
+    /// Waterline, half-breadths at a height above the baseline.
+    pub fn add_waterline(
+        &mut self,
+        name: &str,
+        height: Length,
+        values: Vec<Option<Length>>,
+    ) -> Result<(), OffsetsError> {
+        self.add(name, OffsetKind::HalfBreadth, Some(height), values)
+    }
+
+    /// Buttock, heights at an offset from the centerline.
+    pub fn add_buttock(
+        &mut self,
+        name: &str,
+        offset: Length,
+        values: Vec<Option<Length>>,
+    ) -> Result<(), OffsetsError> {
+        self.add(name, OffsetKind::Height, Some(offset), values)
+    }
+
+    /// Line without position, as the sheer heights and half-breadths or the keel profile.
+    pub fn add_line(
+        &mut self,
+        name: &str,
+        kind: OffsetKind,
+        values: Vec<Option<Length>>,
+    ) -> Result<(), OffsetsError> {
+        self.add(name, kind, None, values)
+    }
+
+    fn add(
+        &mut self,
+        name: &str,
+        kind: OffsetKind,
+        position: Option<Length>,
+        values: Vec<Option<Length>>,
+    ) -> Result<(), OffsetsError> {
+        if values.len() != self.stations.len() {
+            return Err(OffsetsError::StationCount {
+                line: name.to_string(),
+                expected: self.stations.len(),
+                found: values.len(),
+            });
+        }
+        if self.line(name, kind).is_some() {
+            return Err(OffsetsError::Duplicate(name.to_string()));
+        }
+        if kind == OffsetKind::HalfBreadth {
+            if let Some(station) = values
+                .iter()
+                .position(|v| v.is_some_and(|v| v.to_meter() < 0.0))
+            {
+                return Err(OffsetsError::NotSymmetric {
+                    line: name.to_string(),
+                    station,
+                });
+            }
+        }
+        if kind == OffsetKind::Height && position.is_some_and(|p| p.to_meter() < 0.0) {
+            return Err(OffsetsError::PortButtock(name.to_string()));
+        }
+        self.lines.push(OffsetLine {
+            name: name.to_string(),
+            kind,
+            position,
+            values,
+        });
+        Ok(())
+    }
+
+    pub fn stations(&self) -> &[Length] {
+        &self.stations
+    }
+
+    /// Station position by index.
+    pub fn station(&self, index: usize) -> Option<Length> {
+        self.stations.get(index).copied()
+    }
+
+    pub fn lines(&self) -> &[OffsetLine] {
+        &self.lines
+    }
+
+    /// Line by name and kind.
+    pub fn line(&self, name: &str, kind: OffsetKind) -> Option<&OffsetLine> {
+        self.lines
+            .iter()
+            .find(|l| l.kind == kind && l.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Waterlines, from the lowest.
+    pub fn waterlines(&self) -> Vec<&OffsetLine> {
+        let mut waterlines: Vec<&OffsetLine> =
+            self.lines.iter().filter(|l| l.is_waterline()).collect();
+        waterlines.sort_by(|a, b| position_order(a, b));
+        waterlines
+    }
+
+    /// Buttocks, from the centerline out.
+    pub fn buttocks(&self) -> Vec<&OffsetLine> {
+        let mut buttocks: Vec<&OffsetLine> = self.lines.iter().filter(|l| l.is_buttock()).collect();
+        buttocks.sort_by(|a, b| position_order(a, b));
+        buttocks
+    }
+
+    /// Waterline by name.
+    pub fn waterline(&self, name: &str) -> Option<&OffsetLine> {
+        self.line(name, OffsetKind::HalfBreadth)
+            .filter(|l| l.is_waterline())
+    }
+
+    /// Buttock by name.
+    pub fn buttock(&self, name: &str) -> Option<&OffsetLine> {
+        self.line(name, OffsetKind::Height)
+            .filter(|l| l.is_buttock())
+    }
+
+    /// Half-breadth of a waterline at a station index.
+    pub fn half_breadth(&self, waterline: &str, station: usize) -> Option<Length> {
+        self.waterline(waterline)?.value(station)
+    }
+
+    /// Height of a buttock at a station index.
+    pub fn height(&self, buttock: &str, station: usize) -> Option<Length> {
+        self.buttock(buttock)?.value(station)
+    }
+
+    /// Points of a station section, from the keel up to the sheer.
+    pub fn station_points(&self, station: usize) -> Vec<OffsetPoint> {
+        let mut points = Vec::new();
+        let zero = Length::from_meter(0.0);
+        if let Some(height) = self
+            .line(KEEL, OffsetKind::Height)
+            .and_then(|l| l.value(station))
+        {
+            points.push(OffsetPoint {
+                half_breadth: zero,
+                height,
+            });
+        }
+        for line in &self.lines {
+            if let (Some(position), Some(value)) = (line.position, line.value(station)) {
+                points.push(match line.kind {
+                    OffsetKind::HalfBreadth => OffsetPoint {
+                        half_breadth: value,
+                        height: position,
+                    },
+                    OffsetKind::Height => OffsetPoint {
+                        half_breadth: position,
+                        height: value,
+                    },
+                });
+            }
+        }
+        let sheer_height = self
+            .line(SHEER, OffsetKind::Height)
+            .and_then(|l| l.value(station));
+        let sheer_breadth = self
+            .line(SHEER, OffsetKind::HalfBreadth)
+            .and_then(|l| l.value(station));
+        if let (Some(height), Some(half_breadth)) = (sheer_height, sheer_breadth) {
+            points.push(OffsetPoint {
+                half_breadth,
+                height,
+            });
+        }
+        points.sort_by(|a, b| {
+            a.height
+                .to_meter()
+                .partial_cmp(&b.height.to_meter())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        points
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// CSV
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Offset notation of a CSV file.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum OffsetUnits {
+    Millimeter,
+    /// Feet-inches-eighths, 3-4-5 is 3ft 4 5/8in, a trailing + adds a sixteenth.
+    FeetInchesEighths,
+}
+
+/// Parse an offset, None when invalid.
+fn parse_offset(text: &str, units: OffsetUnits) -> Option<Length> {
+    match units {
+        OffsetUnits::Millimeter => text.parse::<f64>().ok().map(Length::from_millimeter),
+        OffsetUnits::FeetInchesEighths => {
+            let (text, sixteenth) = match text.strip_suffix('+') {
+                Some(text) => (text, 1.0 / 16.0),
+                None => (text, 0.0),
+            };
+            let parts: Vec<f64> = text
+                .split('-')
+                .map(|p| p.trim().parse::<f64>().ok())
+                .collect::<Option<Vec<f64>>>()?;
+            match parts[..] {
+                [feet, inches, eighths] if inches < 12.0 && eighths < 8.0 => Some(
+                    Length::from_inch(feet * 12.0 + inches + eighths / 8.0 + sixteenth),
+                ),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Offset in a notation, the inverse of the CSV parsing, feet-inches-eighths to the nearest
+/// sixteenth and millimeters to the unit.
+pub fn format_offset(value: Length, units: OffsetUnits) -> String {
+    match units {
+        // Rounded before the sign, no negative zero.
+        OffsetUnits::Millimeter => format!("{:.0}", value.to_millimiter().round() + 0.0),
+        OffsetUnits::FeetInchesEighths => {
+            let sixteenths = (value.to_inch().abs() * 16.0).round() as u64;
+            let sign = if value.to_inch() < 0.0 && sixteenths > 0 {
+                "-"
+            } else {
+                ""
+            };
+            let eighths = sixteenths / 2;
+            format!(
+                "{}{}-{}-{}{}",
+                sign,
+                eighths / 96,
+                eighths / 8 % 12,
+                eighths % 8,
+                if sixteenths % 2 == 1 { "+" } else { "" }
+            )
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl OffsetsTable {
+    /// Import the offsets from a CSV file
+    ///
+    /// The header is "line, kind, position" followed by the station positions, each other row
+    /// is a line of the table:
+    /// line          Line name, "Sheer" and "Keel" are the sheer and keel profile lines.
+    /// kind          "waterline", "buttock", "height" or "half-breadth".
+    /// position      Waterline height or buttock offset, empty for the other kinds.
+    /// Values are in millimeters or feet-inches-eighths, empty or "-" where the line does not
+    /// cross the station. Empty lines and lines starting with # are skipped.
+    pub fn from_csv<P: AsRef<Path>>(
+        path: P,
+        units: OffsetUnits,
+    ) -> Result<OffsetsTable, OffsetsError> {
+        OffsetsTable::parse_csv(&fs::read_to_string(path)?, units)
+    }
+
+    /// Import the offsets from CSV text, see from_csv.
+    pub fn parse_csv(text: &str, units: OffsetUnits) -> Result<OffsetsTable, OffsetsError> {
+        let mut lines = csv::lines(text);
+        let (header_line, header) = match lines.next() {
+            Some((n, l)) => (n, csv::split_line(l)),
+            None => return Ok(OffsetsTable::default()),
+        };
+        // Empty or a dash where the line does not cross the station.
+        let offset = |n: usize, text: &str| -> Result<Option<Length>, OffsetsError> {
+            if text.is_empty() || text == "-" {
+                return Ok(None);
+            }
+            parse_offset(text, units)
+                .map(Some)
+                .ok_or_else(|| OffsetsError::Parse {
+                    line: n,
+                    value: text.to_string(),
+                })
+        };
+        let mut stations = Vec::new();
+        for field in header.iter().skip(3) {
+            // A station without position.
+            stations.push(
+                offset(header_line, field)?.ok_or_else(|| OffsetsError::Parse {
+                    line: header_line,
+                    value: field.to_string(),
+                })?,
+            );
+        }
+        let mut table = OffsetsTable::new(stations)?;
+
+        for (n, line) in lines {
+            let fields = csv::split_line(line);
+            let field = |i: usize| fields.get(i).map(|f| f.as_str()).unwrap_or("");
+            let name = field(0);
+            let position = offset(n, field(2))?;
+            let values = fields
+                .iter()
+                .skip(3)
+                .map(|f| offset(n, f))
+                .collect::<Result<Vec<Option<Length>>, OffsetsError>>()?;
+            let kind = field(1).to_ascii_lowercase();
+            let missing = || OffsetsError::Parse {
+                line: n,
+                value: field(2).to_string(),
+            };
+            match kind.as_str() {
+                "waterline" => table.add_waterline(name, position.ok_or_else(missing)?, values),
+                "buttock" => table.add_buttock(name, position.ok_or_else(missing)?, values),
+                "height" => table.add_line(name, OffsetKind::Height, values),
+                "half-breadth" | "half_breadth" => {
+                    table.add_line(name, OffsetKind::HalfBreadth, values)
+                }
+                _ => Err(OffsetsError::Kind {
+                    line: n,
+                    kind: field(1).to_string(),
+                }),
+            }?;
+        }
+        Ok(table)
+    }
+}
+
+/// Lines ordered by position.
+fn position_order(a: &OffsetLine, b: &OffsetLine) -> std::cmp::Ordering {
+    let a = a.position.map_or(0.0, |p| p.to_meter());
+    let b = b.position.map_or(0.0, |p| p.to_meter());
+    a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+}
+
+impl fmt::Display for OffsetsTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Offsets]\n\t{:<24}", "Station")?;
+        for station in &self.stations {
+            write!(f, "{:>8.3}", station.to_meter())?;
+        }
+        for line in &self.lines {
+            let name = match line.position {
+                Some(position) => format!("{} {:.3}", line.name, position.to_meter()),
+                None => line.name.clone(),
+            };
+            write!(f, "\n\t{:<24}", format!("{} ({})", name, line.kind))?;
+            for value in &line.values {
+                match value {
+                    Some(value) => write!(f, "{:>8.3}", value.to_meter())?,
+                    None => write!(f, "{:>8}", "-")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn meters(values: &[f64]) -> Vec<Option<Length>> {
+        values
+            .iter()
+            .map(|&v| {
+                if v < 0.0 {
+                    None
+                } else {
+                    Some(Length::from_meter(v))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn offsets_table() {
+        let stations: Vec<Length> = [0.0, 1.0, 2.0]
+            .iter()
+            .map(|&s| Length::from_meter(s))
+            .collect();
+        let mut table = OffsetsTable::new(stations).unwrap();
+        table
+            .add_waterline("WL1", Length::from_meter(0.2), meters(&[-1.0, 0.4, 0.5]))
+            .unwrap();
+        table
+            .add_waterline("DWL", Length::from_meter(0.4), meters(&[0.0, 0.6, 0.7]))
+            .unwrap();
+        table
+            .add_buttock("B1", Length::from_meter(0.3), meters(&[-1.0, 0.15, 0.1]))
+            .unwrap();
+        table
+            .add_line(KEEL, OffsetKind::Height, meters(&[0.4, 0.0, 0.0]))
+            .unwrap();
+        table
+            .add_line(SHEER, OffsetKind::Height, meters(&[1.0, 0.9, 0.85]))
+            .unwrap();
+        table
+            .add_line(SHEER, OffsetKind::HalfBreadth, meters(&[0.0, 0.8, 0.9]))
+            .unwrap();
+
+        assert_eq!(table.station(2).unwrap().to_meter(), 2.0);
+        assert_eq!(table.half_breadth("dwl", 1).unwrap().to_meter(), 0.6);
+        assert!(table.half_breadth("WL1", 0).is_none());
+        assert_eq!(table.height("B1", 2).unwrap().to_meter(), 0.1);
+        assert_eq!(table.waterlines()[0].name(), "WL1");
+        assert_eq!(table.buttocks().len(), 1);
+
+        // Keel, B1, WL1, DWL and sheer.
+        let points = table.station_points(1);
+        assert_eq!(points.len(), 5);
+        assert_eq!(points[1].half_breadth.to_meter(), 0.3);
+        assert_eq!(points[4].height.to_meter(), 0.9);
+
+        assert!(matches!(
+            table.add_waterline("WL2", Length::from_meter(0.3), meters(&[0.1])),
+            Err(OffsetsError::StationCount { .. })
+        ));
+        assert!(matches!(
+            table.add_waterline("DWL", Length::from_meter(0.3), meters(&[0.1, 0.2, 0.3])),
+            Err(OffsetsError::Duplicate(_))
+        ));
+        assert!(matches!(
+            OffsetsTable::new(vec![Length::from_meter(1.0), Length::from_meter(0.5)]),
+            Err(OffsetsError::NotMonotonic(1))
+        ));
+    }
+
+    #[test]
+    fn offsets_csv() {
+        let fie = OffsetUnits::FeetInchesEighths;
+        let inches = |t| parse_offset(t, fie).unwrap().to_inch();
+        assert_eq!(inches("3-4-5"), 40.625);
+        assert_eq!(inches("0-7-3+"), 7.4375);
+        assert!(parse_offset("-", fie).is_none());
+        assert!(parse_offset("3-13-0", fie).is_none());
+
+        let text = "\
+            # Offsets of a 20ft sloop\n\
+            line, kind, position, 0-0-0, 5-0-0, 10-0-0\n\
+            Sheer, height, , 3-6-0, 3-0-4, 2-9-0\n\
+            Sheer, half-breadth, , 0-0-0, 2-9-2+, 3-4-0\n\
+            Keel, height, , 2-0-0, 0-3-0, 0-0-0\n\
+            WL 12, waterline, 1-0-0, -, 2-1-0, 2-8-0\n\
+            B 18, buttock, 1-6-0, -, 0-9-0, 0-4-4\n";
+        let table = OffsetsTable::parse_csv(text, fie).unwrap();
+        assert_eq!(table.stations().len(), 3);
+        assert_eq!(table.station(1).unwrap().to_foot(), 5.0);
+        assert_eq!(table.half_breadth("WL 12", 2).unwrap().to_inch(), 32.0);
+        assert_eq!(table.height("B 18", 2).unwrap().to_inch(), 4.5);
+
+        let text = "line,kind,position,0,1000\nWL1,waterline,300,0,x\n";
+        assert!(matches!(
+            OffsetsTable::parse_csv(text, OffsetUnits::Millimeter),
+            Err(OffsetsError::Parse { line: 2, .. })
+        ));
+        let text = "line,kind,position,0,1000,900\n";
+        assert!(matches!(
+            OffsetsTable::parse_csv(text, OffsetUnits::Millimeter),
+            Err(OffsetsError::NotMonotonic(2))
+        ));
+        let text = "line,kind,position,0,1000\nB1,buttock,-100,0,200\n";
+        assert!(matches!(
+            OffsetsTable::parse_csv(text, OffsetUnits::Millimeter),
+            Err(OffsetsError::PortButtock(_))
+        ));
+    }
+
+    #[test]
+    fn offset_format() {
+        let fie = OffsetUnits::FeetInchesEighths;
+        for text in &["3-4-5", "0-7-3+", "0-0-0", "12-11-7+"] {
+            assert_eq!(format_offset(parse_offset(text, fie).unwrap(), fie), *text);
+        }
+        assert_eq!(format_offset(Length::from_inch(11.99), fie), "1-0-0");
+        assert_eq!(format_offset(Length::from_inch(-14.375), fie), "-1-2-3");
+        // Rounded to nothing, no sign.
+        assert_eq!(format_offset(Length::from_inch(-0.01), fie), "0-0-0");
+        let mm = OffsetUnits::Millimeter;
+        assert_eq!(format_offset(Length::from_meter(1.2504), mm), "1250");
+        assert_eq!(format_offset(Length::from_millimeter(-0.3), mm), "0");
+        assert_eq!(format_offset(Length::from_millimeter(-2.6), mm), "-3");
+    }
+}
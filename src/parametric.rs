@@ -0,0 +1,323 @@
+use super::boat::{ideal_prismatic, Boat, LCB_RANGE};
+use super::hull::Hull;
+use super::hydrostatics::{Hydrostatics, Water};
+use super::section::Section;
+use super::si::Length;
+use super::spline::Interpolation;
+use std::fmt;
+
+/// Freeboard as a fraction of the LOA, when the boat does not tell.
+pub const FREEBOARD_RATIO: f64 = 0.1;
+
+/// Share of the overhangs (LOA - DWL) forward of the DWL, the rest is aft.
+pub const BOW_OVERHANG: f64 = 0.6;
+
+/// Rise of the sheer at the bow and at the stern, fractions of the midship freeboard.
+pub const SHEER_SPRING: (f64, f64) = (0.25, 0.1);
+
+/// Stem head and transom bottom heights, fractions of the freeboard above the DWL.
+pub const OVERHANG_RISE: (f64, f64) = (0.8, 0.3);
+
+/// Exponent of the superellipse sections, 2 is an ellipse, larger is fuller in the bilge.
+pub const SECTION_EXPONENT: f64 = 2.5;
+
+/// Points of a section below the DWL.
+pub const SECTION_POINTS: usize = 9;
+
+/// Range of the exponent of the sectional area curve searched for the Cp.
+pub const AREA_EXPONENT_RANGE: (f64, f64) = (0.2, 20.0);
+
+/// Iterations of the search for the Cp.
+pub const CP_ITERATIONS: usize = 30;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PARAMETRIC HULL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Hull parameters
+///
+/// Principal dimensions and coefficients of a parametric hull, to get geometry without an
+/// offsets table. The DWL runs from the forward perpendicular to DWL aft, the overhangs extend
+/// the hull to the LOA. The sections below the DWL are superellipses of the half-breadth and
+/// depth, cut by the deadrise line of the bottom, and the topsides are straight up to the sheer.
+/// Along the DWL the sectional areas follow 1 - t^n on each side of the maximum section, at
+/// the LCB, the exponent n searched for the Cp from the hull hydrostatics.
+#[derive(Debug, Copy, Clone)]
+pub struct HullParameters {
+    pub loa: Length,
+    pub dwl: Length,
+    /// Beam at the DWL.
+    pub bwl: Length,
+    /// Beam at the sheer, the maximum beam.
+    pub b_max: Length,
+    /// Draft of the canoe body.
+    pub draft: Length,
+    /// Freeboard at the maximum section.
+    pub freeboard: Length,
+    pub cp: f64,
+    /// Maximum section position (% of DWL).
+    pub lcb: f64,
+    /// Deadrise of the bottom (degree).
+    pub deadrise: f64,
+    /// Transom width as a fraction of the maximum beam.
+    pub transom: f64,
+    /// Stations along the DWL.
+    pub stations: usize,
+    pub interpolation: Interpolation,
+}
+
+#[allow(dead_code)]
+impl HullParameters {
+    /// Parameters from the boat dimensions, the Cp ideal for the target speed and the LCB in
+    /// the middle of the typical range when not informed.
+    pub fn from_boat(boat: &Boat) -> HullParameters {
+        HullParameters {
+            loa: boat.loa(),
+            dwl: boat.dwl(),
+            bwl: boat.bwl(),
+            b_max: boat.b_max(),
+            draft: boat.draft(),
+            freeboard: Length::from_meter(boat.loa().to_meter() * FREEBOARD_RATIO),
+            cp: boat
+                .cp()
+                .unwrap_or_else(|| ideal_prismatic(boat.target_slr())),
+            lcb: boat.lcb().unwrap_or((LCB_RANGE.0 + LCB_RANGE.1) / 2.0),
+            deadrise: 12.0,
+            transom: 0.5,
+            stations: 11,
+            interpolation: Interpolation::CubicSpline,
+        }
+    }
+
+    /// Parameters out of range, the dimensions not positive, the DWL longer than the LOA or
+    /// the BWL wider than the maximum beam.
+    pub fn is_valid(&self) -> bool {
+        self.dwl.to_meter() > 0.0
+            && self.loa.to_meter() >= self.dwl.to_meter()
+            && self.bwl.to_meter() > 0.0
+            && self.b_max.to_meter() >= self.bwl.to_meter()
+            && self.draft.to_meter() > 0.0
+            && self.freeboard.to_meter() > 0.0
+            && self.cp > 0.0
+            && self.cp < 1.0
+            && self.lcb > 30.0
+            && self.lcb < 70.0
+            && (0.0..45.0).contains(&self.deadrise)
+            && (0.0..=1.0).contains(&self.transom)
+            && self.stations >= 3
+    }
+
+    /// Hull with the Cp closest to the parameters, None when the parameters are not valid.
+    pub fn generate(&self) -> Option<Hull> {
+        if !self.is_valid() {
+            return None;
+        }
+        let (mut low, mut high) = AREA_EXPONENT_RANGE;
+        let mut best: Option<(f64, Hull)> = None;
+        for _ in 0..CP_ITERATIONS {
+            let exponent = (low * high).sqrt();
+            let candidate = self.hull(exponent)?;
+            let cp = Hydrostatics::new(&candidate, self.draft, Water::Salt)
+                .form_coefficients()?
+                .cp;
+            if cp < self.cp {
+                low = exponent;
+            } else {
+                high = exponent;
+            }
+            let error = (cp - self.cp).abs();
+            if best.as_ref().is_none_or(|(e, _)| error < *e) {
+                best = Some((error, candidate));
+            }
+        }
+        best.map(|(_, hull)| hull)
+    }
+
+    /// Hull for an exponent of the sectional area curve.
+    fn hull(&self, exponent: f64) -> Option<Hull> {
+        let dwl = self.dwl.to_meter();
+        let overhangs = self.loa.to_meter() - dwl;
+        let bow = -overhangs * BOW_OVERHANG;
+        let stern = dwl + overhangs * (1.0 - BOW_OVERHANG);
+        let mut positions: Vec<f64> = (0..self.stations)
+            .map(|i| dwl * i as f64 / (self.stations - 1) as f64)
+            .collect();
+        // The maximum section, the stations are sorted by the hull.
+        positions.push(dwl * self.lcb / 100.0);
+        if overhangs > 0.0 {
+            positions.insert(0, bow / 2.0);
+            positions.insert(0, bow);
+            positions.push((dwl + stern) / 2.0);
+            positions.push(stern);
+        }
+        let sections = positions
+            .into_iter()
+            .filter_map(|x| self.section(x, exponent, bow, stern))
+            .collect();
+        Hull::new(sections, self.interpolation)
+    }
+
+    /// Section at a position aft of the forward perpendicular.
+    fn section(&self, x: f64, exponent: f64, bow: f64, stern: f64) -> Option<Section> {
+        let dwl = self.dwl.to_meter();
+        let waterline = self.draft.to_meter();
+        let freeboard = self.freeboard.to_meter();
+        let middle = dwl * self.lcb / 100.0;
+        // From the maximum section to the ends, 0 to 1.
+        let (forward, t) = if x <= middle {
+            (true, (middle - x) / (middle - bow))
+        } else {
+            (false, (x - middle) / (stern - middle))
+        };
+        let spring = if forward {
+            SHEER_SPRING.0
+        } else {
+            SHEER_SPRING.1
+        };
+        let sheer = waterline + freeboard * (1.0 + spring * t * t);
+        let half_beam = self.b_max.to_meter() / 2.0;
+        let sheer_breadth = if forward {
+            half_beam * (1.0 - t * t)
+        } else {
+            half_beam * (1.0 - (1.0 - self.transom) * t * t)
+        };
+
+        let mut points: Vec<(f64, f64)> = Vec::new();
+        if x < 0.0 || x > dwl {
+            // Overhang, the keel rises from the DWL to the stem head or the transom.
+            let (along, rise) = if x < 0.0 {
+                (x / bow, OVERHANG_RISE.0)
+            } else {
+                ((x - dwl) / (stern - dwl), OVERHANG_RISE.1)
+            };
+            points.push((0.0, waterline + along * rise * freeboard));
+        } else {
+            let u = x / dwl;
+            let m = self.lcb / 100.0;
+            let s = if u <= m {
+                (m - u) / m
+            } else {
+                (u - m) / (1.0 - m)
+            };
+            let area = (1.0 - s.min(1.0).powf(exponent)).max(0.0);
+            let breadth = (self.bwl.to_meter() / 2.0 * area.sqrt()).min(sheer_breadth);
+            let depth = waterline * area.sqrt();
+            let keel = waterline - depth;
+            let slope = self.deadrise.to_radians().tan();
+            points.push((0.0, keel));
+            for j in 1..SECTION_POINTS {
+                let theta = std::f64::consts::FRAC_PI_2 * j as f64 / SECTION_POINTS as f64;
+                let y = breadth * theta.sin().powf(2.0 / SECTION_EXPONENT);
+                let z = (waterline - depth * theta.cos().powf(2.0 / SECTION_EXPONENT))
+                    .max(keel + y * slope);
+                if z < waterline - 1e-9 {
+                    points.push((y, z));
+                }
+            }
+            points.push((breadth, waterline));
+        }
+        points.push((sheer_breadth, sheer));
+        let points: Vec<(Length, Length)> = points
+            .into_iter()
+            .map(|(y, z)| (Length::from_meter(y), Length::from_meter(z)))
+            .collect();
+        Section::new(Length::from_meter(x), &points, self.interpolation)
+    }
+}
+
+impl fmt::Display for HullParameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Hull parameters]\n\
+            \tLOA:       {:>7.3}m\n\
+            \tDWL:       {:>7.3}m\n\
+            \tBWL:       {:>7.3}m\n\
+            \tB max:     {:>7.3}m\n\
+            \tDraft:     {:>7.3}m\n\
+            \tFreeboard: {:>7.3}m\n\
+            \tCp:        {:>7.3}\n\
+            \tLCB:       {:>7.1}%\n\
+            \tDeadrise:  {:>7.1}deg\n\
+            \tTransom:   {:>7.2}\n\
+            \tStations:  {:>7}",
+            self.loa.to_meter(),
+            self.dwl.to_meter(),
+            self.bwl.to_meter(),
+            self.b_max.to_meter(),
+            self.draft.to_meter(),
+            self.freeboard.to_meter(),
+            self.cp,
+            self.lcb,
+            self.deadrise,
+            self.transom,
+            self.stations
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn parametric_hull() {
+        let m = Length::from_meter;
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(m(6.0));
+        boat.set_dwl(m(5.4));
+        boat.set_bwl(m(2.0));
+        boat.set_b_max(m(2.2));
+        boat.set_draft(m(0.4));
+        boat.set_cp(0.55);
+        let parameters = HullParameters::from_boat(&boat);
+        assert_eq!(format!("{:.1}", parameters.lcb), "53.5");
+        assert_eq!(format!("{:.2}", parameters.freeboard.to_meter()), "0.60");
+
+        let hull = parameters.generate().unwrap();
+        // 11 stations, the maximum section and 2 sections on each overhang.
+        assert_eq!(hull.sections().len(), 16);
+        assert_eq!(format!("{:.2}", hull.range().0.to_meter()), "-0.36");
+        assert_eq!(format!("{:.2}", hull.range().1.to_meter()), "5.64");
+        let hydrostatics = Hydrostatics::new(&hull, m(0.4), Water::Salt);
+        let coefficients = hydrostatics.form_coefficients().unwrap();
+        assert_eq!(format!("{:.3}", coefficients.cp), "0.550");
+        assert_eq!(
+            format!("{:.2}", hydrostatics.waterline_beam().to_meter()),
+            "2.00"
+        );
+        assert_eq!(format!("{:.2}", hydrostatics.draft().to_meter()), "0.40");
+        assert_eq!(
+            format!("{:.1}", hydrostatics.waterplane_length().to_meter()),
+            "5.4"
+        );
+        let lcb = hydrostatics.lcb_percent(m(5.4)).unwrap();
+        assert!(lcb > 50.0 && lcb < 57.0);
+        // Transom at half the maximum beam, above the DWL.
+        let stern = hull.sections().last().unwrap();
+        assert_eq!(
+            format!("{:.2}", stern.points().last().unwrap().0.to_meter()),
+            "0.55"
+        );
+        assert!(stern.bottom().to_meter() > 0.4);
+
+        // A fuller hull.
+        let fuller = HullParameters {
+            cp: 0.62,
+            ..parameters
+        };
+        let hydrostatics = Hydrostatics::new(&fuller.generate().unwrap(), m(0.4), Water::Salt);
+        assert_eq!(
+            format!("{:.3}", hydrostatics.form_coefficients().unwrap().cp),
+            "0.620"
+        );
+
+        let invalid = HullParameters {
+            loa: m(5.0),
+            ..parameters
+        };
+        assert!(invalid.generate().is_none());
+    }
+}
@@ -0,0 +1,272 @@
+use super::hull::Hull;
+use super::section::Section;
+use super::si::Length;
+use super::spline::Interpolation;
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PARENT HULL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Parent hull
+///
+/// Built-in hull geometries to start a design from, transformed to the new dimensions instead
+/// of entering offsets from scratch. Stations aft of the forward perpendicular, (half-breadth,
+/// height) points from the keel to the sheer, heights above the baseline.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ParentHull {
+    HardChineDinghy,
+    RoundBilgeCruiser,
+    PlaningPowerboat,
+}
+
+#[allow(dead_code)]
+impl ParentHull {
+    /// Parent hull library.
+    pub const ALL: [ParentHull; 3] = [
+        ParentHull::HardChineDinghy,
+        ParentHull::RoundBilgeCruiser,
+        ParentHull::PlaningPowerboat,
+    ];
+
+    /// Parent hull by name, ignoring case.
+    pub fn from_name(name: &str) -> Option<ParentHull> {
+        ParentHull::ALL
+            .iter()
+            .copied()
+            .find(|p| p.to_string().eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn offsets(self) -> &'static [(f64, &'static [(f64, f64)])] {
+        match self {
+            ParentHull::HardChineDinghy => HARD_CHINE_DINGHY,
+            ParentHull::RoundBilgeCruiser => ROUND_BILGE_CRUISER,
+            ParentHull::PlaningPowerboat => PLANING_POWERBOAT,
+        }
+    }
+
+    /// Straight panels for the chined hulls, fair curves for the round bilge.
+    pub fn interpolation(self) -> Interpolation {
+        match self {
+            ParentHull::HardChineDinghy | ParentHull::PlaningPowerboat => Interpolation::Linear,
+            ParentHull::RoundBilgeCruiser => Interpolation::CubicSpline,
+        }
+    }
+
+    /// Offset points of the sections that are chines, counted from the keel.
+    pub fn chines(self) -> &'static [usize] {
+        match self {
+            ParentHull::HardChineDinghy | ParentHull::PlaningPowerboat => &[1],
+            ParentHull::RoundBilgeCruiser => &[],
+        }
+    }
+
+    /// Design waterline height above the baseline.
+    pub fn waterline(self) -> Length {
+        Length::from_meter(match self {
+            ParentHull::HardChineDinghy => 0.15,
+            ParentHull::RoundBilgeCruiser => 0.55,
+            ParentHull::PlaningPowerboat => 0.3,
+        })
+    }
+
+    pub fn hull(self) -> Hull {
+        let interpolation = self.interpolation();
+        let sections = self
+            .offsets()
+            .iter()
+            .filter_map(|(x, points)| {
+                let points: Vec<(Length, Length)> = points
+                    .iter()
+                    .map(|&(y, z)| (Length::from_meter(y), Length::from_meter(z)))
+                    .collect();
+                Section::new(Length::from_meter(*x), &points, interpolation)
+            })
+            .collect();
+        Hull::new(sections, interpolation).expect("Invalid parent hull offsets!")
+    }
+}
+
+impl fmt::Display for ParentHull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParentHull::HardChineDinghy => write!(f, "Hard chine dinghy"),
+            ParentHull::RoundBilgeCruiser => write!(f, "Round bilge cruiser"),
+            ParentHull::PlaningPowerboat => write!(f, "Planing powerboat"),
+        }
+    }
+}
+
+/// Hard chine dinghy, 3.6m, flat bottomed with rocker and a small transom.
+const HARD_CHINE_DINGHY: &[(f64, &[(f64, f64)])] = &[
+    (0.00, &[(0.000, 0.300), (0.000, 0.480), (0.000, 0.660)]),
+    (0.60, &[(0.000, 0.153), (0.306, 0.273), (0.404, 0.594)]),
+    (1.20, &[(0.000, 0.055), (0.504, 0.175), (0.657, 0.549)]),
+    (1.80, &[(0.000, 0.006), (0.594, 0.126), (0.758, 0.525)]),
+    (2.40, &[(0.000, 0.002), (0.593, 0.122), (0.744, 0.521)]),
+    (3.00, &[(0.000, 0.022), (0.553, 0.142), (0.685, 0.533)]),
+    (3.60, &[(0.000, 0.060), (0.480, 0.180), (0.580, 0.560)]),
+];
+/// Planing powerboat, 5m, deep V with 20 degree deadrise aft and a full transom.
+const PLANING_POWERBOAT: &[(f64, &[(f64, f64)])] = &[
+    (0.00, &[(0.000, 0.350), (0.000, 0.775), (0.000, 1.200)]),
+    (0.62, &[(0.000, 0.130), (0.384, 0.438), (0.512, 1.134)]),
+    (1.25, &[(0.000, 0.017), (0.677, 0.448), (0.854, 1.088)]),
+    (1.88, &[(0.000, 0.000), (0.872, 0.430), (1.027, 1.059)]),
+    (2.50, &[(0.000, 0.000), (0.950, 0.346), (1.045, 1.050)]),
+    (3.12, &[(0.000, 0.000), (0.938, 0.341), (1.033, 1.038)]),
+    (3.75, &[(0.000, 0.000), (0.925, 0.337), (1.022, 1.025)]),
+    (4.38, &[(0.000, 0.000), (0.912, 0.332), (1.011, 1.012)]),
+    (5.00, &[(0.000, 0.000), (0.900, 0.328), (1.000, 1.000)]),
+];
+/// Round bilge cruiser, 9m LOA and 7.6m DWL from 0.7m, canoe body without the keel.
+const ROUND_BILGE_CRUISER: &[(f64, &[(f64, f64)])] = &[
+    (0.00, &[(0.000, 1.100), (0.000, 1.520)]),
+    (0.70, &[(0.000, 0.550), (0.547, 1.456)]),
+    (
+        1.46,
+        &[
+            (0.000, 0.284),
+            (0.264, 0.321),
+            (0.444, 0.346),
+            (0.577, 0.368),
+            (0.663, 0.432),
+            (0.701, 0.550),
+            (0.917, 1.398),
+        ],
+    ),
+    (
+        2.22,
+        &[
+            (0.000, 0.180),
+            (0.368, 0.232),
+            (0.617, 0.267),
+            (0.802, 0.297),
+            (0.921, 0.386),
+            (0.975, 0.550),
+            (1.202, 1.354),
+        ],
+    ),
+    (
+        2.98,
+        &[
+            (0.000, 0.106),
+            (0.441, 0.168),
+            (0.741, 0.210),
+            (0.963, 0.247),
+            (1.106, 0.353),
+            (1.170, 0.550),
+            (1.402, 1.323),
+        ],
+    ),
+    (
+        3.74,
+        &[
+            (0.000, 0.050),
+            (0.497, 0.120),
+            (0.835, 0.167),
+            (1.085, 0.208),
+            (1.246, 0.328),
+            (1.319, 0.550),
+            (1.518, 1.305),
+        ],
+    ),
+    (
+        4.50,
+        &[
+            (0.000, 0.009),
+            (0.537, 0.085),
+            (0.902, 0.136),
+            (1.172, 0.180),
+            (1.347, 0.310),
+            (1.425, 0.550),
+            (1.550, 1.300),
+        ],
+    ),
+    (
+        5.26,
+        &[
+            (0.000, 0.020),
+            (0.527, 0.094),
+            (0.885, 0.144),
+            (1.150, 0.188),
+            (1.321, 0.315),
+            (1.398, 0.550),
+            (1.526, 1.303),
+        ],
+    ),
+    (
+        6.02,
+        &[
+            (0.000, 0.075),
+            (0.472, 0.141),
+            (0.793, 0.186),
+            (1.031, 0.225),
+            (1.184, 0.339),
+            (1.253, 0.550),
+            (1.463, 1.310),
+        ],
+    ),
+    (
+        6.78,
+        &[
+            (0.000, 0.152),
+            (0.396, 0.208),
+            (0.664, 0.245),
+            (0.863, 0.278),
+            (0.992, 0.373),
+            (1.049, 0.550),
+            (1.363, 1.321),
+        ],
+    ),
+    (
+        7.54,
+        &[
+            (0.000, 0.263),
+            (0.286, 0.303),
+            (0.479, 0.330),
+            (0.623, 0.354),
+            (0.716, 0.422),
+            (0.757, 0.550),
+            (1.225, 1.337),
+        ],
+    ),
+    (8.30, &[(0.000, 0.550), (1.049, 1.358)]),
+    (9.00, &[(0.000, 0.800), (0.853, 1.380)]),
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn parent_hull() {
+        use crate::hydrostatics::{Hydrostatics, Water};
+        use crate::transform::HullTransformation;
+
+        assert_eq!(
+            ParentHull::from_name("round bilge CRUISER"),
+            Some(ParentHull::RoundBilgeCruiser)
+        );
+        assert_eq!(ParentHull::from_name("Catamaran"), None);
+        for &parent in ParentHull::ALL.iter() {
+            let hull = parent.hull();
+            assert_eq!(hull.sections().len(), parent.offsets().len());
+            let hydrostatics = Hydrostatics::new(&hull, parent.waterline(), Water::Salt);
+            let cp = hydrostatics.form_coefficients().unwrap().cp;
+            assert!(cp > 0.5 && cp < 0.8, "{} Cp {}", parent, cp);
+        }
+
+        // A longer dinghy from the parent.
+        let parent = ParentHull::HardChineDinghy;
+        let (hull, _) = HullTransformation {
+            length: 4.2 / 3.6,
+            ..HullTransformation::default()
+        }
+        .apply(&parent.hull(), parent.waterline(), Water::Fresh)
+        .unwrap();
+        assert_eq!(format!("{:.2}", hull.range().1.to_meter()), "4.20");
+    }
+}
@@ -0,0 +1,188 @@
+use super::boat::Boat;
+use super::propulsion::{Motoring, MotoringRange};
+use super::seakeeping::{design_wave_height, Seakeeping};
+use super::si::{Length, Power, Speed};
+use super::stability::DesignCategory;
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PERFORMANCE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Performance report
+///
+/// Speeds of the boat under sail and power, with the motoring range when the engine and the
+/// fuel tanks are informed. For a design category, the added resistance in its waves at the
+/// hull speed and the motoring speed it leaves, the power going as the cube of the speed.
+pub struct Performance {
+    hull_speed: Speed,
+    target_speed: Speed,
+    /// Effective power at the hull speed, from the resistance curve.
+    hull_speed_power: Option<Power>,
+    motoring: Option<Motoring>,
+    range: Option<MotoringRange>,
+    seakeeping: Seakeeping,
+    /// Design category, wave height and resistance in its waves over the calm water resistance.
+    sea_state: Option<(DesignCategory, Length, f64)>,
+}
+
+#[allow(dead_code)]
+impl Performance {
+    pub fn from_boat(boat: &Boat) -> Performance {
+        let hull_speed = boat.hull_speed();
+        Performance {
+            hull_speed,
+            target_speed: boat.target_speed(),
+            hull_speed_power: boat
+                .resistance()
+                .and_then(|c| c.at(hull_speed))
+                .map(|p| p.effective_power()),
+            motoring: Motoring::from_boat(boat),
+            range: MotoringRange::from_boat(boat),
+            seakeeping: Seakeeping::from_boat(boat),
+            sea_state: None,
+        }
+    }
+
+    /// Report for the waves of a design category, coastal or offshore.
+    pub fn in_category(boat: &Boat, category: DesignCategory) -> Performance {
+        let report = Performance::from_boat(boat);
+        let wave_height = design_wave_height(category);
+        let sea_state = report
+            .seakeeping
+            .resistance_factor(boat, wave_height, report.hull_speed)
+            .map(|factor| (category, wave_height, factor));
+        Performance {
+            sea_state,
+            ..report
+        }
+    }
+
+    pub fn hull_speed(&self) -> Speed {
+        self.hull_speed
+    }
+
+    pub fn target_speed(&self) -> Speed {
+        self.target_speed
+    }
+
+    /// Effective power at the hull speed.
+    pub fn hull_speed_power(&self) -> Option<Power> {
+        self.hull_speed_power
+    }
+
+    pub fn motoring(&self) -> Option<&Motoring> {
+        self.motoring.as_ref()
+    }
+
+    pub fn range(&self) -> Option<&MotoringRange> {
+        self.range.as_ref()
+    }
+
+    pub fn seakeeping(&self) -> &Seakeeping {
+        &self.seakeeping
+    }
+
+    /// Resistance in the design category waves over the calm water resistance.
+    pub fn resistance_factor(&self) -> Option<f64> {
+        self.sea_state.map(|(_, _, factor)| factor)
+    }
+
+    /// Motoring speed in the design category waves.
+    pub fn motoring_speed_in_waves(&self) -> Option<Speed> {
+        let speed = self.motoring.as_ref()?.speed();
+        Some(Speed::from_knot(
+            speed.to_knot() / self.resistance_factor()?.powf(1.0 / 3.0),
+        ))
+    }
+}
+
+impl fmt::Display for Performance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Performance]\n\
+            \tHull speed:   {:>9.2}kn\n\
+            \tTarget speed: {:>9.2}kn",
+            self.hull_speed.to_knot(),
+            self.target_speed.to_knot()
+        )?;
+        if let Some(power) = self.hull_speed_power {
+            write!(f, "\n\tPE at hull speed: {:>5.1}hp", power.to_horsepower())?;
+        }
+        if let Some(motoring) = &self.motoring {
+            write!(f, "\n{}", motoring)?;
+        }
+        if let Some(range) = &self.range {
+            write!(f, "\n{}", range)?;
+        }
+        write!(f, "\n{}", self.seakeeping)?;
+        if let Some((category, wave_height, factor)) = self.sea_state {
+            write!(
+                f,
+                "\n\tCategory {} waves of {:.1}m: {:+.0}% resistance",
+                category,
+                wave_height.to_meter(),
+                (factor - 1.0) * 100.0
+            )?;
+            if let Some(speed) = self.motoring_speed_in_waves() {
+                write!(f, ", motoring at {:.1}kn", speed.to_knot())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn performance() {
+        use crate::loading::{LoadCondition, LoadConditionKind, Tank};
+        use crate::propulsion::{Engine, EngineType};
+        use crate::si::{Length, Volume, Weight};
+
+        let mut boat = Boat::new("Cruiser".to_string());
+        boat.set_dwl(Length::from_foot(25.0));
+        boat.set_displacement(Weight::from_pound(8000.0));
+        let report = Performance::from_boat(&boat);
+        assert!(report.motoring().is_none());
+        assert!(report.range().is_none());
+        assert!(!report.to_string().contains("[Motoring"));
+
+        boat.set_engine(Engine::new(
+            EngineType::Inboard,
+            Power::from_horsepower(20.0),
+            Weight::from_kilogram(100.0),
+        ));
+        let mut full = LoadCondition::new(LoadConditionKind::Full, boat.displacement());
+        full.add_tank(Tank::diesel("Fuel".to_string(), Volume::from_liter(80.0)));
+        full.add_tank(Tank::water("Water".to_string(), Volume::from_liter(200.0)));
+        boat.set_load_condition(full);
+        let report = Performance::from_boat(&boat);
+        assert!(report.motoring().is_some());
+        let text = report.to_string();
+        assert!(text.contains("[Motoring range - 72l usable]"));
+        assert_eq!(format!("{:.2}", report.hull_speed().to_knot()), "6.70");
+        assert!(report.resistance_factor().is_none());
+
+        // Slower offshore than coastal.
+        boat.set_cp(0.55);
+        boat.set_cw(0.7);
+        boat.set_lcb(53.0);
+        boat.set_lcf(55.0);
+        let coastal = Performance::in_category(&boat, DesignCategory::C);
+        let offshore = Performance::in_category(&boat, DesignCategory::B);
+        assert!(offshore.resistance_factor().unwrap() > coastal.resistance_factor().unwrap());
+        let calm = coastal.motoring().unwrap().speed().to_knot();
+        let coastal_speed = coastal.motoring_speed_in_waves().unwrap().to_knot();
+        assert!(coastal_speed < calm);
+        assert!(offshore.motoring_speed_in_waves().unwrap().to_knot() < coastal_speed);
+        assert!(offshore
+            .to_string()
+            .contains("Category B (Offshore) waves of 4.0m"));
+    }
+}
@@ -0,0 +1,500 @@
+use super::csv::CsvError;
+use super::drawing::{file_target, ColorMode, DrawingError, DrawingStyle, LineKind, GRID_DASH};
+use super::si::Speed;
+use super::vpp::{Vpp, VppGrid};
+use cairo::Context;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// True wind angles searched for the best VMG upwind (degree).
+pub const BEAT_RANGE: (f64, f64) = (25.0, 70.0);
+
+/// True wind angles searched for the best VMG downwind (degree).
+pub const RUN_RANGE: (f64, f64) = (110.0, 180.0);
+
+/// Step of the VMG angle search (degree).
+pub const VMG_ANGLE_STEP: f64 = 1.0;
+
+/// Usual true wind speeds of a polar (knot).
+pub const POLAR_WIND_SPEEDS: [f64; 7] = [6.0, 8.0, 10.0, 12.0, 14.0, 16.0, 20.0];
+
+/// Usual true wind angles of a polar (degree).
+pub const POLAR_WIND_ANGLES: [f64; 9] = [52.0, 60.0, 75.0, 90.0, 110.0, 120.0, 135.0, 150.0, 165.0];
+
+/// Colors of the polar traces, one for each true wind speed, light to strong winds.
+pub const TRACE_COLORS: [(f64, f64, f64); 7] = [
+    (0.0, 0.45, 0.7),
+    (0.0, 0.6, 0.5),
+    (0.35, 0.7, 0.9),
+    (0.9, 0.6, 0.0),
+    (0.8, 0.4, 0.0),
+    (0.8, 0.6, 0.7),
+    (0.0, 0.0, 0.0),
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// POLAR
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Best VMG (velocity made good) at a true wind speed.
+#[derive(Debug, Copy, Clone)]
+pub struct VmgOptimum {
+    /// True wind angle (degree).
+    pub angle: f64,
+    pub speed: Speed,
+    /// VMG toward the wind upwind, away from the wind downwind.
+    pub vmg: Speed,
+}
+
+/// Polar
+///
+/// Boat speed by true wind angle (rows) and true wind speed (columns), with the best VMG
+/// angles and speeds upwind and downwind for each true wind speed.
+#[derive(Debug, Clone)]
+pub struct Polar {
+    true_wind_speeds: Vec<Speed>,
+    true_wind_angles: Vec<f64>,
+    speeds: Vec<Vec<Option<Speed>>>,
+    beat: Vec<Option<VmgOptimum>>,
+    run: Vec<Option<VmgOptimum>>,
+}
+
+#[allow(dead_code)]
+impl Polar {
+    /// Polar of the VPP, the VMG angles searched every VMG_ANGLE_STEP.
+    pub fn from_vpp(vpp: &Vpp, true_wind_speeds: &[Speed], true_wind_angles: &[f64]) -> Polar {
+        let grid = vpp.grid(true_wind_speeds, true_wind_angles);
+        let optimum = |tws: Speed, range: (f64, f64), sign: f64| {
+            let steps = ((range.1 - range.0) / VMG_ANGLE_STEP).round() as usize;
+            (0..=steps)
+                .filter_map(|i| vpp.solve(tws, range.0 + VMG_ANGLE_STEP * i as f64))
+                .map(|p| VmgOptimum {
+                    angle: p.true_wind_angle,
+                    speed: p.speed,
+                    vmg: Speed::from_meter_second(sign * p.vmg().to_meter_second()),
+                })
+                .filter(|o| o.vmg.to_meter_second() > 0.0)
+                .fold(None, |best: Option<VmgOptimum>, o| match best {
+                    Some(b) if b.vmg.to_meter_second() >= o.vmg.to_meter_second() => best,
+                    _ => Some(o),
+                })
+        };
+        Polar {
+            beat: true_wind_speeds
+                .iter()
+                .map(|&tws| optimum(tws, BEAT_RANGE, 1.0))
+                .collect(),
+            run: true_wind_speeds
+                .iter()
+                .map(|&tws| optimum(tws, RUN_RANGE, -1.0))
+                .collect(),
+            ..Polar::from_grid(&grid)
+        }
+    }
+
+    /// Polar of the usual POLAR_WIND_SPEEDS and POLAR_WIND_ANGLES.
+    pub fn standard(vpp: &Vpp) -> Polar {
+        let speeds: Vec<Speed> = POLAR_WIND_SPEEDS
+            .iter()
+            .map(|&s| Speed::from_knot(s))
+            .collect();
+        Polar::from_vpp(vpp, &speeds, &POLAR_WIND_ANGLES)
+    }
+
+    /// Polar of the solutions of a grid, the VMG angles among the angles of the grid.
+    pub fn from_grid(grid: &VppGrid) -> Polar {
+        let angles = grid.true_wind_angles();
+        let columns = grid.true_wind_speeds().len();
+        let speeds: Vec<Vec<Option<Speed>>> = (0..angles.len())
+            .map(|i| {
+                (0..columns)
+                    .map(|j| grid.point(i, j).map(|p| p.speed))
+                    .collect()
+            })
+            .collect();
+        let optimum = |j: usize, sign: f64| {
+            (0..angles.len())
+                .filter_map(|i| grid.point(i, j))
+                .map(|p| VmgOptimum {
+                    angle: p.true_wind_angle,
+                    speed: p.speed,
+                    vmg: Speed::from_meter_second(sign * p.vmg().to_meter_second()),
+                })
+                .filter(|o| o.vmg.to_meter_second() > 0.0)
+                .fold(None, |best: Option<VmgOptimum>, o| match best {
+                    Some(b) if b.vmg.to_meter_second() >= o.vmg.to_meter_second() => best,
+                    _ => Some(o),
+                })
+        };
+        Polar {
+            true_wind_speeds: grid.true_wind_speeds().to_vec(),
+            true_wind_angles: angles.to_vec(),
+            speeds,
+            beat: (0..columns).map(|j| optimum(j, 1.0)).collect(),
+            run: (0..columns).map(|j| optimum(j, -1.0)).collect(),
+        }
+    }
+
+    pub fn true_wind_speeds(&self) -> &[Speed] {
+        &self.true_wind_speeds
+    }
+
+    pub fn true_wind_angles(&self) -> &[f64] {
+        &self.true_wind_angles
+    }
+
+    /// Boat speed at a true wind angle and speed index.
+    pub fn speed(&self, angle: usize, speed: usize) -> Option<Speed> {
+        *self.speeds.get(angle)?.get(speed)?
+    }
+
+    /// Best VMG upwind at a true wind speed index.
+    pub fn beat(&self, speed: usize) -> Option<VmgOptimum> {
+        *self.beat.get(speed)?
+    }
+
+    /// Best VMG downwind at a true wind speed index.
+    pub fn run(&self, speed: usize) -> Option<VmgOptimum> {
+        *self.run.get(speed)?
+    }
+
+    /// Boat speeds of a true wind speed index with the VMG optima, (true wind angle, speed) by
+    /// the angle, for the plot.
+    pub fn curve(&self, speed: usize) -> Vec<(f64, Speed)> {
+        let mut points: Vec<(f64, Speed)> = self
+            .true_wind_angles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &twa)| Some((twa, self.speed(i, speed)?)))
+            .collect();
+        for optimum in [self.beat(speed), self.run(speed)].iter().flatten() {
+            points.push((optimum.angle, optimum.speed));
+        }
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        points
+    }
+
+    /// CSV text, boat speeds in knots by true wind angle rows and true wind speed columns,
+    /// the beat and run angles and VMG rows around them. Empty where the boat cannot sail.
+    pub fn to_csv(&self) -> String {
+        let optional = |v: Option<f64>, precision: usize| {
+            v.map_or_else(String::new, |v| format!("{:.*}", precision, v))
+        };
+        let row = |name: String, values: Vec<String>| format!("{},{}\n", name, values.join(","));
+        let optima = |name: &str, optima: &[Option<VmgOptimum>]| {
+            row(
+                format!("{} angle", name),
+                optima
+                    .iter()
+                    .map(|o| optional(o.map(|o| o.angle), 1))
+                    .collect(),
+            ) + &row(
+                format!("{} VMG", name),
+                optima
+                    .iter()
+                    .map(|o| optional(o.map(|o| o.vmg.to_knot()), 2))
+                    .collect(),
+            )
+        };
+        let mut text = row(
+            "TWA\\TWS".to_string(),
+            self.true_wind_speeds
+                .iter()
+                .map(|s| format!("{:.0}", s.to_knot()))
+                .collect(),
+        );
+        text += &optima("Beat", &self.beat);
+        for (i, twa) in self.true_wind_angles.iter().enumerate() {
+            text += &row(
+                format!("{:.0}", twa),
+                (0..self.true_wind_speeds.len())
+                    .map(|j| optional(self.speed(i, j).map(|s| s.to_knot()), 2))
+                    .collect(),
+            );
+        }
+        text += &optima("Run", &self.run);
+        text
+    }
+
+    /// Write the CSV text to a file.
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), CsvError> {
+        fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+
+    /// Plot the polar in a width by height box at the context origin, the wind from the top and
+    /// the boat speed radial from the center on the left edge, a ring every knot and a spoke
+    /// every 30 degrees. One trace for each true wind speed, labeled in the legend on the right,
+    /// with the best VMG angles upwind and downwind marked. The frame, rings and spokes on the
+    /// grid layer, the traces on the hull layer in TRACE_COLORS, grays in monochrome, and the
+    /// labels on the annotations layer.
+    pub fn draw(&self, context: &Context, width: f64, height: f64, style: &DrawingStyle) {
+        let fastest = (0..self.true_wind_speeds.len())
+            .flat_map(|j| self.curve(j))
+            .map(|(_, s)| s.to_knot())
+            .fold(0.0, f64::max)
+            .ceil();
+        if fastest <= 0.0 {
+            return;
+        }
+        // Room for the labels and the legend.
+        let (left, right, margin) = (20.0, 60.0, 20.0);
+        let (cx, cy) = (left, height / 2.0);
+        let radius = (width - left - right).min(height / 2.0 - margin);
+        let at = |angle: f64, r: f64| {
+            (
+                cx + r * angle.to_radians().sin(),
+                cy - r * angle.to_radians().cos(),
+            )
+        };
+        let to_point = |angle: f64, knots: f64| at(angle, knots / fastest * radius);
+        let size = style.label_size();
+        let step = (fastest / 8.0).ceil();
+        let count = self.true_wind_speeds.len();
+        context.save();
+        style.select_font(context);
+
+        if style.apply(context, LineKind::Grid) {
+            context.rectangle(0.0, 0.0, width, height);
+            context.stroke();
+            context.set_dash(&GRID_DASH, 0.0);
+            let mut knots = step;
+            while knots <= fastest {
+                context.new_path();
+                context.arc(
+                    cx,
+                    cy,
+                    knots / fastest * radius,
+                    -std::f64::consts::FRAC_PI_2,
+                    std::f64::consts::FRAC_PI_2,
+                );
+                context.stroke();
+                knots += step;
+            }
+            let mut angle = 0.0;
+            while angle <= 180.0 {
+                context.move_to(cx, cy);
+                let (x, y) = to_point(angle, fastest);
+                context.line_to(x, y);
+                context.stroke();
+                angle += 30.0;
+            }
+            context.set_dash(&[], 0.0);
+        }
+
+        if style.apply(context, LineKind::Curves) {
+            context.set_line_width(0.75 * style.line_width(LineKind::Curves));
+            for j in 0..count {
+                let (r, g, b) = match style.mode() {
+                    ColorMode::Color => TRACE_COLORS[j % TRACE_COLORS.len()],
+                    ColorMode::Monochrome => {
+                        let gray = 0.6 * j as f64 / count as f64;
+                        (gray, gray, gray)
+                    }
+                };
+                context.set_source_rgb(r, g, b);
+                for (i, (angle, speed)) in self.curve(j).into_iter().enumerate() {
+                    let (x, y) = to_point(angle, speed.to_knot());
+                    if i == 0 {
+                        context.move_to(x, y);
+                    } else {
+                        context.line_to(x, y);
+                    }
+                }
+                context.stroke();
+                for optimum in [self.beat(j), self.run(j)].iter().flatten() {
+                    let (x, y) = to_point(optimum.angle, optimum.speed.to_knot());
+                    context.new_path();
+                    context.arc(x, y, 3.0, 0.0, 2.0 * std::f64::consts::PI);
+                    context.fill();
+                }
+                let y = margin + 1.5 * size * j as f64;
+                context.move_to(width - right + 8.0, y);
+                context.line_to(width - right + 24.0, y);
+                context.stroke();
+            }
+        }
+
+        if style.apply(context, LineKind::Dimensions) {
+            let mut knots = step;
+            while knots <= fastest {
+                let r = knots / fastest * radius;
+                context.move_to(cx - 14.0, cy - r + size / 3.0);
+                context.show_text(&format!("{:.0}", knots));
+                knots += step;
+            }
+            let mut angle = 0.0;
+            while angle <= 180.0 {
+                let label = format!("{:.0}°", angle);
+                let label_width = context.text_extents(&label).width;
+                let (x, y) = at(angle, radius + 10.0);
+                context.move_to(x - label_width / 2.0, y + size / 3.0);
+                context.show_text(&label);
+                angle += 30.0;
+            }
+            for (j, tws) in self.true_wind_speeds.iter().enumerate() {
+                let y = margin + 1.5 * size * j as f64;
+                context.move_to(width - right + 28.0, y + size / 3.0);
+                context.show_text(&format!("{:.0}kn", tws.to_knot()));
+            }
+            context.move_to(
+                width - right + 8.0,
+                margin + 1.5 * size * count as f64 + size / 3.0,
+            );
+            context.show_text("• best VMG");
+        }
+        context.restore();
+    }
+
+    /// Plot the polar to a PDF, SVG or PNG file by the extension, width by height points.
+    pub fn plot<P: AsRef<Path>>(
+        &self,
+        path: P,
+        width: f64,
+        height: f64,
+        style: &DrawingStyle,
+    ) -> Result<(), DrawingError> {
+        file_target(path, width, height)?.render(&|context, width, height| {
+            self.draw(context, width, height, style);
+        })
+    }
+}
+
+impl fmt::Display for Polar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let optional = |s: Option<Speed>| match s {
+            Some(s) => format!("{:>6.2}", s.to_knot()),
+            None => format!("{:>6}", "-"),
+        };
+        write!(f, "[Polar - boat speed (kn)]\n\t  TWA")?;
+        for tws in &self.true_wind_speeds {
+            write!(f, " {:>4.0}kn", tws.to_knot())?;
+        }
+        write!(f, "\n\t Beat")?;
+        for beat in &self.beat {
+            write!(f, " {}", optional(beat.map(|o| o.vmg)))?;
+        }
+        for (i, twa) in self.true_wind_angles.iter().enumerate() {
+            write!(f, "\n\t{:>5.0}", twa)?;
+            for j in 0..self.true_wind_speeds.len() {
+                write!(f, " {}", optional(self.speed(i, j)))?;
+            }
+        }
+        write!(f, "\n\t  Run")?;
+        for run in &self.run {
+            write!(f, " {}", optional(run.map(|o| o.vmg)))?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn polar() {
+        use crate::appendage::{Keel, KeelType};
+        use crate::boat::Boat;
+        use crate::si::{Area, Length, Weight};
+
+        let m = Length::from_meter;
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(m(10.0));
+        boat.set_bwl(m(3.0));
+        boat.set_draft(m(0.6));
+        boat.set_displacement(Weight::from_kilogram(7175.0));
+        boat.set_sail_area(Area::from_meter2(60.0));
+        boat.set_cp(0.55);
+        boat.set_cw(0.7);
+        boat.set_lcb(53.0);
+        boat.set_lcf(55.0);
+        boat.set_vcg(m(0.3));
+        boat.set_wetted_surface(Area::from_meter2(25.0));
+        boat.set_keel(Keel::new(
+            KeelType::Fin,
+            m(1.2),
+            m(0.6),
+            m(1.4),
+            Weight::from_kilogram(2500.0),
+        ));
+        let vpp = Vpp::from_boat(&boat).unwrap();
+        let knots = Speed::from_knot;
+        let polar = Polar::from_vpp(&vpp, &[knots(8.0), knots(16.0)], &[45.0, 90.0, 150.0]);
+        assert_eq!(
+            format!("{:.2}", polar.speed(1, 0).unwrap().to_knot()),
+            "6.98"
+        );
+        let beat = polar.beat(0).unwrap();
+        assert_eq!(beat.angle, 39.0);
+        assert_eq!(format!("{:.2}", beat.vmg.to_knot()), "4.47");
+        assert!(polar.beat(1).unwrap().vmg.to_knot() > beat.vmg.to_knot());
+        assert!(polar.run(1).unwrap().vmg.to_knot() > 0.0);
+        assert_eq!(polar.curve(0).len(), 5);
+
+        let csv = polar.to_csv();
+        assert_eq!(csv.lines().next(), Some("TWA\\TWS,8,16"));
+        assert_eq!(csv.lines().nth(1), Some("Beat angle,39.0,35.0"));
+        assert_eq!(csv.lines().count(), 8);
+        let path = std::env::temp_dir().join(format!("boat_plan_polar_{}.csv", std::process::id()));
+        polar.save_csv(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), csv);
+        std::fs::remove_file(&path).unwrap();
+
+        // From the grid alone, the optima among its angles.
+        let grid = vpp.grid(&[knots(8.0)], &[45.0, 90.0, 150.0]);
+        let coarse = Polar::from_grid(&grid);
+        assert_eq!(coarse.beat(0).unwrap().angle, 45.0);
+        assert_eq!(coarse.run(0).unwrap().angle, 150.0);
+    }
+
+    #[test]
+    fn polar_plot() {
+        use crate::drawing::to_image;
+
+        let knots = Speed::from_knot;
+        let polar = Polar {
+            true_wind_speeds: vec![knots(10.0)],
+            true_wind_angles: vec![45.0, 90.0, 135.0, 180.0],
+            speeds: vec![
+                vec![Some(knots(5.0))],
+                vec![Some(knots(6.0))],
+                vec![Some(knots(6.0))],
+                vec![Some(knots(4.0))],
+            ],
+            beat: vec![Some(VmgOptimum {
+                angle: 40.0,
+                speed: knots(4.8),
+                vmg: knots(3.7),
+            })],
+            run: vec![None],
+        };
+        let style = DrawingStyle::new();
+        let mut image = to_image(300, 300, |context| {
+            polar.draw(context, 300.0, 300.0, &style)
+        })
+        .unwrap();
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        let blank = |x: usize, y: usize| data[y * stride + x * 4..][..3].iter().all(|&v| v == 255);
+        // Center (20, 150), 6kn on the 130 points radius, the beam reach at (150, 150).
+        assert!(!blank(150, 150));
+        assert!(!blank(148, 150));
+        assert!(blank(120, 140));
+        // Beat optimum, 4.8kn at 40 degrees.
+        let r = 4.8 / 6.0 * 130.0;
+        let (x, y) = (
+            20.0 + r * 40f64.to_radians().sin(),
+            150.0 - r * 40f64.to_radians().cos(),
+        );
+        assert!(!blank(x.round() as usize + 2, y.round() as usize));
+
+        let path = std::env::temp_dir().join(format!("boat_plan_polar_{}.pdf", std::process::id()));
+        polar.plot(&path, 300.0, 300.0, &style).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+use super::boat::Boat;
+use super::propulsion::Motoring;
+use super::si::{Length, Power, Speed};
+use std::fmt;
+
+/// Two blade diameter relative to the three blade one, same power and RPM.
+pub const TWO_BLADE_DIAMETER_FACTOR: f64 = 1.05;
+
+/// Slip above which the propeller is considered overloaded.
+pub const MAX_SLIP: f64 = 0.55;
+
+/// Fraction of the diameter recommended as clearance between the tips and the hull.
+pub const TIP_CLEARANCE: f64 = 0.15;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PROPELLER
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Propeller
+///
+/// First estimate by Gerr formulas, for a displacement hull:
+/// Diameter (in) = 632.7 * SHP^0.2 / RPM^0.6 (three blades)
+/// Slip = 1.4 / kn^0.57
+/// Pitch (in) = kn * 1215 / (RPM * (1 - Slip))
+/// A two blade propeller is recommended, for less drag under sail, when it fits the aperture.
+/// Gerr, D. (2001) The propeller handbook. International Marine.
+pub struct Propeller {
+    blades: u8,
+    diameter: Length,
+    pitch: Length,
+    slip: f64,
+    shaft_rpm: f64,
+    cautions: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl Propeller {
+    /// Propeller for the shaft power, engine RPM, reduction ratio and target speed.
+    pub fn size(
+        shaft_power: Power,
+        engine_rpm: f64,
+        reduction: f64,
+        speed: Speed,
+        aperture: Option<Length>,
+    ) -> Propeller {
+        let shaft_rpm = engine_rpm / reduction;
+        let knot = speed.to_knot();
+        let three_blade = 632.7 * shaft_power.to_horsepower().powf(0.2) / shaft_rpm.powf(0.6);
+        let two_blade = three_blade * TWO_BLADE_DIAMETER_FACTOR;
+        let slip = 1.4 / knot.powf(0.57);
+        let pitch = knot * 1215.0 / (shaft_rpm * (1.0 - slip));
+
+        let mut cautions = Vec::new();
+        let fits = |diameter: f64| {
+            aperture.is_none_or(|a| diameter * (1.0 + 2.0 * TIP_CLEARANCE) <= a.to_inch())
+        };
+        let (blades, diameter) = if fits(two_blade) {
+            (2, two_blade)
+        } else {
+            if !fits(three_blade) {
+                cautions.push(
+                    "Diameter does not fit the aperture, increase the reduction ratio".to_string(),
+                );
+            }
+            (3, three_blade)
+        };
+        if slip > MAX_SLIP {
+            cautions.push(format!(
+                "Slip of {:.0}% is high, the speed is low for the power",
+                slip * 100.0
+            ));
+        }
+        let ratio = pitch / diameter;
+        if !(0.5..=1.4).contains(&ratio) {
+            cautions.push(format!(
+                "Pitch to diameter ratio of {:.2} is out of the usual 0.5 to 1.4",
+                ratio
+            ));
+        }
+        cautions.push("First estimate only, confirm with the propeller supplier".to_string());
+
+        Propeller {
+            blades,
+            diameter: Length::from_inch(diameter),
+            pitch: Length::from_inch(pitch),
+            slip,
+            shaft_rpm,
+            cautions,
+        }
+    }
+
+    /// Propeller for the boat engine at the estimated motoring speed, needs the engine.
+    pub fn from_boat(
+        boat: &Boat,
+        engine_rpm: f64,
+        reduction: f64,
+        aperture: Option<Length>,
+    ) -> Option<Propeller> {
+        let engine = boat.engine()?;
+        let motoring = Motoring::from_boat(boat)?;
+        Some(Propeller::size(
+            engine.shaft_power(),
+            engine_rpm,
+            reduction,
+            motoring.speed(),
+            aperture,
+        ))
+    }
+
+    /// Number of blades.
+    pub fn blades(&self) -> u8 {
+        self.blades
+    }
+
+    /// Diameter.
+    pub fn diameter(&self) -> Length {
+        self.diameter
+    }
+
+    /// Pitch.
+    pub fn pitch(&self) -> Length {
+        self.pitch
+    }
+
+    /// Apparent slip.
+    pub fn slip(&self) -> f64 {
+        self.slip
+    }
+
+    /// Propeller shaft RPM.
+    pub fn shaft_rpm(&self) -> f64 {
+        self.shaft_rpm
+    }
+
+    /// Cautions about the estimate.
+    pub fn cautions(&self) -> &[String] {
+        &self.cautions
+    }
+}
+
+impl fmt::Display for Propeller {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Propeller - {} blades]\n\
+            \tDiameter: {:>9.1}in\n\
+            \tPitch:    {:>9.1}in\n\
+            \tSlip:     {:>9.0}%\n\
+            \tRPM:      {:>9.0}",
+            self.blades,
+            self.diameter.to_inch(),
+            self.pitch.to_inch(),
+            self.slip * 100.0,
+            self.shaft_rpm
+        )?;
+        for caution in &self.cautions {
+            write!(f, "\n\t{}", caution)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn propeller() {
+        let propeller = Propeller::size(
+            Power::from_horsepower(20.0),
+            2400.0,
+            2.0,
+            Speed::from_knot(6.0),
+            None,
+        );
+        assert_eq!(propeller.blades(), 2);
+        assert_eq!(format!("{:.0}", propeller.shaft_rpm()), "1200");
+        assert_eq!(format!("{:.1}", propeller.diameter().to_inch()), "17.2");
+        assert_eq!(format!("{:.2}", propeller.slip()), "0.50");
+        assert_eq!(format!("{:.1}", propeller.pitch().to_inch()), "12.3");
+
+        // Not room for the two blade propeller.
+        let propeller = Propeller::size(
+            Power::from_horsepower(20.0),
+            2400.0,
+            2.0,
+            Speed::from_knot(6.0),
+            Some(Length::from_inch(22.0)),
+        );
+        assert_eq!(propeller.blades(), 3);
+        assert_eq!(propeller.cautions().len(), 1);
+    }
+}
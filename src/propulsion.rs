@@ -0,0 +1,658 @@
+use super::boat::Boat;
+use super::loading::LoadConditionKind;
+use super::resistance::HullType;
+use super::si::{Power, Speed, Volume, Weight};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Inboard power delivered to the propeller, after the gearbox and shaft losses.
+pub const INBOARD_SHAFT_EFFICIENCY: f64 = 0.96;
+
+/// Motoring SLR limit, a sailboat hull cannot be driven much past hull speed.
+pub const MAX_MOTORING_SLR: f64 = 1.34;
+
+/// Planing SLR the Crouch formula is good from, slower the hull is not yet planing.
+pub const CROUCH_MIN_SLR: f64 = 2.5;
+
+/// Diesel specific fuel consumption at the rated power (g/kWh).
+pub const DIESEL_SFC: f64 = 250.0;
+
+/// Gasoline specific fuel consumption at the rated power (g/kWh).
+pub const GASOLINE_SFC: f64 = 400.0;
+
+/// Fuel left in the tanks as reserve, fraction of the fuel carried.
+pub const FUEL_RESERVE: f64 = 0.1;
+
+/// Cruise settings, fraction of the motoring speed.
+pub const CRUISE_SETTINGS: [f64; 4] = [0.7, 0.8, 0.9, 1.0];
+
+/// Specific fuel consumption over the consumption at the rated power, by the engine load.
+const PART_LOAD_SFC: [(f64, f64); 5] = [
+    (0.1, 1.6),
+    (0.25, 1.25),
+    (0.5, 1.08),
+    (0.75, 1.0),
+    (1.0, 1.03),
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// ENGINE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Engine type.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum EngineType {
+    Inboard,
+    Outboard,
+}
+
+impl fmt::Display for EngineType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EngineType::Inboard => write!(f, "Inboard"),
+            EngineType::Outboard => write!(f, "Outboard"),
+        }
+    }
+}
+
+/// Engine
+///
+/// Auxiliary engine, power is the rated power (flywheel for inboards, propeller shaft for
+/// outboards).
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Engine {
+    engine_type: EngineType,
+    power: Power,
+    weight: Weight,
+}
+
+#[allow(dead_code)]
+impl Engine {
+    pub fn new(engine_type: EngineType, power: Power, weight: Weight) -> Engine {
+        Engine {
+            engine_type,
+            power,
+            weight,
+        }
+    }
+
+    /// Engine type.
+    pub fn engine_type(&self) -> EngineType {
+        self.engine_type
+    }
+
+    /// Rated power.
+    pub fn power(&self) -> Power {
+        self.power
+    }
+
+    /// Engine weight.
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+
+    /// Power delivered to the propeller.
+    pub fn shaft_power(&self) -> Power {
+        match self.engine_type {
+            EngineType::Inboard => {
+                Power::from_watt(self.power.to_watt() * INBOARD_SHAFT_EFFICIENCY)
+            }
+            EngineType::Outboard => self.power,
+        }
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {:.1}hp ({:.0}kg)",
+            self.engine_type,
+            self.power.to_horsepower(),
+            self.weight.to_kilogram()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// MOTORING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Motoring
+///
+/// Power to weight and motoring speed estimate, by Gerr displacement speed formula:
+/// SLR = 10.665 / (LB / SHP)^(1/3)
+/// Limited to MAX_MOTORING_SLR. Planing hulls by the Crouch formula instead, when fast enough
+/// for it, past CROUCH_MIN_SLR.
+/// Gerr, D. (2001) The propeller handbook. International Marine.
+pub struct Motoring {
+    hp_per_ton: f64,
+    slr: f64,
+    speed: Speed,
+}
+
+#[allow(dead_code)]
+impl Motoring {
+    /// Needs the engine.
+    pub fn from_boat(boat: &Boat) -> Option<Motoring> {
+        let engine = boat.engine()?;
+        let hp_per_ton = engine.power().to_horsepower() / boat.displacement().to_long_ton();
+        let shp = engine.shaft_power().to_horsepower();
+        if let Some(planing) = CrouchPlaning::from_boat(boat) {
+            let speed = planing.speed(engine.shaft_power());
+            let slr = boat.speed_length_ratio(speed);
+            if slr >= CROUCH_MIN_SLR {
+                return Some(Motoring {
+                    hp_per_ton,
+                    slr,
+                    speed,
+                });
+            }
+        }
+        let slr =
+            (10.665 / (boat.displacement().to_pound() / shp).powf(1.0 / 3.0)).min(MAX_MOTORING_SLR);
+        Some(Motoring {
+            hp_per_ton,
+            slr,
+            speed: boat.speed_for_slr(slr),
+        })
+    }
+
+    /// Rated horsepower per long ton of displacement.
+    pub fn hp_per_ton(&self) -> f64 {
+        self.hp_per_ton
+    }
+
+    /// Motoring SLR (speed-length ratio).
+    pub fn slr(&self) -> f64 {
+        self.slr
+    }
+
+    /// Estimated motoring speed.
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+}
+
+impl fmt::Display for Motoring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Motoring]\n\
+            \thp/ton: {:>9.1}\n\
+            \tSLR:    {:>9.2}\n\
+            \tSpeed:  {:>9.1}kn",
+            self.hp_per_ton,
+            self.slr,
+            self.speed.to_knot()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PLANING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Planing hull type, chooses the Crouch constant.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum PlaningType {
+    /// Average runabouts, cruisers and passenger vessels.
+    Cruiser,
+    /// High speed runabouts and light high performance cruisers.
+    Runabout,
+    RaceBoat,
+    /// Three point and stepped hydroplanes.
+    Hydroplane,
+    /// Racing catamarans and sea sleds.
+    Catamaran,
+}
+
+impl PlaningType {
+    /// Crouch constant C.
+    pub fn crouch_constant(self) -> f64 {
+        match self {
+            PlaningType::Cruiser => 150.0,
+            PlaningType::Runabout => 190.0,
+            PlaningType::RaceBoat => 210.0,
+            PlaningType::Hydroplane => 220.0,
+            PlaningType::Catamaran => 230.0,
+        }
+    }
+}
+
+impl fmt::Display for PlaningType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PlaningType::Cruiser => write!(f, "Cruiser"),
+            PlaningType::Runabout => write!(f, "Runabout"),
+            PlaningType::RaceBoat => write!(f, "Race boat"),
+            PlaningType::Hydroplane => write!(f, "Hydroplane"),
+            PlaningType::Catamaran => write!(f, "Catamaran"),
+        }
+    }
+}
+
+/// Crouch planing
+///
+/// Quick planing speed estimate from the power to weight alone, by the Crouch formula:
+/// V = C / sqrt(LB / SHP) knots
+/// C the constant of the planing type. For when the hull is not known well enough for a
+/// planing prediction, good past CROUCH_MIN_SLR.
+/// Gerr, D. (2001) The propeller handbook. International Marine.
+#[derive(Debug, Copy, Clone)]
+pub struct CrouchPlaning {
+    planing_type: PlaningType,
+    displacement: Weight,
+}
+
+#[allow(dead_code)]
+impl CrouchPlaning {
+    pub fn new(planing_type: PlaningType, displacement: Weight) -> CrouchPlaning {
+        CrouchPlaning {
+            planing_type,
+            displacement,
+        }
+    }
+
+    /// Needs the planing hull type.
+    pub fn from_boat(boat: &Boat) -> Option<CrouchPlaning> {
+        match boat.hull_type() {
+            HullType::Planing(planing_type) => {
+                Some(CrouchPlaning::new(planing_type, boat.displacement()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn planing_type(&self) -> PlaningType {
+        self.planing_type
+    }
+
+    /// Speed for a shaft power.
+    pub fn speed(&self, shaft_power: Power) -> Speed {
+        Speed::from_knot(
+            self.planing_type.crouch_constant()
+                / (self.displacement.to_pound() / shaft_power.to_horsepower()).sqrt(),
+        )
+    }
+
+    /// Shaft power for a speed, SHP = LB * (V / C)^2.
+    pub fn shaft_power(&self, speed: Speed) -> Power {
+        Power::from_horsepower(
+            self.displacement.to_pound()
+                * (speed.to_knot() / self.planing_type.crouch_constant()).powi(2),
+        )
+    }
+
+    /// (Speed, shaft power) curve, points evenly spaced between two speeds.
+    pub fn curve(&self, from: Speed, to: Speed, points: usize) -> Vec<(Speed, Power)> {
+        let (a, b) = (from.to_meter_second(), to.to_meter_second());
+        (0..points)
+            .map(|i| {
+                let t = if points > 1 {
+                    i as f64 / (points - 1) as f64
+                } else {
+                    0.0
+                };
+                let speed = Speed::from_meter_second(a + (b - a) * t);
+                (speed, self.shaft_power(speed))
+            })
+            .collect()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// RANGE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Consumption curve
+///
+/// Fuel consumption (l/h) by the engine power, linear between the points, as from the engine
+/// manufacturer data.
+#[derive(Debug, Clone)]
+pub struct ConsumptionCurve {
+    /// (Power, l/h) points by the power.
+    points: Vec<(Power, f64)>,
+}
+
+#[allow(dead_code)]
+impl ConsumptionCurve {
+    /// Points sorted by the power, None without two of them.
+    pub fn new(points: &[(Power, f64)]) -> Option<ConsumptionCurve> {
+        if points.len() < 2 {
+            return None;
+        }
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| {
+            a.0.to_watt()
+                .partial_cmp(&b.0.to_watt())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Some(ConsumptionCurve { points })
+    }
+
+    /// Curve estimated from the rated power, diesel inboards at DIESEL_SFC and gasoline
+    /// outboards at GASOLINE_SFC, the consumption rising at part load by PART_LOAD_SFC.
+    pub fn from_engine(engine: &Engine) -> ConsumptionCurve {
+        let (sfc, density) = match engine.engine_type() {
+            EngineType::Inboard => (DIESEL_SFC, super::loading::DIESEL_DENSITY),
+            EngineType::Outboard => (GASOLINE_SFC, super::loading::GASOLINE_DENSITY),
+        };
+        let rated = engine.power().to_kilowatt();
+        ConsumptionCurve {
+            points: PART_LOAD_SFC
+                .iter()
+                .map(|&(load, factor)| {
+                    (
+                        Power::from_kilowatt(rated * load),
+                        rated * load * sfc * factor / density,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// (Power, l/h) points.
+    pub fn points(&self) -> &[(Power, f64)] {
+        &self.points
+    }
+
+    /// Fuel consumption at a power (l/h), the lowest consumption below the curve, None past it.
+    pub fn at(&self, power: Power) -> Option<f64> {
+        let watt = power.to_watt();
+        let first = self.points.first()?;
+        if watt < first.0.to_watt() {
+            return Some(first.1);
+        }
+        let i = self
+            .points
+            .windows(2)
+            .position(|w| watt >= w[0].0.to_watt() && watt <= w[1].0.to_watt())?;
+        let ((p0, c0), (p1, c1)) = (self.points[i], self.points[i + 1]);
+        let span = p1.to_watt() - p0.to_watt();
+        let t = if span > 0.0 {
+            (watt - p0.to_watt()) / span
+        } else {
+            0.0
+        };
+        Some(c0 + (c1 - c0) * t)
+    }
+}
+
+/// Motoring at a cruise setting.
+#[derive(Debug, Copy, Clone)]
+pub struct CruiseSetting {
+    pub speed: Speed,
+    /// Engine power.
+    pub power: Power,
+    /// Fraction of the rated power.
+    pub load: f64,
+    /// Fuel consumption (l/h).
+    pub consumption: f64,
+    /// Hours motoring on the usable fuel.
+    pub endurance: f64,
+    /// Nautical miles motoring on the usable fuel.
+    pub range: f64,
+}
+
+/// Motoring range
+///
+/// Range and endurance at the CRUISE_SETTINGS, on the fuel carried less FUEL_RESERVE.
+/// The power for a speed from Gerr displacement speed formula, solved for the shaft power,
+/// SHP = LB * (SLR / 10.665)^3, up to the motoring speed. Planing hulls past CROUCH_MIN_SLR by
+/// the Crouch formula, as the motoring speed.
+pub struct MotoringRange {
+    /// Usable fuel.
+    fuel: Volume,
+    settings: Vec<CruiseSetting>,
+}
+
+#[allow(dead_code)]
+impl MotoringRange {
+    /// Needs the engine.
+    pub fn new(boat: &Boat, fuel: Volume, curve: &ConsumptionCurve) -> Option<MotoringRange> {
+        let engine = boat.engine()?;
+        let motoring = Motoring::from_boat(boat)?;
+        let planing = CrouchPlaning::from_boat(boat);
+        let fuel = Volume::from_liter(fuel.to_liter() * (1.0 - FUEL_RESERVE));
+        let transmission = engine.shaft_power().to_watt() / engine.power().to_watt();
+        let settings = CRUISE_SETTINGS
+            .iter()
+            .filter_map(|&fraction| {
+                let speed = Speed::from_knot(motoring.speed().to_knot() * fraction);
+                let slr = boat.speed_length_ratio(speed);
+                let shp = match planing {
+                    Some(planing) if slr >= CROUCH_MIN_SLR => {
+                        planing.shaft_power(speed).to_horsepower()
+                    }
+                    _ => boat.displacement().to_pound() * (slr / 10.665).powi(3),
+                };
+                let power = Power::from_horsepower(shp / transmission);
+                // The rated power at the motoring speed, short of the rounding.
+                let rated = engine.power();
+                let power = if (power.to_watt() - rated.to_watt()).abs() < 1e-9 * rated.to_watt() {
+                    rated
+                } else {
+                    power
+                };
+                let consumption = curve.at(power)?;
+                let endurance = fuel.to_liter() / consumption;
+                Some(CruiseSetting {
+                    speed,
+                    power,
+                    load: power.to_watt() / engine.power().to_watt(),
+                    consumption,
+                    endurance,
+                    range: endurance * speed.to_knot(),
+                })
+            })
+            .collect();
+        Some(MotoringRange { fuel, settings })
+    }
+
+    /// Fuel of the tanks of the full load condition, the consumption curve estimated from the
+    /// engine. Needs the engine and the fuel tanks.
+    pub fn from_boat(boat: &Boat) -> Option<MotoringRange> {
+        let fuel = boat
+            .load_condition(LoadConditionKind::Full)?
+            .fuel_capacity();
+        if fuel.to_liter() <= 0.0 {
+            return None;
+        }
+        MotoringRange::new(boat, fuel, &ConsumptionCurve::from_engine(&boat.engine()?))
+    }
+
+    /// Usable fuel, less the reserve.
+    pub fn fuel(&self) -> Volume {
+        self.fuel
+    }
+
+    /// Range and endurance by the cruise setting, from the slowest.
+    pub fn settings(&self) -> &[CruiseSetting] {
+        &self.settings
+    }
+
+    /// Cruise setting of the longest range.
+    pub fn longest_range(&self) -> Option<CruiseSetting> {
+        self.settings
+            .iter()
+            .copied()
+            .fold(None, |best: Option<CruiseSetting>, s| match best {
+                Some(b) if b.range >= s.range => best,
+                _ => Some(s),
+            })
+    }
+}
+
+impl fmt::Display for MotoringRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Motoring range - {:.0}l usable]\n\
+            \t  Speed   Power  Load     l/h  Endurance    Range",
+            self.fuel.to_liter()
+        )?;
+        for s in &self.settings {
+            write!(
+                f,
+                "\n\t{:>5.2}kn {:>5.1}hp {:>4.0}% {:>7.2} {:>9.1}h {:>6.0}nm",
+                s.speed.to_knot(),
+                s.power.to_horsepower(),
+                s.load * 100.0,
+                s.consumption,
+                s.endurance,
+                s.range
+            )?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::si::Length;
+
+    #[test]
+    fn motoring() {
+        let mut boat = Boat::new("Cruiser".to_string());
+        boat.set_dwl(Length::from_foot(25.0));
+        boat.set_displacement(Weight::from_pound(8000.0));
+        assert!(Motoring::from_boat(&boat).is_none());
+
+        boat.set_engine(Engine::new(
+            EngineType::Outboard,
+            Power::from_horsepower(8.0),
+            Weight::from_kilogram(40.0),
+        ));
+        let motoring = Motoring::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.2}", motoring.hp_per_ton()), "2.24");
+        // 10.665 / 1000^(1/3)
+        assert_eq!(format!("{:.3}", motoring.slr()), "1.067");
+        assert_eq!(format!("{:.2}", motoring.speed().to_knot()), "5.33");
+
+        boat.set_engine(Engine::new(
+            EngineType::Inboard,
+            Power::from_horsepower(30.0),
+            Weight::from_kilogram(120.0),
+        ));
+        let motoring = Motoring::from_boat(&boat).unwrap();
+        assert_eq!(motoring.slr(), MAX_MOTORING_SLR);
+    }
+
+    #[test]
+    fn motoring_range() {
+        use crate::loading::{LoadCondition, LoadConditionKind, Tank};
+        use crate::si::Volume;
+
+        let mut boat = Boat::new("Cruiser".to_string());
+        boat.set_dwl(Length::from_foot(25.0));
+        boat.set_displacement(Weight::from_pound(8000.0));
+        boat.set_engine(Engine::new(
+            EngineType::Inboard,
+            Power::from_horsepower(20.0),
+            Weight::from_kilogram(100.0),
+        ));
+        assert!(MotoringRange::from_boat(&boat).is_none());
+
+        // Diesel at 250g/kWh, 14.9kW at full power.
+        let curve = ConsumptionCurve::from_engine(&boat.engine().unwrap());
+        assert_eq!(
+            format!("{:.2}", curve.at(Power::from_horsepower(20.0)).unwrap()),
+            "4.57"
+        );
+        assert!(curve.at(Power::from_horsepower(25.0)).is_none());
+
+        let mut full = LoadCondition::new(LoadConditionKind::Full, boat.displacement());
+        full.add_tank(Tank::diesel("Fuel".to_string(), Volume::from_liter(100.0)));
+        full.add_tank(Tank::water("Water".to_string(), Volume::from_liter(200.0)));
+        boat.set_load_condition(full);
+        let range = MotoringRange::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.1}", range.fuel().to_liter()), "90.0");
+        assert_eq!(range.settings().len(), CRUISE_SETTINGS.len());
+        let cruise = range.settings()[1];
+        assert_eq!(format!("{:.2}", cruise.speed.to_knot()), "5.36");
+        assert_eq!(format!("{:.1}", cruise.power.to_horsepower()), "8.5");
+        assert_eq!(format!("{:.2}", cruise.consumption), "2.09");
+        assert_eq!(format!("{:.1}", cruise.endurance), "43.1");
+        assert_eq!(format!("{:.0}", cruise.range), "231");
+        // Slower goes farther.
+        let longest = range.longest_range().unwrap();
+        assert_eq!(longest.speed.to_knot(), range.settings()[0].speed.to_knot());
+
+        // Manufacturer curve.
+        let curve = ConsumptionCurve::new(&[
+            (Power::from_horsepower(20.0), 5.0),
+            (Power::from_horsepower(2.0), 0.8),
+        ])
+        .unwrap();
+        let range = MotoringRange::new(&boat, Volume::from_liter(100.0), &curve).unwrap();
+        assert!(range.settings()[1].consumption > cruise.consumption);
+
+        // Planing at every setting, SHP = LB * (V / C)^2, half the power at 0.7 of the speed.
+        let mut boat = Boat::new("Runabout".to_string());
+        boat.set_dwl(Length::from_foot(18.0));
+        boat.set_displacement(Weight::from_pound(2500.0));
+        boat.set_hull_type(HullType::Planing(PlaningType::Runabout));
+        boat.set_engine(Engine::new(
+            EngineType::Outboard,
+            Power::from_horsepower(100.0),
+            Weight::from_kilogram(160.0),
+        ));
+        let curve = ConsumptionCurve::from_engine(&boat.engine().unwrap());
+        let range = MotoringRange::new(&boat, Volume::from_liter(100.0), &curve).unwrap();
+        assert_eq!(range.settings().len(), CRUISE_SETTINGS.len());
+        let cruise = range.settings()[0];
+        assert_eq!(format!("{:.1}", cruise.speed.to_knot()), "26.6");
+        assert_eq!(format!("{:.0}", cruise.power.to_horsepower()), "49");
+        assert_eq!(format!("{:.2}", cruise.load), "0.49");
+    }
+
+    #[test]
+    fn crouch_planing() {
+        let mut boat = Boat::new("Runabout".to_string());
+        boat.set_dwl(Length::from_foot(18.0));
+        boat.set_displacement(Weight::from_pound(2500.0));
+        assert!(CrouchPlaning::from_boat(&boat).is_none());
+        boat.set_hull_type(HullType::Planing(PlaningType::Runabout));
+        let planing = CrouchPlaning::from_boat(&boat).unwrap();
+        // 190 / sqrt(2500 / 100)
+        let speed = planing.speed(Power::from_horsepower(100.0));
+        assert_eq!(format!("{:.1}", speed.to_knot()), "38.0");
+        assert_eq!(
+            format!("{:.1}", planing.shaft_power(speed).to_horsepower()),
+            "100.0"
+        );
+        let curve = planing.curve(Speed::from_knot(20.0), Speed::from_knot(40.0), 5);
+        assert_eq!(curve.len(), 5);
+        assert_eq!(format!("{:.1}", curve[4].1.to_horsepower()), "110.8");
+
+        // Motoring speed past the displacement limit.
+        boat.set_engine(Engine::new(
+            EngineType::Outboard,
+            Power::from_horsepower(100.0),
+            Weight::from_kilogram(160.0),
+        ));
+        let motoring = Motoring::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.1}", motoring.speed().to_knot()), "38.0");
+        assert!(motoring.slr() > CROUCH_MIN_SLR);
+    }
+
+    #[test]
+    fn underpowered_planing() {
+        let mut boat = Boat::new("Runabout".to_string());
+        boat.set_dwl(Length::from_foot(18.0));
+        boat.set_displacement(Weight::from_pound(2500.0));
+        boat.set_hull_type(HullType::Planing(PlaningType::Runabout));
+        boat.set_engine(Engine::new(
+            EngineType::Outboard,
+            Power::from_horsepower(5.0),
+            Weight::from_kilogram(25.0),
+        ));
+        // Crouch gives 8.5kn, SLR 2.0, not planing. The displacement speed, 10.665 / 500^(1/3)
+        // limited to hull speed.
+        let motoring = Motoring::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.2}", motoring.slr()), "1.34");
+        assert_eq!(format!("{:.1}", motoring.speed().to_knot()), "5.7");
+    }
+}
@@ -0,0 +1,872 @@
+use super::boat::Boat;
+use super::drawing::{DrawingStyle, LineKind, GRID_DASH};
+use super::hydrostatics::{Hydrostatics, Water};
+use super::propulsion::{CrouchPlaning, PlaningType, CROUCH_MIN_SLR};
+use super::si::{Area, Force, Length, Power, Speed, Volume, GRAVITY};
+use cairo::Context;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Points of a resistance curve.
+pub const RESISTANCE_POINTS: usize = 21;
+
+/// Fn (Froude number) range of the Delft series regression.
+pub const DELFT_FROUDE_RANGE: (f64, f64) = (0.10, 0.60);
+
+/// Fn (Froude number) range of the Holtrop-Mennen estimate.
+pub const HOLTROP_FROUDE_RANGE: (f64, f64) = (0.10, 0.60);
+
+/// SLR (speed-length ratio) the Crouch curve ends at.
+pub const CROUCH_MAX_SLR: f64 = 8.0;
+
+/// Propulsive efficiency of a planing hull, the effective power over the shaft power, to turn
+/// the Crouch shaft power into a resistance.
+pub const PLANING_PROPULSIVE_EFFICIENCY: f64 = 0.55;
+
+/// Appendage form factor 1 + k2 of the Holtrop-Mennen estimate, rudder behind a skeg.
+pub const APPENDAGE_FORM_FACTOR: f64 = 1.5;
+
+/// Delft series residuary resistance coefficients a0 to a8 by Fn, from 0.10 to 0.60.
+/// Keuning, J. A. and Sonnenberg, U. B. (1998) Approximation of the hydrodynamic forces on a
+/// sailing yacht based on the Delft Systematic Yacht Hull Series. HISWA Symposium.
+const DELFT_COEFFICIENTS: [[f64; 9]; 11] = [
+    [
+        -0.0014, 0.0403, 0.0470, -0.0227, -0.0119, 0.0061, -0.0086, -0.0307, -0.0553,
+    ],
+    [
+        0.0004, -0.1808, 0.1793, -0.0004, 0.0097, 0.0118, -0.0055, 0.1721, -0.1728,
+    ],
+    [
+        0.0014, -0.1071, 0.0637, 0.0090, 0.0153, 0.0011, 0.0012, 0.1021, -0.0648,
+    ],
+    [
+        0.0027, 0.0463, -0.1263, 0.0150, 0.0274, -0.0299, 0.0110, -0.0595, 0.1220,
+    ],
+    [
+        0.0056, -0.8005, 0.4891, 0.0269, 0.0519, -0.0313, 0.0292, 0.7314, -0.3619,
+    ],
+    [
+        0.0032, -0.1011, -0.0813, -0.0382, 0.0320, -0.1481, 0.0837, 0.0223, 0.1587,
+    ],
+    [
+        -0.0064, 2.3095, -1.5152, 0.0751, -0.0858, -0.5349, 0.1715, -2.4550, 1.1865,
+    ],
+    [
+        -0.0171, 3.4017, -1.9862, 0.3242, -0.1450, -0.8043, 0.2952, -3.5284, 1.3575,
+    ],
+    [
+        -0.0201, 7.1576, -6.3304, 0.5829, 0.1630, -0.3966, 0.5023, -7.1579, 5.2534,
+    ],
+    [
+        0.0495, 1.5618, -6.0661, 0.8641, 1.1702, 1.7610, 0.9176, -2.1191, 5.4281,
+    ],
+    [
+        0.0808, -5.3233, -1.1513, 0.9663, 1.6084, 2.7459, 0.8491, 4.7129, 1.1089,
+    ],
+];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// RESISTANCE CURVE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Resistance at a speed.
+#[derive(Debug, Copy, Clone)]
+pub struct ResistancePoint {
+    pub speed: Speed,
+    pub froude_number: f64,
+    pub frictional: Force,
+    pub residuary: Force,
+}
+
+impl ResistancePoint {
+    pub fn total(&self) -> Force {
+        Force::from_newton(self.frictional.to_newton() + self.residuary.to_newton())
+    }
+
+    /// Effective power, the total resistance times the speed.
+    pub fn effective_power(&self) -> Power {
+        self.total().power(self.speed)
+    }
+}
+
+/// Resistance curve
+///
+/// Upright resistance in calm water by speed, split into the frictional and the residuary
+/// resistance, by any of the methods.
+#[derive(Debug, Clone)]
+pub struct ResistanceCurve {
+    method: ResistanceMethod,
+    points: Vec<ResistancePoint>,
+}
+
+#[allow(dead_code)]
+impl ResistanceCurve {
+    pub fn new(method: ResistanceMethod, points: Vec<ResistancePoint>) -> ResistanceCurve {
+        ResistanceCurve { method, points }
+    }
+
+    pub fn method(&self) -> ResistanceMethod {
+        self.method
+    }
+
+    pub fn points(&self) -> &[ResistancePoint] {
+        &self.points
+    }
+
+    /// Resistance at a speed, linear between the points, None outside the curve.
+    pub fn at(&self, speed: Speed) -> Option<ResistancePoint> {
+        let v = speed.to_meter_second();
+        self.points.windows(2).find_map(|w| {
+            let (v0, v1) = (w[0].speed.to_meter_second(), w[1].speed.to_meter_second());
+            if v < v0 || v > v1 || v1 <= v0 {
+                return None;
+            }
+            let t = (v - v0) / (v1 - v0);
+            let mix = |a: f64, b: f64| a + (b - a) * t;
+            Some(ResistancePoint {
+                speed,
+                froude_number: mix(w[0].froude_number, w[1].froude_number),
+                frictional: Force::from_newton(mix(
+                    w[0].frictional.to_newton(),
+                    w[1].frictional.to_newton(),
+                )),
+                residuary: Force::from_newton(mix(
+                    w[0].residuary.to_newton(),
+                    w[1].residuary.to_newton(),
+                )),
+            })
+        })
+    }
+
+    /// Largest total resistance of the curve.
+    pub fn maximum(&self) -> Force {
+        Force::from_newton(
+            self.points
+                .iter()
+                .fold(0.0, |max, p| f64::max(max, p.total().to_newton())),
+        )
+    }
+
+    /// Plot the total (solid) and the frictional (dashed) resistance in a width by height box
+    /// at the context origin, the speed along x. The frame on the grid layer, the curves on the
+    /// hull layer and the labels on the annotations layer.
+    pub fn draw(&self, context: &Context, width: f64, height: f64, style: &DrawingStyle) {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => (first.speed.to_knot(), last.speed.to_knot()),
+            _ => return,
+        };
+        let maximum = self.maximum().to_newton();
+        if last <= first || maximum <= 0.0 {
+            return;
+        }
+        let to_x = |v: Speed| (v.to_knot() - first) / (last - first) * width;
+        let to_y = |r: Force| height * (1.0 - r.to_newton() / maximum);
+        let size = style.label_size();
+        context.save();
+        style.select_font(context);
+        if style.apply(context, LineKind::Grid) {
+            context.rectangle(0.0, 0.0, width, height);
+            context.stroke();
+        }
+
+        if style.apply(context, LineKind::Curves) {
+            let line_width = style.line_width(LineKind::Curves);
+            for &frictional in &[false, true] {
+                if frictional {
+                    // Finer than the total.
+                    context.set_line_width(0.5 * line_width);
+                    context.set_dash(&GRID_DASH, 0.0);
+                }
+                for (i, p) in self.points.iter().enumerate() {
+                    let r = if frictional { p.frictional } else { p.total() };
+                    if i == 0 {
+                        context.move_to(to_x(p.speed), to_y(r));
+                    } else {
+                        context.line_to(to_x(p.speed), to_y(r));
+                    }
+                }
+                context.stroke();
+            }
+            context.set_dash(&[], 0.0);
+        }
+
+        if style.apply(context, LineKind::Dimensions) {
+            context.move_to(4.0, 4.0 + size);
+            context.show_text(&format!("{:.0}N", maximum));
+            context.move_to(4.0, height - 4.0);
+            context.show_text(&format!("{:.1}kn to {:.1}kn", first, last));
+        }
+        context.restore();
+    }
+}
+
+impl fmt::Display for ResistanceCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Resistance - {}]\n\
+            \t  Speed      Fn       Rf       Rr        R       PE",
+            self.method
+        )?;
+        for p in &self.points {
+            write!(
+                f,
+                "\n\t{:>5.2}kn {:>7.3} {:>7.1}N {:>7.1}N {:>7.1}N {:>6.2}kW",
+                p.speed.to_knot(),
+                p.froude_number,
+                p.frictional.to_newton(),
+                p.residuary.to_newton(),
+                p.total().to_newton(),
+                p.effective_power().to_kilowatt()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Frictional resistance of a wetted surface by the ITTC-57 line, on a reference length.
+/// Cf = 0.075 / (log10(Rn) - 2)^2
+pub fn frictional_resistance(speed: Speed, length: Length, surface: Area, water: Water) -> Force {
+    let v = speed.to_meter_second();
+    let rn = v * length.to_meter() / water.kinematic_viscosity();
+    if rn <= 100.0 {
+        return Force::from_newton(0.0);
+    }
+    let cf = 0.075 / (rn.log10() - 2.0).powi(2);
+    Force::from_newton(
+        0.5 * water.density().to_kilogram_meter3() * v * v * surface.to_meter2() * cf,
+    )
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DELFT RESISTANCE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Delft resistance
+///
+/// Upright resistance by the DSYHS (Delft Systematic Yacht Hull Series) regression, for
+/// sailing yacht canoe bodies inside the ranges of the series, Fn from 0.10 to 0.60.
+/// Rr / (V rho g) = a0 + (a1 LCB / LWL + a2 Cp + a3 V^(2/3) / Aw + a4 BWL / LWL) V^(1/3) / LWL
+///     + (a5 V^(2/3) / Sc + a6 LCB / LCF + a7 (LCB / LWL)^2 + a8 Cp^2) V^(1/3) / LWL
+/// with LCB and LCF from the forward perpendicular and Sc the canoe body wetted surface. The
+/// frictional resistance is on the whole wetted surface, appendages included.
+#[derive(Debug, Copy, Clone)]
+pub struct DelftResistance {
+    lwl: Length,
+    bwl: Length,
+    volume: Volume,
+    cp: f64,
+    /// LCB aft of the forward perpendicular, fraction of the LWL.
+    lcb: f64,
+    /// LCF aft of the forward perpendicular, fraction of the LWL.
+    lcf: f64,
+    waterplane_area: Area,
+    canoe_surface: Area,
+    wetted_surface: Area,
+    water: Water,
+}
+
+#[allow(dead_code)]
+impl DelftResistance {
+    /// From the entered dimensions and coefficients, needs the Cp, the Cw, the LCB and the
+    /// LCF. The canoe body wetted surface is estimated.
+    pub fn from_boat(boat: &Boat) -> Option<DelftResistance> {
+        Some(DelftResistance {
+            lwl: boat.dwl(),
+            bwl: boat.bwl(),
+            volume: boat.displaced_volume(),
+            cp: boat.cp()?,
+            lcb: boat.lcb()? / 100.0,
+            lcf: boat.lcf()? / 100.0,
+            waterplane_area: boat.waterplane_area()?,
+            canoe_surface: boat.estimated_wetted_surface(),
+            wetted_surface: boat.wetted_surface(),
+            water: Water::Salt,
+        })
+    }
+
+    /// From the immersed hull geometry, the appendages from the boat. None when nothing is
+    /// immersed.
+    pub fn from_hydrostatics(boat: &Boat, hydrostatics: &Hydrostatics) -> Option<DelftResistance> {
+        let lwl = hydrostatics.waterplane_length();
+        let start = hydrostatics.waterplane_start().to_meter();
+        let cp = hydrostatics.form_coefficients()?.cp;
+        let relative = |x: Length| (x.to_meter() - start) / lwl.to_meter();
+        let canoe = hydrostatics.wetted_surface().to_meter2();
+        let appendages = appendage_surface(boat).to_meter2();
+        Some(DelftResistance {
+            lwl,
+            bwl: hydrostatics.waterline_beam(),
+            volume: hydrostatics.volume(),
+            cp,
+            lcb: relative(hydrostatics.lcb()?),
+            lcf: relative(hydrostatics.lcf()?),
+            waterplane_area: hydrostatics.waterplane_area(),
+            canoe_surface: hydrostatics.wetted_surface(),
+            wetted_surface: Area::from_meter2(canoe + appendages),
+            water: hydrostatics.water(),
+        })
+    }
+
+    pub fn froude_number(&self, speed: Speed) -> f64 {
+        speed.to_meter_second() / (GRAVITY * self.lwl.to_meter()).sqrt()
+    }
+
+    pub fn speed_for_froude_number(&self, froude_number: f64) -> Speed {
+        Speed::from_meter_second(froude_number * (GRAVITY * self.lwl.to_meter()).sqrt())
+    }
+
+    /// Residuary resistance, linear between the Fn of the coefficients, None outside the
+    /// range of the series.
+    pub fn residuary(&self, speed: Speed) -> Option<Force> {
+        let froude_number = self.froude_number(speed);
+        let (low, high) = DELFT_FROUDE_RANGE;
+        if froude_number < low - 1e-9 || froude_number > high + 1e-9 {
+            return None;
+        }
+        let step = (high - low) / (DELFT_COEFFICIENTS.len() - 1) as f64;
+        let position =
+            ((froude_number - low) / step).clamp(0.0, (DELFT_COEFFICIENTS.len() - 1) as f64);
+        let i = (position.floor() as usize).min(DELFT_COEFFICIENTS.len() - 2);
+        let t = position - i as f64;
+        let value = (1.0 - t) * self.regression(&DELFT_COEFFICIENTS[i])
+            + t * self.regression(&DELFT_COEFFICIENTS[i + 1]);
+        let weight = self.volume.to_meter3() * self.water.density().to_kilogram_meter3() * GRAVITY;
+        Some(Force::from_newton((value * weight).max(0.0)))
+    }
+
+    /// Rr / (V rho g) for a row of coefficients.
+    fn regression(&self, a: &[f64; 9]) -> f64 {
+        let lwl = self.lwl.to_meter();
+        let v = self.volume.to_meter3();
+        let slenderness = v.powf(1.0 / 3.0) / lwl;
+        let lcb = self.lcb;
+        a[0] + (a[1] * lcb
+            + a[2] * self.cp
+            + a[3] * v.powf(2.0 / 3.0) / self.waterplane_area.to_meter2()
+            + a[4] * self.bwl.to_meter() / lwl)
+            * slenderness
+            + (a[5] * v.powf(2.0 / 3.0) / self.canoe_surface.to_meter2()
+                + a[6] * lcb / self.lcf
+                + a[7] * lcb * lcb
+                + a[8] * self.cp * self.cp)
+                * slenderness
+    }
+
+    /// Frictional resistance on a length of 0.7 LWL.
+    pub fn frictional(&self, speed: Speed) -> Force {
+        let length = Length::from_meter(0.7 * self.lwl.to_meter());
+        frictional_resistance(speed, length, self.wetted_surface, self.water)
+    }
+
+    /// Resistance at a speed inside the range of the series.
+    pub fn at(&self, speed: Speed) -> Option<ResistancePoint> {
+        Some(ResistancePoint {
+            speed,
+            froude_number: self.froude_number(speed),
+            frictional: self.frictional(speed),
+            residuary: self.residuary(speed)?,
+        })
+    }
+
+    /// Resistance over the range of the series.
+    pub fn curve(&self) -> ResistanceCurve {
+        let (low, high) = DELFT_FROUDE_RANGE;
+        ResistanceCurve::new(
+            ResistanceMethod::Delft,
+            (0..RESISTANCE_POINTS)
+                .filter_map(|i| {
+                    let froude_number =
+                        low + (high - low) * i as f64 / (RESISTANCE_POINTS - 1) as f64;
+                    self.at(self.speed_for_froude_number(froude_number))
+                })
+                .collect(),
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HOLTROP-MENNEN RESISTANCE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Holtrop-Mennen resistance
+///
+/// Resistance of displacement motor hulls by the Holtrop and Mennen regression of model tests,
+/// the 1984 revision without a bulb:
+/// R = RF (1 + k1) + RAPP + RW + RTR + RA
+/// RF by the ITTC-57 line on the LWL with the form factor 1 + k1, RAPP the appendages with
+/// the APPENDAGE_FORM_FACTOR, RW the wave resistance, RTR the immersed transom and RA the
+/// model-ship correlation. The frictional part of the curve is RF (1 + k1) + RAPP, the
+/// residuary part the rest.
+/// Holtrop, J. (1984) A statistical re-analysis of resistance and propulsion data.
+/// International Shipbuilding Progress.
+#[derive(Debug, Copy, Clone)]
+pub struct HoltropResistance {
+    lwl: Length,
+    bwl: Length,
+    draft: Length,
+    volume: Volume,
+    cp: f64,
+    cm: f64,
+    cw: f64,
+    /// LCB forward of the middle of the LWL, % of the LWL.
+    lcb: f64,
+    hull_surface: Area,
+    appendage_surface: Area,
+    transom_area: Area,
+    water: Water,
+}
+
+#[allow(dead_code)]
+impl HoltropResistance {
+    /// From the entered dimensions and coefficients, needs the Cp, the Cm, the Cw and the
+    /// LCB. The hull wetted surface is estimated by the Holtrop formula, there is no transom
+    /// immersed.
+    pub fn from_boat(boat: &Boat) -> Option<HoltropResistance> {
+        let (l, b, t) = (
+            boat.dwl().to_meter(),
+            boat.bwl().to_meter(),
+            boat.draft().to_meter(),
+        );
+        let (cp, cm, cw) = (boat.cp()?, boat.cm()?, boat.cw()?);
+        let cb = cp * cm;
+        let hull_surface = l
+            * (2.0 * t + b)
+            * cm.sqrt()
+            * (0.453 + 0.4425 * cb - 0.2862 * cm - 0.003467 * b / t + 0.3696 * cw);
+        Some(HoltropResistance {
+            lwl: boat.dwl(),
+            bwl: boat.bwl(),
+            draft: boat.draft(),
+            volume: boat.displaced_volume(),
+            cp,
+            cm,
+            cw,
+            lcb: 50.0 - boat.lcb()?,
+            hull_surface: Area::from_meter2(hull_surface),
+            appendage_surface: appendage_surface(boat),
+            transom_area: Area::from_meter2(0.0),
+            water: Water::Salt,
+        })
+    }
+
+    /// From the immersed hull geometry, the transom as the area of the aftmost section and
+    /// the appendages from the boat. None when nothing is immersed.
+    pub fn from_hydrostatics(
+        boat: &Boat,
+        hydrostatics: &Hydrostatics,
+    ) -> Option<HoltropResistance> {
+        let coefficients = hydrostatics.form_coefficients()?;
+        let lwl = hydrostatics.waterplane_length();
+        let start = hydrostatics.waterplane_start().to_meter();
+        let lcb = (hydrostatics.lcb()?.to_meter() - start) / lwl.to_meter() * 100.0;
+        Some(HoltropResistance {
+            lwl,
+            bwl: hydrostatics.waterline_beam(),
+            draft: hydrostatics.draft(),
+            volume: hydrostatics.volume(),
+            cp: coefficients.cp,
+            cm: coefficients.cm,
+            cw: coefficients.cw,
+            lcb: 50.0 - lcb,
+            hull_surface: hydrostatics.wetted_surface(),
+            appendage_surface: appendage_surface(boat),
+            transom_area: hydrostatics
+                .sectional_areas()
+                .last()
+                .copied()
+                .unwrap_or_else(|| Area::from_meter2(0.0)),
+            water: hydrostatics.water(),
+        })
+    }
+
+    pub fn froude_number(&self, speed: Speed) -> f64 {
+        speed.to_meter_second() / (GRAVITY * self.lwl.to_meter()).sqrt()
+    }
+
+    pub fn speed_for_froude_number(&self, froude_number: f64) -> Speed {
+        Speed::from_meter_second(froude_number * (GRAVITY * self.lwl.to_meter()).sqrt())
+    }
+
+    /// Length of the run.
+    fn run(&self) -> f64 {
+        let cp = self.cp;
+        self.lwl.to_meter() * (1.0 - cp + 0.06 * cp * self.lcb / (4.0 * cp - 1.0))
+    }
+
+    /// Hull form factor, 1 + k1.
+    pub fn form_factor(&self) -> f64 {
+        let (l, b, t) = (
+            self.lwl.to_meter(),
+            self.bwl.to_meter(),
+            self.draft.to_meter(),
+        );
+        0.93 + 0.487118
+            * (b / l).powf(1.06806)
+            * (t / l).powf(0.46106)
+            * (l / self.run()).powf(0.121563)
+            * (l.powi(3) / self.volume.to_meter3()).powf(0.36486)
+            * (1.0 - self.cp).powf(-0.604247)
+    }
+
+    /// Half angle of entrance of the waterline (degree).
+    pub fn entrance_angle(&self) -> f64 {
+        let (l, b) = (self.lwl.to_meter(), self.bwl.to_meter());
+        1.0 + 89.0
+            * (-(l / b).powf(0.80856)
+                * (1.0 - self.cw).max(0.0).powf(0.30484)
+                * (1.0 - self.cp - 0.0225 * self.lcb).max(0.0).powf(0.6367)
+                * (self.run() / b).powf(0.34574)
+                * (100.0 * self.volume.to_meter3() / l.powi(3)).powf(0.16302))
+            .exp()
+    }
+
+    /// Wave resistance, the low speed regression up to Fn 0.40, the high speed one from 0.55
+    /// and linear in between.
+    pub fn wave(&self, speed: Speed) -> Force {
+        let (l, b, t) = (
+            self.lwl.to_meter(),
+            self.bwl.to_meter(),
+            self.draft.to_meter(),
+        );
+        let v = self.volume.to_meter3();
+        let weight = v * self.water.density().to_kilogram_meter3() * GRAVITY;
+        let c7 = if b / l < 0.11 {
+            0.229577 * (b / l).powf(1.0 / 3.0)
+        } else if b / l < 0.25 {
+            b / l
+        } else {
+            0.5 - 0.0625 * l / b
+        };
+        let c1 = 2223105.0
+            * c7.powf(3.78613)
+            * (t / b).powf(1.07961)
+            * (90.0 - self.entrance_angle()).powf(-1.37565);
+        let c5 = 1.0 - 0.8 * self.transom_area.to_meter2() / (b * t * self.cm);
+        let cp = self.cp;
+        let c16 = if cp < 0.8 {
+            8.07981 * cp - 13.8673 * cp * cp + 6.984388 * cp.powi(3)
+        } else {
+            1.73014 - 0.7067 * cp
+        };
+        let m1 = 0.0140407 * l / t - 1.75254 * v.powf(1.0 / 3.0) / l - 4.79323 * b / l - c16;
+        let slenderness = l.powi(3) / v;
+        let c15 = if slenderness < 512.0 {
+            -1.69385
+        } else if slenderness > 1726.91 {
+            0.0
+        } else {
+            -1.69385 + (l / v.powf(1.0 / 3.0) - 8.0) / 2.36
+        };
+        let lambda = if l / b < 12.0 {
+            1.446 * cp - 0.03 * l / b
+        } else {
+            1.446 * cp - 0.36
+        };
+        let c17 = 6919.3
+            * self.cm.powf(-1.3346)
+            * (v / l.powi(3)).powf(2.00977)
+            * (l / b - 2.0).max(0.0).powf(1.40692);
+        let m3 = -7.2035 * (b / l).powf(0.326869) * (t / b).powf(0.605375);
+        let m4 = |f: f64| c15 * 0.4 * (-0.034 * f.powf(-3.29)).exp();
+        let low = |f: f64| {
+            c1 * c5 * weight * (m1 * f.powf(-0.9) + m4(f) * (lambda * f.powi(-2)).cos()).exp()
+        };
+        let high = |f: f64| {
+            c17 * c5 * weight * (m3 * f.powf(-0.9) + m4(f) * (lambda * f.powi(-2)).cos()).exp()
+        };
+        let f = self.froude_number(speed);
+        let rw = if f <= 0.0 {
+            0.0
+        } else if f <= 0.4 {
+            low(f)
+        } else if f >= 0.55 {
+            high(f)
+        } else {
+            low(0.4) + (10.0 * f - 4.0) * (high(0.55) - low(0.4)) / 1.5
+        };
+        Force::from_newton(rw.max(0.0))
+    }
+
+    /// Frictional resistance of the hull with the form factor, plus the appendages.
+    pub fn frictional(&self, speed: Speed) -> Force {
+        let hull = frictional_resistance(speed, self.lwl, self.hull_surface, self.water);
+        let appendages = frictional_resistance(speed, self.lwl, self.appendage_surface, self.water);
+        Force::from_newton(
+            hull.to_newton() * self.form_factor() + appendages.to_newton() * APPENDAGE_FORM_FACTOR,
+        )
+    }
+
+    /// Wave, immersed transom and correlation resistance.
+    pub fn residuary(&self, speed: Speed) -> Force {
+        let (l, b, t) = (
+            self.lwl.to_meter(),
+            self.bwl.to_meter(),
+            self.draft.to_meter(),
+        );
+        let v = speed.to_meter_second();
+        let pressure = 0.5 * self.water.density().to_kilogram_meter3() * v * v;
+        let at = self.transom_area.to_meter2();
+        let transom = if at > 0.0 {
+            let froude_number = v / (2.0 * GRAVITY * at / (b + b * self.cw)).sqrt();
+            if froude_number < 5.0 {
+                pressure * at * 0.2 * (1.0 - 0.2 * froude_number)
+            } else {
+                0.0
+            }
+        } else {
+            0.0
+        };
+        let cb = self.cp * self.cm;
+        let c4 = (t / l).min(0.04);
+        let ca = 0.006 * (l + 100.0).powf(-0.16) - 0.00205
+            + 0.003 * (l / 7.5).sqrt() * cb.powi(4) * (0.04 - c4);
+        let correlation = pressure * self.hull_surface.to_meter2() * ca;
+        Force::from_newton(self.wave(speed).to_newton() + transom + correlation)
+    }
+
+    pub fn at(&self, speed: Speed) -> ResistancePoint {
+        ResistancePoint {
+            speed,
+            froude_number: self.froude_number(speed),
+            frictional: self.frictional(speed),
+            residuary: self.residuary(speed),
+        }
+    }
+
+    /// Resistance over the HOLTROP_FROUDE_RANGE.
+    pub fn curve(&self) -> ResistanceCurve {
+        let (low, high) = HOLTROP_FROUDE_RANGE;
+        ResistanceCurve::new(
+            ResistanceMethod::HoltropMennen,
+            (0..RESISTANCE_POINTS)
+                .map(|i| {
+                    let froude_number =
+                        low + (high - low) * i as f64 / (RESISTANCE_POINTS - 1) as f64;
+                    self.at(self.speed_for_froude_number(froude_number))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Wetted surface of the appendages, the boat wetted surface past the canoe body estimate.
+fn appendage_surface(boat: &Boat) -> Area {
+    Area::from_meter2(
+        (boat.wetted_surface().to_meter2() - boat.estimated_wetted_surface().to_meter2()).max(0.0),
+    )
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// RESISTANCE METHOD
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Hull type, chooses the resistance method.
+#[derive(PartialEq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum HullType {
+    #[default]
+    SailingYacht,
+    DisplacementMotor,
+    /// Planing hull by the low fidelity Crouch formula.
+    Planing(PlaningType),
+}
+
+impl HullType {
+    pub fn resistance_method(self) -> ResistanceMethod {
+        match self {
+            HullType::SailingYacht => ResistanceMethod::Delft,
+            HullType::DisplacementMotor => ResistanceMethod::HoltropMennen,
+            HullType::Planing(planing_type) => ResistanceMethod::Crouch(planing_type),
+        }
+    }
+}
+
+impl fmt::Display for HullType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HullType::SailingYacht => write!(f, "Sailing yacht"),
+            HullType::DisplacementMotor => write!(f, "Displacement motor"),
+            HullType::Planing(planing_type) => write!(f, "Planing {}", planing_type),
+        }
+    }
+}
+
+/// Resistance method.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ResistanceMethod {
+    Delft,
+    HoltropMennen,
+    /// Crouch formula, the resistance not split into frictional and residuary.
+    Crouch(PlaningType),
+}
+
+#[allow(dead_code)]
+impl ResistanceMethod {
+    /// Curve from the entered dimensions and coefficients.
+    pub fn from_boat(self, boat: &Boat) -> Option<ResistanceCurve> {
+        match self {
+            ResistanceMethod::Delft => DelftResistance::from_boat(boat).map(|r| r.curve()),
+            ResistanceMethod::HoltropMennen => {
+                HoltropResistance::from_boat(boat).map(|r| r.curve())
+            }
+            ResistanceMethod::Crouch(planing_type) => Some(crouch_curve(boat, planing_type)),
+        }
+    }
+
+    /// Curve from the immersed hull geometry.
+    pub fn from_hydrostatics(
+        self,
+        boat: &Boat,
+        hydrostatics: &Hydrostatics,
+    ) -> Option<ResistanceCurve> {
+        match self {
+            ResistanceMethod::Delft => {
+                DelftResistance::from_hydrostatics(boat, hydrostatics).map(|r| r.curve())
+            }
+            ResistanceMethod::HoltropMennen => {
+                HoltropResistance::from_hydrostatics(boat, hydrostatics).map(|r| r.curve())
+            }
+            ResistanceMethod::Crouch(planing_type) => Some(crouch_curve(boat, planing_type)),
+        }
+    }
+}
+
+impl fmt::Display for ResistanceMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ResistanceMethod::Delft => write!(f, "Delft series"),
+            ResistanceMethod::HoltropMennen => write!(f, "Holtrop-Mennen"),
+            ResistanceMethod::Crouch(_) => write!(f, "Crouch"),
+        }
+    }
+}
+
+/// Resistance curve of the Crouch shaft power from CROUCH_MIN_SLR to CROUCH_MAX_SLR,
+/// R = SHP * PLANING_PROPULSIVE_EFFICIENCY / V, all of it residuary.
+fn crouch_curve(boat: &Boat, planing_type: PlaningType) -> ResistanceCurve {
+    let planing = CrouchPlaning::new(planing_type, boat.displacement());
+    let points = planing
+        .curve(
+            boat.speed_for_slr(CROUCH_MIN_SLR),
+            boat.speed_for_slr(CROUCH_MAX_SLR),
+            RESISTANCE_POINTS,
+        )
+        .into_iter()
+        .map(|(speed, power)| ResistancePoint {
+            speed,
+            froude_number: boat.froude_number(speed),
+            frictional: Force::from_newton(0.0),
+            residuary: Force::from_newton(
+                power.to_watt() * PLANING_PROPULSIVE_EFFICIENCY / speed.to_meter_second(),
+            ),
+        })
+        .collect();
+    ResistanceCurve::new(ResistanceMethod::Crouch(planing_type), points)
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn delft_resistance() {
+        use crate::drawing::{to_image, Layer, Layers};
+        use crate::si::Weight;
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(10.0));
+        boat.set_bwl(Length::from_meter(3.0));
+        boat.set_draft(Length::from_meter(0.6));
+        boat.set_displacement(Weight::from_kilogram(7175.0));
+        boat.set_cp(0.55);
+        boat.set_cw(0.7);
+        assert!(DelftResistance::from_boat(&boat).is_none());
+        boat.set_lcb(53.0);
+        boat.set_lcf(55.0);
+        boat.set_wetted_surface(Area::from_meter2(25.0));
+        let delft = DelftResistance::from_boat(&boat).unwrap();
+
+        let speed = Speed::from_meter_second(3.0);
+        // Rn = 1.767e7, Cf = 0.002724
+        assert_eq!(
+            format!("{:.1}", delft.frictional(speed).to_newton()),
+            "314.1"
+        );
+        assert!(delft.residuary(Speed::from_meter_second(0.5)).is_none());
+
+        let curve = delft.curve();
+        assert_eq!(curve.points().len(), RESISTANCE_POINTS);
+        assert_eq!(format!("{:.3}", curve.points()[0].froude_number), "0.100");
+        // The residuary resistance takes over past hull speed.
+        let slow = delft.at(delft.speed_for_froude_number(0.25)).unwrap();
+        let fast = delft.at(delft.speed_for_froude_number(0.45)).unwrap();
+        assert!(slow.residuary.to_newton() < slow.frictional.to_newton());
+        assert!(fast.residuary.to_newton() > fast.frictional.to_newton());
+        assert!(fast.total().to_newton() > slow.total().to_newton());
+        let between = curve.at(Speed::from_meter_second(2.5)).unwrap();
+        assert_eq!(format!("{:.3}", between.froude_number), "0.252");
+        assert!(curve.at(Speed::from_meter_second(10.0)).is_none());
+        assert_eq!(curve.method(), ResistanceMethod::Delft);
+
+        let mut style = DrawingStyle::new();
+        let plot = |style: &DrawingStyle| {
+            let mut image =
+                to_image(400, 200, |context| curve.draw(context, 400.0, 200.0, style)).unwrap();
+            let data = image.get_data().unwrap();
+            data.iter().any(|&v| v < 128)
+        };
+        assert!(plot(&style));
+        // Nothing with the layers of the plot hidden.
+        style.set_layers(Layers::only(&[Layer::Rig]));
+        assert!(!plot(&style));
+    }
+
+    #[test]
+    fn holtrop_resistance() {
+        use crate::si::Weight;
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(10.0));
+        boat.set_bwl(Length::from_meter(3.2));
+        boat.set_draft(Length::from_meter(0.8));
+        // Cb 0.48
+        boat.set_displacement(Weight::from_kilogram(12595.0));
+        boat.set_cp(0.6);
+        boat.set_cm(0.8);
+        boat.set_cw(0.75);
+        boat.set_lcf(55.0);
+        assert!(HoltropResistance::from_boat(&boat).is_none());
+        boat.set_lcb(52.0);
+        let holtrop = HoltropResistance::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.3}", holtrop.form_factor()), "1.373");
+        assert_eq!(format!("{:.1}", holtrop.entrance_angle()), "32.2");
+        let speed = holtrop.speed_for_froude_number(0.4);
+        assert_eq!(
+            format!("{:.0}", holtrop.at(speed).total().to_newton()),
+            "2976"
+        );
+
+        // The same curve for the hull type, close to the Delft series below hull speed.
+        assert_eq!(boat.resistance().unwrap().method(), ResistanceMethod::Delft);
+        boat.set_hull_type(HullType::DisplacementMotor);
+        let curve = boat.resistance().unwrap();
+        assert_eq!(curve.method(), ResistanceMethod::HoltropMennen);
+        assert_eq!(curve.points().len(), RESISTANCE_POINTS);
+        let delft = ResistanceMethod::Delft.from_boat(&boat).unwrap();
+        let (a, b) = (
+            curve.at(speed).unwrap().total().to_newton(),
+            delft.at(speed).unwrap().total().to_newton(),
+        );
+        assert!((a - b).abs() / b < 0.1);
+
+        // Low fidelity planing curve.
+        boat.set_hull_type(HullType::Planing(PlaningType::Cruiser));
+        let curve = boat.resistance().unwrap();
+        assert_eq!(curve.method().to_string(), "Crouch");
+        let first = curve.points()[0];
+        assert_eq!(first.frictional.to_newton(), 0.0);
+        assert_eq!(
+            format!("{:.2}", boat.speed_length_ratio(first.speed)),
+            "2.50"
+        );
+    }
+}
@@ -0,0 +1,593 @@
+use super::si::{Area, Length};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SAIL PLAN POINT
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Point of the sail plan, x aft of the forward perpendicular and z above the DWL.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct SailPoint {
+    pub x: Length,
+    pub z: Length,
+}
+
+impl SailPoint {
+    pub fn new(x: Length, z: Length) -> SailPoint {
+        SailPoint { x, z }
+    }
+}
+
+impl fmt::Display for SailPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "x: {:.3}m, z: {:.3}m",
+            self.x.to_meter(),
+            self.z.to_meter()
+        )
+    }
+}
+
+/// Centroid of a triangle.
+fn centroid(a: SailPoint, b: SailPoint, c: SailPoint) -> SailPoint {
+    SailPoint::new(
+        Length::from_meter((a.x.to_meter() + b.x.to_meter() + c.x.to_meter()) / 3.0),
+        Length::from_meter((a.z.to_meter() + b.z.to_meter() + c.z.to_meter()) / 3.0),
+    )
+}
+
+/// Center of a set of areas, weighted by the area.
+fn combined_center(parts: &[(Area, SailPoint)]) -> Option<SailPoint> {
+    let area: f64 = parts.iter().map(|(a, _)| a.to_meter2()).sum();
+    if area <= 0.0 {
+        return None;
+    }
+    let x: f64 = parts
+        .iter()
+        .map(|(a, p)| a.to_meter2() * p.x.to_meter())
+        .sum();
+    let z: f64 = parts
+        .iter()
+        .map(|(a, p)| a.to_meter2() * p.z.to_meter())
+        .sum();
+    Some(SailPoint::new(
+        Length::from_meter(x / area),
+        Length::from_meter(z / area),
+    ))
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SAIL
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Sail kind.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum SailKind {
+    Main,
+    Jib,
+    Genoa,
+    Spinnaker,
+}
+
+impl fmt::Display for SailKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SailKind::Main => write!(f, "Main"),
+            SailKind::Jib => write!(f, "Jib"),
+            SailKind::Genoa => write!(f, "Genoa"),
+            SailKind::Spinnaker => write!(f, "Spinnaker"),
+        }
+    }
+}
+
+/// Sail geometry, the three sides or the area given by the sailmaker.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum SailGeometry {
+    Sides {
+        luff: Length,
+        foot: Length,
+        leech: Length,
+    },
+    Area(Area),
+}
+
+/// Sail
+///
+/// From the sides the sail is taken as a triangle, roach and spinnaker shoulders are not
+/// included, so the sailmaker area should be preferred when known.
+/// The sail position is given by the tack, head and clew corners.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sail {
+    name: String,
+    kind: SailKind,
+    geometry: SailGeometry,
+    #[serde(default)]
+    corners: Option<[SailPoint; 3]>,
+}
+
+#[allow(dead_code)]
+impl Sail {
+    pub fn from_sides(
+        name: String,
+        kind: SailKind,
+        luff: Length,
+        foot: Length,
+        leech: Length,
+    ) -> Sail {
+        Sail {
+            name,
+            kind,
+            geometry: SailGeometry::Sides { luff, foot, leech },
+            corners: None,
+        }
+    }
+
+    pub fn from_area(name: String, kind: SailKind, area: Area) -> Sail {
+        Sail {
+            name,
+            kind,
+            geometry: SailGeometry::Area(area),
+            corners: None,
+        }
+    }
+
+    /// Sail positioned on the sail plan.
+    pub fn at(mut self, tack: SailPoint, head: SailPoint, clew: SailPoint) -> Sail {
+        self.corners = Some([tack, head, clew]);
+        self
+    }
+
+    /// Sail name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sail kind.
+    pub fn kind(&self) -> SailKind {
+        self.kind
+    }
+
+    /// Sail geometry.
+    pub fn geometry(&self) -> SailGeometry {
+        self.geometry
+    }
+
+    /// Tack, head and clew, when positioned.
+    pub fn corners(&self) -> Option<[SailPoint; 3]> {
+        self.corners
+    }
+
+    /// Geometric center of effort, the centroid of the sail triangle.
+    pub fn center_of_effort(&self) -> Option<SailPoint> {
+        let [tack, head, clew] = self.corners?;
+        Some(centroid(tack, head, clew))
+    }
+
+    /// Sail area, from the sides by Heron's formula.
+    pub fn area(&self) -> Area {
+        match self.geometry {
+            SailGeometry::Area(area) => area,
+            SailGeometry::Sides { luff, foot, leech } => {
+                let (a, b, c) = (luff.to_meter(), foot.to_meter(), leech.to_meter());
+                let s = (a + b + c) / 2.0;
+                Area::from_meter2((s * (s - a) * (s - b) * (s - c)).max(0.0).sqrt())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Sail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}): {:.2}m2",
+            self.name,
+            self.kind,
+            self.area().to_meter2()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// RIG
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Gaff mainsail area as a fraction of P * E, the quadrilateral is fuller than the triangle.
+/// Rough, the sailmaker area should be entered in the inventory when known.
+pub const GAFF_MAINSAIL_FACTOR: f64 = 0.70;
+
+/// Rig type.
+///
+/// Decides which sails the sail area (and so the SA/D) includes:
+/// sloop, cutter and fractional sloop, mainsail plus 100% foretriangle (the cutter staysail
+/// is not counted); ketch and yawl add the mizzen; cat, mainsail only; gaff,
+/// gaff mainsail plus 100% foretriangle.
+/// The sail plan drawing follows the rig type too.
+#[derive(PartialEq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum RigType {
+    #[default]
+    Sloop,
+    Cutter,
+    FractionalSloop,
+    Ketch,
+    Yawl,
+    Cat,
+    Gaff,
+}
+
+impl fmt::Display for RigType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RigType::Sloop => write!(f, "Sloop"),
+            RigType::Cutter => write!(f, "Cutter"),
+            RigType::FractionalSloop => write!(f, "Fractional sloop"),
+            RigType::Ketch => write!(f, "Ketch"),
+            RigType::Yawl => write!(f, "Yawl"),
+            RigType::Cat => write!(f, "Cat"),
+            RigType::Gaff => write!(f, "Gaff"),
+        }
+    }
+}
+
+/// Mizzen of a ketch or yawl, luff (Py) and foot (Ey).
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Mizzen {
+    pub p: Length,
+    pub e: Length,
+}
+
+impl Mizzen {
+    /// Mizzen area, Py * Ey / 2.
+    pub fn area(&self) -> Area {
+        Area::from_meter2(self.p.to_meter() * self.e.to_meter() / 2.0)
+    }
+}
+
+/// Rig placement on the hull.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct RigPlacement {
+    /// Front of the mast aft of the forward perpendicular.
+    pub mast: Length,
+    /// Height of the sheer at the mast above the DWL, base of the foretriangle.
+    pub sheer: Length,
+    /// Height of the boom above the DWL, tack of the mainsail and mizzen.
+    pub boom: Length,
+    /// Front of the mizzen mast aft of the forward perpendicular.
+    #[serde(default)]
+    pub mizzen: Option<Length>,
+}
+
+/// Rig
+///
+/// Standard rig measurements.
+/// I: height of the foretriangle, from the sheer to the jib halyard.
+/// J: base of the foretriangle, from the front of the mast to the forestay at the deck.
+/// P: mainsail luff, along the aft face of the mast.
+/// E: mainsail foot, along the boom.
+/// The sail inventory lists the sails actually carried.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rig {
+    #[serde(default)]
+    rig_type: RigType,
+    i: Length,
+    j: Length,
+    p: Length,
+    e: Length,
+    #[serde(default)]
+    mizzen: Option<Mizzen>,
+    #[serde(default)]
+    sails: Vec<Sail>,
+    #[serde(default)]
+    placement: Option<RigPlacement>,
+}
+
+#[allow(dead_code)]
+impl Rig {
+    pub fn new(i: Length, j: Length, p: Length, e: Length) -> Rig {
+        Rig {
+            rig_type: RigType::Sloop,
+            i,
+            j,
+            p,
+            e,
+            mizzen: None,
+            sails: Vec::new(),
+            placement: None,
+        }
+    }
+
+    /// Rig type.
+    pub fn rig_type(&self) -> RigType {
+        self.rig_type
+    }
+    #[allow(dead_code)]
+    pub fn set_rig_type(&mut self, val: RigType) {
+        self.rig_type = val;
+    }
+
+    /// Mizzen (ketch or yawl).
+    pub fn mizzen(&self) -> Option<Mizzen> {
+        self.mizzen
+    }
+    #[allow(dead_code)]
+    pub fn set_mizzen(&mut self, val: Mizzen) {
+        self.mizzen = Some(val);
+    }
+
+    /// Foretriangle counted in the sail area.
+    pub fn has_foretriangle(&self) -> bool {
+        self.rig_type != RigType::Cat
+    }
+
+    /// Mizzen counted in the sail area.
+    pub fn counted_mizzen(&self) -> Option<Mizzen> {
+        match self.rig_type {
+            RigType::Ketch | RigType::Yawl => self.mizzen,
+            _ => None,
+        }
+    }
+
+    /// I (foretriangle height).
+    pub fn i(&self) -> Length {
+        self.i
+    }
+    #[allow(dead_code)]
+    pub fn set_i(&mut self, val: Length) {
+        self.i = val;
+    }
+
+    /// J (foretriangle base).
+    pub fn j(&self) -> Length {
+        self.j
+    }
+    #[allow(dead_code)]
+    pub fn set_j(&mut self, val: Length) {
+        self.j = val;
+    }
+
+    /// P (mainsail luff).
+    pub fn p(&self) -> Length {
+        self.p
+    }
+    #[allow(dead_code)]
+    pub fn set_p(&mut self, val: Length) {
+        self.p = val;
+    }
+
+    /// E (mainsail foot).
+    pub fn e(&self) -> Length {
+        self.e
+    }
+    #[allow(dead_code)]
+    pub fn set_e(&mut self, val: Length) {
+        self.e = val;
+    }
+
+    /// Rig placement, needed for the center of effort.
+    pub fn placement(&self) -> Option<RigPlacement> {
+        self.placement
+    }
+    #[allow(dead_code)]
+    pub fn set_placement(&mut self, val: RigPlacement) {
+        self.placement = Some(val);
+    }
+
+    /// Sail inventory.
+    pub fn sails(&self) -> &[Sail] {
+        &self.sails
+    }
+
+    /// Sail by name.
+    pub fn sail(&self, name: &str) -> Option<&Sail> {
+        self.sails.iter().find(|s| s.name == name)
+    }
+
+    /// Add a sail to the inventory.
+    pub fn add_sail(&mut self, sail: Sail) {
+        self.sails.push(sail);
+    }
+
+    /// Area of a combination of sails from the inventory, if all of them are carried.
+    pub fn combination_area(&self, names: &[&str]) -> Option<Area> {
+        let mut area = 0.0;
+        for name in names {
+            area += self.sail(name)?.area().to_meter2();
+        }
+        Some(Area::from_meter2(area))
+    }
+
+    /// Geometric CE of a combination of sails, all of them must be positioned.
+    pub fn combination_center_of_effort(&self, names: &[&str]) -> Option<SailPoint> {
+        let mut parts = Vec::new();
+        for name in names {
+            let sail = self.sail(name)?;
+            parts.push((sail.area(), sail.center_of_effort()?));
+        }
+        combined_center(&parts)
+    }
+
+    /// Geometric CE of the sails counted in the sail area, needs the placement.
+    /// The mizzen is left out when its mast is not placed.
+    pub fn center_of_effort(&self) -> Option<SailPoint> {
+        let place = self.placement?;
+        let mast = place.mast;
+        let main = centroid(
+            SailPoint::new(mast, place.boom),
+            SailPoint::new(mast, place.boom + self.p),
+            SailPoint::new(mast + self.e, place.boom),
+        );
+        let fore = centroid(
+            SailPoint::new(mast - self.j, place.sheer),
+            SailPoint::new(mast, place.sheer + self.i),
+            SailPoint::new(mast, place.sheer),
+        );
+        let mut parts = vec![(self.mainsail_area(), main)];
+        if self.has_foretriangle() {
+            parts.push((self.foretriangle_area(), fore));
+        }
+        if let (Some(mizzen), Some(mast)) = (self.counted_mizzen(), place.mizzen) {
+            let center = centroid(
+                SailPoint::new(mast, place.boom),
+                SailPoint::new(mast, place.boom + mizzen.p),
+                SailPoint::new(mast + mizzen.e, place.boom),
+            );
+            parts.push((mizzen.area(), center));
+        }
+        combined_center(&parts)
+    }
+
+    /// Mainsail area, P * E / 2 (gaff, P * E * GAFF_MAINSAIL_FACTOR).
+    pub fn mainsail_area(&self) -> Area {
+        let factor = match self.rig_type {
+            RigType::Gaff => GAFF_MAINSAIL_FACTOR,
+            _ => 0.5,
+        };
+        Area::from_meter2(self.p.to_meter() * self.e.to_meter() * factor)
+    }
+
+    /// 100% foretriangle area, I * J / 2.
+    pub fn foretriangle_area(&self) -> Area {
+        Area::from_meter2(self.i.to_meter() * self.j.to_meter() / 2.0)
+    }
+
+    /// Sail area by the rig type convention.
+    pub fn sail_area(&self) -> Area {
+        let mut area = self.mainsail_area().to_meter2();
+        if self.has_foretriangle() {
+            area += self.foretriangle_area().to_meter2();
+        }
+        if let Some(mizzen) = self.counted_mizzen() {
+            area += mizzen.area().to_meter2();
+        }
+        Area::from_meter2(area)
+    }
+}
+
+impl fmt::Display for Rig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Rig - {}]\n\
+            \tI:            {:>9.2}m\n\
+            \tJ:            {:>9.2}m\n\
+            \tP:            {:>9.2}m\n\
+            \tE:            {:>9.2}m\n\
+            \tMainsail:     {:>9.2}m2\n\
+            \tForetriangle: {:>9.2}m2\n\
+            \tSail area:    {:>9.2}m2",
+            self.rig_type,
+            self.i.to_meter(),
+            self.j.to_meter(),
+            self.p.to_meter(),
+            self.e.to_meter(),
+            self.mainsail_area().to_meter2(),
+            self.foretriangle_area().to_meter2(),
+            self.sail_area().to_meter2()
+        )?;
+        for sail in &self.sails {
+            write!(f, "\n\t{}", sail)?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn rig() {
+        let rig = Rig::new(
+            Length::from_foot(40.0),
+            Length::from_foot(13.0),
+            Length::from_foot(35.0),
+            Length::from_foot(12.0),
+        );
+        assert_eq!(format!("{:.0}", rig.mainsail_area().to_foot2()), "210");
+        assert_eq!(format!("{:.0}", rig.foretriangle_area().to_foot2()), "260");
+        assert_eq!(format!("{:.0}", rig.sail_area().to_foot2()), "470");
+    }
+
+    #[test]
+    fn sail() {
+        let jib = Sail::from_sides(
+            "Jib".to_string(),
+            SailKind::Jib,
+            Length::from_meter(5.0),
+            Length::from_meter(3.0),
+            Length::from_meter(4.0),
+        );
+        assert_eq!(format!("{:.2}", jib.area().to_meter2()), "6.00");
+        let mut rig = Rig::new(
+            Length::from_meter(6.0),
+            Length::from_meter(2.0),
+            Length::from_meter(7.0),
+            Length::from_meter(3.0),
+        );
+        rig.add_sail(jib);
+        assert_eq!(rig.sail("Jib").unwrap().kind(), SailKind::Jib);
+        assert_eq!(
+            format!("{:.2}", rig.combination_area(&["Jib"]).unwrap().to_meter2()),
+            "6.00"
+        );
+    }
+
+    #[test]
+    fn center_of_effort() {
+        let mut rig = Rig::new(
+            Length::from_meter(9.0),
+            Length::from_meter(3.0),
+            Length::from_meter(9.0),
+            Length::from_meter(3.0),
+        );
+        assert!(rig.center_of_effort().is_none());
+        rig.set_placement(RigPlacement {
+            mast: Length::from_meter(3.0),
+            sheer: Length::from_meter(1.0),
+            boom: Length::from_meter(1.0),
+            mizzen: None,
+        });
+        // Foretriangle CE at x 2, main at x 4, equal areas.
+        let ce = rig.center_of_effort().unwrap();
+        assert_eq!(format!("{:.3}", ce.x.to_meter()), "3.000");
+        assert_eq!(format!("{:.3}", ce.z.to_meter()), "4.000");
+
+        let point = |x, z| SailPoint::new(Length::from_meter(x), Length::from_meter(z));
+        rig.add_sail(
+            Sail::from_area("Main".to_string(), SailKind::Main, Area::from_meter2(13.5)).at(
+                point(3.0, 1.0),
+                point(3.0, 10.0),
+                point(6.0, 1.0),
+            ),
+        );
+        let ce = rig.combination_center_of_effort(&["Main"]).unwrap();
+        assert_eq!(format!("{}", ce), "x: 4.000m, z: 4.000m");
+        assert!(rig.combination_center_of_effort(&["Jib"]).is_none());
+    }
+
+    #[test]
+    fn rig_type() {
+        let mut rig = Rig::new(
+            Length::from_meter(9.0),
+            Length::from_meter(3.0),
+            Length::from_meter(9.0),
+            Length::from_meter(3.0),
+        );
+        assert_eq!(rig.rig_type(), RigType::Sloop);
+        assert_eq!(format!("{:.1}", rig.sail_area().to_meter2()), "27.0");
+        rig.set_rig_type(RigType::Cat);
+        assert_eq!(format!("{:.1}", rig.sail_area().to_meter2()), "13.5");
+        rig.set_rig_type(RigType::Gaff);
+        assert_eq!(format!("{:.1}", rig.sail_area().to_meter2()), "32.4");
+
+        rig.set_mizzen(Mizzen {
+            p: Length::from_meter(5.0),
+            e: Length::from_meter(2.0),
+        });
+        assert_eq!(format!("{:.1}", rig.sail_area().to_meter2()), "32.4");
+        rig.set_rig_type(RigType::Ketch);
+        assert_eq!(format!("{:.1}", rig.sail_area().to_meter2()), "32.0");
+    }
+}
@@ -0,0 +1,261 @@
+use super::material::{Material, MaterialKind};
+use super::si::{Length, Pressure};
+use std::fmt;
+
+/// Design stress as a fraction of the ultimate flexural strength (plywood).
+pub const DESIGN_STRESS_FACTOR: f64 = 0.5;
+
+/// Standard marine plywood thicknesses (mm).
+pub const PLYWOOD_THICKNESSES: [f64; 9] = [4.0, 6.0, 9.0, 12.0, 15.0, 18.0, 21.0, 25.0, 30.0];
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PANEL LOCATION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Panel location.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PanelLocation {
+    Bottom,
+    Side,
+    Deck,
+    Bulkhead,
+}
+
+impl PanelLocation {
+    /// Practical minimum thickness for handling, fastenings and wear (mm).
+    pub fn minimum_thickness(self) -> Length {
+        Length::from_millimeter(match self {
+            PanelLocation::Bottom => 6.0,
+            PanelLocation::Side => 4.0,
+            PanelLocation::Deck => 6.0,
+            PanelLocation::Bulkhead => 6.0,
+        })
+    }
+}
+
+impl fmt::Display for PanelLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PanelLocation::Bottom => write!(f, "Bottom"),
+            PanelLocation::Side => write!(f, "Side"),
+            PanelLocation::Deck => write!(f, "Deck"),
+            PanelLocation::Bulkhead => write!(f, "Bulkhead"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// PANEL SCANTLING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Scantling error.
+#[derive(Debug)]
+pub enum ScantlingError {
+    /// Only plywood panels are covered.
+    NotPlywood(Material),
+    /// Pressure or span not greater than zero.
+    NotPositive(&'static str),
+    /// Thicker than the standard sheets, the panel needs closer stiffeners.
+    TooThick(Length),
+}
+
+impl fmt::Display for ScantlingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScantlingError::NotPlywood(material) => write!(f, "{} is not a plywood", material),
+            ScantlingError::NotPositive(name) => write!(f, "{} must be greater than zero", name),
+            ScantlingError::TooThick(thickness) => write!(
+                f,
+                "Required thickness of {:.1}mm exceeds the standard sheets, reduce the stiffener spacing",
+                thickness.to_millimiter()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScantlingError {}
+
+/// Panel scantling
+///
+/// Plywood plate thickness, simplified from ISO 12215-5 for a flat panel:
+/// t = b * sqrt(p * k2 / (1000 * sd))
+/// t thickness (mm), b short side or stiffener spacing (mm), p design pressure (kPa),
+/// sd design stress (N/mm2) and k2 the aspect ratio factor
+/// k2 = (0.271 AR^2 + 0.910 AR - 0.554) / (AR^2 - 0.313 AR + 1.351), AR from 1 to 2.
+/// The thickness is rounded up to a standard sheet and the stiffener spacing that sheet
+/// allows is given back. First estimate only, not a substitute for the full standard.
+pub struct PanelScantling {
+    location: PanelLocation,
+    material: Material,
+    pressure: Pressure,
+    span: Length,
+    required_thickness: Length,
+    thickness: Length,
+    max_spacing: Length,
+}
+
+#[allow(dead_code)]
+impl PanelScantling {
+    /// Panel of a span (short side) and length (long side, None for a long panel).
+    pub fn plywood(
+        location: PanelLocation,
+        material: Material,
+        pressure: Pressure,
+        span: Length,
+        length: Option<Length>,
+    ) -> Result<PanelScantling, ScantlingError> {
+        if material.kind() != MaterialKind::Plywood {
+            return Err(ScantlingError::NotPlywood(material));
+        }
+        if pressure.to_kilopascal() <= 0.0 {
+            return Err(ScantlingError::NotPositive("Design pressure"));
+        }
+        if span.to_meter() <= 0.0 {
+            return Err(ScantlingError::NotPositive("Span"));
+        }
+        let k2 = length.map_or(aspect_ratio_factor(2.0), |l| {
+            aspect_ratio_factor(l.to_meter() / span.to_meter())
+        });
+        let design_stress =
+            DESIGN_STRESS_FACTOR * material.properties().flexural_strength.to_megapascal();
+        let factor = (pressure.to_kilopascal() * k2 / (1000.0 * design_stress)).sqrt();
+
+        let required =
+            (span.to_millimiter() * factor).max(location.minimum_thickness().to_millimiter());
+        let thickness = PLYWOOD_THICKNESSES
+            .iter()
+            .copied()
+            .find(|t| *t >= required)
+            .ok_or_else(|| ScantlingError::TooThick(Length::from_millimeter(required)))?;
+        Ok(PanelScantling {
+            location,
+            material,
+            pressure,
+            span,
+            required_thickness: Length::from_millimeter(required),
+            thickness: Length::from_millimeter(thickness),
+            max_spacing: Length::from_millimeter(thickness / factor),
+        })
+    }
+
+    pub fn location(&self) -> PanelLocation {
+        self.location
+    }
+
+    pub fn material(&self) -> Material {
+        self.material
+    }
+
+    /// Design pressure.
+    pub fn pressure(&self) -> Pressure {
+        self.pressure
+    }
+
+    /// Span, the stiffener spacing.
+    pub fn span(&self) -> Length {
+        self.span
+    }
+
+    /// Thickness required by the pressure, or the minimum for the location.
+    pub fn required_thickness(&self) -> Length {
+        self.required_thickness
+    }
+
+    /// Recommended standard sheet thickness.
+    pub fn thickness(&self) -> Length {
+        self.thickness
+    }
+
+    /// Largest stiffener spacing for the recommended sheet.
+    pub fn max_spacing(&self) -> Length {
+        self.max_spacing
+    }
+}
+
+/// ISO 12215-5 aspect ratio factor k2 for bending moment.
+fn aspect_ratio_factor(aspect_ratio: f64) -> f64 {
+    let ar = aspect_ratio.clamp(1.0, 2.0);
+    (0.271 * ar * ar + 0.910 * ar - 0.554) / (ar * ar - 0.313 * ar + 1.351)
+}
+
+impl fmt::Display for PanelScantling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Panel scantling - {} - {}]\n\
+            \tPressure:    {:>9.1}kPa\n\
+            \tSpan:        {:>9.0}mm\n\
+            \tRequired:    {:>9.1}mm\n\
+            \tThickness:   {:>9.0}mm\n\
+            \tMax spacing: {:>9.0}mm",
+            self.location,
+            self.material,
+            self.pressure.to_kilopascal(),
+            self.span.to_millimiter(),
+            self.required_thickness.to_millimiter(),
+            self.thickness.to_millimiter(),
+            self.max_spacing.to_millimiter()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn panel_scantling() {
+        assert_eq!(format!("{:.3}", aspect_ratio_factor(1.0)), "0.308");
+        assert_eq!(format!("{:.3}", aspect_ratio_factor(4.0)), "0.497");
+
+        // Long 400mm span at 20kPa, okoume sd of 20N/mm2.
+        let panel = PanelScantling::plywood(
+            PanelLocation::Bottom,
+            Material::OkoumePlywood,
+            Pressure::from_kilopascal(20.0),
+            Length::from_millimeter(400.0),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{:.1}", panel.required_thickness().to_millimiter()),
+            "8.9"
+        );
+        assert_eq!(panel.thickness().to_millimiter(), 9.0);
+        assert_eq!(format!("{:.0}", panel.max_spacing().to_millimiter()), "404");
+
+        // Light pressure, the location minimum rules.
+        let panel = PanelScantling::plywood(
+            PanelLocation::Side,
+            Material::OkoumePlywood,
+            Pressure::from_kilopascal(2.0),
+            Length::from_millimeter(200.0),
+            None,
+        )
+        .unwrap();
+        assert_eq!(panel.thickness().to_millimiter(), 4.0);
+
+        assert!(matches!(
+            PanelScantling::plywood(
+                PanelLocation::Deck,
+                Material::Aluminum,
+                Pressure::from_kilopascal(10.0),
+                Length::from_millimeter(400.0),
+                None,
+            ),
+            Err(ScantlingError::NotPlywood(Material::Aluminum))
+        ));
+        // Too wide a span for the standard sheets.
+        assert!(matches!(
+            PanelScantling::plywood(
+                PanelLocation::Bottom,
+                Material::OkoumePlywood,
+                Pressure::from_kilopascal(50.0),
+                Length::from_millimeter(1500.0),
+                None,
+            ),
+            Err(ScantlingError::TooThick(_))
+        ));
+    }
+}
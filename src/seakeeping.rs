@@ -0,0 +1,199 @@
+use super::boat::{Boat, SEA_WATER_DENSITY};
+use super::si::{Force, Length, Speed, GRAVITY};
+use super::stability::DesignCategory;
+use std::fmt;
+
+/// Pitch gyradius over the DWL, when the weight schedule is empty.
+pub const PITCH_GYRADIUS_FACTOR: f64 = 0.25;
+
+/// Added resistance coefficient of a hull with the usual PITCH_GYRADIUS_FACTOR, mean over an
+/// irregular sea, well below the peak of regular waves at the pitch resonance.
+pub const ADDED_RESISTANCE_COEFFICIENT: f64 = 0.25;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SEAKEEPING
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Significant wave height (m) the boat of a design category is expected to meet, ISO 12217
+/// limits, the open ended ocean category taken as 6m.
+pub fn design_wave_height(category: DesignCategory) -> Length {
+    Length::from_meter(match category {
+        DesignCategory::A => 6.0,
+        DesignCategory::B => 4.0,
+        DesignCategory::C => 2.0,
+        DesignCategory::D => 0.3,
+    })
+}
+
+/// Seakeeping
+///
+/// Rough seakeeping estimate from the principal dimensions and the weight distribution.
+/// The pitch gyradius k from the weight schedule, sqrt(sum(m (x - LCG)^2) / sum(m)), the
+/// items taken as point weights, or PITCH_GYRADIUS_FACTOR * DWL without it. Weights kept away
+/// from the ends pitch less and lose less speed.
+/// Added resistance in head waves of significant height H, after Jinkine and Ferdinande:
+/// Raw = sigma rho g (H / 2)^2 B^2 / L, sigma = ADDED_RESISTANCE_COEFFICIENT (k / 0.25 L)^2
+/// Motion comfort by the Brewer comfort ratio:
+/// CR = D / (0.65 (0.7 LWL + 0.3 LOA) B^(4/3)), D in pounds and lengths in feet.
+pub struct Seakeeping {
+    pitch_gyradius: Length,
+    length: Length,
+    beam: Length,
+    comfort_ratio: f64,
+}
+
+#[allow(dead_code)]
+impl Seakeeping {
+    pub fn from_boat(boat: &Boat) -> Seakeeping {
+        let length = boat.dwl();
+        let schedule = boat.weight_schedule();
+        let pitch_gyradius = schedule
+            .lcg()
+            .map(|lcg| {
+                let moment: f64 = schedule
+                    .items()
+                    .iter()
+                    .map(|i| i.weight().to_kilogram() * (i.lcg() - lcg).to_meter().powi(2))
+                    .sum();
+                Length::from_meter((moment / schedule.weight().to_kilogram()).sqrt())
+            })
+            .unwrap_or_else(|| Length::from_meter(PITCH_GYRADIUS_FACTOR * length.to_meter()));
+        let comfort_ratio = boat.displacement().to_pound()
+            / (0.65
+                * (0.7 * length.to_foot() + 0.3 * boat.loa().to_foot())
+                * boat.b_max().to_foot().powf(4.0 / 3.0));
+        Seakeeping {
+            pitch_gyradius,
+            length,
+            beam: boat.bwl(),
+            comfort_ratio,
+        }
+    }
+
+    /// Pitch gyradius.
+    pub fn pitch_gyradius(&self) -> Length {
+        self.pitch_gyradius
+    }
+
+    /// Pitch gyradius over the DWL.
+    pub fn gyradius_ratio(&self) -> f64 {
+        self.pitch_gyradius.to_meter() / self.length.to_meter()
+    }
+
+    /// Brewer comfort ratio, about 20 to 30 for coastal cruisers and 40 and up offshore.
+    pub fn comfort_ratio(&self) -> f64 {
+        self.comfort_ratio
+    }
+
+    /// Added resistance in head waves of a significant height.
+    pub fn added_resistance(&self, wave_height: Length) -> Force {
+        let sigma =
+            ADDED_RESISTANCE_COEFFICIENT * (self.gyradius_ratio() / PITCH_GYRADIUS_FACTOR).powi(2);
+        Force::from_newton(
+            sigma
+                * SEA_WATER_DENSITY
+                * GRAVITY
+                * (wave_height.to_meter() / 2.0).powi(2)
+                * self.beam.to_meter().powi(2)
+                / self.length.to_meter(),
+        )
+    }
+
+    /// Resistance in waves over the calm water resistance at a speed, None outside the
+    /// resistance curve.
+    pub fn resistance_factor(&self, boat: &Boat, wave_height: Length, speed: Speed) -> Option<f64> {
+        let calm = boat.resistance()?.at(speed)?.total().to_newton();
+        if calm <= 0.0 {
+            return None;
+        }
+        Some((calm + self.added_resistance(wave_height).to_newton()) / calm)
+    }
+}
+
+impl fmt::Display for Seakeeping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Seakeeping]\n\
+            \tPitch gyradius: {:>9.3}m ({:.2} DWL)\n\
+            \tComfort ratio:  {:>9.1}",
+            self.pitch_gyradius.to_meter(),
+            self.gyradius_ratio(),
+            self.comfort_ratio
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn seakeeping() {
+        use crate::si::Weight;
+        use crate::weights::{WeightItem, WeightSchedule};
+
+        let m = Length::from_meter;
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(m(11.0));
+        boat.set_dwl(m(10.0));
+        boat.set_b_max(m(3.5));
+        boat.set_bwl(m(3.0));
+        boat.set_draft(m(0.6));
+        boat.set_displacement(Weight::from_kilogram(7175.0));
+        boat.set_cp(0.55);
+        boat.set_cw(0.7);
+        boat.set_lcb(53.0);
+        boat.set_lcf(55.0);
+        let usual = Seakeeping::from_boat(&boat);
+        assert_eq!(usual.gyradius_ratio(), PITCH_GYRADIUS_FACTOR);
+        assert_eq!(format!("{:.1}", usual.comfort_ratio()), "27.8");
+        // 0.25 * 1025 * 9.81 * 1 * 9 / 10
+        let wave = design_wave_height(DesignCategory::C);
+        assert_eq!(
+            format!("{:.0}", usual.added_resistance(wave).to_newton()),
+            "2262"
+        );
+        let speed = Speed::from_knot(6.0);
+        let factor = usual.resistance_factor(&boat, wave, speed).unwrap();
+        assert!(factor > 1.0);
+        let sheltered = usual
+            .resistance_factor(&boat, design_wave_height(DesignCategory::D), speed)
+            .unwrap();
+        assert!(sheltered < factor);
+
+        // Weights concentrated amidships pitch less.
+        let kg = Weight::from_kilogram;
+        let mut schedule = WeightSchedule::new();
+        schedule.push(WeightItem::new(
+            "Hull".to_string(),
+            kg(4175.0),
+            m(5.0),
+            m(0.5),
+        ));
+        schedule.push(WeightItem::new(
+            "Ballast".to_string(),
+            kg(2500.0),
+            m(5.5),
+            m(-0.8),
+        ));
+        schedule.push(WeightItem::new(
+            "Anchor".to_string(),
+            kg(500.0),
+            m(0.5),
+            m(1.0),
+        ));
+        boat.set_weight_schedule(schedule);
+        let light_ends = Seakeeping::from_boat(&boat);
+        assert_eq!(
+            format!("{:.3}", light_ends.pitch_gyradius().to_meter()),
+            "1.216"
+        );
+        assert!(
+            light_ends.added_resistance(wave).to_newton()
+                < usual.added_resistance(wave).to_newton()
+        );
+    }
+}
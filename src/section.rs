@@ -0,0 +1,370 @@
+use super::hydrostatics::simpson;
+use super::offsets::OffsetsTable;
+use super::si::{Area, Length};
+use super::spline::{Interpolation, Spline};
+use std::fmt;
+
+/// Steps to scan the section height for a half-breadth.
+const SCAN_STEPS: usize = 100;
+
+/// Steps to integrate the section, even for Simpson's rule.
+const AREA_STEPS: usize = 40;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SECTION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Section
+///
+/// Station section, the half-breadth as a curve of the height through the offset points,
+/// from the keel up to the sheer. Position aft of the forward perpendicular, heights above the
+/// baseline.
+#[derive(Debug, Clone)]
+pub struct Section {
+    position: Length,
+    curve: Spline,
+}
+
+#[allow(dead_code)]
+impl Section {
+    /// Section of (half-breadth, height) points, needs two points at different heights.
+    pub fn new(
+        position: Length,
+        points: &[(Length, Length)],
+        interpolation: Interpolation,
+    ) -> Option<Section> {
+        let points: Vec<(f64, f64)> = points
+            .iter()
+            .map(|(y, z)| (z.to_meter(), y.to_meter()))
+            .collect();
+        Some(Section {
+            position,
+            curve: Spline::new(&points, interpolation)?,
+        })
+    }
+
+    /// Section of a station of the offsets table.
+    pub fn from_offsets(
+        table: &OffsetsTable,
+        station: usize,
+        interpolation: Interpolation,
+    ) -> Option<Section> {
+        let points: Vec<(Length, Length)> = table
+            .station_points(station)
+            .iter()
+            .map(|p| (p.half_breadth, p.height))
+            .collect();
+        Section::new(table.station(station)?, &points, interpolation)
+    }
+
+    /// Position aft of the forward perpendicular.
+    pub fn position(&self) -> Length {
+        self.position
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.curve.interpolation()
+    }
+
+    /// Lowest point, at the keel.
+    pub fn bottom(&self) -> Length {
+        Length::from_meter(self.curve.range().0)
+    }
+
+    /// Highest point, at the sheer.
+    pub fn top(&self) -> Length {
+        Length::from_meter(self.curve.range().1)
+    }
+
+    /// Offset points, (half-breadth, height) from the keel up.
+    pub fn points(&self) -> Vec<(Length, Length)> {
+        self.curve
+            .points()
+            .iter()
+            .map(|&(z, y)| (Length::from_meter(y), Length::from_meter(z)))
+            .collect()
+    }
+
+    /// Half-breadth at a height, None below the keel or above the sheer.
+    /// The spline may overshoot near a flat keel, negative half-breadths are taken as zero.
+    pub fn half_breadth(&self, height: Length) -> Option<Length> {
+        self.curve
+            .eval(height.to_meter())
+            .map(|y| Length::from_meter(y.max(0.0)))
+    }
+
+    /// Lowest height where the section reaches a half-breadth, where a buttock crosses it.
+    /// None when the section is narrower.
+    pub fn height_at(&self, half_breadth: Length) -> Option<Length> {
+        let target = half_breadth.to_meter();
+        let (bottom, top) = self.curve.range();
+        let step = (top - bottom) / SCAN_STEPS as f64;
+        let width = |z: f64| self.curve.eval(z).unwrap_or(0.0);
+        let mut low = bottom;
+        for i in 1..=SCAN_STEPS {
+            let high = bottom + step * i as f64;
+            if width(high) >= target {
+                // Bisection inside the step.
+                let mut high = high;
+                for _ in 0..30 {
+                    let middle = (low + high) / 2.0;
+                    if width(middle) >= target {
+                        high = middle;
+                    } else {
+                        low = middle;
+                    }
+                }
+                return Some(Length::from_meter(high));
+            }
+            low = high;
+        }
+        None
+    }
+
+    /// Area of both sides below a waterline, the section up to the sheer when above.
+    pub fn area_below(&self, waterline: Length) -> Area {
+        let (heights, widths) = self.widths_below(waterline);
+        Area::from_meter2(simpson(&heights, &widths))
+    }
+
+    /// Height of the centroid of the area below a waterline, None when nothing is immersed.
+    pub fn centroid_below(&self, waterline: Length) -> Option<Length> {
+        let (heights, widths) = self.widths_below(waterline);
+        let area = simpson(&heights, &widths);
+        if area <= 0.0 {
+            return None;
+        }
+        let moments: Vec<f64> = heights.iter().zip(&widths).map(|(z, w)| z * w).collect();
+        Some(Length::from_meter(simpson(&heights, &moments) / area))
+    }
+
+    /// Girth below a waterline, both sides around the section from the waterline, the bottom
+    /// width included on a flat bottom.
+    pub fn girth_below(&self, waterline: Length) -> Length {
+        let (heights, widths) = self.widths_below(waterline);
+        let arc: f64 = heights
+            .windows(2)
+            .zip(widths.windows(2))
+            .map(|(z, w)| ((z[1] - z[0]).powi(2) + ((w[1] - w[0]) / 2.0).powi(2)).sqrt())
+            .sum();
+        let bottom = widths.first().map_or(0.0, |w| w / 2.0);
+        Length::from_meter(2.0 * (bottom + arc))
+    }
+
+    /// Outline of both sides, (half-breadth, height) in meters, counterclockwise from the keel
+    /// up the starboard side, across a flat deck at the sheer and down the port side.
+    pub fn outline(&self) -> Vec<(f64, f64)> {
+        let bottom = self.bottom().to_meter();
+        let top = self.top().to_meter();
+        let starboard: Vec<(f64, f64)> = (0..=AREA_STEPS)
+            .map(|i| {
+                let z = bottom + (top - bottom) * i as f64 / AREA_STEPS as f64;
+                let y = self
+                    .half_breadth(Length::from_meter(z))
+                    .map_or(0.0, |y| y.to_meter());
+                (y, z)
+            })
+            .collect();
+        let port = starboard.iter().rev().map(|&(y, z)| (-y, z));
+        starboard.iter().copied().chain(port).collect()
+    }
+
+    /// Immersed area of the heeled section and its centroid, (area, half-breadth, height).
+    ///
+    /// Heel in degrees to starboard, the waterline at a depth from the baseline on the
+    /// centerline, measured square to the water. The deck is taken watertight.
+    pub fn immersed(&self, heel: f64, depth: Length) -> (Area, Length, Length) {
+        let (sin, cos) = heel.to_radians().sin_cos();
+        let depth = depth.to_meter();
+        // Below the water: -y sin + z cos <= depth.
+        let height = |p: &(f64, f64)| depth - (-p.0 * sin + p.1 * cos);
+        let outline = self.outline();
+        let mut clipped: Vec<(f64, f64)> = Vec::new();
+        for (i, p) in outline.iter().enumerate() {
+            let q = &outline[(i + 1) % outline.len()];
+            let (hp, hq) = (height(p), height(q));
+            if hp >= 0.0 {
+                clipped.push(*p);
+            }
+            if (hp >= 0.0) != (hq >= 0.0) {
+                let t = hp / (hp - hq);
+                clipped.push((p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t));
+            }
+        }
+        let (mut area, mut y, mut z) = (0.0, 0.0, 0.0);
+        for (i, p) in clipped.iter().enumerate() {
+            let q = &clipped[(i + 1) % clipped.len()];
+            let cross = p.0 * q.1 - q.0 * p.1;
+            area += cross / 2.0;
+            y += (p.0 + q.0) * cross / 6.0;
+            z += (p.1 + q.1) * cross / 6.0;
+        }
+        if area <= 0.0 {
+            return (
+                Area::from_meter2(0.0),
+                Length::from_meter(0.0),
+                self.bottom(),
+            );
+        }
+        (
+            Area::from_meter2(area),
+            Length::from_meter(y / area),
+            Length::from_meter(z / area),
+        )
+    }
+
+    /// Heights from the keel to a waterline and the full width (both sides) at them.
+    fn widths_below(&self, waterline: Length) -> (Vec<f64>, Vec<f64>) {
+        let bottom = self.bottom().to_meter();
+        let top = waterline.to_meter().min(self.top().to_meter());
+        if top <= bottom {
+            return (Vec::new(), Vec::new());
+        }
+        (0..=AREA_STEPS)
+            .map(|i| {
+                let z = bottom + (top - bottom) * i as f64 / AREA_STEPS as f64;
+                let y = self
+                    .half_breadth(Length::from_meter(z))
+                    .map_or(0.0, |y| y.to_meter());
+                (z, 2.0 * y)
+            })
+            .unzip()
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Section {:.3}m - {}]",
+            self.position.to_meter(),
+            self.interpolation()
+        )?;
+        for (y, z) in self.points() {
+            write!(f, "\n\t{:>8.3} {:>8.3}", y.to_meter(), z.to_meter())?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::offsets::{OffsetKind, KEEL, SHEER};
+
+    #[test]
+    fn section() {
+        let m = |v: f64| Some(Length::from_meter(v));
+        let mut table =
+            OffsetsTable::new(vec![Length::from_meter(0.0), Length::from_meter(2.0)]).unwrap();
+        table
+            .add_line(KEEL, OffsetKind::Height, vec![m(0.5), m(0.0)])
+            .unwrap();
+        table
+            .add_line(SHEER, OffsetKind::Height, vec![m(1.0), m(1.0)])
+            .unwrap();
+        table
+            .add_line(SHEER, OffsetKind::HalfBreadth, vec![m(0.0), m(1.0)])
+            .unwrap();
+        table
+            .add_waterline("WL1", Length::from_meter(0.25), vec![None, m(0.6)])
+            .unwrap();
+        table
+            .add_waterline("WL2", Length::from_meter(0.5), vec![None, m(0.85)])
+            .unwrap();
+
+        let section = Section::from_offsets(&table, 1, Interpolation::CubicSpline).unwrap();
+        assert_eq!(section.position().to_meter(), 2.0);
+        assert_eq!(section.bottom().to_meter(), 0.0);
+        assert_eq!(section.top().to_meter(), 1.0);
+        assert_eq!(section.points().len(), 4);
+        assert_eq!(
+            format!(
+                "{:.3}",
+                section
+                    .half_breadth(Length::from_meter(0.5))
+                    .unwrap()
+                    .to_meter()
+            ),
+            "0.850"
+        );
+        let between = section
+            .half_breadth(Length::from_meter(0.375))
+            .unwrap()
+            .to_meter();
+        assert!(between > 0.725 && between < 0.85);
+        assert!(section.half_breadth(Length::from_meter(1.1)).is_none());
+        assert_eq!(
+            format!(
+                "{:.3}",
+                section
+                    .height_at(Length::from_meter(0.6))
+                    .unwrap()
+                    .to_meter()
+            ),
+            "0.250"
+        );
+        assert!(section.height_at(Length::from_meter(1.2)).is_none());
+        assert_eq!(section.area_below(Length::from_meter(0.0)).to_meter2(), 0.0);
+        assert!(section.centroid_below(Length::from_meter(0.0)).is_none());
+
+        let linear = Section::from_offsets(&table, 1, Interpolation::Linear).unwrap();
+        assert_eq!(
+            format!(
+                "{:.3}",
+                linear
+                    .half_breadth(Length::from_meter(0.375))
+                    .unwrap()
+                    .to_meter()
+            ),
+            "0.725"
+        );
+        // Keel to 0.6 by 0.25, 0.6 to 0.85 by 0.25, both sides.
+        assert_eq!(
+            format!(
+                "{:.4}",
+                linear.girth_below(Length::from_meter(0.5)).to_meter()
+            ),
+            format!("{:.4}", 2.0 * (0.6f64.hypot(0.25) + 0.25f64.hypot(0.25)))
+        );
+        // Upright, the same area and the centroid on the centerline.
+        let (area, y, z) = linear.immersed(0.0, Length::from_meter(0.5));
+        assert_eq!(format!("{:.4}", area.to_meter2()), "0.5125");
+        assert_eq!(format!("{:.4}", y.to_meter()), "0.0000");
+        assert_eq!(
+            format!("{:.4}", z.to_meter()),
+            format!(
+                "{:.4}",
+                linear
+                    .centroid_below(Length::from_meter(0.5))
+                    .unwrap()
+                    .to_meter()
+            )
+        );
+        // Heeled, the centroid moves to starboard.
+        let (_, y, _) = linear.immersed(20.0, Length::from_meter(0.5));
+        assert!(y.to_meter() > 0.0);
+        // Capsized, the whole section under water.
+        let (area, _, _) = linear.immersed(180.0, Length::from_meter(0.0));
+        assert_eq!(format!("{:.4}", area.to_meter2()), "1.4375");
+        // Trapezoids of 0.25 * (0 + 0.6) / 2 and 0.25 * (0.6 + 0.85) / 2, both sides.
+        assert_eq!(
+            format!(
+                "{:.4}",
+                linear.area_below(Length::from_meter(0.5)).to_meter2()
+            ),
+            "0.5125"
+        );
+        // Only the keel and sheer at the bow, both on the centerline.
+        let bow = Section::from_offsets(&table, 0, Interpolation::Linear).unwrap();
+        assert_eq!(
+            bow.half_breadth(Length::from_meter(0.75))
+                .unwrap()
+                .to_meter(),
+            0.0
+        );
+    }
+}
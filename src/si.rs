@@ -1,18 +1,24 @@
-use std::ops::{Add, Div};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Div, Sub};
+
+/// Standard gravity (m/s2).
+pub const GRAVITY: f64 = 9.80665;
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 // Lenght
 ///////////////////////////////////////////////////////////////////////////////////////////////////
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Length {
     // Meter.
+    #[serde(rename = "meter")]
     val: f64,
 }
 
 #[allow(dead_code)]
 impl Length {
     pub fn from_meter(val: f64) -> Length {
-        Length { val: val }
+        Length { val }
     }
 
     pub fn to_meter(&self) -> f64 {
@@ -58,6 +64,16 @@ impl Add for Length {
     }
 }
 
+impl Sub for Length {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            val: self.val - other.val,
+        }
+    }
+}
+
 impl Div for Length {
     type Output = Self;
 
@@ -71,16 +87,17 @@ impl Div for Length {
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 // Area
 ///////////////////////////////////////////////////////////////////////////////////////////////////
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Area {
     // Meter2.
+    #[serde(rename = "meter2")]
     val: f64,
 }
 
 #[allow(dead_code)]
 impl Area {
     pub fn from_meter2(val: f64) -> Area {
-        Area { val: val }
+        Area { val }
     }
 
     pub fn to_meter2(&self) -> f64 {
@@ -116,19 +133,91 @@ impl Div for Area {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Volume
+///////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Volume {
+    // Meter3.
+    #[serde(rename = "meter3")]
+    val: f64,
+}
+
+#[allow(dead_code)]
+impl Volume {
+    pub fn from_meter3(val: f64) -> Volume {
+        Volume { val }
+    }
+
+    pub fn to_meter3(&self) -> f64 {
+        self.val
+    }
+
+    pub fn from_liter(val: f64) -> Volume {
+        Volume { val: val / 1000.0 }
+    }
+
+    pub fn to_liter(&self) -> f64 {
+        self.val * 1000.0
+    }
+
+    pub fn from_foot3(val: f64) -> Volume {
+        Volume {
+            val: val * 0.3048_f64.powi(3),
+        }
+    }
+
+    pub fn to_foot3(&self) -> f64 {
+        self.val / 0.3048_f64.powi(3)
+    }
+
+    // US gallon.
+    pub fn from_gallon(val: f64) -> Volume {
+        Volume {
+            val: val * 3.785_411_784 / 1000.0,
+        }
+    }
+
+    // US gallon.
+    pub fn to_gallon(&self) -> f64 {
+        self.val * 1000.0 / 3.785_411_784
+    }
+}
+
+impl Add for Volume {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            val: self.val + other.val,
+        }
+    }
+}
+
+impl Div for Volume {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self {
+            val: self.val / other.val,
+        }
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 // Weight
 ///////////////////////////////////////////////////////////////////////////////////////////////////
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Weight {
     // Kilogram.
+    #[serde(rename = "kilogram")]
     val: f64,
 }
 
 #[allow(dead_code)]
 impl Weight {
     pub fn from_kilogram(val: f64) -> Weight {
-        Weight { val: val }
+        Weight { val }
     }
 
     pub fn to_kilogram(&self) -> f64 {
@@ -193,6 +282,304 @@ impl Div for Weight {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Speed
+///////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Speed {
+    // Meter / second.
+    #[serde(rename = "meter_second")]
+    val: f64,
+}
+
+#[allow(dead_code)]
+impl Speed {
+    pub fn from_meter_second(val: f64) -> Speed {
+        Speed { val }
+    }
+
+    pub fn to_meter_second(&self) -> f64 {
+        self.val
+    }
+
+    // Nautical mile (1852m) / hour.
+    pub fn from_knot(val: f64) -> Speed {
+        Speed {
+            val: val * 1852.0 / 3600.0,
+        }
+    }
+
+    // Nautical mile (1852m) / hour.
+    pub fn to_knot(&self) -> f64 {
+        self.val * 3600.0 / 1852.0
+    }
+
+    pub fn from_kilometer_hour(val: f64) -> Speed {
+        Speed { val: val / 3.6 }
+    }
+
+    pub fn to_kilometer_hour(&self) -> f64 {
+        self.val * 3.6
+    }
+}
+
+impl Add for Speed {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            val: self.val + other.val,
+        }
+    }
+}
+
+impl Div for Speed {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self {
+            val: self.val / other.val,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Density
+///////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Density {
+    // Kilogram / meter3.
+    #[serde(rename = "kilogram_meter3")]
+    val: f64,
+}
+
+#[allow(dead_code)]
+impl Density {
+    pub fn from_kilogram_meter3(val: f64) -> Density {
+        Density { val }
+    }
+
+    pub fn to_kilogram_meter3(&self) -> f64 {
+        self.val
+    }
+
+    pub fn from_pound_foot3(val: f64) -> Density {
+        Density {
+            val: Weight::from_pound(val).to_kilogram() / Volume::from_foot3(1.0).to_meter3(),
+        }
+    }
+
+    pub fn to_pound_foot3(&self) -> f64 {
+        Weight::from_kilogram(self.val).to_pound() * Volume::from_foot3(1.0).to_meter3()
+    }
+
+    /// Weight of a volume with this density.
+    pub fn weight(self, volume: Volume) -> Weight {
+        Weight::from_kilogram(self.val * volume.to_meter3())
+    }
+
+    /// Volume of a weight with this density.
+    pub fn volume(self, weight: Weight) -> Volume {
+        Volume::from_meter3(weight.to_kilogram() / self.val)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Force
+///////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Force {
+    // Newton.
+    #[serde(rename = "newton")]
+    val: f64,
+}
+
+#[allow(dead_code)]
+impl Force {
+    pub fn from_newton(val: f64) -> Force {
+        Force { val }
+    }
+
+    pub fn to_newton(&self) -> f64 {
+        self.val
+    }
+
+    pub fn from_kilogram_force(val: f64) -> Force {
+        Force { val: val * GRAVITY }
+    }
+
+    pub fn to_kilogram_force(&self) -> f64 {
+        self.val / GRAVITY
+    }
+
+    pub fn from_pound_force(val: f64) -> Force {
+        Force {
+            val: val * 4.448222,
+        }
+    }
+
+    pub fn to_pound_force(&self) -> f64 {
+        self.val / 4.448222
+    }
+
+    /// Power to move against the force at a speed.
+    pub fn power(self, speed: Speed) -> Power {
+        Power::from_watt(self.val * speed.to_meter_second())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Power
+///////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Power {
+    // Watt.
+    #[serde(rename = "watt")]
+    val: f64,
+}
+
+#[allow(dead_code)]
+impl Power {
+    pub fn from_watt(val: f64) -> Power {
+        Power { val }
+    }
+
+    pub fn to_watt(&self) -> f64 {
+        self.val
+    }
+
+    pub fn from_kilowatt(val: f64) -> Power {
+        Power { val: val * 1000.0 }
+    }
+
+    pub fn to_kilowatt(&self) -> f64 {
+        self.val / 1000.0
+    }
+
+    // Mechanical horsepower (550 ft lbf / s).
+    pub fn from_horsepower(val: f64) -> Power {
+        Power {
+            val: val * 745.699872,
+        }
+    }
+
+    // Mechanical horsepower (550 ft lbf / s).
+    pub fn to_horsepower(&self) -> f64 {
+        self.val / 745.699872
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Pressure
+///////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Pressure {
+    // Pascal.
+    #[serde(rename = "pascal")]
+    val: f64,
+}
+
+#[allow(dead_code)]
+impl Pressure {
+    pub fn from_pascal(val: f64) -> Pressure {
+        Pressure { val }
+    }
+
+    pub fn to_pascal(&self) -> f64 {
+        self.val
+    }
+
+    pub fn from_kilopascal(val: f64) -> Pressure {
+        Pressure { val: val * 1.0e3 }
+    }
+
+    pub fn to_kilopascal(&self) -> f64 {
+        self.val / 1.0e3
+    }
+
+    pub fn from_megapascal(val: f64) -> Pressure {
+        Pressure { val: val * 1.0e6 }
+    }
+
+    pub fn to_megapascal(&self) -> f64 {
+        self.val / 1.0e6
+    }
+
+    // Pound-force / inch2.
+    pub fn from_psi(val: f64) -> Pressure {
+        Pressure {
+            val: val * 6894.757,
+        }
+    }
+
+    // Pound-force / inch2.
+    pub fn to_psi(&self) -> f64 {
+        self.val / 6894.757
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Unit system
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Units a value is shown in, on drawings and reports.
+#[derive(PartialEq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum UnitSystem {
+    /// Meters.
+    #[default]
+    Metric,
+    /// Feet and inches.
+    Imperial,
+}
+
+#[allow(dead_code)]
+impl UnitSystem {
+    /// Length with its units, 1.250m or 4' 1.2".
+    pub fn length(self, length: Length) -> String {
+        match self {
+            UnitSystem::Metric => format!("{:.3}m", length.to_meter()),
+            UnitSystem::Imperial => {
+                let inches = (length.to_inch().abs() * 10.0).round() / 10.0;
+                let feet = (inches / 12.0).floor();
+                let sign = if length.to_meter() < 0.0 { "-" } else { "" };
+                format!("{}{:.0}' {:.1}\"", sign, feet, inches - feet * 12.0)
+            }
+        }
+    }
+
+    /// Weight with its units, 1250kg or 2756lb.
+    pub fn weight(self, weight: Weight) -> String {
+        match self {
+            UnitSystem::Metric => format!("{:.0}kg", weight.to_kilogram()),
+            UnitSystem::Imperial => format!("{:.0}lb", weight.to_pound()),
+        }
+    }
+
+    /// Area with its units, 9.50m2 or 102.3ft2.
+    pub fn area(self, area: Area) -> String {
+        match self {
+            UnitSystem::Metric => format!("{:.2}m2", area.to_meter2()),
+            UnitSystem::Imperial => format!("{:.1}ft2", area.to_foot2()),
+        }
+    }
+
+    /// Volume with its units, 1.250m3 or 44.14ft3.
+    pub fn volume(self, volume: Volume) -> String {
+        match self {
+            UnitSystem::Metric => format!("{:.3}m3", volume.to_meter3()),
+            UnitSystem::Imperial => format!("{:.2}ft3", volume.to_foot3()),
+        }
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UnitSystem::Metric => write!(f, "Metric"),
+            UnitSystem::Imperial => write!(f, "Imperial"),
+        }
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 // TEST
 ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -228,6 +615,83 @@ mod test {
         assert_eq!(format!("{:.6}", foot2.to_meter2()), "0.092902");
     }
 
+    #[test]
+    fn volume_conversions() {
+        let meter3 = Volume::from_meter3(1.0);
+        assert_eq!(meter3.to_liter(), 1000.0);
+        assert_eq!(format!("{:.4}", meter3.to_foot3()), "35.3147");
+        assert_eq!(format!("{:.3}", meter3.to_gallon()), "264.172");
+
+        let foot3 = Volume::from_foot3(64.0);
+        assert_eq!(format!("{:.4}", foot3.to_meter3()), "1.8123");
+
+        let liter = Volume::from_liter(1.0);
+        assert_eq!(liter.to_meter3(), 0.001);
+    }
+
+    #[test]
+    fn speed_conversions() {
+        let knot = Speed::from_knot(1.0);
+        assert_eq!(format!("{:.4}", knot.to_meter_second()), "0.5144");
+        assert_eq!(knot.to_kilometer_hour(), 1.852);
+
+        let meter_second = Speed::from_meter_second(1.0);
+        assert_eq!(format!("{:.4}", meter_second.to_knot()), "1.9438");
+
+        let kilometer_hour = Speed::from_kilometer_hour(36.0);
+        assert_eq!(kilometer_hour.to_meter_second(), 10.0);
+    }
+
+    #[test]
+    fn power_conversions() {
+        let horsepower = Power::from_horsepower(1.0);
+        assert_eq!(format!("{:.4}", horsepower.to_kilowatt()), "0.7457");
+
+        let kilowatt = Power::from_kilowatt(1.0);
+        assert_eq!(kilowatt.to_watt(), 1000.0);
+        assert_eq!(format!("{:.4}", kilowatt.to_horsepower()), "1.3410");
+    }
+
+    #[test]
+    fn force_conversions() {
+        let kilogram = Force::from_kilogram_force(1.0);
+        assert_eq!(kilogram.to_newton(), 9.80665);
+        assert_eq!(format!("{:.4}", kilogram.to_pound_force()), "2.2046");
+
+        let newton = Force::from_newton(100.0);
+        let power = newton.power(Speed::from_meter_second(2.0));
+        assert_eq!(power.to_watt(), 200.0);
+    }
+
+    #[test]
+    fn pressure_conversions() {
+        let psi = Pressure::from_psi(1.0);
+        assert_eq!(format!("{:.3}", psi.to_kilopascal()), "6.895");
+
+        let megapascal = Pressure::from_megapascal(1.0);
+        assert_eq!(megapascal.to_kilopascal(), 1000.0);
+        assert_eq!(format!("{:.1}", megapascal.to_psi()), "145.0");
+    }
+
+    #[test]
+    fn density_conversions() {
+        let water = Density::from_kilogram_meter3(1025.0);
+        assert_eq!(format!("{:.2}", water.to_pound_foot3()), "63.99");
+        assert_eq!(water.weight(Volume::from_meter3(2.0)).to_kilogram(), 2050.0);
+        assert_eq!(water.volume(Weight::from_kilogram(2050.0)).to_meter3(), 2.0);
+
+        let water = Density::from_pound_foot3(62.4);
+        assert_eq!(format!("{:.1}", water.to_kilogram_meter3()), "999.6");
+    }
+
+    #[test]
+    fn serialize_units() {
+        let loa = Length::from_foot(10.0);
+        assert_eq!(serde_json::to_string(&loa).unwrap(), r#"{"meter":3.048}"#);
+        let loa: Length = serde_json::from_str(r#"{"meter":3.048}"#).unwrap();
+        assert_eq!(loa.to_meter(), 3.048);
+    }
+
     #[test]
     fn wight_conversions() {
         let kilogram = Weight::from_kilogram(1.0);
@@ -258,4 +722,24 @@ mod test {
         let short_ton = Weight::from_short_ton(1.0);
         assert_eq!(short_ton.to_kilogram(), 907.185);
     }
+
+    #[test]
+    fn unit_system() {
+        assert_eq!(UnitSystem::default(), UnitSystem::Metric);
+        let length = Length::from_meter(1.25);
+        assert_eq!(UnitSystem::Metric.length(length), "1.250m");
+        assert_eq!(UnitSystem::Imperial.length(length), "4' 1.2\"");
+        // Rounding up to the next foot.
+        let length = Length::from_inch(23.97);
+        assert_eq!(UnitSystem::Imperial.length(length), "2' 0.0\"");
+        let weight = Weight::from_kilogram(1250.0);
+        assert_eq!(UnitSystem::Metric.weight(weight), "1250kg");
+        assert_eq!(UnitSystem::Imperial.weight(weight), "2756lb");
+        let area = Area::from_meter2(9.5);
+        assert_eq!(UnitSystem::Metric.area(area), "9.50m2");
+        assert_eq!(UnitSystem::Imperial.area(area), "102.3ft2");
+        let volume = Volume::from_meter3(1.25);
+        assert_eq!(UnitSystem::Metric.volume(volume), "1.250m3");
+        assert_eq!(UnitSystem::Imperial.volume(volume), "44.14ft3");
+    }
 }
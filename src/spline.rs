@@ -0,0 +1,162 @@
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// SPLINE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Interpolation between the points of a curve.
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+pub enum Interpolation {
+    /// Straight lines, for hard chines and knuckles.
+    Linear,
+    /// Natural cubic spline, for round bilge sections and fair curves.
+    #[default]
+    CubicSpline,
+}
+
+impl fmt::Display for Interpolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Interpolation::Linear => write!(f, "Linear"),
+            Interpolation::CubicSpline => write!(f, "Cubic spline"),
+        }
+    }
+}
+
+/// Spline
+///
+/// Curve y(x) through points of increasing x, evaluated between the first and last points.
+/// The natural cubic spline has zero curvature at the ends, like a batten free at the ends.
+#[derive(Debug, Clone)]
+pub struct Spline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// Second derivatives at the points, zero for the linear interpolation.
+    m: Vec<f64>,
+    interpolation: Interpolation,
+}
+
+#[allow(dead_code)]
+impl Spline {
+    /// Needs two points at least, points are sorted by x and repeated x are averaged.
+    pub fn new(points: &[(f64, f64)], interpolation: Interpolation) -> Option<Spline> {
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let mut x: Vec<f64> = Vec::new();
+        let mut y: Vec<f64> = Vec::new();
+        let mut count = 1.0;
+        for (px, py) in sorted {
+            match x.last() {
+                Some(&last) if (px - last).abs() < 1e-9 => {
+                    let i = y.len() - 1;
+                    y[i] = (y[i] * count + py) / (count + 1.0);
+                    count += 1.0;
+                }
+                _ => {
+                    x.push(px);
+                    y.push(py);
+                    count = 1.0;
+                }
+            }
+        }
+        if x.len() < 2 {
+            return None;
+        }
+        let m = match interpolation {
+            Interpolation::Linear => vec![0.0; x.len()],
+            Interpolation::CubicSpline => second_derivatives(&x, &y),
+        };
+        Some(Spline {
+            x,
+            y,
+            m,
+            interpolation,
+        })
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Range of x.
+    pub fn range(&self) -> (f64, f64) {
+        (self.x[0], self.x[self.x.len() - 1])
+    }
+
+    /// Points the spline goes through.
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.x.iter().copied().zip(self.y.iter().copied()).collect()
+    }
+
+    /// Value at x, None outside the range.
+    pub fn eval(&self, x: f64) -> Option<f64> {
+        let (first, last) = self.range();
+        if x < first - 1e-9 || x > last + 1e-9 {
+            return None;
+        }
+        let i = self
+            .x
+            .windows(2)
+            .position(|w| x <= w[1])
+            .unwrap_or(self.x.len() - 2);
+        let h = self.x[i + 1] - self.x[i];
+        let a = (self.x[i + 1] - x) / h;
+        let b = (x - self.x[i]) / h;
+        Some(
+            a * self.y[i]
+                + b * self.y[i + 1]
+                + ((a.powi(3) - a) * self.m[i] + (b.powi(3) - b) * self.m[i + 1]) * h * h / 6.0,
+        )
+    }
+}
+
+/// Second derivatives of the natural cubic spline, by the tridiagonal (Thomas) algorithm.
+fn second_derivatives(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let mut m = vec![0.0; n];
+    if n < 3 {
+        return m;
+    }
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    for i in 1..n - 1 {
+        let h0 = x[i] - x[i - 1];
+        let h1 = x[i + 1] - x[i];
+        let rhs = 6.0 * ((y[i + 1] - y[i]) / h1 - (y[i] - y[i - 1]) / h0);
+        let diagonal = 2.0 * (h0 + h1) - h0 * c[i - 1];
+        c[i] = h1 / diagonal;
+        d[i] = (rhs - h0 * d[i - 1]) / diagonal;
+    }
+    for i in (1..n - 1).rev() {
+        m[i] = d[i] - c[i] * m[i + 1];
+    }
+    m
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn spline() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (1.0, 1.0)];
+        let linear = Spline::new(&points, Interpolation::Linear).unwrap();
+        assert_eq!(linear.points().len(), 3);
+        assert_eq!(linear.eval(0.5), Some(0.5));
+        assert!(linear.eval(2.5).is_none());
+
+        let cubic = Spline::new(&points, Interpolation::CubicSpline).unwrap();
+        assert_eq!(cubic.eval(1.0), Some(1.0));
+        // Rounder than the straight line.
+        assert_eq!(format!("{:.4}", cubic.eval(0.5).unwrap()), "0.6875");
+
+        // A straight line stays straight.
+        let line = [(0.0, 1.0), (1.0, 3.0), (3.0, 7.0)];
+        let cubic = Spline::new(&line, Interpolation::CubicSpline).unwrap();
+        assert_eq!(format!("{:.6}", cubic.eval(2.0).unwrap()), "5.000000");
+
+        assert!(Spline::new(&[(1.0, 1.0)], Interpolation::Linear).is_none());
+    }
+}
@@ -0,0 +1,1737 @@
+use super::boat::{Boat, ScreeningStabilityValue};
+use super::drawing::{
+    file_target, hatch, DrawingError, DrawingStyle, Hatch, LineKind, ARROW_SIZE, GRID_DASH,
+};
+use super::hull::Hull;
+use super::hydrostatics::{simpson, Equilibrium, Hydrostatics, Water, EQUILIBRIUM_TOLERANCE};
+use super::loading::{LoadCondition, LoadConditionKind};
+use super::si::{Length, Volume, Weight, GRAVITY};
+use cairo::Context;
+use std::fmt;
+use std::path::Path;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// INITIAL STABILITY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Initial stability character.
+/// Classified by GM / BWL, so boats of different sizes can be compared.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum InitialStabilityCharacter {
+    Unstable,
+    Tender,
+    Moderate,
+    Stiff,
+}
+
+impl fmt::Display for InitialStabilityCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InitialStabilityCharacter::Unstable => write!(f, "Unstable"),
+            InitialStabilityCharacter::Tender => write!(f, "Tender"),
+            InitialStabilityCharacter::Moderate => write!(f, "Moderate"),
+            InitialStabilityCharacter::Stiff => write!(f, "Stiff"),
+        }
+    }
+}
+
+/// Initial stability
+///
+/// GM = KB + BM - KG - FSC
+/// First-cut estimate from the form coefficients, or from the hull geometry when available,
+/// valid only for small heel angles.
+/// FSC is the free surface correction of the slack tanks, in a load condition only.
+/// Roll stiffness as the righting moment per degree of heel, Displacement * GM * sin(1°), and
+/// the natural roll period T = 2 pi k / (g GM) exp 0.5, with the roll radius k = 0.4 BWL.
+pub struct InitialStability {
+    kb: Length,
+    bm: Length,
+    kg: Length,
+    free_surface: Length,
+    gm: Length,
+    displacement: Weight,
+    bwl: Length,
+    initial_stability_character: InitialStabilityCharacter,
+}
+
+#[allow(dead_code)]
+impl InitialStability {
+    /// Needs Cw and VCG to be informed.
+    pub fn from_boat(boat: &Boat) -> Option<InitialStability> {
+        InitialStability::with_free_surface(boat, Length::from_meter(0.0))
+    }
+
+    /// Boat floating in a load condition, less the free surface of its slack tanks.
+    /// Needs Cw and the condition VCG to be informed.
+    pub fn from_condition(boat: &Boat, kind: LoadConditionKind) -> Option<InitialStability> {
+        let condition = boat.load_condition(kind)?;
+        let free_surface = FreeSurface::new(condition).correction();
+        InitialStability::with_free_surface(&condition.apply(boat), free_surface)
+    }
+
+    fn with_free_surface(boat: &Boat, free_surface: Length) -> Option<InitialStability> {
+        Some(InitialStability::classify(
+            boat.kb()?,
+            boat.bm()?,
+            boat.vcg()?,
+            free_surface,
+            boat.displacement(),
+            boat.bwl(),
+        ))
+    }
+
+    /// KB and BM from the hull geometry, KG above the bottom of the canoe body.
+    /// None when nothing is immersed.
+    pub fn from_hydrostatics(hydrostatics: &Hydrostatics, kg: Length) -> Option<InitialStability> {
+        Some(InitialStability::classify(
+            hydrostatics.kb()?,
+            hydrostatics.bm()?,
+            kg,
+            Length::from_meter(0.0),
+            hydrostatics.displacement(),
+            hydrostatics.waterline_beam(),
+        ))
+    }
+
+    /// KB and BM from the hull geometry, KG from the weight schedule, or the boat VCG when the
+    /// schedule is empty.
+    pub fn from_geometry(boat: &Boat, hydrostatics: &Hydrostatics) -> Option<InitialStability> {
+        let kg = boat.weight_schedule().vcg().or_else(|| boat.vcg())?;
+        InitialStability::from_hydrostatics(hydrostatics, kg)
+    }
+
+    fn classify(
+        kb: Length,
+        bm: Length,
+        kg: Length,
+        free_surface: Length,
+        displacement: Weight,
+        bwl: Length,
+    ) -> InitialStability {
+        let gm = kb + bm - kg - free_surface;
+        let value = gm.to_meter() / bwl.to_meter();
+        InitialStability {
+            kb,
+            bm,
+            kg,
+            free_surface,
+            gm,
+            displacement,
+            bwl,
+            initial_stability_character: if value < 0.0 {
+                InitialStabilityCharacter::Unstable
+            } else if value < 0.15 {
+                InitialStabilityCharacter::Tender
+            } else if value <= 0.40 {
+                InitialStabilityCharacter::Moderate
+            } else {
+                InitialStabilityCharacter::Stiff
+            },
+        }
+    }
+
+    /// KB (height of the center of buoyancy).
+    pub fn kb(&self) -> Length {
+        self.kb
+    }
+
+    /// BM (transverse metacentric radius).
+    pub fn bm(&self) -> Length {
+        self.bm
+    }
+
+    /// KG (height of the center of gravity).
+    pub fn kg(&self) -> Length {
+        self.kg
+    }
+
+    /// FSC (free surface correction), the virtual rise of G by the slack tanks.
+    pub fn free_surface(&self) -> Length {
+        self.free_surface
+    }
+
+    /// GM (transverse metacentric height), corrected for the free surface.
+    pub fn gm(&self) -> Length {
+        self.gm
+    }
+
+    /// Righting moment per degree of heel (kg.m), the roll stiffness.
+    pub fn righting_moment(&self) -> f64 {
+        self.displacement.to_kilogram() * self.gm.to_meter() * 1.0f64.to_radians().sin()
+    }
+
+    /// Natural roll period (s), None when unstable.
+    pub fn roll_period(&self) -> Option<f64> {
+        if self.gm.to_meter() <= 0.0 {
+            return None;
+        }
+        Some(
+            2.0 * std::f64::consts::PI * 0.4 * self.bwl.to_meter()
+                / (GRAVITY * self.gm.to_meter()).sqrt(),
+        )
+    }
+
+    /// Initial stability character.
+    pub fn character(&self) -> InitialStabilityCharacter {
+        self.initial_stability_character
+    }
+}
+
+impl fmt::Display for InitialStability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[Initial stability]\n\
+            \tKB:    {:>7.3}m\n\
+            \tBM:    {:>7.3}m\n\
+            \tKG:    {:>7.3}m\n\
+            \tFSC:   {:>7.3}m\n\
+            \tGM:    {:>7.3}m [{}]\n\
+            \tRM:    {:>7.1}kg.m/deg\n\
+            \tRoll:  {:>7}s",
+            self.kb.to_meter(),
+            self.bm.to_meter(),
+            self.kg.to_meter(),
+            self.free_surface.to_meter(),
+            self.gm.to_meter(),
+            self.initial_stability_character,
+            self.righting_moment(),
+            self.roll_period()
+                .map_or("-".to_string(), |t| format!("{:.1}", t))
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FREE SURFACE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Free surface
+///
+/// The fluid of a slack tank moves to the low side when heeled, as if its weight were higher.
+/// Free surface moment of a tank = fluid density * i (kg.m), i the transverse moment of
+/// inertia of the fluid surface.
+/// FSC = sum of the moments / displacement, the virtual rise of G.
+pub struct FreeSurface {
+    /// Tank name and free surface moment (kg.m), the slack tanks only.
+    tanks: Vec<(String, f64)>,
+    displacement: Weight,
+}
+
+#[allow(dead_code)]
+impl FreeSurface {
+    pub fn new(condition: &LoadCondition) -> FreeSurface {
+        FreeSurface {
+            tanks: condition
+                .tanks()
+                .iter()
+                .filter(|t| t.free_surface_inertia() > 0.0)
+                .map(|t| {
+                    (
+                        t.name().to_string(),
+                        t.density().to_kilogram_meter3() * t.free_surface_inertia(),
+                    )
+                })
+                .collect(),
+            displacement: condition.displacement(),
+        }
+    }
+
+    /// Free surface moment by tank (kg.m).
+    pub fn tanks(&self) -> &[(String, f64)] {
+        &self.tanks
+    }
+
+    /// Total free surface moment (kg.m).
+    pub fn moment(&self) -> f64 {
+        self.tanks.iter().map(|(_, m)| m).sum()
+    }
+
+    /// Virtual rise of G of a tank.
+    pub fn tank_correction(&self, name: &str) -> Option<Length> {
+        let (_, moment) = self.tanks.iter().find(|(n, _)| n == name)?;
+        Some(Length::from_meter(moment / self.displacement.to_kilogram()))
+    }
+
+    /// FSC (free surface correction), the virtual rise of G.
+    pub fn correction(&self) -> Length {
+        Length::from_meter(self.moment() / self.displacement.to_kilogram())
+    }
+}
+
+impl fmt::Display for FreeSurface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Free surface]")?;
+        for (name, moment) in &self.tanks {
+            write!(
+                f,
+                "\n\t{:<16} {:>9.1}kg.m {:>7.4}m",
+                name,
+                moment,
+                moment / self.displacement.to_kilogram()
+            )?;
+        }
+        write!(
+            f,
+            "\n\t{:<16} {:>9.1}kg.m {:>7.4}m",
+            "FSC",
+            self.moment(),
+            self.correction().to_meter()
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// ANGLE OF VANISHING STABILITY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Offshore suitability character, by the AVS.
+/// Less than 100°       Inshore, sheltered waters
+/// 100° - 120°          Coastal
+/// 120° - 130°          Offshore
+/// 130°+                Ocean
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum OffshoreCharacter {
+    Inshore,
+    Coastal,
+    Offshore,
+    Ocean,
+}
+
+impl fmt::Display for OffshoreCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OffshoreCharacter::Inshore => write!(f, "Inshore"),
+            OffshoreCharacter::Coastal => write!(f, "Coastal"),
+            OffshoreCharacter::Offshore => write!(f, "Offshore"),
+            OffshoreCharacter::Ocean => write!(f, "Ocean"),
+        }
+    }
+}
+
+/// AVS estimate
+///
+/// Angle of vanishing stability (or LPS, limit of positive stability) estimated by the
+/// US Sailing screening formula, when no GZ curve is available:
+/// AVS = 110 + 400 / (SV - 10)
+/// SV is the ScreeningStabilityValue. An estimate only, the GZ curve gives the actual AVS.
+pub struct AvsEstimate {
+    screening_value: f64,
+    avs: f64,
+    offshore_character: OffshoreCharacter,
+}
+
+#[allow(dead_code)]
+impl AvsEstimate {
+    /// Needs the ballast ratio.
+    pub fn from_boat(boat: &Boat) -> Option<AvsEstimate> {
+        let screening_value = ScreeningStabilityValue::from_boat(boat)?.value();
+        Some(AvsEstimate::from_screening_value(screening_value))
+    }
+
+    /// Estimate for a screening value.
+    pub fn from_screening_value(screening_value: f64) -> AvsEstimate {
+        // Below 10 the formula breaks down, the boat is self righting.
+        let avs = if screening_value <= 10.0 {
+            180.0
+        } else {
+            (110.0 + 400.0 / (screening_value - 10.0)).min(180.0)
+        };
+        AvsEstimate {
+            screening_value,
+            avs,
+            offshore_character: if avs < 100.0 {
+                OffshoreCharacter::Inshore
+            } else if avs < 120.0 {
+                OffshoreCharacter::Coastal
+            } else if avs < 130.0 {
+                OffshoreCharacter::Offshore
+            } else {
+                OffshoreCharacter::Ocean
+            },
+        }
+    }
+
+    /// SV (screening value).
+    pub fn screening_value(&self) -> f64 {
+        self.screening_value
+    }
+
+    /// Estimated AVS (degree).
+    pub fn avs(&self) -> f64 {
+        self.avs
+    }
+
+    /// Offshore suitability character.
+    pub fn character(&self) -> OffshoreCharacter {
+        self.offshore_character
+    }
+}
+
+impl fmt::Display for AvsEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[AVS estimate]\n\
+            \tSV:    {:>7.1}\n\
+            \tAVS:   {:>7.0}° [{}]",
+            self.screening_value, self.avs, self.offshore_character
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// STIX - STABILITY INDEX
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Apparent wind speed of the STIX wind moment factor (m/s).
+pub const STIX_WIND_SPEED: f64 = 17.0;
+
+/// Air density (kg/m3).
+pub const AIR_DENSITY: f64 = 1.225;
+
+/// ISO 12217 design category.
+#[derive(PartialEq, PartialOrd, Debug, Copy, Clone)]
+pub enum DesignCategory {
+    /// Ocean.
+    A,
+    /// Offshore.
+    B,
+    /// Inshore.
+    C,
+    /// Sheltered waters.
+    D,
+}
+
+impl DesignCategory {
+    /// Categories, from the most to the least demanding.
+    pub const ALL: [DesignCategory; 4] = [
+        DesignCategory::A,
+        DesignCategory::B,
+        DesignCategory::C,
+        DesignCategory::D,
+    ];
+
+    /// Minimum STIX of the category.
+    pub fn minimum_stix(self) -> f64 {
+        match self {
+            DesignCategory::A => 32.0,
+            DesignCategory::B => 23.0,
+            DesignCategory::C => 14.0,
+            DesignCategory::D => 5.0,
+        }
+    }
+}
+
+impl fmt::Display for DesignCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DesignCategory::A => write!(f, "A (Ocean)"),
+            DesignCategory::B => write!(f, "B (Offshore)"),
+            DesignCategory::C => write!(f, "C (Inshore)"),
+            DesignCategory::D => write!(f, "D (Sheltered waters)"),
+        }
+    }
+}
+
+/// STIX inputs not carried by the boat, taken from the GZ curve and the sail plan.
+#[derive(Debug, Copy, Clone)]
+pub struct StixInputs {
+    /// Area under the GZ curve up to the vanishing or downflooding angle (m.degree).
+    pub gz_area: f64,
+    /// Angle of vanishing stability (degree).
+    pub vanishing_angle: f64,
+    /// Downflooding angle (degree).
+    pub downflooding_angle: f64,
+    /// GZ at 90°.
+    pub gz_90: Length,
+    /// GZ at the downflooding angle.
+    pub gz_downflooding: Length,
+    /// Height of the sail plan centroid above the waterline.
+    pub sail_centroid: Length,
+    /// Depth of the lateral plane centroid below the waterline.
+    pub lateral_centroid: Length,
+}
+
+/// STIX (stability index)
+///
+/// ISO 12217-2 stability index of sailboats longer than 6m:
+/// STIX = (7 + 2.25 LBS) * (FDS * FIR * FKR * FDL * FBD * FWM * FDF) exp 0.5
+/// LBS = (2 LWL + LH) / 3
+/// FDS dynamic stability factor = AGZ / (15.81 LH exp 0.5), 0.5 to 1.5
+/// FIR inversion recovery factor = AVS / (125 - m / 1600), 0.4 to 1.5
+/// FKR knockdown recovery factor = 0.875 + 0.0965 FR, FR = GZ90 m / (2 AS HCE), 0.5 to 1.5
+/// FDL displacement-length factor = 0.6 + 15 m FL / (LBS exp 3 (333 - 8 LBS)), FL = (LBS / 11) exp 0.2, 0.75 to 1.25
+/// FBD beam-displacement factor from FB = 3.3 BH / (0.03 m) exp (1/3), 0.75 to 1.25
+/// FWM wind moment factor = GZD / wind heeling lever at the downflooding angle, 0.5 to 1.0
+/// FDF downflooding factor = downflooding angle / 90, 0.5 to 1.25
+/// m is the loaded displacement (kg), lengths in m.
+/// Simplified, confirm the category with the full standard.
+pub struct Stix {
+    length: Length,
+    dynamic_stability: f64,
+    inversion_recovery: f64,
+    knockdown_recovery: f64,
+    displacement_length: f64,
+    beam_displacement: f64,
+    wind_moment: f64,
+    downflooding: f64,
+    value: f64,
+}
+
+#[allow(dead_code)]
+impl Stix {
+    pub fn new(boat: &Boat, inputs: &StixInputs) -> Stix {
+        let lh = boat.loa().to_meter();
+        let lbs = (2.0 * boat.dwl().to_meter() + lh) / 3.0;
+        let mass = boat.displacement().to_kilogram();
+        let sail_area = boat.sail_area().to_meter2();
+
+        let dynamic_stability = (inputs.gz_area / (15.81 * lh.sqrt())).clamp(0.5, 1.5);
+        let inversion_recovery = (inputs.vanishing_angle / (125.0 - mass / 1600.0)).clamp(0.4, 1.5);
+        let knockdown_recovery =
+            if inputs.downflooding_angle > 90.0 && inputs.gz_90.to_meter() > 0.0 {
+                let recovery = inputs.gz_90.to_meter() * mass
+                    / (2.0 * sail_area * inputs.sail_centroid.to_meter());
+                (0.875 + 0.0965 * recovery).clamp(0.5, 1.5)
+            } else {
+                0.5
+            };
+        let length_factor = (lbs / 11.0).powf(0.2);
+        let displacement_length = (0.6
+            + 15.0 * mass * length_factor / (lbs.powi(3) * (333.0 - 8.0 * lbs)))
+            .clamp(0.75, 1.25);
+        let bh = boat.b_max().to_meter();
+        let bwl = boat.bwl().to_meter();
+        let fb = 3.3 * bh / (0.03 * mass).powf(1.0 / 3.0);
+        let beam_displacement = if fb > 2.2 {
+            (13.31 * bwl / (bh * fb.powi(3))).sqrt()
+        } else if fb < 1.45 {
+            (fb * fb * bwl / (1.77 * bh)).sqrt()
+        } else {
+            (1.19 * bwl / bh).sqrt()
+        }
+        .clamp(0.75, 1.25);
+        let wind_moment = if inputs.downflooding_angle >= 90.0 {
+            1.0
+        } else {
+            let angle = inputs.downflooding_angle.to_radians();
+            let heeling_lever = 0.5
+                * AIR_DENSITY
+                * STIX_WIND_SPEED.powi(2)
+                * sail_area
+                * (inputs.sail_centroid + inputs.lateral_centroid).to_meter()
+                * angle.cos().powi(2)
+                / (mass * 9.81);
+            (inputs.gz_downflooding.to_meter() / heeling_lever).clamp(0.5, 1.0)
+        };
+        let downflooding = (inputs.downflooding_angle / 90.0).clamp(0.5, 1.25);
+
+        let product = dynamic_stability
+            * inversion_recovery
+            * knockdown_recovery
+            * displacement_length
+            * beam_displacement
+            * wind_moment
+            * downflooding;
+        Stix {
+            length: Length::from_meter(lbs),
+            dynamic_stability,
+            inversion_recovery,
+            knockdown_recovery,
+            displacement_length,
+            beam_displacement,
+            wind_moment,
+            downflooding,
+            value: (7.0 + 2.25 * lbs) * product.sqrt(),
+        }
+    }
+
+    /// LBS (length of the STIX formula).
+    pub fn length(&self) -> Length {
+        self.length
+    }
+
+    /// FDS (dynamic stability factor).
+    pub fn dynamic_stability(&self) -> f64 {
+        self.dynamic_stability
+    }
+
+    /// FIR (inversion recovery factor).
+    pub fn inversion_recovery(&self) -> f64 {
+        self.inversion_recovery
+    }
+
+    /// FKR (knockdown recovery factor).
+    pub fn knockdown_recovery(&self) -> f64 {
+        self.knockdown_recovery
+    }
+
+    /// FDL (displacement-length factor).
+    pub fn displacement_length(&self) -> f64 {
+        self.displacement_length
+    }
+
+    /// FBD (beam-displacement factor).
+    pub fn beam_displacement(&self) -> f64 {
+        self.beam_displacement
+    }
+
+    /// FWM (wind moment factor).
+    pub fn wind_moment(&self) -> f64 {
+        self.wind_moment
+    }
+
+    /// FDF (downflooding factor).
+    pub fn downflooding(&self) -> f64 {
+        self.downflooding
+    }
+
+    /// STIX.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Design category implied by the STIX, None below the category D minimum.
+    pub fn category(&self) -> Option<DesignCategory> {
+        DesignCategory::ALL
+            .iter()
+            .copied()
+            .find(|c| self.value >= c.minimum_stix())
+    }
+}
+
+impl fmt::Display for Stix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[STIX]\n\
+            \tLBS:   {:>7.2}m\n\
+            \tFDS:   {:>7.3}\n\
+            \tFIR:   {:>7.3}\n\
+            \tFKR:   {:>7.3}\n\
+            \tFDL:   {:>7.3}\n\
+            \tFBD:   {:>7.3}\n\
+            \tFWM:   {:>7.3}\n\
+            \tFDF:   {:>7.3}\n\
+            \tSTIX:  {:>7.1}",
+            self.length.to_meter(),
+            self.dynamic_stability,
+            self.inversion_recovery,
+            self.knockdown_recovery,
+            self.displacement_length,
+            self.beam_displacement,
+            self.wind_moment,
+            self.downflooding,
+            self.value
+        )?;
+        match self.category() {
+            Some(category) => write!(f, " [{}]", category),
+            None => write!(f, " [No category]"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// DESIGN CATEGORY ASSESSMENT
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Shortest hull the STIX applies to.
+pub const STIX_MIN_LENGTH: f64 = 6.0;
+
+/// Lightest displacement for the category A (kg).
+pub const CATEGORY_A_MIN_DISPLACEMENT: f64 = 3000.0;
+
+/// Criterion of a design category.
+pub struct CategoryCriterion {
+    category: DesignCategory,
+    name: &'static str,
+    required: f64,
+    actual: f64,
+}
+
+#[allow(dead_code)]
+impl CategoryCriterion {
+    pub fn category(&self) -> DesignCategory {
+        self.category
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Minimum value required.
+    pub fn required(&self) -> f64 {
+        self.required
+    }
+
+    pub fn actual(&self) -> f64 {
+        self.actual
+    }
+
+    pub fn passed(&self) -> bool {
+        self.actual >= self.required
+    }
+}
+
+impl fmt::Display for CategoryCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<20} {:>9.1} {:>9.1} {}",
+            self.name,
+            self.required,
+            self.actual,
+            if self.passed() { "Pass" } else { "Fail" }
+        )
+    }
+}
+
+/// Design category assessment
+///
+/// Combines the STIX, AVS, downflooding angle and size requirements of each ISO 12217-2
+/// category, the boat plausibly satisfies the most demanding category passing every criterion.
+/// AVS minimum: A 130 - 0.002 m (100° at least), B 115 - 0.005 m (95° at least), C 95°, D 75°.
+/// Downflooding angle minimum: A and B 40°, C 35°, D 30°.
+/// The STIX applies from 6m LH and the category A needs 3000kg at least.
+/// Simplified, not a substitute for the full standard.
+pub struct DesignAssessment {
+    stix: Stix,
+    criteria: Vec<CategoryCriterion>,
+}
+
+#[allow(dead_code)]
+impl DesignAssessment {
+    pub fn new(boat: &Boat, inputs: &StixInputs) -> DesignAssessment {
+        let stix = Stix::new(boat, inputs);
+        let mass = boat.displacement().to_kilogram();
+        let mut criteria = Vec::new();
+        for &category in DesignCategory::ALL.iter() {
+            let (avs, downflooding) = match category {
+                DesignCategory::A => ((130.0 - 0.002 * mass).max(100.0), 40.0),
+                DesignCategory::B => ((115.0 - 0.005 * mass).max(95.0), 40.0),
+                DesignCategory::C => (95.0, 35.0),
+                DesignCategory::D => (75.0, 30.0),
+            };
+            let mut push = |name, required, actual| {
+                criteria.push(CategoryCriterion {
+                    category,
+                    name,
+                    required,
+                    actual,
+                })
+            };
+            push("STIX", category.minimum_stix(), stix.value());
+            push("AVS", avs, inputs.vanishing_angle);
+            push(
+                "Downflooding angle",
+                downflooding,
+                inputs.downflooding_angle,
+            );
+            push("LH", STIX_MIN_LENGTH, boat.loa().to_meter());
+            if category == DesignCategory::A {
+                push("Displacement", CATEGORY_A_MIN_DISPLACEMENT, mass);
+            }
+        }
+        DesignAssessment { stix, criteria }
+    }
+
+    pub fn stix(&self) -> &Stix {
+        &self.stix
+    }
+
+    /// Criteria of all the categories.
+    pub fn criteria(&self) -> &[CategoryCriterion] {
+        &self.criteria
+    }
+
+    /// Criteria of a category.
+    pub fn criteria_of(
+        &self,
+        category: DesignCategory,
+    ) -> impl Iterator<Item = &CategoryCriterion> {
+        self.criteria.iter().filter(move |c| c.category == category)
+    }
+
+    /// Whether every criterion of the category passes.
+    pub fn satisfies(&self, category: DesignCategory) -> bool {
+        self.criteria_of(category).all(|c| c.passed())
+    }
+
+    /// Most demanding category satisfied, None when not even the D is.
+    pub fn category(&self) -> Option<DesignCategory> {
+        DesignCategory::ALL
+            .iter()
+            .copied()
+            .find(|&c| self.satisfies(c))
+    }
+}
+
+impl fmt::Display for DesignAssessment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Design category]")?;
+        for &category in DesignCategory::ALL.iter() {
+            write!(f, "\n\t{}", category)?;
+            for criterion in self.criteria_of(category) {
+                write!(f, "\n\t\t{}", criterion)?;
+            }
+        }
+        match self.category() {
+            Some(category) => write!(f, "\n\tCategory: {}", category),
+            None => write!(f, "\n\tCategory: None"),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// GZ CURVE - RIGHTING ARM
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Iterations of the heeled equilibrium solver.
+pub const HEELED_ITERATIONS: usize = 30;
+
+/// Heel angles from upright to capsized, in steps of degrees, none without a positive step.
+pub fn heel_angles(step: f64) -> Vec<f64> {
+    if step.is_nan() || step <= 0.0 {
+        return Vec::new();
+    }
+    let count = ((180.0 / step).round() as usize).max(1);
+    (0..=count)
+        .map(|i| i as f64 * 180.0 / count as f64)
+        .collect()
+}
+
+/// Center of buoyancy of the heeled hull, positions aft of the forward perpendicular, to
+/// starboard of the centerline and above the baseline.
+#[derive(Debug, Copy, Clone)]
+pub struct Buoyancy {
+    pub volume: Volume,
+    pub lcb: Length,
+    pub tcb: Length,
+    pub vcb: Length,
+}
+
+impl Buoyancy {
+    /// Immersed hull at a heel to starboard (degree). The waterline is at a depth from the
+    /// baseline midway between the end sections, changing along the length by a slope for the
+    /// trim.
+    pub fn heeled(hull: &Hull, heel: f64, depth: Length, slope: f64) -> Buoyancy {
+        let (first, last) = hull.range();
+        let reference = (first.to_meter() + last.to_meter()) / 2.0;
+        let mut x = Vec::new();
+        let mut areas = Vec::new();
+        let mut ys = Vec::new();
+        let mut zs = Vec::new();
+        for section in hull.sections() {
+            let position = section.position().to_meter();
+            let local = Length::from_meter(depth.to_meter() + (position - reference) * slope);
+            let (area, y, z) = section.immersed(heel, local);
+            let area = area.to_meter2();
+            x.push(position);
+            areas.push(area);
+            ys.push(area * y.to_meter());
+            zs.push(area * z.to_meter());
+        }
+        let volume = simpson(&x, &areas);
+        let moments: Vec<f64> = x.iter().zip(&areas).map(|(x, a)| x * a).collect();
+        let center =
+            |moment: f64| Length::from_meter(if volume > 0.0 { moment / volume } else { 0.0 });
+        Buoyancy {
+            volume: Volume::from_meter3(volume),
+            lcb: center(simpson(&x, &moments)),
+            tcb: center(simpson(&x, &ys)),
+            vcb: center(simpson(&x, &zs)),
+        }
+    }
+
+    /// Righting arm of the buoyancy about a center of gravity on the centerline at a height
+    /// above the baseline, positive righting, heel in degrees.
+    /// GZ = TCB * cos(heel) + (VCB - KG) * sin(heel)
+    pub fn righting_arm(&self, heel: f64, kg: Length) -> Length {
+        let (sin, cos) = heel.to_radians().sin_cos();
+        Length::from_meter(self.tcb.to_meter() * cos + (self.vcb.to_meter() - kg.to_meter()) * sin)
+    }
+}
+
+/// Heeled hull floating a volume, with the LCB over the LCG when known, otherwise at the
+/// starting trim. Newton iterations with numerical derivatives from a starting depth and slope.
+/// None when it does not float.
+fn heeled_equilibrium(
+    hull: &Hull,
+    heel: f64,
+    volume: Volume,
+    lcg: Option<Length>,
+    start: (f64, f64),
+) -> Option<(f64, f64, Buoyancy)> {
+    let target = volume.to_meter3();
+    let (mut depth, mut slope) = start;
+    let buoyancy = |d: f64, s: f64| Buoyancy::heeled(hull, heel, Length::from_meter(d), s);
+    let errors = |b: &Buoyancy| {
+        (
+            b.volume.to_meter3() - target,
+            lcg.map_or(0.0, |lcg| b.lcb.to_meter() - lcg.to_meter()),
+        )
+    };
+    let (h_depth, h_slope) = (1e-4, 1e-5);
+    for _ in 0..HEELED_ITERATIONS {
+        let current = buoyancy(depth, slope);
+        let (f1, f2) = errors(&current);
+        if f1.abs() / target < EQUILIBRIUM_TOLERANCE.0 && f2.abs() < EQUILIBRIUM_TOLERANCE.1 {
+            return Some((depth, slope, current));
+        }
+        let (d1, d2) = errors(&buoyancy(depth + h_depth, slope));
+        let a11 = (d1 - f1) / h_depth;
+        let a21 = (d2 - f2) / h_depth;
+        if a11 <= 0.0 {
+            // Out of the hull, no waterplane.
+            return None;
+        }
+        match lcg {
+            Some(_) => {
+                let (s1, s2) = errors(&buoyancy(depth, slope + h_slope));
+                let a12 = (s1 - f1) / h_slope;
+                let a22 = (s2 - f2) / h_slope;
+                let det = a11 * a22 - a12 * a21;
+                if det.abs() < 1e-12 {
+                    depth -= f1 / a11;
+                } else {
+                    depth -= (f1 * a22 - f2 * a12) / det;
+                    slope -= (a11 * f2 - a21 * f1) / det;
+                }
+            }
+            None => depth -= f1 / a11,
+        }
+    }
+    let current = buoyancy(depth, slope);
+    let (f1, _) = errors(&current);
+    if f1.abs() / target < EQUILIBRIUM_TOLERANCE.0 * 10.0 {
+        Some((depth, slope, current))
+    } else {
+        None
+    }
+}
+
+/// Buoyancy of the hull floating a displacement at each heel, each angle starting from the
+/// previous one. Angles the solver can not float are left out. None when the hull does not
+/// float the displacement upright.
+fn heeled_buoyancies(
+    hull: &Hull,
+    displacement: Weight,
+    lcg: Option<Length>,
+    water: Water,
+    angles: &[f64],
+) -> Option<Vec<(f64, Buoyancy)>> {
+    let upright = Equilibrium::solve(hull, displacement, lcg, water)?;
+    let volume = water.density().volume(displacement);
+    let mut start = (
+        upright.waterline().to_meter(),
+        upright.trim().to_radians().tan(),
+    );
+    let mut buoyancies = Vec::new();
+    for &heel in angles {
+        if let Some((depth, slope, buoyancy)) = heeled_equilibrium(hull, heel, volume, lcg, start) {
+            buoyancies.push((heel, buoyancy));
+            start = (depth, slope);
+        }
+    }
+    Some(buoyancies)
+}
+
+/// GZ curve
+///
+/// Righting arm over the heel angles from the immersed hull geometry, free to trim, at each
+/// angle floating the displacement with the LCB over the LCG. The deck is taken watertight and
+/// flat between the sheers, no cabin, no downflooding. Areas in m.degree.
+pub struct GzCurve {
+    points: Vec<(f64, Length)>,
+    kg: Length,
+}
+
+#[allow(dead_code)]
+impl GzCurve {
+    /// VCG above the bottom of the canoe body, LCG aft of the forward perpendicular.
+    /// None when the hull does not float the displacement upright.
+    pub fn new(
+        hull: &Hull,
+        displacement: Weight,
+        vcg: Length,
+        lcg: Option<Length>,
+        water: Water,
+        angles: &[f64],
+    ) -> Option<GzCurve> {
+        let kg = vcg + GzCurve::bottom(hull);
+        let points = heeled_buoyancies(hull, displacement, lcg, water, angles)?
+            .iter()
+            .map(|(heel, buoyancy)| (*heel, buoyancy.righting_arm(*heel, kg)))
+            .collect();
+        Some(GzCurve { points, kg })
+    }
+
+    /// Curve of the boat displacement, VCG and LCB, every 5 degrees.
+    /// Needs the VCG to be informed.
+    pub fn from_boat(hull: &Hull, boat: &Boat) -> Option<GzCurve> {
+        let lcg = boat
+            .lcb()
+            .map(|lcb| Length::from_meter(lcb / 100.0 * boat.dwl().to_meter()));
+        GzCurve::new(
+            hull,
+            boat.displacement(),
+            boat.vcg()?,
+            lcg,
+            Water::Salt,
+            &heel_angles(5.0),
+        )
+    }
+
+    /// GZ curve from points, KG above the baseline.
+    fn from_points(points: Vec<(f64, Length)>, kg: Length) -> GzCurve {
+        GzCurve { points, kg }
+    }
+
+    /// Lowest keel point of the hull, the bottom of the canoe body.
+    fn bottom(hull: &Hull) -> Length {
+        Length::from_meter(
+            hull.sections()
+                .iter()
+                .map(|s| s.bottom().to_meter())
+                .fold(f64::MAX, f64::min),
+        )
+    }
+
+    /// KG above the baseline.
+    pub fn kg(&self) -> Length {
+        self.kg
+    }
+
+    /// Curve with G raised by the free surface correction, GZ - FSC * sin(heel).
+    pub fn with_free_surface(&self, free_surface: &FreeSurface) -> GzCurve {
+        let correction = free_surface.correction().to_meter();
+        GzCurve::from_points(
+            self.points
+                .iter()
+                .map(|&(heel, gz)| {
+                    (
+                        heel,
+                        Length::from_meter(gz.to_meter() - correction * heel.to_radians().sin()),
+                    )
+                })
+                .collect(),
+            self.kg + free_surface.correction(),
+        )
+    }
+
+    /// (Heel in degrees, GZ) points.
+    pub fn points(&self) -> &[(f64, Length)] {
+        &self.points
+    }
+
+    /// GZ at a heel, interpolated, None outside the computed angles.
+    pub fn gz(&self, heel: f64) -> Option<Length> {
+        let i = self
+            .points
+            .windows(2)
+            .position(|w| heel >= w[0].0 && heel <= w[1].0)?;
+        let ((a0, gz0), (a1, gz1)) = (self.points[i], self.points[i + 1]);
+        let t = if a1 > a0 {
+            (heel - a0) / (a1 - a0)
+        } else {
+            0.0
+        };
+        Some(Length::from_meter(
+            gz0.to_meter() + (gz1.to_meter() - gz0.to_meter()) * t,
+        ))
+    }
+
+    /// Largest GZ and its heel.
+    pub fn maximum(&self) -> Option<(f64, Length)> {
+        self.points
+            .iter()
+            .copied()
+            .fold(None, |max: Option<(f64, Length)>, p| match max {
+                Some(m) if m.1.to_meter() >= p.1.to_meter() => Some(m),
+                _ => Some(p),
+            })
+    }
+
+    /// AVS (angle of vanishing stability), where GZ turns negative after the maximum.
+    /// None when it stays positive over the computed angles.
+    pub fn avs(&self) -> Option<f64> {
+        let (max_angle, _) = self.maximum()?;
+        self.points.windows(2).find_map(|w| {
+            let ((a0, gz0), (a1, gz1)) = (w[0], w[1]);
+            let (gz0, gz1) = (gz0.to_meter(), gz1.to_meter());
+            if a0 >= max_angle && gz0 > 0.0 && gz1 <= 0.0 {
+                Some(a0 + (a1 - a0) * gz0 / (gz0 - gz1))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Area under the curve between two heels (m.degree), negative where GZ is negative.
+    pub fn area(&self, from: f64, to: f64) -> f64 {
+        let mut angles: Vec<f64> = vec![from];
+        angles.extend(
+            self.points
+                .iter()
+                .map(|p| p.0)
+                .filter(|a| *a > from && *a < to),
+        );
+        angles.push(to);
+        let values: Vec<f64> = angles
+            .iter()
+            .map(|a| self.gz(*a).map_or(0.0, |gz| gz.to_meter()))
+            .collect();
+        angles
+            .windows(2)
+            .zip(values.windows(2))
+            .map(|(a, v)| (a[1] - a[0]) * (v[0] + v[1]) / 2.0)
+            .sum()
+    }
+
+    /// Area of the positive part of the curve, up to the AVS (m.degree).
+    pub fn positive_area(&self) -> f64 {
+        let last = self.points.last().map_or(0.0, |p| p.0);
+        self.area(0.0, self.avs().unwrap_or(last))
+    }
+
+    /// Plot the curve in a width by height box at the context origin, the heel along x and the
+    /// GZ up from the zero line. The positive area up to the AVS shaded, the maximum GZ marked
+    /// and the AVS dashed, each labeled with its value. The frame and the heel axis on the grid
+    /// layer, the curve on the hull layer and the marks and labels on the annotations layer.
+    pub fn draw(&self, context: &Context, width: f64, height: f64, style: &DrawingStyle) {
+        let last = match self.points.last() {
+            Some(&(heel, _)) if heel > 0.0 => heel,
+            _ => return,
+        };
+        let (low, high) = self
+            .points
+            .iter()
+            .fold((0.0, 0.0), |(low, high): (f64, f64), (_, gz)| {
+                (low.min(gz.to_meter()), high.max(gz.to_meter()))
+            });
+        if high <= low {
+            return;
+        }
+        // Room for the labels around the plot.
+        let (left, right, top, bottom) = (40.0, 16.0, 20.0, 20.0);
+        let (plot_width, plot_height) = (width - left - right, height - top - bottom);
+        let to_x = |heel: f64| left + heel / last * plot_width;
+        let to_y = |gz: f64| top + plot_height * (high - gz) / (high - low);
+        let avs = self.avs();
+        let end = avs.unwrap_or(last);
+        let size = style.label_size();
+        context.save();
+        style.select_font(context);
+
+        if style.apply(context, LineKind::Curves) {
+            let mut area = vec![(to_x(0.0), to_y(0.0))];
+            area.extend(
+                self.points
+                    .iter()
+                    .filter(|p| p.0 < end)
+                    .map(|&(heel, gz)| (to_x(heel), to_y(gz.to_meter()))),
+            );
+            area.push((to_x(end), to_y(0.0)));
+            hatch(context, &area, Hatch::Fill(0.15));
+        }
+
+        // Frame, and the heel axis on the zero line with ticks every 30 degrees.
+        if style.apply(context, LineKind::Grid) {
+            context.rectangle(0.0, 0.0, width, height);
+            context.move_to(to_x(0.0), to_y(0.0));
+            context.line_to(to_x(last), to_y(0.0));
+            let mut heel = 0.0;
+            while heel <= last {
+                context.move_to(to_x(heel), to_y(0.0) - 2.0);
+                context.line_to(to_x(heel), to_y(0.0) + 2.0);
+                heel += 30.0;
+            }
+            context.stroke();
+        }
+
+        if style.apply(context, LineKind::Curves) {
+            for (i, &(heel, gz)) in self.points.iter().enumerate() {
+                if i == 0 {
+                    context.move_to(to_x(heel), to_y(gz.to_meter()));
+                } else {
+                    context.line_to(to_x(heel), to_y(gz.to_meter()));
+                }
+            }
+            context.stroke();
+        }
+
+        if style.apply(context, LineKind::Dimensions) {
+            let mut heel = 0.0;
+            while heel <= last {
+                let label = format!("{:.0}°", heel);
+                let label_width = context.text_extents(&label).width;
+                context.move_to(to_x(heel) - label_width / 2.0, height - size / 2.0);
+                context.show_text(&label);
+                heel += 30.0;
+            }
+            context.move_to(4.0, to_y(high) + size / 3.0);
+            context.show_text(&format!("{:.2}m", high));
+            context.move_to(4.0, to_y(0.0) + size / 3.0);
+            context.show_text("0");
+            if let Some((heel, gz)) = self.maximum() {
+                let (x, y) = (to_x(heel), to_y(gz.to_meter()));
+                context.new_path();
+                context.arc(x, y, ARROW_SIZE / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+                context.stroke();
+                context.move_to(x + ARROW_SIZE, y - ARROW_SIZE);
+                context.show_text(&format!("Max GZ {:.3}m at {:.0}°", gz.to_meter(), heel));
+            }
+            if let Some(avs) = avs {
+                context.set_dash(&GRID_DASH, 0.0);
+                context.move_to(to_x(avs), top);
+                context.line_to(to_x(avs), top + plot_height);
+                context.stroke();
+                context.set_dash(&[], 0.0);
+                context.move_to(to_x(avs) + 4.0, top + size);
+                context.show_text(&format!("AVS {:.1}°", avs));
+            }
+            context.move_to(to_x(end / 3.0), to_y(0.0) - (to_y(0.0) - to_y(high)) / 4.0);
+            context.show_text(&format!("Area {:.1}m.deg", self.positive_area()));
+        }
+        context.restore();
+    }
+
+    /// Plot the curve to a PDF, SVG or PNG file by the extension, width by height points.
+    pub fn plot<P: AsRef<Path>>(
+        &self,
+        path: P,
+        width: f64,
+        height: f64,
+        style: &DrawingStyle,
+    ) -> Result<(), DrawingError> {
+        file_target(path, width, height)?.render(&|context, width, height| {
+            self.draw(context, width, height, style);
+        })
+    }
+}
+
+impl fmt::Display for GzCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[GZ curve]\n\tKG: {:>7.3}m", self.kg.to_meter())?;
+        if let Some((angle, gz)) = self.maximum() {
+            write!(f, "\n\tMax GZ: {:.3}m at {:.0}°", gz.to_meter(), angle)?;
+        }
+        match self.avs() {
+            Some(avs) => write!(f, "\n\tAVS: {:.1}°", avs)?,
+            None => write!(f, "\n\tAVS: -")?,
+        }
+        write!(f, "\n\tArea: {:.1}m.deg", self.positive_area())?;
+        for (angle, gz) in &self.points {
+            write!(f, "\n\t{:>5.0}° {:>7.3}m", angle, gz.to_meter())?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// KN - CROSS CURVES OF STABILITY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// KN cross curves
+///
+/// Righting arm about the bottom of the canoe body (K) for a range of displacements and heels,
+/// computed once from the hull geometry. The GZ of any loading follows without new immersed
+/// geometry: GZ = KN - KG * sin(heel), KG above the bottom of the canoe body.
+/// The LCG is taken the same for all the displacements.
+pub struct CrossCurves {
+    displacements: Vec<Weight>,
+    angles: Vec<f64>,
+    /// KN by displacement, then by heel.
+    kn: Vec<Vec<Option<Length>>>,
+    bottom: Length,
+}
+
+#[allow(dead_code)]
+impl CrossCurves {
+    /// Displacements sorted from the lightest, the ones the hull does not float are left out.
+    pub fn new(
+        hull: &Hull,
+        displacements: &[Weight],
+        angles: &[f64],
+        lcg: Option<Length>,
+        water: Water,
+    ) -> CrossCurves {
+        let bottom = GzCurve::bottom(hull);
+        let mut displacements = displacements.to_vec();
+        displacements.sort_by(|a, b| {
+            a.to_kilogram()
+                .partial_cmp(&b.to_kilogram())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut floating = Vec::new();
+        let mut kn = Vec::new();
+        for displacement in displacements {
+            if let Some(buoyancies) = heeled_buoyancies(hull, displacement, lcg, water, angles) {
+                kn.push(
+                    angles
+                        .iter()
+                        .map(|heel| {
+                            buoyancies
+                                .iter()
+                                .find(|(a, _)| a == heel)
+                                .map(|(_, b)| b.righting_arm(*heel, bottom))
+                        })
+                        .collect(),
+                );
+                floating.push(displacement);
+            }
+        }
+        CrossCurves {
+            displacements: floating,
+            angles: angles.to_vec(),
+            kn,
+            bottom,
+        }
+    }
+
+    pub fn displacements(&self) -> &[Weight] {
+        &self.displacements
+    }
+
+    /// Heel angles (degree).
+    pub fn angles(&self) -> &[f64] {
+        &self.angles
+    }
+
+    /// KN of a computed displacement and heel, by indices.
+    pub fn value(&self, displacement: usize, heel: usize) -> Option<Length> {
+        *self.kn.get(displacement)?.get(heel)?
+    }
+
+    /// KN at a displacement and a computed heel, interpolated between the displacements.
+    /// None outside the computed displacements.
+    pub fn kn(&self, displacement: Weight, heel: f64) -> Option<Length> {
+        let j = self.angles.iter().position(|a| *a == heel)?;
+        let w = displacement.to_kilogram();
+        if self.displacements.len() == 1 {
+            return if (self.displacements[0].to_kilogram() - w).abs() < 1e-9 {
+                self.value(0, j)
+            } else {
+                None
+            };
+        }
+        let i = self
+            .displacements
+            .windows(2)
+            .position(|d| w >= d[0].to_kilogram() && w <= d[1].to_kilogram())?;
+        let (w0, w1) = (
+            self.displacements[i].to_kilogram(),
+            self.displacements[i + 1].to_kilogram(),
+        );
+        let (kn0, kn1) = (
+            self.value(i, j)?.to_meter(),
+            self.value(i + 1, j)?.to_meter(),
+        );
+        Some(Length::from_meter(kn0 + (kn1 - kn0) * (w - w0) / (w1 - w0)))
+    }
+
+    /// GZ curve of a loading from the cross curves, VCG above the bottom of the canoe body.
+    pub fn gz_curve(&self, displacement: Weight, vcg: Length) -> Option<GzCurve> {
+        let points: Vec<(f64, Length)> = self
+            .angles
+            .iter()
+            .filter_map(|&heel| {
+                let kn = self.kn(displacement, heel)?;
+                Some((
+                    heel,
+                    kn - Length::from_meter(vcg.to_meter() * heel.to_radians().sin()),
+                ))
+            })
+            .collect();
+        if points.is_empty() {
+            return None;
+        }
+        Some(GzCurve::from_points(points, vcg + self.bottom))
+    }
+}
+
+impl fmt::Display for CrossCurves {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[KN cross curves]\n\t{:>8}", "kg")?;
+        for angle in &self.angles {
+            write!(f, " {:>6.0}°", angle)?;
+        }
+        for (displacement, row) in self.displacements.iter().zip(&self.kn) {
+            write!(f, "\n\t{:>8.0}", displacement.to_kilogram())?;
+            for kn in row {
+                match kn {
+                    Some(kn) => write!(f, " {:>6.3}m", kn.to_meter())?,
+                    None => write!(f, " {:>7}", "-")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::appendage::{Keel, KeelType};
+    #[allow(unused_imports)]
+    use crate::si::Area;
+    #[allow(unused_imports)]
+    use crate::si::Weight;
+
+    #[test]
+    fn initial_stability() {
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(8.0));
+        boat.set_bwl(Length::from_meter(2.5));
+        boat.set_draft(Length::from_meter(0.5));
+        boat.set_displacement(Weight::from_kilogram(4100.0));
+        assert!(InitialStability::from_boat(&boat).is_none());
+
+        boat.set_cb(0.40);
+        boat.set_cw(0.70);
+        boat.set_vcg(Length::from_meter(0.3));
+        let stability = InitialStability::from_boat(&boat).unwrap();
+        // 0.5 * (5/6 - 0.40 / 2.1)
+        assert_eq!(format!("{:.3}", stability.kb().to_meter()), "0.321");
+        assert_eq!(format!("{:.3}", stability.bm().to_meter()), "1.386");
+        assert_eq!(format!("{:.3}", stability.gm().to_meter()), "1.407");
+        assert_eq!(stability.character(), InitialStabilityCharacter::Stiff);
+
+        // Tender.
+        boat.set_vcg(Length::from_meter(1.4));
+        let stability = InitialStability::from_boat(&boat).unwrap();
+        assert_eq!(stability.character(), InitialStabilityCharacter::Tender);
+
+        // Unstable.
+        boat.set_vcg(Length::from_meter(1.8));
+        let stability = InitialStability::from_boat(&boat).unwrap();
+        assert_eq!(stability.character(), InitialStabilityCharacter::Unstable);
+    }
+
+    #[test]
+    fn avs_estimate() {
+        let mut boat = Boat::new("".to_string());
+        boat.set_b_max(Length::from_foot(10.5));
+        boat.set_draft(Length::from_foot(1.5));
+        boat.set_displacement(Weight::from_pound(15680.0));
+        assert!(AvsEstimate::from_boat(&boat).is_none());
+
+        let keel = |ballast| {
+            Keel::new(
+                KeelType::Fin,
+                Length::from_meter(1.2),
+                Length::from_meter(0.6),
+                Length::from_meter(1.5),
+                Weight::from_pound(ballast),
+            )
+        };
+        boat.set_keel(keel(6272.0));
+        let avs = AvsEstimate::from_boat(&boat).unwrap();
+        assert_eq!(format!("{:.1}", avs.screening_value()), "29.4");
+        assert_eq!(format!("{:.0}", avs.avs()), "131");
+        assert_eq!(avs.character(), OffshoreCharacter::Ocean);
+
+        // Light ballast.
+        boat.set_keel(keel(3136.0));
+        let avs = AvsEstimate::from_boat(&boat).unwrap();
+        assert_eq!(avs.character(), OffshoreCharacter::Coastal);
+    }
+
+    #[test]
+    fn stix() {
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(Length::from_meter(11.0));
+        boat.set_dwl(Length::from_meter(9.5));
+        boat.set_b_max(Length::from_meter(3.6));
+        boat.set_bwl(Length::from_meter(3.0));
+        boat.set_displacement(Weight::from_kilogram(7000.0));
+        boat.set_sail_area(Area::from_meter2(65.0));
+        let mut inputs = StixInputs {
+            gz_area: 60.0,
+            vanishing_angle: 125.0,
+            downflooding_angle: 110.0,
+            gz_90: Length::from_meter(0.45),
+            gz_downflooding: Length::from_meter(0.3),
+            sail_centroid: Length::from_meter(6.5),
+            lateral_centroid: Length::from_meter(0.8),
+        };
+        let stix = Stix::new(&boat, &inputs);
+        assert_eq!(format!("{:.2}", stix.length().to_meter()), "10.00");
+        assert_eq!(format!("{:.3}", stix.dynamic_stability()), "1.144");
+        assert_eq!(format!("{:.3}", stix.knockdown_recovery()), "1.235");
+        // 0.6 + 15 * 7000 * (10 / 11) exp 0.2 / (10 exp 3 * (333 - 8 * 10))
+        assert_eq!(format!("{:.3}", stix.displacement_length()), "1.007");
+        assert_eq!(stix.wind_moment(), 1.0);
+        assert_eq!(format!("{:.1}", stix.value()), "39.5");
+        assert_eq!(stix.category(), Some(DesignCategory::A));
+
+        // Downflooding before a knockdown.
+        inputs.downflooding_angle = 60.0;
+        inputs.gz_area = 30.0;
+        let stix = Stix::new(&boat, &inputs);
+        assert_eq!(stix.knockdown_recovery(), 0.5);
+        assert_eq!(stix.category(), Some(DesignCategory::D));
+    }
+
+    #[test]
+    fn design_category() {
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(Length::from_meter(11.0));
+        boat.set_dwl(Length::from_meter(9.5));
+        boat.set_b_max(Length::from_meter(3.6));
+        boat.set_bwl(Length::from_meter(3.0));
+        boat.set_displacement(Weight::from_kilogram(7000.0));
+        boat.set_sail_area(Area::from_meter2(65.0));
+        let mut inputs = StixInputs {
+            gz_area: 60.0,
+            vanishing_angle: 125.0,
+            downflooding_angle: 110.0,
+            gz_90: Length::from_meter(0.45),
+            gz_downflooding: Length::from_meter(0.3),
+            sail_centroid: Length::from_meter(6.5),
+            lateral_centroid: Length::from_meter(0.8),
+        };
+        // AVS of 125 against 116 required.
+        let assessment = boat.design_category(&inputs);
+        assert_eq!(assessment.category(), Some(DesignCategory::A));
+
+        // STIX would allow A but the AVS does not.
+        inputs.vanishing_angle = 112.0;
+        let assessment = boat.design_category(&inputs);
+        let failed: Vec<_> = assessment
+            .criteria_of(DesignCategory::A)
+            .filter(|c| !c.passed())
+            .map(|c| c.name())
+            .collect();
+        assert_eq!(failed, vec!["AVS"]);
+        assert_eq!(assessment.category(), Some(DesignCategory::B));
+    }
+
+    #[test]
+    fn gz_curve() {
+        // Box 4m long, 2m wide and 1m deep, floating at 0.5m, KG 0.3m.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.0), 1.0, (1.0, 1.0));
+        let curve = GzCurve::new(
+            &hull,
+            Weight::from_kilogram(4100.0),
+            m(0.3),
+            Some(m(2.0)),
+            Water::Salt,
+            &heel_angles(5.0),
+        )
+        .unwrap();
+        assert_eq!(curve.points().len(), 37);
+        assert!(curve.points()[0].1.to_meter().abs() < 1e-6);
+        // Wall sided, GZ = sin(heel) * (GM + BM / 2 * tan(heel) exp 2), GM = 0.6167m.
+        assert_eq!(
+            format!("{:.4}", curve.gz(5.0).unwrap().to_meter()),
+            "0.0540"
+        );
+        let (angle, gz) = curve.maximum().unwrap();
+        assert_eq!(angle, 45.0);
+        assert_eq!(format!("{:.3}", gz.to_meter()), "0.407");
+        // On its side, VCB 0.5m over KG 0.3m.
+        assert_eq!(
+            format!("{:.3}", curve.gz(90.0).unwrap().to_meter()),
+            "0.200"
+        );
+        assert_eq!(format!("{:.0}", curve.avs().unwrap()), "116");
+        assert!(curve.positive_area() > curve.area(0.0, 30.0));
+        assert!(curve.gz(180.0).unwrap().to_meter().abs() < 1e-3);
+    }
+
+    #[test]
+    fn heel_angle_steps() {
+        assert_eq!(heel_angles(45.0), vec![0.0, 45.0, 90.0, 135.0, 180.0]);
+        // Rounded to an even division of the half turn.
+        assert_eq!(heel_angles(70.0), vec![0.0, 60.0, 120.0, 180.0]);
+        assert_eq!(heel_angles(360.0), vec![0.0, 180.0]);
+        assert!(heel_angles(0.0).is_empty());
+        assert!(heel_angles(-5.0).is_empty());
+        assert!(heel_angles(f64::NAN).is_empty());
+    }
+
+    #[test]
+    fn cross_curves() {
+        // Box 4m long, 2m wide and 1m deep.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.0), 1.0, (1.0, 1.0));
+        let angles = heel_angles(15.0);
+        let displacements: Vec<Weight> = [5000.0, 3000.0, 4000.0, 20000.0]
+            .iter()
+            .map(|w| Weight::from_kilogram(*w))
+            .collect();
+        let curves = CrossCurves::new(&hull, &displacements, &angles, Some(m(2.0)), Water::Salt);
+        // Sorted, the too heavy one left out.
+        assert_eq!(curves.displacements().len(), 3);
+        assert_eq!(curves.displacements()[0].to_kilogram(), 3000.0);
+
+        // Same GZ as the full computation.
+        let displacement = Weight::from_kilogram(4100.0);
+        let direct = GzCurve::new(
+            &hull,
+            displacement,
+            m(0.3),
+            Some(m(2.0)),
+            Water::Salt,
+            &angles,
+        )
+        .unwrap();
+        let derived = curves
+            .gz_curve(Weight::from_kilogram(4000.0), m(0.3))
+            .unwrap();
+        let exact = GzCurve::new(
+            &hull,
+            Weight::from_kilogram(4000.0),
+            m(0.3),
+            Some(m(2.0)),
+            Water::Salt,
+            &angles,
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{:.4}", derived.gz(45.0).unwrap().to_meter()),
+            format!("{:.4}", exact.gz(45.0).unwrap().to_meter())
+        );
+        // Interpolated between 4000 and 5000 kg, close to the full computation.
+        let interpolated = curves.gz_curve(displacement, m(0.3)).unwrap();
+        let difference =
+            interpolated.gz(30.0).unwrap().to_meter() - direct.gz(30.0).unwrap().to_meter();
+        assert!(difference.abs() < 0.01);
+        assert!(curves.kn(Weight::from_kilogram(1000.0), 30.0).is_none());
+    }
+
+    #[test]
+    fn free_surface() {
+        use crate::loading::Tank;
+        use crate::si::Volume;
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(9.0));
+        boat.set_bwl(Length::from_meter(3.0));
+        boat.set_draft(Length::from_meter(0.5));
+        boat.set_displacement(Weight::from_kilogram(5000.0));
+        boat.set_cw(0.7);
+        let mut condition = LoadCondition::new(LoadConditionKind::Full, boat.displacement());
+        condition.set_vcg(Length::from_meter(0.6));
+        condition.add_tank(
+            Tank::water("Water".to_string(), Volume::from_liter(200.0))
+                .at(Length::from_meter(0.3), 50.0)
+                .with_free_surface(Length::from_meter(1.0), Length::from_meter(1.2)),
+        );
+        condition.add_tank(
+            Tank::diesel("Diesel".to_string(), Volume::from_liter(100.0))
+                .at(Length::from_meter(0.3), 60.0)
+                .with_free_surface(Length::from_meter(0.5), Length::from_meter(0.6)),
+        );
+        condition.tank_mut("Water").unwrap().set_fill(0.5);
+        let free_surface = FreeSurface::new(&condition);
+        // The full diesel tank has no free surface.
+        assert_eq!(free_surface.tanks().len(), 1);
+        // 1000 * 1 * 1.2^3 / 12
+        assert_eq!(format!("{:.1}", free_surface.moment()), "144.0");
+        // 144 / (5000 + 100 water + 84 diesel)
+        assert_eq!(
+            format!("{:.4}", free_surface.correction().to_meter()),
+            "0.0278"
+        );
+        assert!(free_surface.tank_correction("Diesel").is_none());
+
+        boat.set_load_condition(condition);
+        let stability = InitialStability::from_condition(&boat, LoadConditionKind::Full).unwrap();
+        assert_eq!(
+            format!("{:.4}", stability.free_surface().to_meter()),
+            "0.0278"
+        );
+        assert_eq!(
+            format!(
+                "{:.4}",
+                (stability.kb() + stability.bm() - stability.kg() - stability.gm()).to_meter()
+            ),
+            "0.0278"
+        );
+
+        let curve = GzCurve::from_points(
+            vec![
+                (0.0, Length::from_meter(0.0)),
+                (90.0, Length::from_meter(0.5)),
+            ],
+            Length::from_meter(0.6),
+        );
+        let corrected = curve.with_free_surface(&free_surface);
+        assert_eq!(
+            format!("{:.4}", corrected.gz(90.0).unwrap().to_meter()),
+            "0.4722"
+        );
+    }
+
+    #[test]
+    fn initial_stability_from_geometry() {
+        use crate::weights::{WeightItem, WeightSchedule};
+
+        // Box 4m long, 2m wide, floating at 0.5m.
+        let m = Length::from_meter;
+        let hull = crate::hull::test::chine_hull(&|_| (0.0, 0.0), 1.0, (1.0, 1.0));
+        let hydrostatics = Hydrostatics::new(&hull, m(0.5), Water::Salt);
+
+        let mut boat = Boat::new("".to_string());
+        assert!(InitialStability::from_geometry(&boat, &hydrostatics).is_none());
+        boat.set_vcg(m(0.5));
+        let mut schedule = WeightSchedule::new();
+        schedule.push(WeightItem::new(
+            "Hull".to_string(),
+            Weight::from_kilogram(4100.0),
+            m(2.0),
+            m(0.3),
+        ));
+        boat.set_weight_schedule(schedule);
+        let stability = InitialStability::from_geometry(&boat, &hydrostatics).unwrap();
+        // The weight schedule KG, 0.25 + 0.6667 - 0.3
+        assert_eq!(format!("{:.3}", stability.kg().to_meter()), "0.300");
+        assert_eq!(format!("{:.4}", stability.gm().to_meter()), "0.6167");
+        assert_eq!(format!("{:.1}", stability.righting_moment()), "44.1");
+        assert_eq!(format!("{:.2}", stability.roll_period().unwrap()), "2.04");
+
+        let stability = InitialStability::from_hydrostatics(&hydrostatics, m(1.5)).unwrap();
+        assert_eq!(stability.character(), InitialStabilityCharacter::Unstable);
+        assert!(stability.roll_period().is_none());
+
+        let ratios = crate::boat::Ratios::new(&boat);
+        assert!(ratios.initial_stability().is_none());
+        let ratios = ratios.with_hydrostatics(&boat, &hydrostatics);
+        assert_eq!(
+            format!("{:.4}", ratios.initial_stability().unwrap().gm().to_meter()),
+            "0.6167"
+        );
+    }
+
+    #[test]
+    fn gz_plot() {
+        use crate::drawing::to_image;
+
+        // GZ = 0.5 sin(2 heel), the maximum 0.5m at 45 degrees and the AVS at 90.
+        let points = heel_angles(5.0)
+            .into_iter()
+            .map(|a| (a, Length::from_meter(0.5 * (2.0 * a).to_radians().sin())))
+            .collect();
+        let curve = GzCurve::from_points(points, Length::from_meter(0.3));
+        assert_eq!(format!("{:.1}", curve.avs().unwrap()), "90.0");
+        let style = DrawingStyle::new();
+        let mut image = to_image(400, 200, |context| {
+            curve.draw(context, 400.0, 200.0, &style)
+        })
+        .unwrap();
+        let stride = image.get_stride() as usize;
+        let data = image.get_data().unwrap();
+        let value = |x: usize, y: usize| data[y * stride + x * 4];
+        // Plot 344 by 160 from (40, 20), the zero line at 100, the curve peak at (126, 20).
+        assert!(value(126, 20) < 128);
+        // Shaded under the positive part, left clear under the negative part.
+        assert_eq!(value(100, 90), 217);
+        assert_eq!(value(290, 110), 255);
+
+        let path = std::env::temp_dir().join(format!("boat_plan_gz_{}.svg", std::process::id()));
+        curve.plot(&path, 400.0, 200.0, &style).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<svg"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+use super::appendage::KeelType;
+use super::boat::Boat;
+use super::loading::LoadConditionKind;
+use super::si::{Length, Weight};
+use std::fmt;
+
+/// Trailer weight as a fraction of the boat weight, a typical road trailer.
+pub const TRAILER_WEIGHT_FRACTION: f64 = 0.25;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// ROAD LIMITS
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Towing weight class, the heaviest trailer the class can tow.
+#[derive(Debug, Clone)]
+pub struct TowingClass {
+    pub name: String,
+    pub max_weight: Weight,
+}
+
+impl TowingClass {
+    pub fn new(name: &str, max_weight: Weight) -> TowingClass {
+        TowingClass {
+            name: name.to_string(),
+            max_weight,
+        }
+    }
+}
+
+/// Road limits
+///
+/// Limits to tow the boat without a special permit, and the launch ramp depth.
+/// Towing classes are sorted from the lightest.
+#[derive(Debug, Clone)]
+pub struct RoadLimits {
+    pub max_beam: Length,
+    /// Water depth at the end of the launch ramp, for the draft on the trailer.
+    pub max_draft: Length,
+    pub towing_classes: Vec<TowingClass>,
+}
+
+#[allow(dead_code)]
+impl RoadLimits {
+    /// United States, 8'6" wide and the trailer hitch classes.
+    pub fn us() -> RoadLimits {
+        RoadLimits {
+            max_beam: Length::from_inch(102.0),
+            max_draft: Length::from_foot(2.0),
+            towing_classes: vec![
+                TowingClass::new("Class I", Weight::from_pound(2000.0)),
+                TowingClass::new("Class II", Weight::from_pound(3500.0)),
+                TowingClass::new("Class III", Weight::from_pound(5000.0)),
+                TowingClass::new("Class IV", Weight::from_pound(10000.0)),
+            ],
+        }
+    }
+
+    /// European Union, 2.55m wide and the driving licence categories.
+    pub fn eu() -> RoadLimits {
+        RoadLimits {
+            max_beam: Length::from_meter(2.55),
+            max_draft: Length::from_meter(0.6),
+            towing_classes: vec![
+                TowingClass::new("B (light trailer)", Weight::from_kilogram(750.0)),
+                TowingClass::new("B96", Weight::from_kilogram(1250.0)),
+                TowingClass::new("BE", Weight::from_kilogram(3500.0)),
+            ],
+        }
+    }
+}
+
+impl Default for RoadLimits {
+    fn default() -> RoadLimits {
+        RoadLimits::us()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TRAILERABILITY
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Trailerability
+///
+/// Beam, towed weight and draft on the trailer against the road limits. The towed weight is
+/// the lightship weight, when the condition is set, plus the trailer. The draft on the trailer
+/// is the canoe body draft with a centerboard raised, the total draft otherwise.
+pub struct Trailerability {
+    beam: Length,
+    towed_weight: Weight,
+    draft: Length,
+    max_beam: Length,
+    max_draft: Length,
+    towing_class: Option<TowingClass>,
+}
+
+#[allow(dead_code)]
+impl Trailerability {
+    pub fn new(boat: &Boat, limits: &RoadLimits) -> Trailerability {
+        let weight = boat
+            .load_condition(LoadConditionKind::Lightship)
+            .map_or(boat.displacement(), |c| c.displacement());
+        let towed_weight =
+            Weight::from_kilogram(weight.to_kilogram() * (1.0 + TRAILER_WEIGHT_FRACTION));
+        let draft = match boat.keel() {
+            Some(keel) if keel.keel_type() == KeelType::Centerboard => boat.draft(),
+            _ => boat.total_draft(),
+        };
+        Trailerability {
+            beam: boat.b_max(),
+            towed_weight,
+            draft,
+            max_beam: limits.max_beam,
+            max_draft: limits.max_draft,
+            towing_class: limits
+                .towing_classes
+                .iter()
+                .find(|c| c.max_weight.to_kilogram() >= towed_weight.to_kilogram())
+                .cloned(),
+        }
+    }
+
+    pub fn beam(&self) -> Length {
+        self.beam
+    }
+
+    /// Boat plus trailer weight.
+    pub fn towed_weight(&self) -> Weight {
+        self.towed_weight
+    }
+
+    /// Draft on the trailer.
+    pub fn draft(&self) -> Length {
+        self.draft
+    }
+
+    /// Beam inside the road limit, no permit needed.
+    pub fn beam_ok(&self) -> bool {
+        self.beam.to_meter() <= self.max_beam.to_meter()
+    }
+
+    /// Draft launchable from the ramp.
+    pub fn draft_ok(&self) -> bool {
+        self.draft.to_meter() <= self.max_draft.to_meter()
+    }
+
+    /// Lightest towing class that can tow the boat, None when too heavy for all.
+    pub fn towing_class(&self) -> Option<&TowingClass> {
+        self.towing_class.as_ref()
+    }
+
+    /// Trailerable without permit, by the lightest towing class at most.
+    pub fn is_trailerable(&self) -> bool {
+        self.beam_ok() && self.draft_ok() && self.towing_class.is_some()
+    }
+}
+
+impl fmt::Display for Trailerability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let check = |ok| if ok { "Pass" } else { "Fail" };
+        write!(
+            f,
+            "[Trailerability]\n\
+            \tBeam:         {:>9.2}m (limit {:.2}m) {}\n\
+            \tDraft:        {:>9.2}m (limit {:.2}m) {}\n\
+            \tTowed weight: {:>9.0}kg {}",
+            self.beam.to_meter(),
+            self.max_beam.to_meter(),
+            check(self.beam_ok()),
+            self.draft.to_meter(),
+            self.max_draft.to_meter(),
+            check(self.draft_ok()),
+            self.towed_weight.to_kilogram(),
+            match &self.towing_class {
+                Some(class) => class.name.clone(),
+                None => "Too heavy".to_string(),
+            }
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::appendage::Keel;
+
+    #[test]
+    fn trailerability() {
+        let mut boat = Boat::new("Trailer sailer".to_string());
+        boat.set_b_max(Length::from_meter(2.45));
+        boat.set_draft(Length::from_meter(0.3));
+        boat.set_displacement(Weight::from_kilogram(900.0));
+        boat.set_keel(Keel::new(
+            KeelType::Centerboard,
+            Length::from_meter(0.5),
+            Length::from_meter(0.4),
+            Length::from_meter(1.0),
+            Weight::from_kilogram(150.0),
+        ));
+        let trailer = boat.trailerability(&RoadLimits::eu());
+        assert_eq!(
+            format!("{:.0}", trailer.towed_weight().to_kilogram()),
+            "1125"
+        );
+        assert_eq!(trailer.towing_class().unwrap().name, "B96");
+        assert_eq!(trailer.draft().to_meter(), 0.3);
+        assert!(trailer.is_trailerable());
+
+        let trailer = boat.trailerability(&RoadLimits::us());
+        assert_eq!(trailer.towing_class().unwrap().name, "Class II");
+
+        // Over the EU width.
+        boat.set_b_max(Length::from_meter(2.58));
+        assert!(!boat.trailerability(&RoadLimits::eu()).beam_ok());
+        assert!(boat.trailerability(&RoadLimits::us()).beam_ok());
+
+        // Fixed keel, too deep for the ramp and too heavy.
+        boat.set_keel(Keel::new(
+            KeelType::Fin,
+            Length::from_meter(0.5),
+            Length::from_meter(0.4),
+            Length::from_meter(1.0),
+            Weight::from_kilogram(150.0),
+        ));
+        boat.set_displacement(Weight::from_kilogram(3000.0));
+        let trailer = boat.trailerability(&RoadLimits::eu());
+        assert!(!trailer.draft_ok());
+        assert!(trailer.towing_class().is_none());
+    }
+}
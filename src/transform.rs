@@ -0,0 +1,357 @@
+use super::hull::Hull;
+use super::hydrostatics::{Hydrostatics, Water};
+use super::section::Section;
+use super::si::Length;
+use std::fmt;
+
+/// Iterations of the search for the Cp and the LCB.
+pub const TRANSFORMATION_ITERATIONS: usize = 40;
+
+/// Largest swing of the sections, keeps them in order along the hull.
+pub const SWING_LIMIT: f64 = 0.9;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// HULL TRANSFORMATION
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Hull transformation
+///
+/// Systematic variation of a parent hull:
+/// Scale the positions, the half-breadths and the heights, from the forward perpendicular,
+/// the centerline and the baseline.
+/// Change the Cp moving the stations of each half of the DWL toward the ends, fuller, or
+/// toward the maximum section, finer, u' = u + k u (1 - u) with u from the maximum section.
+/// Shift the LCB swinging the sectional area curve, the stations inside the DWL move by
+/// k L s (1 - s) with s from the forward end, the ends of the DWL stay.
+/// Hold the displacement scaling the beam and the depth back by the same factor, the
+/// volume changes as its square.
+#[derive(Debug, Copy, Clone)]
+pub struct HullTransformation {
+    pub length: f64,
+    pub beam: f64,
+    pub depth: f64,
+    /// Target Cp, None to keep the Cp of the scaled hull.
+    pub cp: Option<f64>,
+    /// LCB shift aft, None to keep the LCB of the scaled hull.
+    pub lcb_shift: Option<Length>,
+    pub hold_displacement: bool,
+}
+
+impl Default for HullTransformation {
+    fn default() -> HullTransformation {
+        HullTransformation {
+            length: 1.0,
+            beam: 1.0,
+            depth: 1.0,
+            cp: None,
+            lcb_shift: None,
+            hold_displacement: false,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl HullTransformation {
+    /// Transformed hull and its waterline, None when the hull does not float at the waterline,
+    /// the scales are not positive or the Cp or the LCB is out of reach.
+    pub fn apply(&self, hull: &Hull, waterline: Length, water: Water) -> Option<(Hull, Length)> {
+        if self.length <= 0.0 || self.beam <= 0.0 || self.depth <= 0.0 {
+            return None;
+        }
+        let parent = Hydrostatics::new(hull, waterline, water);
+        if parent.volume().to_meter3() <= 0.0 {
+            return None;
+        }
+        let mut hull = rebuild(hull, |x| x * self.length, self.beam, self.depth)?;
+        let waterline = Length::from_meter(waterline.to_meter() * self.depth);
+
+        if let Some(cp) = self.cp {
+            let middle = maximum_section(&Hydrostatics::new(&hull, waterline, water))?;
+            let ends = dwl_ends(&Hydrostatics::new(&hull, waterline, water));
+            let swing = |k: f64| {
+                rebuild(
+                    &hull,
+                    |x| {
+                        let end = if x <= middle { ends.0 } else { ends.1 };
+                        let half = end - middle;
+                        if half == 0.0 {
+                            return x;
+                        }
+                        let u = (x - middle) / half;
+                        if u <= 0.0 || u >= 1.0 {
+                            return x;
+                        }
+                        middle + (u + k * u * (1.0 - u)) * half
+                    },
+                    1.0,
+                    1.0,
+                )
+            };
+            let value = |h: &Hull| {
+                Hydrostatics::new(h, waterline, water)
+                    .form_coefficients()
+                    .map(|c| c.cp)
+            };
+            hull = search(swing, value, cp)?;
+        }
+
+        if let Some(shift) = self.lcb_shift {
+            let scaled = Hydrostatics::new(&hull, waterline, water);
+            let target = scaled.lcb()?.to_meter() + shift.to_meter();
+            let (start, end) = dwl_ends(&scaled);
+            let length = end - start;
+            let swing = |k: f64| {
+                rebuild(
+                    &hull,
+                    |x| {
+                        let s = (x - start) / length;
+                        if s <= 0.0 || s >= 1.0 {
+                            return x;
+                        }
+                        x + k * length * s * (1.0 - s)
+                    },
+                    1.0,
+                    1.0,
+                )
+            };
+            let value = |h: &Hull| {
+                Hydrostatics::new(h, waterline, water)
+                    .lcb()
+                    .map(|l| l.to_meter())
+            };
+            hull = search(swing, value, target)?;
+        }
+
+        if self.hold_displacement {
+            let volume = Hydrostatics::new(&hull, waterline, water)
+                .volume()
+                .to_meter3();
+            if volume <= 0.0 {
+                return None;
+            }
+            let factor = (parent.volume().to_meter3() / volume).sqrt();
+            hull = rebuild(&hull, |x| x, factor, factor)?;
+            return Some((hull, Length::from_meter(waterline.to_meter() * factor)));
+        }
+        Some((hull, waterline))
+    }
+}
+
+/// Hull of the sections moved and scaled.
+fn rebuild<F>(hull: &Hull, position: F, beam: f64, depth: f64) -> Option<Hull>
+where
+    F: Fn(f64) -> f64,
+{
+    let sections = hull
+        .sections()
+        .iter()
+        .filter_map(|s| {
+            let points: Vec<(Length, Length)> = s
+                .points()
+                .into_iter()
+                .map(|(y, z)| {
+                    (
+                        Length::from_meter(y.to_meter() * beam),
+                        Length::from_meter(z.to_meter() * depth),
+                    )
+                })
+                .collect();
+            Section::new(
+                Length::from_meter(position(s.position().to_meter())),
+                &points,
+                s.interpolation(),
+            )
+        })
+        .collect();
+    Hull::new(sections, hull.interpolation())
+}
+
+/// Bisection of the swing in (-SWING_LIMIT, SWING_LIMIT) for the target, the value growing
+/// with the swing. None when the target is out of reach of the swing.
+fn search<S, V>(swing: S, value: V, target: f64) -> Option<Hull>
+where
+    S: Fn(f64) -> Option<Hull>,
+    V: Fn(&Hull) -> Option<f64>,
+{
+    let reach = |k: f64| value(&swing(k)?);
+    if target < reach(-SWING_LIMIT)? || target > reach(SWING_LIMIT)? {
+        return None;
+    }
+    let (mut low, mut high) = (-SWING_LIMIT, SWING_LIMIT);
+    let mut hull = None;
+    for _ in 0..TRANSFORMATION_ITERATIONS {
+        let k = (low + high) / 2.0;
+        let candidate = swing(k)?;
+        if value(&candidate)? < target {
+            low = k;
+        } else {
+            high = k;
+        }
+        hull = Some(candidate);
+    }
+    hull
+}
+
+/// Station of the maximum sectional area.
+fn maximum_section(hydrostatics: &Hydrostatics) -> Option<f64> {
+    hydrostatics
+        .stations()
+        .iter()
+        .zip(hydrostatics.sectional_areas())
+        .fold(None, |max: Option<(f64, f64)>, (x, a)| match max {
+            Some((_, area)) if area >= a.to_meter2() => max,
+            _ => Some((x.to_meter(), a.to_meter2())),
+        })
+        .map(|(x, _)| x)
+}
+
+/// Forward and aft ends of the DWL.
+fn dwl_ends(hydrostatics: &Hydrostatics) -> (f64, f64) {
+    let start = hydrostatics.waterplane_start().to_meter();
+    (start, start + hydrostatics.waterplane_length().to_meter())
+}
+
+impl fmt::Display for HullTransformation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let optional = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+        write!(
+            f,
+            "[Hull transformation]\n\
+            \tLength:       {:>7.3}\n\
+            \tBeam:         {:>7.3}\n\
+            \tDepth:        {:>7.3}\n\
+            \tCp:           {:>7}\n\
+            \tLCB shift:    {:>7}\n\
+            \tDisplacement: {:>7}",
+            self.length,
+            self.beam,
+            self.depth,
+            optional(self.cp.map(|cp| format!("{:.3}", cp))),
+            optional(self.lcb_shift.map(|s| format!("{:.3}m", s.to_meter()))),
+            if self.hold_displacement {
+                "Held"
+            } else {
+                "Free"
+            }
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn hull_transformation() {
+        use crate::boat::Boat;
+        use crate::parametric::HullParameters;
+
+        let m = Length::from_meter;
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(m(6.0));
+        boat.set_dwl(m(5.4));
+        boat.set_bwl(m(2.0));
+        boat.set_b_max(m(2.2));
+        boat.set_draft(m(0.4));
+        boat.set_cp(0.55);
+        let parent = HullParameters::from_boat(&boat).generate().unwrap();
+        let hydrostatics = Hydrostatics::new(&parent, m(0.4), Water::Salt);
+        let volume = hydrostatics.volume().to_meter3();
+        let lcb = hydrostatics.lcb().unwrap().to_meter();
+
+        // Identity.
+        let (hull, waterline) = HullTransformation::default()
+            .apply(&parent, m(0.4), Water::Salt)
+            .unwrap();
+        assert_eq!(waterline.to_meter(), 0.4);
+        let same = Hydrostatics::new(&hull, waterline, Water::Salt);
+        assert_eq!(
+            format!("{:.6}", same.volume().to_meter3()),
+            format!("{:.6}", volume)
+        );
+
+        // Longer, wider and deeper, the volume by the product.
+        let scale = HullTransformation {
+            length: 1.1,
+            beam: 1.2,
+            depth: 0.9,
+            ..HullTransformation::default()
+        };
+        let (hull, waterline) = scale.apply(&parent, m(0.4), Water::Salt).unwrap();
+        assert_eq!(format!("{:.2}", waterline.to_meter()), "0.36");
+        let scaled = Hydrostatics::new(&hull, waterline, Water::Salt);
+        assert_eq!(
+            format!("{:.4}", scaled.volume().to_meter3() / volume),
+            "1.1880"
+        );
+        assert_eq!(format!("{:.2}", scaled.waterline_beam().to_meter()), "2.40");
+
+        // Fuller, the same displacement.
+        let fuller = HullTransformation {
+            cp: Some(0.6),
+            hold_displacement: true,
+            ..HullTransformation::default()
+        };
+        let (hull, waterline) = fuller.apply(&parent, m(0.4), Water::Salt).unwrap();
+        let transformed = Hydrostatics::new(&hull, waterline, Water::Salt);
+        assert_eq!(
+            format!("{:.3}", transformed.form_coefficients().unwrap().cp),
+            "0.600"
+        );
+        assert_eq!(
+            format!("{:.4}", transformed.volume().to_meter3()),
+            format!("{:.4}", volume)
+        );
+
+        // LCB 10cm aft.
+        let aft = HullTransformation {
+            lcb_shift: Some(m(0.1)),
+            ..HullTransformation::default()
+        };
+        let (hull, waterline) = aft.apply(&parent, m(0.4), Water::Salt).unwrap();
+        let shifted = Hydrostatics::new(&hull, waterline, Water::Salt);
+        assert_eq!(
+            format!("{:.3}", shifted.lcb().unwrap().to_meter() - lcb),
+            "0.100"
+        );
+
+        assert!(HullTransformation {
+            beam: 0.0,
+            ..HullTransformation::default()
+        }
+        .apply(&parent, m(0.4), Water::Salt)
+        .is_none());
+    }
+
+    #[test]
+    fn out_of_reach() {
+        use crate::boat::Boat;
+        use crate::parametric::HullParameters;
+
+        let m = Length::from_meter;
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(m(6.0));
+        boat.set_dwl(m(5.4));
+        boat.set_bwl(m(2.0));
+        boat.set_b_max(m(2.2));
+        boat.set_draft(m(0.4));
+        boat.set_cp(0.55);
+        let parent = HullParameters::from_boat(&boat).generate().unwrap();
+        let transformation = |cp: f64, shift: f64| HullTransformation {
+            cp: Some(cp),
+            lcb_shift: Some(m(shift)),
+            ..HullTransformation::default()
+        };
+        assert!(transformation(0.55, 0.0)
+            .apply(&parent, m(0.4), Water::Salt)
+            .is_some());
+        assert!(transformation(0.95, 0.0)
+            .apply(&parent, m(0.4), Water::Salt)
+            .is_none());
+        assert!(transformation(0.55, 3.0)
+            .apply(&parent, m(0.4), Water::Salt)
+            .is_none());
+    }
+}
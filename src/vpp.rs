@@ -0,0 +1,423 @@
+use super::aero::{SailAerodynamics, SailSet, SailTrim, MIN_FLAT};
+use super::boat::{Boat, SEA_WATER_DENSITY};
+use super::resistance::ResistanceCurve;
+use super::si::{Force, Length, Speed, Weight, GRAVITY};
+use super::stability::{GzCurve, InitialStability};
+use super::wind::Wind;
+use std::fmt;
+
+/// CE height above the DWL over the square root of the sail area, when the rig is not placed.
+pub const CE_HEIGHT_FACTOR: f64 = 0.7;
+
+/// CLR depth below the DWL as a fraction of the total draft.
+pub const CLR_DEPTH_FACTOR: f64 = 0.45;
+
+/// Effective span of the keel and the canoe body for the induced drag, fraction of the total
+/// draft.
+pub const EFFECTIVE_DRAFT_FACTOR: f64 = 0.9;
+
+/// Largest heel the sails are trimmed for (degree), the sails are flattened past it.
+pub const MAX_HEEL: f64 = 30.0;
+
+/// Trims tried from full to MIN_FLAT.
+pub const FLAT_STEPS: usize = 6;
+
+/// Boat speeds scanned for the equilibrium, up to the end of the resistance curve.
+pub const VPP_SPEED_STEPS: usize = 60;
+
+/// Iterations of the bisections.
+pub const VPP_ITERATIONS: usize = 40;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// VPP
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Boat sailing at equilibrium.
+#[derive(Debug, Copy, Clone)]
+pub struct VppPoint {
+    pub true_wind_speed: Speed,
+    /// True wind angle off the bow (degree).
+    pub true_wind_angle: f64,
+    pub speed: Speed,
+    /// Heel (degree).
+    pub heel: f64,
+    pub apparent_wind: Wind,
+    pub sails: SailSet,
+    /// Fraction of the lift the sails are trimmed to.
+    pub flat: f64,
+    /// Still driving past the end of the resistance curve, the speed held at its end.
+    pub capped: bool,
+}
+
+impl VppPoint {
+    /// VMG (velocity made good) toward the true wind, negative downwind.
+    pub fn vmg(&self) -> Speed {
+        Speed::from_meter_second(
+            self.speed.to_meter_second() * self.true_wind_angle.to_radians().cos(),
+        )
+    }
+}
+
+/// VPP (velocity prediction program)
+///
+/// Boat speed and heel in steady sailing, balancing the sail forces against the hull.
+/// The driving and heeling forces of the sails come from the sail aerodynamics, the jib or
+/// the spinnaker set, whichever is faster.
+/// The heel is where the righting moment, displacement * GZ, holds the heeling force times the
+/// arm from the CE down to the CLR. The speed is where the driving force meets the upright
+/// resistance plus the induced drag of the keel, Fh^2 / (q pi Te^2) from the side force.
+/// When the heel passes MAX_HEEL the sails are flattened, down to MIN_FLAT, for the fastest
+/// trim. Leeway and the added resistance of the heel are left out.
+#[derive(Debug, Clone)]
+pub struct Vpp {
+    aerodynamics: SailAerodynamics,
+    /// CE height above the DWL.
+    center_of_effort: Length,
+    /// CLR depth below the DWL.
+    center_of_lateral_resistance: Length,
+    effective_draft: Length,
+    displacement: Weight,
+    /// (Heel, GZ) points.
+    gz: Vec<(f64, Length)>,
+    resistance: ResistanceCurve,
+}
+
+#[allow(dead_code)]
+impl Vpp {
+    /// Needs the resistance curve and the initial stability, the righting arm taken as
+    /// GM sin(heel) = GZ. The CE from the rig when placed.
+    pub fn from_boat(boat: &Boat) -> Option<Vpp> {
+        let resistance = boat.resistance()?;
+        let gm = InitialStability::from_boat(boat)?.gm();
+        if gm.to_meter() <= 0.0 {
+            return None;
+        }
+        let root = boat.sail_area().to_meter2().sqrt();
+        let center_of_effort = boat
+            .rig()
+            .and_then(|r| r.center_of_effort())
+            .map(|ce| ce.z)
+            .unwrap_or_else(|| Length::from_meter(CE_HEIGHT_FACTOR * root));
+        let total_draft = boat.total_draft().to_meter();
+        Some(Vpp {
+            aerodynamics: SailAerodynamics::from_boat(boat),
+            center_of_effort,
+            center_of_lateral_resistance: Length::from_meter(CLR_DEPTH_FACTOR * total_draft),
+            effective_draft: Length::from_meter(EFFECTIVE_DRAFT_FACTOR * total_draft),
+            displacement: boat.displacement(),
+            gz: (0..=18)
+                .map(|i| {
+                    let heel = 5.0 * i as f64;
+                    (
+                        heel,
+                        Length::from_meter(gm.to_meter() * heel.to_radians().sin()),
+                    )
+                })
+                .collect(),
+            resistance,
+        })
+    }
+
+    /// Righting arm from the GZ curve of the hull geometry.
+    pub fn with_gz_curve(mut self, curve: &GzCurve) -> Vpp {
+        self.gz = curve.points().to_vec();
+        self
+    }
+
+    /// Resistance curve from another method or the hull geometry.
+    pub fn with_resistance(mut self, resistance: ResistanceCurve) -> Vpp {
+        self.resistance = resistance;
+        self
+    }
+
+    /// Heeling arm, from the CE to the CLR.
+    pub fn heeling_arm(&self) -> Length {
+        self.center_of_effort + self.center_of_lateral_resistance
+    }
+
+    /// Righting moment at a heel (N.m).
+    fn righting_moment(&self, heel: f64) -> Option<f64> {
+        let i = self
+            .gz
+            .windows(2)
+            .position(|w| heel >= w[0].0 && heel <= w[1].0)?;
+        let ((a0, gz0), (a1, gz1)) = (self.gz[i], self.gz[i + 1]);
+        let t = if a1 > a0 {
+            (heel - a0) / (a1 - a0)
+        } else {
+            0.0
+        };
+        let gz = gz0.to_meter() + (gz1.to_meter() - gz0.to_meter()) * t;
+        Some(self.displacement.to_kilogram() * GRAVITY * gz)
+    }
+
+    /// Heel where the righting moment holds a heeling moment, None when it cannot.
+    fn heel(&self, moment: f64) -> Option<f64> {
+        let limit = self.gz.last()?.0;
+        let (mut low, mut high) = (0.0, limit);
+        if self.righting_moment(high)? < moment {
+            return None;
+        }
+        for _ in 0..VPP_ITERATIONS {
+            let heel = (low + high) / 2.0;
+            if self.righting_moment(heel)? < moment {
+                low = heel;
+            } else {
+                high = heel;
+            }
+        }
+        Some((low + high) / 2.0)
+    }
+
+    /// Upright resistance at a speed, below the curve as the square of the speed.
+    fn upright_resistance(&self, speed: Speed) -> Option<Force> {
+        let first = self.resistance.points().first()?;
+        if speed.to_meter_second() < first.speed.to_meter_second() {
+            let ratio = speed.to_meter_second() / first.speed.to_meter_second();
+            return Some(Force::from_newton(
+                first.total().to_newton() * ratio * ratio,
+            ));
+        }
+        self.resistance.at(speed).map(|p| p.total())
+    }
+
+    pub fn aerodynamics(&self) -> &SailAerodynamics {
+        &self.aerodynamics
+    }
+
+    /// Driving force past the resistance and the heel at a speed, None when the boat cannot be
+    /// held up or the speed is past the resistance curve.
+    fn excess(
+        &self,
+        true_wind_speed: Speed,
+        true_wind_angle: f64,
+        speed: Speed,
+        sails: SailSet,
+        flat: f64,
+    ) -> Option<(f64, f64)> {
+        let apparent_wind = Wind::new(true_wind_speed, true_wind_angle).apparent(speed);
+        let forces = self
+            .aerodynamics
+            .forces(sails, apparent_wind, SailTrim::Flattened(flat));
+        let (drive, side) = (forces.drive.to_newton(), forces.heel.to_newton());
+        let heel = self.heel(side * self.heeling_arm().to_meter())?;
+        let lateral = side * heel.to_radians().cos();
+        let q = 0.5
+            * SEA_WATER_DENSITY
+            * speed.to_meter_second().powi(2)
+            * std::f64::consts::PI
+            * self.effective_draft.to_meter().powi(2);
+        let induced = if q > 0.0 { lateral * lateral / q } else { 0.0 };
+        let resistance = self.upright_resistance(speed)?.to_newton() + induced;
+        Some((drive - resistance, heel))
+    }
+
+    /// Fastest equilibrium with a set at a trim, None when the boat cannot sail. Capped at the
+    /// end of the resistance curve when the drive still passes the resistance there.
+    fn equilibrium(
+        &self,
+        true_wind_speed: Speed,
+        true_wind_angle: f64,
+        sails: SailSet,
+        flat: f64,
+    ) -> Option<VppPoint> {
+        let last = self.resistance.points().last()?.speed.to_meter_second();
+        let speed = |i: usize| Speed::from_meter_second(last * i as f64 / VPP_SPEED_STEPS as f64);
+        let excess = |v: Speed| self.excess(true_wind_speed, true_wind_angle, v, sails, flat);
+        let mut low = None;
+        let mut high = None;
+        for i in 1..=VPP_SPEED_STEPS {
+            match excess(speed(i)) {
+                Some((e, _)) if e > 0.0 => low = Some(speed(i)),
+                _ => {
+                    if low.is_some() {
+                        high = Some(speed(i));
+                        break;
+                    }
+                }
+            }
+        }
+        let mut low = low?.to_meter_second();
+        let capped = high.is_none();
+        if let Some(high) = high {
+            let mut high = high.to_meter_second();
+            for _ in 0..VPP_ITERATIONS {
+                let v = (low + high) / 2.0;
+                match excess(Speed::from_meter_second(v)) {
+                    Some((e, _)) if e > 0.0 => low = v,
+                    _ => high = v,
+                }
+            }
+        }
+        let speed = Speed::from_meter_second(low);
+        let (_, heel) = excess(speed)?;
+        Some(VppPoint {
+            true_wind_speed,
+            true_wind_angle,
+            speed,
+            heel,
+            apparent_wind: Wind::new(true_wind_speed, true_wind_angle).apparent(speed),
+            sails,
+            flat,
+            capped,
+        })
+    }
+
+    /// Fastest set and trim for a true wind, the heel within MAX_HEEL. True wind angle in
+    /// degree.
+    pub fn solve(&self, true_wind_speed: Speed, true_wind_angle: f64) -> Option<VppPoint> {
+        self.aerodynamics
+            .sets()
+            .into_iter()
+            .flat_map(|sails| (0..FLAT_STEPS).map(move |i| (sails, i)))
+            .filter_map(|(sails, i)| {
+                let flat = 1.0 - (1.0 - MIN_FLAT) * i as f64 / (FLAT_STEPS - 1) as f64;
+                self.equilibrium(true_wind_speed, true_wind_angle, sails, flat)
+            })
+            .filter(|p| p.heel <= MAX_HEEL)
+            .fold(None, |best: Option<VppPoint>, p| match best {
+                Some(b) if b.speed.to_meter_second() >= p.speed.to_meter_second() => best,
+                _ => Some(p),
+            })
+    }
+
+    /// Solutions for a grid of true wind speeds and angles.
+    pub fn grid(&self, true_wind_speeds: &[Speed], true_wind_angles: &[f64]) -> VppGrid {
+        VppGrid {
+            true_wind_speeds: true_wind_speeds.to_vec(),
+            true_wind_angles: true_wind_angles.to_vec(),
+            points: true_wind_angles
+                .iter()
+                .map(|&twa| {
+                    true_wind_speeds
+                        .iter()
+                        .map(|&tws| self.solve(tws, twa))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// VPP grid
+///
+/// Solutions by true wind angle (rows) and true wind speed (columns), None where the boat
+/// cannot sail.
+#[derive(Debug, Clone)]
+pub struct VppGrid {
+    true_wind_speeds: Vec<Speed>,
+    true_wind_angles: Vec<f64>,
+    points: Vec<Vec<Option<VppPoint>>>,
+}
+
+#[allow(dead_code)]
+impl VppGrid {
+    pub fn true_wind_speeds(&self) -> &[Speed] {
+        &self.true_wind_speeds
+    }
+
+    pub fn true_wind_angles(&self) -> &[f64] {
+        &self.true_wind_angles
+    }
+
+    /// Solution at a true wind angle and speed index.
+    pub fn point(&self, angle: usize, speed: usize) -> Option<&VppPoint> {
+        self.points.get(angle)?.get(speed)?.as_ref()
+    }
+}
+
+impl fmt::Display for VppGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[VPP - boat speed (kn) / heel (deg)]\n\t  TWA")?;
+        for tws in &self.true_wind_speeds {
+            write!(f, " {:>8.0}kn", tws.to_knot())?;
+        }
+        for (i, twa) in self.true_wind_angles.iter().enumerate() {
+            write!(f, "\n\t{:>5.0}", twa)?;
+            for j in 0..self.true_wind_speeds.len() {
+                match self.point(i, j) {
+                    Some(p) => write!(f, " {:>5.2}/{:>4.1}", p.speed.to_knot(), p.heel)?,
+                    None => write!(f, " {:>10}", "-")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn vpp() {
+        use crate::appendage::{Keel, KeelType};
+        use crate::si::Area;
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_loa(Length::from_meter(11.0));
+        boat.set_dwl(Length::from_meter(10.0));
+        boat.set_bwl(Length::from_meter(3.0));
+        boat.set_b_max(Length::from_meter(3.5));
+        boat.set_draft(Length::from_meter(0.6));
+        boat.set_displacement(Weight::from_kilogram(7175.0));
+        boat.set_sail_area(Area::from_meter2(60.0));
+        boat.set_cp(0.55);
+        boat.set_cw(0.7);
+        boat.set_lcb(53.0);
+        boat.set_lcf(55.0);
+        boat.set_wetted_surface(Area::from_meter2(25.0));
+        boat.set_keel(Keel::new(
+            KeelType::Fin,
+            Length::from_meter(1.2),
+            Length::from_meter(0.6),
+            Length::from_meter(1.4),
+            Weight::from_kilogram(2500.0),
+        ));
+        assert!(Vpp::from_boat(&boat).is_none());
+        boat.set_vcg(Length::from_meter(0.3));
+        let vpp = Vpp::from_boat(&boat).unwrap();
+
+        let knots = Speed::from_knot;
+        let grid = vpp.grid(
+            &[knots(6.0), knots(12.0), knots(20.0)],
+            &[30.0, 45.0, 90.0, 150.0],
+        );
+        let reach = grid.point(2, 1).unwrap();
+        assert_eq!(format!("{:.2}", reach.speed.to_knot()), "8.06");
+        assert!(reach.apparent_wind.angle < 90.0);
+        // Faster and more heeled with the wind, the heel held by flattening the sails.
+        for i in 0..4 {
+            let light = grid.point(i, 0).unwrap();
+            let strong = grid.point(i, 2).unwrap();
+            assert!(strong.speed.to_knot() > light.speed.to_knot());
+            assert!(strong.heel >= light.heel);
+            assert!(strong.heel <= MAX_HEEL);
+        }
+        assert!(grid.point(0, 1).unwrap().vmg().to_knot() > 0.0);
+        assert!(grid.point(3, 1).unwrap().vmg().to_knot() < 0.0);
+        // Jib on the wind, spinnaker off it.
+        assert_eq!(grid.point(0, 1).unwrap().sails, SailSet::Upwind);
+        assert_eq!(grid.point(3, 1).unwrap().sails, SailSet::Downwind);
+        assert!(!reach.capped);
+
+        // Resistance known up to 4kn only, the reach held at the end of the curve.
+        let slow: Vec<_> = vpp
+            .resistance
+            .points()
+            .iter()
+            .copied()
+            .filter(|p| p.speed.to_knot() <= 4.0)
+            .collect();
+        let end = slow.last().unwrap().speed;
+        let method = vpp.resistance.method();
+        let capped = vpp
+            .with_resistance(ResistanceCurve::new(method, slow))
+            .solve(knots(12.0), 90.0)
+            .unwrap();
+        assert!(capped.capped);
+        assert_eq!(capped.speed.to_knot(), end.to_knot());
+    }
+}
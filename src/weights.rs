@@ -0,0 +1,266 @@
+use super::boat::Boat;
+use super::diagnostics::Diagnostics;
+use super::material::Material;
+use super::si::{Area, Length, Weight};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Acceptable difference between the schedule total and the declared displacement.
+pub const WEIGHT_TOLERANCE: f64 = 0.05;
+
+/// Acceptable difference between the schedule LCG and the declared LCB (% of DWL).
+pub const LCG_TOLERANCE: f64 = 1.0;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// WEIGHT ITEM
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Weight item
+///
+/// LCG aft of the forward perpendicular, VCG above the bottom of the canoe body and
+/// TCG from the centerline, positive to starboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightItem {
+    name: String,
+    weight: Weight,
+    lcg: Length,
+    vcg: Length,
+    #[serde(default = "centerline")]
+    tcg: Length,
+}
+
+fn centerline() -> Length {
+    Length::from_meter(0.0)
+}
+
+#[allow(dead_code)]
+impl WeightItem {
+    /// Item on the centerline.
+    pub fn new(name: String, weight: Weight, lcg: Length, vcg: Length) -> WeightItem {
+        WeightItem {
+            name,
+            weight,
+            lcg,
+            vcg,
+            tcg: centerline(),
+        }
+    }
+
+    /// Panel weight from the material, area and thickness.
+    pub fn panel(
+        name: String,
+        material: Material,
+        area: Area,
+        thickness: Length,
+        lcg: Length,
+        vcg: Length,
+    ) -> WeightItem {
+        WeightItem::new(name, material.panel_weight(area, thickness), lcg, vcg)
+    }
+
+    /// Item off the centerline.
+    pub fn off_center(mut self, tcg: Length) -> WeightItem {
+        self.tcg = tcg;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+
+    /// LCG aft of the forward perpendicular.
+    pub fn lcg(&self) -> Length {
+        self.lcg
+    }
+
+    /// VCG above the bottom of the canoe body.
+    pub fn vcg(&self) -> Length {
+        self.vcg
+    }
+
+    /// TCG from the centerline, positive to starboard.
+    pub fn tcg(&self) -> Length {
+        self.tcg
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// WEIGHT SCHEDULE
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Weight schedule
+///
+/// Weights and centers of the boat, rolled up to the total weight and CG.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WeightSchedule {
+    items: Vec<WeightItem>,
+}
+
+#[allow(dead_code)]
+impl WeightSchedule {
+    pub fn new() -> WeightSchedule {
+        WeightSchedule::default()
+    }
+
+    pub fn push(&mut self, item: WeightItem) {
+        self.items.push(item);
+    }
+
+    pub fn items(&self) -> &[WeightItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Total weight.
+    pub fn weight(&self) -> Weight {
+        Weight::from_kilogram(self.items.iter().map(|i| i.weight.to_kilogram()).sum())
+    }
+
+    /// Weighted mean of an item position.
+    fn center<F: Fn(&WeightItem) -> Length>(&self, position: F) -> Option<Length> {
+        let weight = self.weight().to_kilogram();
+        if weight <= 0.0 {
+            return None;
+        }
+        let moment: f64 = self
+            .items
+            .iter()
+            .map(|i| i.weight.to_kilogram() * position(i).to_meter())
+            .sum();
+        Some(Length::from_meter(moment / weight))
+    }
+
+    /// LCG aft of the forward perpendicular.
+    pub fn lcg(&self) -> Option<Length> {
+        self.center(|i| i.lcg)
+    }
+
+    /// VCG above the bottom of the canoe body.
+    pub fn vcg(&self) -> Option<Length> {
+        self.center(|i| i.vcg)
+    }
+
+    /// TCG from the centerline.
+    pub fn tcg(&self) -> Option<Length> {
+        self.center(|i| i.tcg)
+    }
+
+    /// Check the schedule against the declared displacement and LCB.
+    pub fn check(&self, boat: &Boat) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        let lcg = match self.lcg() {
+            Some(lcg) => lcg,
+            None => return diagnostics,
+        };
+        let weight = self.weight().to_kilogram();
+        let displacement = boat.displacement().to_kilogram();
+        let difference = (weight - displacement) / displacement;
+        if difference.abs() > WEIGHT_TOLERANCE {
+            diagnostics.warning(format!(
+                "Weight schedule total of {:.0}kg differs {:.0}% from the displacement",
+                weight,
+                difference * 100.0
+            ));
+        }
+        if let Some(lcb) = boat.lcb() {
+            let lcg = lcg.to_meter() / boat.dwl().to_meter() * 100.0;
+            if (lcg - lcb).abs() > LCG_TOLERANCE {
+                diagnostics.warning(format!(
+                    "Weight schedule LCG at {:.1}% of DWL is off the LCB at {:.1}%, the boat will trim",
+                    lcg, lcb
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+impl fmt::Display for WeightSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Weight schedule]")?;
+        for item in &self.items {
+            write!(
+                f,
+                "\n\t{:<20} {:>9.1}kg {:>7.3} {:>7.3} {:>7.3}",
+                item.name,
+                item.weight.to_kilogram(),
+                item.lcg.to_meter(),
+                item.vcg.to_meter(),
+                item.tcg.to_meter()
+            )?;
+        }
+        if let (Some(lcg), Some(vcg), Some(tcg)) = (self.lcg(), self.vcg(), self.tcg()) {
+            write!(
+                f,
+                "\n\t{:<20} {:>9.1}kg {:>7.3} {:>7.3} {:>7.3}",
+                "Total",
+                self.weight().to_kilogram(),
+                lcg.to_meter(),
+                vcg.to_meter(),
+                tcg.to_meter()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn weight_schedule() {
+        let mut schedule = WeightSchedule::new();
+        assert!(schedule.lcg().is_none());
+        schedule.push(WeightItem::new(
+            "Hull".to_string(),
+            Weight::from_kilogram(600.0),
+            Length::from_meter(4.0),
+            Length::from_meter(0.4),
+        ));
+        schedule.push(
+            WeightItem::new(
+                "Battery".to_string(),
+                Weight::from_kilogram(40.0),
+                Length::from_meter(5.0),
+                Length::from_meter(0.2),
+            )
+            .off_center(Length::from_meter(0.8)),
+        );
+        schedule.push(WeightItem::panel(
+            "Deck".to_string(),
+            Material::OkoumePlywood,
+            Area::from_meter2(10.0),
+            Length::from_millimeter(9.0),
+            Length::from_meter(4.0),
+            Length::from_meter(1.0),
+        ));
+        // 600 + 40 + 49.5
+        assert_eq!(format!("{:.1}", schedule.weight().to_kilogram()), "689.5");
+        assert_eq!(
+            format!("{:.3}", schedule.lcg().unwrap().to_meter()),
+            "4.058"
+        );
+        assert_eq!(
+            format!("{:.3}", schedule.tcg().unwrap().to_meter()),
+            "0.046"
+        );
+
+        let mut boat = Boat::new("".to_string());
+        boat.set_dwl(Length::from_meter(8.0));
+        boat.set_displacement(Weight::from_kilogram(700.0));
+        boat.set_lcb(50.7);
+        assert!(schedule.check(&boat).is_empty());
+        boat.set_displacement(Weight::from_kilogram(800.0));
+        boat.set_lcb(55.0);
+        assert_eq!(schedule.check(&boat).warnings().count(), 2);
+    }
+}
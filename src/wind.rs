@@ -0,0 +1,142 @@
+use super::si::Speed;
+use std::fmt;
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// WIND
+///////////////////////////////////////////////////////////////////////////////////////////////////
+/// Wind
+///
+/// Wind relative to the boat, the speed and the angle off the bow it blows from (degree), from
+/// -180 to 180, positive to starboard. The true wind is the wind over the water, the apparent
+/// wind the wind over the moving boat, the true wind plus the wind of the boat speed from
+/// dead ahead. Leeway is left out, the boat moves along its heading.
+#[derive(Debug, Copy, Clone)]
+pub struct Wind {
+    pub speed: Speed,
+    pub angle: f64,
+}
+
+#[allow(dead_code)]
+impl Wind {
+    pub fn new(speed: Speed, angle: f64) -> Wind {
+        Wind {
+            speed,
+            angle: normalize(angle),
+        }
+    }
+
+    /// Wind blowing from a compass direction (degree), on a compass heading (degree).
+    pub fn from_direction(speed: Speed, direction: f64, heading: f64) -> Wind {
+        Wind::new(speed, direction - heading)
+    }
+
+    /// Compass direction the wind blows from, on a compass heading (degree), from 0 to 360.
+    pub fn direction(&self, heading: f64) -> f64 {
+        (self.angle + heading).rem_euclid(360.0)
+    }
+
+    /// Apparent wind of this true wind at a boat speed.
+    pub fn apparent(&self, boat_speed: Speed) -> Wind {
+        self.add_ahead(boat_speed.to_meter_second())
+    }
+
+    /// True wind of this apparent wind at a boat speed.
+    pub fn true_wind(&self, boat_speed: Speed) -> Wind {
+        self.add_ahead(-boat_speed.to_meter_second())
+    }
+
+    /// Wind with a wind from dead ahead added.
+    fn add_ahead(&self, ahead: f64) -> Wind {
+        let (speed, angle) = (self.speed.to_meter_second(), self.angle.to_radians());
+        let along = speed * angle.cos() + ahead;
+        let across = speed * angle.sin();
+        if along == 0.0 && across == 0.0 {
+            return Wind::new(Speed::from_meter_second(0.0), 0.0);
+        }
+        Wind::new(
+            Speed::from_meter_second(along.hypot(across)),
+            across.atan2(along).to_degrees(),
+        )
+    }
+
+    /// Angle off the bow without the side (degree), from 0 to 180.
+    pub fn off_the_bow(&self) -> f64 {
+        self.angle.abs()
+    }
+
+    pub fn is_starboard(&self) -> bool {
+        self.angle >= 0.0
+    }
+
+    /// Wind component along the boat, from ahead positive.
+    pub fn headwind(&self) -> Speed {
+        Speed::from_meter_second(self.speed.to_meter_second() * self.angle.to_radians().cos())
+    }
+
+    /// Wind component across the boat.
+    pub fn crosswind(&self) -> Speed {
+        Speed::from_meter_second(self.speed.to_meter_second() * self.angle.to_radians().sin().abs())
+    }
+}
+
+/// Angle to the range -180 to 180.
+fn normalize(angle: f64) -> f64 {
+    let angle = (angle + 180.0).rem_euclid(360.0) - 180.0;
+    if angle == -180.0 {
+        180.0
+    } else {
+        angle
+    }
+}
+
+impl fmt::Display for Wind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.1}kn at {:.0}deg {}",
+            self.speed.to_knot(),
+            self.off_the_bow(),
+            if self.is_starboard() {
+                "starboard"
+            } else {
+                "port"
+            }
+        )
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// TEST
+///////////////////////////////////////////////////////////////////////////////////////////////////
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn wind() {
+        let knots = Speed::from_knot;
+        let true_wind = Wind::new(knots(10.0), 90.0);
+        let apparent = true_wind.apparent(knots(10.0));
+        assert_eq!(format!("{:.2}", apparent.speed.to_knot()), "14.14");
+        assert_eq!(format!("{:.1}", apparent.angle), "45.0");
+        let back = apparent.true_wind(knots(10.0));
+        assert_eq!(format!("{:.2}", back.speed.to_knot()), "10.00");
+        assert_eq!(format!("{:.1}", back.angle), "90.0");
+
+        // Running dead downwind slower than the wind.
+        let run = Wind::new(knots(12.0), 180.0).apparent(knots(7.0));
+        assert_eq!(format!("{:.2}", run.speed.to_knot()), "5.00");
+        assert_eq!(run.angle, 180.0);
+
+        // Northerly on a heading of 315, on the starboard bow.
+        let wind = Wind::from_direction(knots(15.0), 0.0, 315.0);
+        assert_eq!(wind.angle, 45.0);
+        assert!(wind.is_starboard());
+        assert_eq!(wind.direction(315.0), 0.0);
+        let port = Wind::from_direction(knots(15.0), 270.0, 315.0);
+        assert_eq!(port.angle, -45.0);
+        assert_eq!(port.to_string(), "15.0kn at 45deg port");
+        assert_eq!(format!("{:.2}", port.headwind().to_knot()), "10.61");
+        assert_eq!(format!("{:.2}", port.crosswind().to_knot()), "10.61");
+    }
+}